@@ -12,13 +12,14 @@
 //   See the License for the specific language governing permissions and
 //   limitations under the License.
 
+#![cfg(feature = "unstable")]
 #![feature(test)]
 
 extern crate genmesh;
 extern crate test;
 
 use genmesh::generators::{IndexedPolygon, SharedVertex};
-use genmesh::generators::{Plane, SphereUV};
+use genmesh::generators::{Plane, SphereUv};
 use genmesh::*;
 use test::{black_box, Bencher};
 
@@ -98,7 +99,7 @@ fn plane_256x256_index_triangulate(bench: &mut Bencher) {
 #[bench]
 fn sphere_16x16_index(bench: &mut Bencher) {
     bench.iter(|| {
-        let plane = SphereUV::new(16, 16);
+        let plane = SphereUv::new(16, 16);
         for i in plane.indexed_polygon_iter() {
             black_box(i);
         }
@@ -108,7 +109,7 @@ fn sphere_16x16_index(bench: &mut Bencher) {
 #[bench]
 fn sphere_256x256_index(bench: &mut Bencher) {
     bench.iter(|| {
-        let plane = SphereUV::new(256, 256);
+        let plane = SphereUv::new(256, 256);
         for i in plane.indexed_polygon_iter() {
             black_box(i);
         }
@@ -118,7 +119,7 @@ fn sphere_256x256_index(bench: &mut Bencher) {
 #[bench]
 fn sphere_16x16_vertex(bench: &mut Bencher) {
     bench.iter(|| {
-        let plane = SphereUV::new(16, 16);
+        let plane = SphereUv::new(16, 16);
         for i in plane.shared_vertex_iter() {
             black_box(i);
         }
@@ -128,7 +129,7 @@ fn sphere_16x16_vertex(bench: &mut Bencher) {
 #[bench]
 fn sphere_256x256_vertex(bench: &mut Bencher) {
     bench.iter(|| {
-        let plane = SphereUV::new(256, 256);
+        let plane = SphereUv::new(256, 256);
         for i in plane.shared_vertex_iter() {
             black_box(i);
         }
@@ -138,7 +139,7 @@ fn sphere_256x256_vertex(bench: &mut Bencher) {
 #[bench]
 fn sphere_16x16_index_triangulate(bench: &mut Bencher) {
     bench.iter(|| {
-        let plane = SphereUV::new(16, 16);
+        let plane = SphereUv::new(16, 16);
         for i in plane.indexed_polygon_iter().triangulate() {
             black_box(i);
         }
@@ -148,7 +149,7 @@ fn sphere_16x16_index_triangulate(bench: &mut Bencher) {
 #[bench]
 fn sphere_256x256_index_triangulate(bench: &mut Bencher) {
     bench.iter(|| {
-        let plane = SphereUV::new(256, 256);
+        let plane = SphereUv::new(256, 256);
         for i in plane.indexed_polygon_iter().triangulate() {
             black_box(i);
         }
@@ -0,0 +1,499 @@
+//! Analytic (exact) primitives and the curves along which they intersect.
+//!
+//! Unlike the `generators`, these shapes are described by a handful of
+//! parameters instead of polygons, which makes it possible to compute
+//! exact outlines without approximating the surfaces first.
+
+use std::f32::consts::PI;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::math;
+use crate::{Line, Normal, Position};
+
+/// An infinite plane made of the points `p` for which `dot(normal, p) == distance`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    /// Unit normal of the plane
+    pub normal: Normal,
+    /// Signed distance of the plane from the origin along `normal`
+    pub distance: f32,
+}
+
+impl Plane {
+    /// Create a plane from its normal and its distance from the origin.
+    /// The normal does not need to be of unit length.
+    pub fn new(normal: Normal, distance: f32) -> Self {
+        let n = Vector3::from(normal);
+        let len = n.magnitude();
+        Plane {
+            normal: (n / len).into(),
+            distance: distance / len,
+        }
+    }
+
+    /// Create a plane passing through `point` facing `normal`. Panics if
+    /// the normal is zero.
+    pub fn from_point_normal(point: Position, normal: Normal) -> Self {
+        let n = math::try_normalize(normal.into()).expect("the normal of a plane must not be zero");
+        Plane {
+            normal: n.into(),
+            distance: n.dot(point.into()),
+        }
+    }
+
+    /// Signed distance from `point` to the plane, positive on the side
+    /// the normal points to.
+    pub fn signed_distance(&self, point: Position) -> f32 {
+        Vector3::from(self.normal).dot(point.into()) - self.distance
+    }
+}
+
+/// A sphere described by its center and radius.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sphere {
+    /// Center of the sphere
+    pub center: Position,
+    /// Radius of the sphere
+    pub radius: f32,
+}
+
+impl Sphere {
+    /// Create a new sphere.
+    pub fn new(center: Position, radius: f32) -> Self {
+        Sphere { center, radius }
+    }
+
+    /// Compute the smallest sphere containing all of `points`.
+    /// Returns `None` if there are no points.
+    pub fn enclosing<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        let points: Vec<Vector3<f32>> = points.into_iter().map(Vector3::from).collect();
+        math::min_enclosing_ball(&points).map(|(c, r)| Sphere::new(c.into(), r))
+    }
+
+    /// Check whether `point` is inside the sphere or on its surface,
+    /// within a small tolerance.
+    pub fn contains(&self, point: Position) -> bool {
+        let d = (Vector3::from(point) - Vector3::from(self.center)).magnitude();
+        d <= self.radius + 1e-5 * (1. + self.radius)
+    }
+
+    /// Compute the circle along which the sphere and `plane` meet, if they do.
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<Ellipse> {
+        let n = Vector3::from(plane.normal);
+        let d = plane.signed_distance(self.center);
+        if d.abs() > self.radius {
+            return None;
+        }
+
+        let r = (self.radius * self.radius - d * d).max(0.).sqrt();
+        let (u, v) = orthonormal_basis(n);
+        Some(Ellipse {
+            center: (Vector3::from(self.center) - n * d).into(),
+            major: (u * r).into(),
+            minor: (v * r).into(),
+        })
+    }
+
+    /// Compute the circle along which the two spheres meet, if they do.
+    /// Concentric spheres never produce a curve, even when they coincide.
+    pub fn intersect_sphere(&self, other: &Sphere) -> Option<Ellipse> {
+        let c0 = Vector3::from(self.center);
+        let c1 = Vector3::from(other.center);
+        let axis = c1 - c0;
+        let dist = axis.magnitude();
+        if dist == 0.
+            || dist > self.radius + other.radius
+            || dist < (self.radius - other.radius).abs()
+        {
+            return None;
+        }
+
+        // distance from `c0` along the axis to the plane holding the circle
+        let a =
+            (dist * dist + self.radius * self.radius - other.radius * other.radius) / (2. * dist);
+        let normal = axis / dist;
+        self.intersect_plane(&Plane::from_point_normal(
+            (c0 + normal * a).into(),
+            normal.into(),
+        ))
+    }
+}
+
+/// A capsule: all the points within `radius` of the segment from `a` to `b`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Capsule {
+    /// Center of the first hemispherical cap
+    pub a: Position,
+    /// Center of the second hemispherical cap
+    pub b: Position,
+    /// Radius of the capsule
+    pub radius: f32,
+}
+
+impl Capsule {
+    /// Create a new capsule.
+    pub fn new(a: Position, b: Position, radius: f32) -> Self {
+        Capsule { a, b, radius }
+    }
+
+    /// Fit a capsule around `points`. The axis of the capsule is the direction
+    /// in which the points spread the most, its radius the farthest distance of
+    /// a point from that axis, and the segment is then kept as short as
+    /// possible while containing every point.
+    /// Returns `None` if there are no points.
+    pub fn fit<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        let points: Vec<Vector3<f32>> = points.into_iter().map(Vector3::from).collect();
+        let (mean, cov) = math::covariance(points.iter().cloned())?;
+        let (_, axes) = math::symmetric_eigen(cov);
+        let axis = axes[0];
+
+        // position along the axis, and squared distance to it
+        let local: Vec<(f32, f32)> = points
+            .iter()
+            .map(|p| {
+                let d = p - mean;
+                let t = d.dot(axis);
+                (t, (d - axis * t).magnitude2())
+            })
+            .collect();
+        let r2 = local.iter().fold(0f32, |m, &(_, d2)| m.max(d2));
+
+        // each point constrains how far the end of the segment has to reach,
+        // so that it is covered by the hemispherical cap
+        let mut lo = f32::INFINITY;
+        let mut hi = f32::NEG_INFINITY;
+        for &(t, d2) in &local {
+            let h = (r2 - d2).max(0.).sqrt();
+            lo = lo.min(t + h);
+            hi = hi.max(t - h);
+        }
+        if lo > hi {
+            let mid = (lo + hi) * 0.5;
+            lo = mid;
+            hi = mid;
+        }
+
+        Some(Capsule {
+            a: (mean + axis * lo).into(),
+            b: (mean + axis * hi).into(),
+            radius: r2.sqrt(),
+        })
+    }
+
+    /// Check whether `point` is inside the capsule or on its surface,
+    /// within a small tolerance.
+    pub fn contains(&self, point: Position) -> bool {
+        let a = Vector3::from(self.a);
+        let ab = Vector3::from(self.b) - a;
+        let ap = Vector3::from(point) - a;
+        let len2 = ab.magnitude2();
+        let t = if len2 > 0. {
+            (ap.dot(ab) / len2).clamp(0., 1.)
+        } else {
+            0.
+        };
+        (ap - ab * t).magnitude() <= self.radius + 1e-5 * (1. + self.radius)
+    }
+}
+
+/// An infinite cylinder around the line through `origin` along `axis`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cylinder {
+    /// A point on the axis of the cylinder
+    pub origin: Position,
+    /// Unit direction of the axis of the cylinder
+    pub axis: Normal,
+    /// Radius of the cylinder
+    pub radius: f32,
+}
+
+impl Cylinder {
+    /// Create a new cylinder. The axis does not need to be of unit length,
+    /// but panics if it is zero.
+    pub fn new(origin: Position, axis: Normal, radius: f32) -> Self {
+        let axis =
+            math::try_normalize(axis.into()).expect("the axis of a cylinder must not be zero");
+        Cylinder {
+            origin,
+            axis: axis.into(),
+            radius,
+        }
+    }
+
+    /// Compute the ellipse along which the cylinder and `plane` meet.
+    ///
+    /// Returns `None` if the plane is parallel to the axis, in which case
+    /// the intersection is either empty or made of infinite lines.
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<Ellipse> {
+        let n = Vector3::from(plane.normal);
+        let a = Vector3::from(self.axis);
+        let cos = n.dot(a);
+        if cos.abs() < 1e-6 {
+            return None;
+        }
+
+        // where the axis pierces the plane
+        let t = -plane.signed_distance(self.origin) / cos;
+        let center = Vector3::from(self.origin) + a * t;
+
+        // the direction in the plane that is also perpendicular to the axis
+        // keeps the radius, the one across it is stretched by 1 / cos.
+        let minor = math::try_normalize(a.cross(n)).unwrap_or_else(|| orthonormal_basis(n).0);
+        let major = minor.cross(n).normalize();
+
+        Some(Ellipse {
+            center: center.into(),
+            major: (major * (self.radius / cos.abs())).into(),
+            minor: (minor * self.radius).into(),
+        })
+    }
+}
+
+/// An axis-aligned bounding box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    /// Corner with the smallest coordinates
+    pub min: Position,
+    /// Corner with the largest coordinates
+    pub max: Position,
+}
+
+impl Aabb {
+    /// Create a box from its two corners.
+    pub fn new(min: Position, max: Position) -> Self {
+        Aabb { min, max }
+    }
+
+    /// Compute the smallest box containing all of `points`.
+    /// Returns `None` if there are no points.
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        let mut iter = points.into_iter();
+        let first = iter.next()?;
+        Some(iter.fold(Aabb::new(first, first), |aabb, p| aabb.grow(p)))
+    }
+
+    /// Extend the box so it contains `point`.
+    pub fn grow(self, point: Position) -> Self {
+        Aabb {
+            min: [
+                self.min.x.min(point.x),
+                self.min.y.min(point.y),
+                self.min.z.min(point.z),
+            ]
+            .into(),
+            max: [
+                self.max.x.max(point.x),
+                self.max.y.max(point.y),
+                self.max.z.max(point.z),
+            ]
+            .into(),
+        }
+    }
+
+    /// Compute the smallest box containing both boxes.
+    pub fn union(self, other: Aabb) -> Self {
+        self.grow(other.min).grow(other.max)
+    }
+
+    /// Center of the box.
+    pub fn center(&self) -> Position {
+        ((Vector3::from(self.min) + Vector3::from(self.max)) * 0.5).into()
+    }
+
+    /// Size of the box along each axis.
+    pub fn size(&self) -> Position {
+        (Vector3::from(self.max) - Vector3::from(self.min)).into()
+    }
+
+    /// Check whether `point` is inside the box or on its surface.
+    pub fn contains(&self, point: Position) -> bool {
+        point.x >= self.min.x
+            && point.y >= self.min.y
+            && point.z >= self.min.z
+            && point.x <= self.max.x
+            && point.y <= self.max.y
+            && point.z <= self.max.z
+    }
+
+    /// Check whether the two boxes overlap, touching counts as overlapping.
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x
+            && self.min.y <= other.max.y
+            && self.min.z <= other.max.z
+            && other.min.x <= self.max.x
+            && other.min.y <= self.max.y
+            && other.min.z <= self.max.z
+    }
+}
+
+/// An oriented bounding box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Obb {
+    /// Center of the box
+    pub center: Position,
+    /// Unit axes of the box, forming a right-handed basis
+    pub axes: [Normal; 3],
+    /// Half of the size of the box along each of `axes`
+    pub half_extents: [f32; 3],
+}
+
+impl Obb {
+    /// Fit an oriented box around `points` using principal component analysis:
+    /// the axes of the box are the eigenvectors of the covariance of the points,
+    /// sorted from the direction of largest spread to the smallest.
+    /// Returns `None` if there are no points.
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        let points: Vec<Vector3<f32>> = points.into_iter().map(Vector3::from).collect();
+        let (_, cov) = math::covariance(points.iter().cloned())?;
+        let (_, axes) = math::symmetric_eigen(cov);
+
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for p in &points {
+            for i in 0..3 {
+                let d = axes[i].dot(*p);
+                min[i] = min[i].min(d);
+                max[i] = max[i].max(d);
+            }
+        }
+
+        let mut center = Vector3::new(0., 0., 0.);
+        let mut half_extents = [0.; 3];
+        for i in 0..3 {
+            center += axes[i] * ((min[i] + max[i]) * 0.5);
+            half_extents[i] = (max[i] - min[i]) * 0.5;
+        }
+
+        Some(Obb {
+            center: center.into(),
+            axes: [axes[0].into(), axes[1].into(), axes[2].into()],
+            half_extents,
+        })
+    }
+
+    /// Volume of the box.
+    pub fn volume(&self) -> f32 {
+        8. * self.half_extents[0] * self.half_extents[1] * self.half_extents[2]
+    }
+
+    /// The 8 corners of the box.
+    pub fn corners(&self) -> [Position; 8] {
+        let c = Vector3::from(self.center);
+        let mut result = [self.center; 8];
+        for (i, corner) in result.iter_mut().enumerate() {
+            let mut p = c;
+            for (axis, (&a, &h)) in self.axes.iter().zip(self.half_extents.iter()).enumerate() {
+                let sign = if i & (1 << axis) != 0 { 1. } else { -1. };
+                p += Vector3::from(a) * (h * sign);
+            }
+            *corner = p.into();
+        }
+        result
+    }
+
+    /// Check whether `point` is inside the box or on its surface,
+    /// within a small tolerance.
+    pub fn contains(&self, point: Position) -> bool {
+        let d = Vector3::from(point) - Vector3::from(self.center);
+        self.axes
+            .iter()
+            .zip(self.half_extents.iter())
+            .all(|(&a, &h)| Vector3::from(a).dot(d).abs() <= h + 1e-5 * (1. + h))
+    }
+}
+
+/// A planar ellipse, tracing `center + major * cos(t) + minor * sin(t)`.
+/// Circles are represented as ellipses with axes of equal length.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ellipse {
+    /// Center of the ellipse
+    pub center: Position,
+    /// Vector from the center to the curve at `t = 0`
+    pub major: Position,
+    /// Vector from the center to the curve at `t = PI / 2`
+    pub minor: Position,
+}
+
+impl Ellipse {
+    /// Evaluate the point of the curve at angle `t`.
+    pub fn point(&self, t: f32) -> Position {
+        let c = Vector3::from(self.center);
+        let u = Vector3::from(self.major);
+        let v = Vector3::from(self.minor);
+        (c + u * t.cos() + v * t.sin()).into()
+    }
+
+    /// Approximate the curve as a closed loop of `segments` lines, each
+    /// line starting where the previous one ends.
+    pub fn lines(&self, segments: usize) -> EllipseLines {
+        assert!(segments > 2);
+        EllipseLines {
+            ellipse: *self,
+            i: 0,
+            segments,
+        }
+    }
+}
+
+/// An iterator over the line strip approximating an `Ellipse`
+#[derive(Clone, Debug)]
+pub struct EllipseLines {
+    ellipse: Ellipse,
+    i: usize,
+    segments: usize,
+}
+
+impl EllipseLines {
+    fn at(&self, i: usize) -> Position {
+        // the last point is forced back onto the first, so that the
+        // loop closes exactly regardless of rounding
+        let i = i % self.segments;
+        self.ellipse
+            .point(i as f32 / self.segments as f32 * PI * 2.)
+    }
+}
+
+impl Iterator for EllipseLines {
+    type Item = Line<Position>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.segments - self.i;
+        (n, Some(n))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i == self.segments {
+            return None;
+        }
+        let line = Line::new(self.at(self.i), self.at(self.i + 1));
+        self.i += 1;
+        Some(line)
+    }
+}
+
+/// Build two unit vectors perpendicular to `n` and to each other,
+/// such that `(u, v, n)` is right-handed. A zero `n` is taken to be +Z.
+pub(crate) fn orthonormal_basis(n: Vector3<f32>) -> (Vector3<f32>, Vector3<f32>) {
+    let n = math::try_normalize(n).unwrap_or_else(Vector3::unit_z);
+    let helper = if n.x.abs() < 0.9 {
+        Vector3::unit_x()
+    } else {
+        Vector3::unit_y()
+    };
+    let u = helper.cross(n).normalize();
+    let v = n.cross(u);
+    (u, v)
+}
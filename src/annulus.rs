@@ -0,0 +1,153 @@
+use std::f32::consts::PI;
+
+use super::generators::{IndexedPolygon, SharedVertex};
+use super::Polygon::{PolyQuad, PolyTri};
+use super::{MapVertex, Polygon, Quad, Triangle, Vertex};
+
+/// Represents a flat ring in the XY plane, centered at (0, 0, 0) and facing +Z.
+///
+/// If the inner radius is 0 the ring degenerates into a subdivided disk, where
+/// the innermost ring is emitted as a triangle fan around a single center vertex.
+#[derive(Clone, Copy)]
+pub struct Annulus {
+    idx: usize,
+    end: usize,
+    inner_radius: f32,
+    outer_radius: f32,
+    sub_u: usize,
+    sub_r: usize,
+}
+
+impl Annulus {
+    /// Create a new annulus.
+    /// `inner_radius` is the radius of the hole, it may be 0
+    /// `outer_radius` is the radius of the outer edge, must be greater than `inner_radius`
+    /// `u` is the number of points around the ring, must be > 2
+    /// `r` is the number of rings from the inner edge to the outer one, must be > 0
+    pub fn new(inner_radius: f32, outer_radius: f32, u: usize, r: usize) -> Self {
+        assert!(u > 2 && r > 0);
+        assert!(inner_radius >= 0. && inner_radius < outer_radius);
+        Annulus {
+            idx: 0,
+            end: u * r,
+            inner_radius,
+            outer_radius,
+            sub_u: u,
+            sub_r: r,
+        }
+    }
+
+    fn is_disk(&self) -> bool {
+        self.inner_radius == 0.
+    }
+
+    fn vert(&self, u: usize, r: usize) -> Vertex {
+        let t = r as f32 / self.sub_r as f32;
+        let radius = self.inner_radius + (self.outer_radius - self.inner_radius) * t;
+        let a = (u as f32 / self.sub_u as f32) * PI * 2.;
+        Vertex {
+            pos: [a.cos() * radius, a.sin() * radius, 0.].into(),
+            normal: [0., 0., 1.].into(),
+        }
+    }
+
+    // index of the shared vertex `u` on ring `r`, ring 0 being the inner edge
+    fn index(&self, u: usize, r: usize) -> usize {
+        let u = u % self.sub_u;
+        if self.is_disk() {
+            if r == 0 {
+                0
+            } else {
+                1 + (r - 1) * self.sub_u + u
+            }
+        } else {
+            r * self.sub_u + u
+        }
+    }
+}
+
+impl Iterator for Annulus {
+    type Item = Polygon<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.idx;
+        (n, Some(n))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.idx += 1;
+            Some(
+                self.indexed_polygon(self.idx - 1)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl DoubleEndedIterator for Annulus {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            Some(
+                self.indexed_polygon(self.end)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Annulus {}
+
+impl SharedVertex<Vertex> for Annulus {
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        if self.is_disk() {
+            if idx == 0 {
+                self.vert(0, 0)
+            } else {
+                let idx = idx - 1;
+                self.vert(idx % self.sub_u, idx / self.sub_u + 1)
+            }
+        } else {
+            self.vert(idx % self.sub_u, idx / self.sub_u)
+        }
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        if self.is_disk() {
+            self.sub_r * self.sub_u + 1
+        } else {
+            (self.sub_r + 1) * self.sub_u
+        }
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for Annulus {
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        let u = idx % self.sub_u;
+        let r = idx / self.sub_u;
+
+        if r == 0 && self.is_disk() {
+            PolyTri(Triangle::new(
+                self.index(u, 0),
+                self.index(u, 1),
+                self.index(u + 1, 1),
+            ))
+        } else {
+            PolyQuad(Quad::new(
+                self.index(u, r),
+                self.index(u, r + 1),
+                self.index(u + 1, r + 1),
+                self.index(u + 1, r),
+            ))
+        }
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.sub_u * self.sub_r
+    }
+}
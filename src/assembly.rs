@@ -0,0 +1,101 @@
+//! Layout helpers for compound shapes built out of several meshes.
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::analytic::Aabb;
+use crate::math;
+use crate::{Normal, Position, Vertex};
+
+/// The direction in which the parts of an exploded view are pushed apart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExplodeMode {
+    /// Move every part away from the common center, along the line joining
+    /// the common center to the center of the part.
+    Radial,
+    /// Move every part along the given axis only, proportionally to how far
+    /// along that axis its center lies from the common center.
+    Axis(Normal),
+}
+
+/// Compute the translation to apply to each part of an assembly to get an
+/// exploded view of it.
+///
+/// `bounds` holds the bounding box of every part. The parts are pushed away
+/// from `center`, or from the center of all the boxes combined if `None`.
+/// `factor` scales the separation: with `1.0` the distance of each part
+/// from the center doubles, with `0.0` nothing moves. Panics if the axis
+/// of `ExplodeMode::Axis` is zero.
+pub fn explode_offsets(
+    bounds: &[Aabb],
+    center: Option<Position>,
+    mode: ExplodeMode,
+    factor: f32,
+) -> Vec<Position> {
+    let center = match center {
+        Some(c) => Vector3::from(c),
+        None => match bounds.iter().cloned().reduce(Aabb::union) {
+            Some(all) => all.center().into(),
+            None => return Vec::new(),
+        },
+    };
+
+    let axis = match mode {
+        ExplodeMode::Radial => None,
+        ExplodeMode::Axis(axis) => Some(
+            math::try_normalize(axis.into())
+                .expect("the axis of an exploded view must not be zero"),
+        ),
+    };
+
+    bounds
+        .iter()
+        .map(|b| {
+            let dir = Vector3::from(b.center()) - center;
+            let offset = match axis {
+                None => dir * factor,
+                Some(axis) => axis * axis.dot(dir) * factor,
+            };
+            offset.into()
+        })
+        .collect()
+}
+
+/// Build an exploded view of an assembly of indexed meshes.
+///
+/// Each part is a list of vertices and the polygons indexing into them. The
+/// returned parts are translated copies of the input; the polygons are left
+/// untouched since exploding does not change the topology.
+/// See `explode_offsets` for the meaning of `mode` and `factor`.
+pub fn explode_parts<P: Clone>(
+    parts: &[(Vec<Vertex>, Vec<P>)],
+    mode: ExplodeMode,
+    factor: f32,
+) -> Vec<(Vec<Vertex>, Vec<P>)> {
+    // parts without vertices have no center: they stay out of the common
+    // center and do not move
+    let bounds: Vec<Option<Aabb>> = parts
+        .iter()
+        .map(|(vertices, _)| Aabb::from_points(vertices.iter().map(|v| v.pos)))
+        .collect();
+    let present: Vec<Aabb> = bounds.iter().flatten().cloned().collect();
+    let mut offsets = explode_offsets(&present, None, mode, factor).into_iter();
+
+    parts
+        .iter()
+        .zip(&bounds)
+        .map(|((vertices, polygons), b)| {
+            let offset = match b {
+                Some(_) => Vector3::from(offsets.next().unwrap()),
+                None => Vector3::new(0., 0., 0.),
+            };
+            let vertices = vertices
+                .iter()
+                .map(|v| Vertex {
+                    pos: (Vector3::from(v.pos) + offset).into(),
+                    normal: v.normal,
+                })
+                .collect();
+            (vertices, polygons.clone())
+        })
+        .collect()
+}
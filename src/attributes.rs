@@ -0,0 +1,96 @@
+//! Access to the attributes of any vertex type.
+
+use crate::{Normal, Position, TexturedVertex, Vertex};
+
+/// Describes the components of a vertex, so that engine specific vertex
+/// types can go through the same pipeline as `Vertex`: indexing, normal
+/// computation, `Mesh` and the exporters.
+///
+/// Only the position is required, the other attributes are absent unless
+/// the implementation says otherwise. All the vertices of a mesh are
+/// expected to have the same attributes.
+pub trait VertexAttributes {
+    /// The position of the vertex
+    fn position(&self) -> Position;
+
+    /// The normal of the vertex, if it has one
+    fn normal(&self) -> Option<Normal> {
+        None
+    }
+
+    /// The texture coordinates of the vertex, if it has some
+    fn uv(&self) -> Option<[f32; 2]> {
+        None
+    }
+
+    /// The color of the vertex as linear RGBA, if it has one
+    fn color(&self) -> Option<[f32; 4]> {
+        None
+    }
+
+    /// Any other values attached to the vertex, such as weights or
+    /// simulation results. `Mesh` keeps them, and the exporters write them
+    /// as extra attributes.
+    fn custom(&self) -> &[f32] {
+        &[]
+    }
+
+    /// Replace the normal of the vertex. Vertex types without a normal
+    /// ignore it, which is the default.
+    fn set_normal(&mut self, normal: Normal) {
+        let _ = normal;
+    }
+}
+
+// The number of custom values of each vertex, and all of them one vertex
+// after the other, or `None` if the vertices have different numbers.
+#[cfg(any(feature = "gltf", feature = "ply"))]
+pub(crate) fn custom_values<V: VertexAttributes>(vertices: &[V]) -> Option<(usize, Vec<f32>)> {
+    let count = vertices.first().map_or(0, |v| v.custom().len());
+    let mut values = Vec::with_capacity(count * vertices.len());
+    for v in vertices {
+        if v.custom().len() != count {
+            return None;
+        }
+        values.extend_from_slice(v.custom());
+    }
+    Some((count, values))
+}
+
+impl VertexAttributes for Position {
+    fn position(&self) -> Position {
+        *self
+    }
+}
+
+impl VertexAttributes for Vertex {
+    fn position(&self) -> Position {
+        self.pos
+    }
+
+    fn normal(&self) -> Option<Normal> {
+        Some(self.normal)
+    }
+
+    fn set_normal(&mut self, normal: Normal) {
+        self.normal = normal;
+    }
+}
+
+impl VertexAttributes for TexturedVertex {
+    fn position(&self) -> Position {
+        self.pos
+    }
+
+    fn normal(&self) -> Option<Normal> {
+        Some(self.normal)
+    }
+
+    fn uv(&self) -> Option<[f32; 2]> {
+        Some(self.uv)
+    }
+
+    fn set_normal(&mut self, normal: Normal) {
+        self.normal = normal;
+    }
+}
@@ -0,0 +1,455 @@
+//! Chamfering and rounding the sharp edges of polygon meshes.
+
+use std::collections::HashMap;
+use std::f32::consts::FRAC_PI_2;
+
+use cgmath::{InnerSpace, Matrix3, SquareMatrix, Vector3};
+
+use crate::math::{newell_normal, outer, position_key, try_normalize};
+use crate::poly::EmitVertices;
+use crate::Polygon::{PolyNGon, PolyQuad, PolyTri};
+use crate::{Polygon, Position, Quad, Triangle, Vertex};
+
+// faces whose normals are closer than this, about 2.5 degrees apart, meet
+// at an edge that is too flat to bevel
+const FLAT: f32 = 0.999;
+
+/// Bevel the convex edges of a polygon mesh, so that hard-surface shapes
+/// catch the light along their edges instead of ending in a razor edge.
+///
+/// Each edge between two faces meeting at a convex angle is cut back by
+/// `width` on both faces, and replaced by a strip of `segments` quads: a
+/// flat chamfer with one segment, a rounded profile with more. The holes
+/// left at the corners where beveled edges meet are closed with a single
+/// polygon for a chamfer, and with a fan of triangles around a new vertex
+/// for a rounded bevel. Flat and concave edges, and the open boundaries,
+/// are left as they are.
+///
+/// Polygons are joined where they have vertices at exactly the same
+/// position, so the output of generators with a vertex per face corner,
+/// like `Cube`, can be beveled directly. The width should be less than
+/// half the shortest beveled edge, or the cut faces overlap.
+///
+/// Every face, strip and corner gets its own vertices, with flat normals
+/// except across a rounded profile.
+pub fn bevel(
+    positions: &[Position],
+    polygons: &[Polygon<usize>],
+    width: f32,
+    segments: usize,
+) -> (Vec<Vertex>, Vec<Polygon<usize>>) {
+    assert!(width >= 0. && segments > 0);
+    let mesh = Topology::new(positions, polygons);
+    let sectors = Sectors::new(&mesh, width);
+    let mut out = Output::default();
+
+    // the faces, cut back from the beveled edges
+    for (f, face) in mesh.faces.iter().enumerate() {
+        let ring = (0..face.len())
+            .map(|k| (sectors.point(mesh.corner(f, k)), mesh.normals[f]))
+            .collect();
+        out.polygon(ring);
+    }
+
+    // a strip along each beveled edge, listed once from the face going
+    // from its smaller vertex to its larger one
+    for (f, face) in mesh.faces.iter().enumerate() {
+        for k in 0..face.len() {
+            let (u, v) = (face[k], face[(k + 1) % face.len()]);
+            let g = match mesh.bevelled(f, k) {
+                Some((g, _)) if u < v => g,
+                _ => continue,
+            };
+            let pu = sectors.profile(&mesh, u, f, g, segments);
+            let pv = sectors.profile(&mesh, v, f, g, segments);
+            if segments == 1 {
+                out.polygon(flat(&[pv[0].0, pu[0].0, pu[1].0, pv[1].0]));
+                continue;
+            }
+            let base = out.vertices.len();
+            for &(p, n) in pu.iter().chain(&pv) {
+                out.vertex(p, n);
+            }
+            let (a, b) = (base, base + segments + 1);
+            for s in 0..segments {
+                out.polygons
+                    .push(PolyQuad(Quad::new(b + s, a + s, a + s + 1, b + s + 1)));
+            }
+        }
+    }
+
+    // the corners, where beveled edges meet
+    for v in 0..mesh.points.len() {
+        let walk = match mesh.around(v) {
+            Some(walk) => walk,
+            None => continue,
+        };
+        let mut ring: Vec<(Vector3<f32>, Vector3<f32>)> = Vec::new();
+        let mut planes = Vec::new();
+        for (i, &(f, k)) in walk.iter().enumerate() {
+            let (g, _) = walk[(i + 1) % walk.len()];
+            if mesh.bevelled(f, k).is_some() {
+                let profile = sectors.profile(&mesh, v, f, g, segments);
+                ring.extend_from_slice(&profile[..segments]);
+                planes.push(sectors.normal(mesh.corner(f, k)));
+            }
+        }
+        if ring.len() < 3 {
+            continue;
+        }
+        // the walk goes clockwise around the vertex
+        ring.reverse();
+        if segments == 1 {
+            let points: Vec<Vector3<f32>> = ring.iter().map(|p| p.0).collect();
+            out.polygon(flat(&points));
+            continue;
+        }
+        let center = corner_center(mesh.points[v], &ring, &planes, width);
+        let normal = planes.iter().fold(Vector3::new(0., 0., 0.), |a, &b| a + b);
+        let normal = try_normalize(normal).unwrap_or(normal);
+        let c = out.vertex(center, normal);
+        let base = out.vertices.len();
+        for &(p, n) in &ring {
+            out.vertex(p, n);
+        }
+        for i in 0..ring.len() {
+            let next = base + (i + 1) % ring.len();
+            out.polygons.push(PolyTri(Triangle::new(c, base + i, next)));
+        }
+    }
+
+    (out.vertices, out.polygons)
+}
+
+// the faces as loops of vertices that are merged by position
+struct Topology {
+    points: Vec<Vector3<f32>>,
+    faces: Vec<Vec<usize>>,
+    normals: Vec<Vector3<f32>>,
+    // the offset of the first corner of each face among all the corners
+    first: Vec<usize>,
+    // the face and the corner each directed edge starts from
+    edges: HashMap<(usize, usize), (usize, usize)>,
+}
+
+impl Topology {
+    fn new(positions: &[Position], polygons: &[Polygon<usize>]) -> Self {
+        let mut ids: HashMap<[u32; 3], usize> = HashMap::new();
+        let mut points = Vec::new();
+        let merged: Vec<usize> = positions
+            .iter()
+            .map(|p| {
+                *ids.entry(position_key(*p)).or_insert_with(|| {
+                    points.push(Vector3::from(*p));
+                    points.len() - 1
+                })
+            })
+            .collect();
+
+        let mut faces = Vec::new();
+        for poly in polygons {
+            let mut face: Vec<usize> = Vec::new();
+            poly.clone().emit_vertices(|i| face.push(merged[i]));
+            face.dedup();
+            while face.len() > 1 && face.first() == face.last() {
+                face.pop();
+            }
+            if face.len() >= 3 {
+                faces.push(face);
+            }
+        }
+
+        let normals = faces
+            .iter()
+            .map(|face: &Vec<usize>| {
+                let ring: Vec<Vector3<f32>> = face.iter().map(|&i| points[i]).collect();
+                try_normalize(newell_normal(&ring)).unwrap_or_else(|| Vector3::new(0., 0., 0.))
+            })
+            .collect();
+        let mut first = Vec::with_capacity(faces.len());
+        let mut edges = HashMap::new();
+        let mut corners = 0;
+        for (f, face) in faces.iter().enumerate() {
+            first.push(corners);
+            corners += face.len();
+            for k in 0..face.len() {
+                edges.insert((face[k], face[(k + 1) % face.len()]), (f, k));
+            }
+        }
+        Topology {
+            points,
+            faces,
+            normals,
+            first,
+            edges,
+        }
+    }
+
+    fn corner(&self, f: usize, k: usize) -> usize {
+        self.first[f] + k % self.faces[f].len()
+    }
+
+    fn corner_count(&self) -> usize {
+        self.first
+            .last()
+            .map_or(0, |&c| c + self.faces.last().unwrap().len())
+    }
+
+    fn point(&self, f: usize, k: usize) -> Vector3<f32> {
+        self.points[self.faces[f][k % self.faces[f].len()]]
+    }
+
+    // the other face along the edge starting at corner `k` of face `f`,
+    // and the corner its side of the edge starts from
+    fn twin(&self, f: usize, k: usize) -> Option<(usize, usize)> {
+        let face = &self.faces[f];
+        self.edges
+            .get(&(face[(k + 1) % face.len()], face[k % face.len()]))
+            .cloned()
+    }
+
+    // the twin of the edge, if the edge is to be beveled
+    fn bevelled(&self, f: usize, k: usize) -> Option<(usize, usize)> {
+        let (g, kg) = self.twin(f, k)?;
+        let (nf, ng) = (self.normals[f], self.normals[g]);
+        let ring = &self.faces[g];
+        let centroid = ring
+            .iter()
+            .fold(Vector3::new(0., 0., 0.), |a, &i| a + self.points[i])
+            / ring.len() as f32;
+        let convex = nf.dot(centroid - self.point(f, k)) < 0.;
+        if nf.dot(ng) < FLAT && convex {
+            Some((g, kg))
+        } else {
+            None
+        }
+    }
+
+    // the corners around vertex `v`, each with the face and the offset of
+    // the corner in it, going from each face to the one across its edge
+    // leaving `v`; `None` on an open boundary
+    fn around(&self, v: usize) -> Option<Vec<(usize, usize)>> {
+        let start = self
+            .faces
+            .iter()
+            .position(|face| face.contains(&v))
+            .map(|f| (f, self.faces[f].iter().position(|&i| i == v).unwrap()))?;
+        let mut walk = vec![start];
+        loop {
+            let &(f, k) = walk.last().unwrap();
+            let (g, kg) = self.twin(f, k)?;
+            // the twin ends at `v`
+            let next = (g, (kg + 1) % self.faces[g].len());
+            if next == start {
+                return Some(walk);
+            }
+            if walk.len() > self.faces.len() {
+                return None;
+            }
+            walk.push(next);
+        }
+    }
+}
+
+// the corners around a vertex that stay together because the edges
+// between them are not beveled, and the point each group is moved to
+struct Sectors {
+    of: Vec<usize>,
+    points: Vec<Vector3<f32>>,
+    normals: Vec<Vector3<f32>>,
+}
+
+impl Sectors {
+    fn new(mesh: &Topology, width: f32) -> Self {
+        let mut parent: Vec<usize> = (0..mesh.corner_count()).collect();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+        for (f, face) in mesh.faces.iter().enumerate() {
+            for k in 0..face.len() {
+                if let (Some((g, kg)), None) = (mesh.twin(f, k), mesh.bevelled(f, k)) {
+                    for (a, b) in [
+                        (mesh.corner(f, k), mesh.corner(g, kg + 1)),
+                        (mesh.corner(f, k + 1), mesh.corner(g, kg)),
+                    ] {
+                        let (a, b) = (root(&mut parent, a), root(&mut parent, b));
+                        parent[a] = b;
+                    }
+                }
+            }
+        }
+
+        let mut ids: HashMap<usize, usize> = HashMap::new();
+        let mut of = Vec::with_capacity(parent.len());
+        for c in 0..parent.len() {
+            let r = root(&mut parent, c);
+            let next = ids.len();
+            of.push(*ids.entry(r).or_insert(next));
+        }
+
+        // the lines along the beveled edges, as their direction into the
+        // face, which the point has to be `width` away from
+        let mut vertex = vec![0; ids.len()];
+        let mut normals = vec![Vector3::new(0., 0., 0.); ids.len()];
+        let mut lines: Vec<Vec<Vector3<f32>>> = vec![Vec::new(); ids.len()];
+        for (f, face) in mesh.faces.iter().enumerate() {
+            let n = mesh.normals[f];
+            for k in 0..face.len() {
+                let s = of[mesh.corner(f, k)];
+                vertex[s] = face[k];
+                normals[s] += n;
+                let p = mesh.point(f, k);
+                let prev = k + face.len() - 1;
+                if mesh.bevelled(f, prev).is_some() {
+                    lines[s].extend(try_normalize(n.cross(p - mesh.point(f, prev))));
+                }
+                if mesh.bevelled(f, k).is_some() {
+                    lines[s].extend(try_normalize(n.cross(mesh.point(f, k + 1) - p)));
+                }
+            }
+        }
+
+        let points = (0..ids.len())
+            .map(|s| {
+                let p = mesh.points[vertex[s]];
+                let n = try_normalize(normals[s]).unwrap_or(normals[s]);
+                normals[s] = n;
+                if lines[s].is_empty() {
+                    return p;
+                }
+                // as close as can be to `width` from every line, while
+                // staying in the plane of the faces
+                let sum = lines[s]
+                    .iter()
+                    .fold(Vector3::new(0., 0., 0.), |a, &m| a + m);
+                let ata = lines[s].iter().fold(outer(n, n), |a, &m| a + outer(m, m));
+                let offset = match ata.invert() {
+                    Some(inv) if ata.determinant().abs() > 1e-4 => inv * sum * width,
+                    _ => try_normalize(sum).unwrap_or(sum) * width,
+                };
+                p + offset
+            })
+            .collect();
+        Sectors {
+            of,
+            points,
+            normals,
+        }
+    }
+
+    fn point(&self, corner: usize) -> Vector3<f32> {
+        self.points[self.of[corner]]
+    }
+
+    fn normal(&self, corner: usize) -> Vector3<f32> {
+        self.normals[self.of[corner]]
+    }
+
+    // the profile across the beveled edge between the faces `f` and `g`,
+    // at its end `v`, going from `f` to `g`; the points and their normals
+    fn profile(
+        &self,
+        mesh: &Topology,
+        v: usize,
+        f: usize,
+        g: usize,
+        segments: usize,
+    ) -> Vec<(Vector3<f32>, Vector3<f32>)> {
+        let corner = |f: usize| mesh.corner(f, mesh.faces[f].iter().position(|&i| i == v).unwrap());
+        let (cf, cg) = (corner(f), corner(g));
+        // computed the same way from both sides, so that the strips and
+        // the corners line up exactly
+        if self.of[cf] > self.of[cg] {
+            let mut profile = self.profile(mesh, v, g, f, segments);
+            profile.reverse();
+            return profile;
+        }
+        let (a, b) = (self.point(cf), self.point(cg));
+        let (nf, ng) = (mesh.normals[f], mesh.normals[g]);
+        if segments == 1 {
+            return vec![(a, nf), (b, ng)];
+        }
+        // an arc tangent to both faces: from the point on the edge the two
+        // cut back points come from, around the far corner of their
+        // parallelogram
+        let p = mesh.points[v];
+        let dir = try_normalize(nf.cross(ng)).unwrap_or(nf);
+        let on_edge = |q: Vector3<f32>| p + dir * dir.dot(q - p);
+        let c = (on_edge(a) + on_edge(b)) * 0.5;
+        let o = a + b - c;
+        (0..=segments)
+            .map(|s| {
+                let t = s as f32 / segments as f32 * FRAC_PI_2;
+                let (cos, sin) = (t.cos(), t.sin());
+                let n = nf * cos + ng * sin;
+                (
+                    o + (c - b) * cos + (c - a) * sin,
+                    try_normalize(n).unwrap_or(n),
+                )
+            })
+            .collect()
+    }
+}
+
+// the tip of a rounded corner: on the sphere of radius `width` touching
+// the faces around the vertex `v`, towards `v`
+fn corner_center(
+    v: Vector3<f32>,
+    ring: &[(Vector3<f32>, Vector3<f32>)],
+    planes: &[Vector3<f32>],
+    width: f32,
+) -> Vector3<f32> {
+    let mean = ring.iter().fold(Vector3::new(0., 0., 0.), |a, p| a + p.0) / ring.len() as f32;
+    let ata = planes
+        .iter()
+        .fold(Matrix3::from_value(0.), |a, &n| a + outer(n, n));
+    let atb = planes
+        .iter()
+        .fold(Vector3::new(0., 0., 0.), |a, &n| a + n * (n.dot(v) - width));
+    match ata.invert() {
+        Some(inv) if ata.determinant().abs() > 1e-4 => {
+            let o = inv * atb;
+            try_normalize(v - o).map_or(mean, |d| o + d * width)
+        }
+        _ => mean,
+    }
+}
+
+// a polygon with the normal of its plane at every vertex
+fn flat(points: &[Vector3<f32>]) -> Vec<(Vector3<f32>, Vector3<f32>)> {
+    let n = newell_normal(points);
+    let n = try_normalize(n).unwrap_or(n);
+    points.iter().map(|&p| (p, n)).collect()
+}
+
+#[derive(Default)]
+struct Output {
+    vertices: Vec<Vertex>,
+    polygons: Vec<Polygon<usize>>,
+}
+
+impl Output {
+    fn vertex(&mut self, pos: Vector3<f32>, normal: Vector3<f32>) -> usize {
+        self.vertices.push(Vertex {
+            pos: pos.into(),
+            normal: normal.into(),
+        });
+        self.vertices.len() - 1
+    }
+
+    fn polygon(&mut self, ring: Vec<(Vector3<f32>, Vector3<f32>)>) {
+        let base = self.vertices.len();
+        for &(p, n) in &ring {
+            self.vertex(p, n);
+        }
+        self.polygons.push(match ring.len() {
+            3 => PolyTri(Triangle::new(base, base + 1, base + 2)),
+            4 => PolyQuad(Quad::new(base, base + 1, base + 2, base + 3)),
+            n => PolyNGon((base..base + n).collect()),
+        });
+    }
+}
@@ -0,0 +1,159 @@
+//! Bounding volumes of vertex and polygon streams.
+
+use crate::analytic::{Aabb, Sphere};
+use crate::poly::EmitVertices;
+use crate::{ConstNGon, Line, NGon, Polygon, Position, Quad, Triangle, Vertex};
+
+/// Something made of one or more positions, such as a vertex or a
+/// polygon of vertices.
+pub trait EmitPositions {
+    /// Consume `self`, calling `emit` with each of its positions.
+    fn emit_positions<F>(self, emit: F)
+    where
+        F: FnMut(Position);
+}
+
+impl EmitPositions for Position {
+    fn emit_positions<F>(self, mut emit: F)
+    where
+        F: FnMut(Position),
+    {
+        emit(self)
+    }
+}
+
+impl EmitPositions for Vertex {
+    fn emit_positions<F>(self, mut emit: F)
+    where
+        F: FnMut(Position),
+    {
+        emit(self.pos)
+    }
+}
+
+impl<V: EmitPositions> EmitPositions for Line<V> {
+    fn emit_positions<F>(self, mut emit: F)
+    where
+        F: FnMut(Position),
+    {
+        self.emit_vertices(|v| v.emit_positions(&mut emit))
+    }
+}
+
+impl<V: EmitPositions> EmitPositions for Triangle<V> {
+    fn emit_positions<F>(self, mut emit: F)
+    where
+        F: FnMut(Position),
+    {
+        self.emit_vertices(|v| v.emit_positions(&mut emit))
+    }
+}
+
+impl<V: EmitPositions> EmitPositions for Quad<V> {
+    fn emit_positions<F>(self, mut emit: F)
+    where
+        F: FnMut(Position),
+    {
+        self.emit_vertices(|v| v.emit_positions(&mut emit))
+    }
+}
+
+impl<V: EmitPositions> EmitPositions for NGon<V> {
+    fn emit_positions<F>(self, mut emit: F)
+    where
+        F: FnMut(Position),
+    {
+        self.emit_vertices(|v| v.emit_positions(&mut emit))
+    }
+}
+
+impl<V: EmitPositions, const N: usize> EmitPositions for ConstNGon<V, N> {
+    fn emit_positions<F>(self, mut emit: F)
+    where
+        F: FnMut(Position),
+    {
+        self.emit_vertices(|v| v.emit_positions(&mut emit))
+    }
+}
+
+impl<V: EmitPositions> EmitPositions for Polygon<V> {
+    fn emit_positions<F>(self, mut emit: F)
+    where
+        F: FnMut(Position),
+    {
+        self.emit_vertices(|v| v.emit_positions(&mut emit))
+    }
+}
+
+/// Terminal adapters computing bounding volumes around all of the
+/// positions of a stream of vertices or polygons.
+pub trait Bounds: Sized {
+    /// Compute the smallest axis aligned box around the stream.
+    /// Returns `None` if the stream is empty.
+    fn bounding_box(self) -> Option<Aabb>;
+
+    /// Compute the smallest sphere around the stream, see
+    /// `Sphere::enclosing`. Returns `None` if the stream is empty.
+    fn bounding_sphere(self) -> Option<Sphere>;
+}
+
+impl<T, P> Bounds for T
+where
+    T: Iterator<Item = P>,
+    P: EmitPositions,
+{
+    fn bounding_box(self) -> Option<Aabb> {
+        let mut aabb: Option<Aabb> = None;
+        for item in self {
+            item.emit_positions(|p| {
+                aabb = Some(match aabb {
+                    Some(aabb) => aabb.grow(p),
+                    None => Aabb::new(p, p),
+                });
+            });
+        }
+        aabb
+    }
+
+    fn bounding_sphere(self) -> Option<Sphere> {
+        let mut points = Vec::new();
+        for item in self {
+            item.emit_positions(|p| points.push(p));
+        }
+        Sphere::enclosing(points)
+    }
+}
+
+/// Compute the smallest axis aligned box around the positions used by the
+/// `triangles` of an indexed mesh. Positions that no triangle refers to
+/// are left out. Returns `None` if there are no triangles.
+pub fn indexed_bounding_box(positions: &[Position], triangles: &[Triangle<usize>]) -> Option<Aabb> {
+    used(positions, triangles).bounding_box()
+}
+
+/// Compute the smallest sphere around the positions used by the
+/// `triangles` of an indexed mesh. Positions that no triangle refers to
+/// are left out. Returns `None` if there are no triangles.
+pub fn indexed_bounding_sphere(
+    positions: &[Position],
+    triangles: &[Triangle<usize>],
+) -> Option<Sphere> {
+    used(positions, triangles).bounding_sphere()
+}
+
+fn used<'a>(
+    positions: &'a [Position],
+    triangles: &[Triangle<usize>],
+) -> impl Iterator<Item = Position> + 'a {
+    let mut used = vec![false; positions.len()];
+    for t in triangles {
+        used[t.x] = true;
+        used[t.y] = true;
+        used[t.z] = true;
+    }
+    positions
+        .iter()
+        .zip(used)
+        .filter(|&(_, used)| used)
+        .map(|(&p, _)| p)
+}
@@ -0,0 +1,89 @@
+use cgmath::{InnerSpace, Vector3};
+
+use crate::flat::{flat_faces_generator, FlatFaces};
+use crate::math;
+
+/// The twelve edges of a `Cube` as square beams, a closed solid for
+/// gizmos, selection boxes and window frames.
+///
+/// The outside of each face of the cube, in the order +x, -x, +y, -y, +z,
+/// -z, is a square ring cut into four quads around the hole through it.
+/// Then come the inner faces of the beams, two for each beam, facing the
+/// holes. Every face has its own vertices, so that it is flat shaded.
+#[derive(Clone, Debug)]
+pub struct BoxFrame {
+    faces: FlatFaces,
+}
+
+impl BoxFrame {
+    /// Create the frame of the cube from -1 to 1 on each axis, with beams
+    /// `thickness` wide, between 0 and 1.
+    pub fn new(thickness: f32) -> Self {
+        assert!(thickness > 0. && thickness < 1.);
+        let s = 1. - thickness;
+        let mut faces = FlatFaces::default();
+        // a point from its coordinates along each of the axes of `frame`
+        let point = |frame: [usize; 3], u: f32, v: f32, w: f32| {
+            let mut p = Vector3::new(0., 0., 0.);
+            p[frame[0]] = u;
+            p[frame[1]] = v;
+            p[frame[2]] = w;
+            p
+        };
+        let mut add = |mut corners: [Vector3<f32>; 4], facing: Vector3<f32>| {
+            if math::newell_normal(&corners).dot(facing) < 0. {
+                corners.reverse();
+            }
+            faces.add(&corners);
+        };
+
+        // the rings, around the corners of each face
+        for axis in 0..3 {
+            let frame = [(axis + 1) % 3, (axis + 2) % 3, axis];
+            for side in [1., -1.] {
+                let square = [(1., 1.), (-1., 1.), (-1., -1.), (1., -1.)];
+                for k in 0..4 {
+                    let (a, b) = (square[k], square[(k + 1) % 4]);
+                    add(
+                        [
+                            point(frame, a.0, a.1, side),
+                            point(frame, b.0, b.1, side),
+                            point(frame, b.0 * s, b.1 * s, side),
+                            point(frame, a.0 * s, a.1 * s, side),
+                        ],
+                        point(frame, 0., 0., side),
+                    );
+                }
+            }
+        }
+
+        // the beam along each axis, in each corner across it, has a face
+        // on each of the two holes it borders
+        for axis in 0..3 {
+            let frame = [(axis + 1) % 3, (axis + 2) % 3, axis];
+            for (b, c) in [(1., 1.), (-1., 1.), (-1., -1.), (1., -1.)] {
+                add(
+                    [
+                        point(frame, b * s, c * s, -s),
+                        point(frame, b * s, c * s, s),
+                        point(frame, b * s, c, s),
+                        point(frame, b * s, c, -s),
+                    ],
+                    point(frame, -b, 0., 0.),
+                );
+                add(
+                    [
+                        point(frame, b * s, c * s, -s),
+                        point(frame, b * s, c * s, s),
+                        point(frame, b, c * s, s),
+                        point(frame, b, c * s, -s),
+                    ],
+                    point(frame, 0., -c, 0.),
+                );
+            }
+        }
+        BoxFrame { faces }
+    }
+}
+
+flat_faces_generator!(BoxFrame);
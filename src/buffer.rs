@@ -0,0 +1,458 @@
+//! Packing of vertices into interleaved byte buffers, ready to be
+//! uploaded to the GPU.
+//!
+//! Each attribute can be stored at full precision or quantized into a
+//! more compact format, which matters when vertex bandwidth is scarce.
+//! The `BufferLayout` returned next to the bytes describes the formats and
+//! offsets that were used, to set up the vertex fetch with.
+//!
+//! All the values are little endian, and every attribute starts on a four
+//! byte boundary.
+//!
+//! ```
+//! use genmesh::buffer::{Attribute, Format, InterleavedBuilder, NormalFormat, PositionFormat};
+//! use genmesh::generators::Cube;
+//! use genmesh::Vertices;
+//!
+//! let mut builder = InterleavedBuilder::new(PositionFormat::Float16, NormalFormat::Octahedral);
+//! builder.extend(Cube::new().vertices());
+//! let (layout, bytes) = builder.finish();
+//! assert_eq!(12, layout.stride);
+//! assert_eq!(Format::Snorm16x2, layout.attributes[1].format);
+//! assert_eq!(Attribute::Normal, layout.attributes[1].attribute);
+//! assert_eq!(24 * 12, bytes.len());
+//! ```
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::{Normal, Vertex};
+
+/// The meaning of an attribute of the vertices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Attribute {
+    /// The position of the vertex
+    Position,
+    /// The normal of the vertex
+    Normal,
+    /// The texture coordinates of the vertex
+    TexCoord,
+}
+
+impl Attribute {
+    /// The shader input location conventionally used for the attribute:
+    /// 0 for positions, 1 for normals and 2 for texture coordinates.
+    pub fn shader_location(self) -> u32 {
+        match self {
+            Attribute::Position => 0,
+            Attribute::Normal => 1,
+            Attribute::TexCoord => 2,
+        }
+    }
+}
+
+/// The format an attribute is stored in. The names follow the usual
+/// graphics API conventions: the type of each component, then the count.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Two 32-bit floats
+    Float32x2,
+    /// Three 32-bit floats
+    Float32x3,
+    /// Two 16-bit floats
+    Float16x2,
+    /// Four 16-bit floats
+    Float16x4,
+    /// Two unsigned 16-bit integers mapped to `[0, 1]`
+    Unorm16x2,
+    /// Two signed 16-bit integers mapped to `[-1, 1]`
+    Snorm16x2,
+}
+
+impl Format {
+    /// The size of a value of this format, in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            Format::Float32x2 => 8,
+            Format::Float32x3 => 12,
+            Format::Float16x2 | Format::Unorm16x2 | Format::Snorm16x2 => 4,
+            Format::Float16x4 => 8,
+        }
+    }
+}
+
+/// Storage of the positions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PositionFormat {
+    /// Full precision, `Format::Float32x3`
+    Float32,
+    /// Half floats, `Format::Float16x4` with `w` set to 1
+    Float16,
+}
+
+/// Storage of the normals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NormalFormat {
+    /// Full precision, `Format::Float32x3`
+    Float32,
+    /// Half floats, `Format::Float16x4` with `w` set to 0
+    Float16,
+    /// Octahedral encoding in `Format::Snorm16x2`, see `encode_octahedral`
+    Octahedral,
+}
+
+/// Storage of the texture coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UvFormat {
+    /// Full precision, `Format::Float32x2`
+    Float32,
+    /// Half floats, `Format::Float16x2`
+    Float16,
+    /// `Format::Unorm16x2`; coordinates outside of `[0, 1]` are clamped
+    Unorm16,
+}
+
+/// Where an attribute is found in each vertex of a buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AttributeLayout {
+    /// What the attribute holds
+    pub attribute: Attribute,
+    /// How it is stored
+    pub format: Format,
+    /// Offset from the start of the vertex, in bytes
+    pub offset: usize,
+}
+
+/// The layout of the vertices of an interleaved buffer.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BufferLayout {
+    /// Distance between two consecutive vertices, in bytes
+    pub stride: usize,
+    /// The attributes of each vertex, in the order they are stored
+    pub attributes: Vec<AttributeLayout>,
+}
+
+#[cfg(feature = "wgpu")]
+impl From<Format> for wgpu::VertexFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Float32x2 => wgpu::VertexFormat::Float32x2,
+            Format::Float32x3 => wgpu::VertexFormat::Float32x3,
+            Format::Float16x2 => wgpu::VertexFormat::Float16x2,
+            Format::Float16x4 => wgpu::VertexFormat::Float16x4,
+            Format::Unorm16x2 => wgpu::VertexFormat::Unorm16x2,
+            Format::Snorm16x2 => wgpu::VertexFormat::Snorm16x2,
+        }
+    }
+}
+
+#[cfg(feature = "wgpu")]
+impl BufferLayout {
+    /// Describe the attributes for wgpu, each bound to the shader location
+    /// given by `Attribute::shader_location`.
+    pub fn wgpu_attributes(&self) -> Vec<wgpu::VertexAttribute> {
+        self.attributes
+            .iter()
+            .map(|a| wgpu::VertexAttribute {
+                format: a.format.into(),
+                offset: a.offset as wgpu::BufferAddress,
+                shader_location: a.attribute.shader_location(),
+            })
+            .collect()
+    }
+
+    /// Describe the buffer for wgpu, with the `attributes` returned by
+    /// `wgpu_attributes`, which have to outlive the description.
+    pub fn wgpu_layout<'a>(
+        &self,
+        attributes: &'a [wgpu::VertexAttribute],
+    ) -> wgpu::VertexBufferLayout<'a> {
+        wgpu::VertexBufferLayout {
+            array_stride: self.stride as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes,
+        }
+    }
+}
+
+#[cfg(feature = "wgpu")]
+const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 2] = [
+    wgpu::VertexAttribute {
+        format: wgpu::VertexFormat::Float32x3,
+        offset: 0,
+        shader_location: 0,
+    },
+    wgpu::VertexAttribute {
+        format: wgpu::VertexFormat::Float32x3,
+        offset: 12,
+        shader_location: 1,
+    },
+];
+
+#[cfg(feature = "wgpu")]
+impl Vertex {
+    /// Describe a buffer of plain `Vertex` values for wgpu, with the
+    /// position at shader location 0 and the normal at location 1.
+    pub fn wgpu_layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &VERTEX_ATTRIBUTES,
+        }
+    }
+}
+
+/// Writes vertices one after the other into an interleaved buffer,
+/// in the chosen formats.
+#[derive(Clone, Debug)]
+pub struct InterleavedBuilder {
+    position: PositionFormat,
+    normal: NormalFormat,
+    uv: Option<UvFormat>,
+    data: Vec<u8>,
+}
+
+impl InterleavedBuilder {
+    /// Create a builder for vertices made of a position and a normal.
+    pub fn new(position: PositionFormat, normal: NormalFormat) -> Self {
+        InterleavedBuilder {
+            position,
+            normal,
+            uv: None,
+            data: Vec::new(),
+        }
+    }
+
+    /// Also store texture coordinates after the normal. The vertices
+    /// must then be added with `push_with_uv`.
+    pub fn with_uvs(mut self, uv: UvFormat) -> Self {
+        self.uv = Some(uv);
+        self
+    }
+
+    /// The layout of the buffer being built.
+    pub fn layout(&self) -> BufferLayout {
+        let mut attributes = Vec::with_capacity(3);
+        let mut offset = 0;
+        let mut add = |attribute, format: Format| {
+            attributes.push(AttributeLayout {
+                attribute,
+                format,
+                offset,
+            });
+            offset += format.size();
+        };
+        add(
+            Attribute::Position,
+            match self.position {
+                PositionFormat::Float32 => Format::Float32x3,
+                PositionFormat::Float16 => Format::Float16x4,
+            },
+        );
+        add(
+            Attribute::Normal,
+            match self.normal {
+                NormalFormat::Float32 => Format::Float32x3,
+                NormalFormat::Float16 => Format::Float16x4,
+                NormalFormat::Octahedral => Format::Snorm16x2,
+            },
+        );
+        if let Some(uv) = self.uv {
+            add(
+                Attribute::TexCoord,
+                match uv {
+                    UvFormat::Float32 => Format::Float32x2,
+                    UvFormat::Float16 => Format::Float16x2,
+                    UvFormat::Unorm16 => Format::Unorm16x2,
+                },
+            );
+        }
+        BufferLayout {
+            stride: offset,
+            attributes,
+        }
+    }
+
+    /// Number of vertices written so far.
+    pub fn len(&self) -> usize {
+        self.data.len() / self.layout().stride
+    }
+
+    /// Whether no vertex has been written yet.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Append a vertex. Panics if the buffer has texture coordinates.
+    pub fn push(&mut self, vertex: Vertex) {
+        assert!(self.uv.is_none(), "the buffer expects texture coordinates");
+        self.write(vertex);
+    }
+
+    /// Append a vertex and its texture coordinates. Panics if the buffer
+    /// has no texture coordinates.
+    pub fn push_with_uv(&mut self, vertex: Vertex, uv: [f32; 2]) {
+        let format = self
+            .uv
+            .expect("the buffer does not have texture coordinates");
+        self.write(vertex);
+        match format {
+            UvFormat::Float32 => self.f32s(&uv),
+            UvFormat::Float16 => self.f16s(&uv),
+            UvFormat::Unorm16 => {
+                for &c in &uv {
+                    let q = (c.clamp(0., 1.) * 65535.).round() as u16;
+                    self.data.extend_from_slice(&q.to_le_bytes());
+                }
+            }
+        }
+    }
+
+    /// Append all the vertices of `vertices`. Panics if the buffer has
+    /// texture coordinates.
+    pub fn extend<I: IntoIterator<Item = Vertex>>(&mut self, vertices: I) {
+        for v in vertices {
+            self.push(v);
+        }
+    }
+
+    /// Return the layout together with the bytes of the vertices.
+    pub fn finish(self) -> (BufferLayout, Vec<u8>) {
+        (self.layout(), self.data)
+    }
+
+    fn write(&mut self, vertex: Vertex) {
+        let p = vertex.pos;
+        match self.position {
+            PositionFormat::Float32 => self.f32s(&[p.x, p.y, p.z]),
+            PositionFormat::Float16 => self.f16s(&[p.x, p.y, p.z, 1.]),
+        }
+        let n = vertex.normal;
+        match self.normal {
+            NormalFormat::Float32 => self.f32s(&[n.x, n.y, n.z]),
+            NormalFormat::Float16 => self.f16s(&[n.x, n.y, n.z, 0.]),
+            NormalFormat::Octahedral => {
+                for c in &encode_octahedral(n) {
+                    self.data.extend_from_slice(&c.to_le_bytes());
+                }
+            }
+        }
+    }
+
+    fn f32s(&mut self, values: &[f32]) {
+        for v in values {
+            self.data.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+
+    fn f16s(&mut self, values: &[f32]) {
+        for &v in values {
+            self.data.extend_from_slice(&f32_to_f16(v).to_le_bytes());
+        }
+    }
+}
+
+/// Convert a float to the bits of the nearest half float, rounding ties
+/// to even. Values too large for a half float become infinite.
+pub fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let man = bits & 0x7f_ffff;
+
+    if exp == 0xff {
+        // infinity stays infinity, NaN stays NaN
+        return sign | 0x7c00 | if man != 0 { 0x200 } else { 0 };
+    }
+    let exp = exp - 127 + 15;
+    if exp >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if exp <= 0 {
+        // subnormal half float, or zero
+        if exp < -10 {
+            return sign;
+        }
+        let man = man | 0x80_0000;
+        let shift = (14 - exp) as u32;
+        let half = man >> shift;
+        let round = (man >> (shift - 1)) & 1 != 0;
+        let sticky = man & ((1 << (shift - 1)) - 1) != 0;
+        let half = if round && (sticky || half & 1 != 0) {
+            half + 1
+        } else {
+            half
+        };
+        return sign | half as u16;
+    }
+
+    let half = ((exp as u32) << 10) | (man >> 13);
+    let round = man & 0x1000 != 0;
+    let sticky = man & 0xfff != 0;
+    // a carry out of the mantissa correctly bumps the exponent
+    let half = if round && (sticky || half & 1 != 0) {
+        half + 1
+    } else {
+        half
+    };
+    sign | half as u16
+}
+
+/// Convert the bits of a half float back to a float. This is exact.
+pub fn f16_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exp = ((half >> 10) & 0x1f) as u32;
+    let man = (half & 0x3ff) as u32;
+    match exp {
+        0 => {
+            let value = man as f32 / (1 << 24) as f32;
+            if sign != 0 {
+                -value
+            } else {
+                value
+            }
+        }
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (man << 13)),
+        _ => f32::from_bits(sign | ((exp + 112) << 23) | (man << 13)),
+    }
+}
+
+/// Encode a unit normal into two signed normalized 16-bit values, by
+/// projecting it onto an octahedron that is then unfolded into a square.
+/// The error is well below what lighting can show.
+pub fn encode_octahedral(normal: Normal) -> [i16; 2] {
+    let n = Vector3::from(normal);
+    let l1 = n.x.abs() + n.y.abs() + n.z.abs();
+    if l1 == 0. || !l1.is_finite() {
+        return [0, 0];
+    }
+    let (x, y) = (n.x / l1, n.y / l1);
+    let (x, y) = if n.z < 0. {
+        ((1. - y.abs()) * sign(x), (1. - x.abs()) * sign(y))
+    } else {
+        (x, y)
+    };
+    let snorm = |v: f32| (v.clamp(-1., 1.) * 32767.).round() as i16;
+    [snorm(x), snorm(y)]
+}
+
+/// Decode a normal stored by `encode_octahedral`.
+pub fn decode_octahedral(encoded: [i16; 2]) -> Normal {
+    let unorm = |v: i16| (v as f32 / 32767.).max(-1.);
+    let (x, y) = (unorm(encoded[0]), unorm(encoded[1]));
+    let z = 1. - x.abs() - y.abs();
+    let (x, y) = if z < 0. {
+        ((1. - y.abs()) * sign(x), (1. - x.abs()) * sign(y))
+    } else {
+        (x, y)
+    };
+    Vector3::new(x, y, z).normalize().into()
+}
+
+// like `f32::signum`, but without the distinction between 0 and -0
+fn sign(v: f32) -> f32 {
+    if v >= 0. {
+        1.
+    } else {
+        -1.
+    }
+}
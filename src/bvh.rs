@@ -0,0 +1,370 @@
+//! Bounding volume hierarchy over triangles, to speed up spatial queries.
+//!
+//! The tree is built once from a list of triangles, and answers ray casts,
+//! box overlap and nearest point queries in logarithmic time. When the
+//! triangles move without changing their connectivity, as with animated
+//! meshes, the tree can be refitted to them instead of rebuilt.
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::analytic::Aabb;
+use crate::{Position, Triangle};
+
+// triangles per leaf
+const LEAF_SIZE: usize = 4;
+
+#[derive(Clone, Debug)]
+struct Node {
+    bounds: Aabb,
+    // first triangle of a leaf, or the left child of an interior node,
+    // whose right child immediately follows it
+    first: usize,
+    // number of triangles in a leaf, zero for interior nodes
+    count: usize,
+}
+
+/// A bounding volume hierarchy of axis aligned boxes over triangles.
+///
+/// Triangles are identified by their index in the slice the tree was
+/// built from.
+#[derive(Clone, Debug)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    // triangle indices, ordered so that each leaf covers a range of them
+    order: Vec<usize>,
+    triangles: Vec<[Vector3<f32>; 3]>,
+}
+
+/// Where a ray hits a triangle of a `Bvh`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// Index of the triangle that was hit
+    pub triangle: usize,
+    /// Distance from the origin of the ray to the hit, in multiples of
+    /// the length of the ray direction
+    pub distance: f32,
+    /// The point that was hit
+    pub point: Position,
+}
+
+/// The point of a `Bvh` closest to a query point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Nearest {
+    /// Index of the triangle the point lies on
+    pub triangle: usize,
+    /// Distance between the query point and `point`
+    pub distance: f32,
+    /// The closest point
+    pub point: Position,
+}
+
+impl Bvh {
+    /// Build the hierarchy, splitting the triangles in halves around the
+    /// median of their centers along the longest axis of each node.
+    pub fn build(triangles: &[Triangle<Position>]) -> Self {
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            order: (0..triangles.len()).collect(),
+            triangles: triangles
+                .iter()
+                .map(|t| [t.x.into(), t.y.into(), t.z.into()])
+                .collect(),
+        };
+        if triangles.is_empty() {
+            return bvh;
+        }
+
+        let centers: Vec<Vector3<f32>> = bvh
+            .triangles
+            .iter()
+            .map(|&[a, b, c]| (a + b + c) / 3.)
+            .collect();
+        bvh.nodes.push(Node {
+            bounds: bvh.range_bounds(0, triangles.len()),
+            first: 0,
+            count: triangles.len(),
+        });
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let (first, count) = (bvh.nodes[n].first, bvh.nodes[n].count);
+            if count <= LEAF_SIZE {
+                continue;
+            }
+
+            let spread = Aabb::from_points(
+                bvh.order[first..first + count]
+                    .iter()
+                    .map(|&i| centers[i].into()),
+            )
+            .unwrap()
+            .size();
+            let axis = if spread.x >= spread.y && spread.x >= spread.z {
+                0
+            } else if spread.y >= spread.z {
+                1
+            } else {
+                2
+            };
+            let mid = count / 2;
+            bvh.order[first..first + count].select_nth_unstable_by(mid, |&a, &b| {
+                centers[a][axis].total_cmp(&centers[b][axis])
+            });
+
+            let left = bvh.nodes.len();
+            for &(start, len) in &[(first, mid), (first + mid, count - mid)] {
+                bvh.nodes.push(Node {
+                    bounds: bvh.range_bounds(start, len),
+                    first: start,
+                    count: len,
+                });
+            }
+            bvh.nodes[n].first = left;
+            bvh.nodes[n].count = 0;
+            stack.push(left);
+            stack.push(left + 1);
+        }
+        bvh
+    }
+
+    /// Update the boxes of the tree after the triangles have moved.
+    ///
+    /// `triangles` must hold as many triangles as the tree was built from,
+    /// in the same order. The shape of the tree is kept, so queries stay
+    /// correct but may slow down if the triangles moved a lot relative to
+    /// each other.
+    pub fn refit(&mut self, triangles: &[Triangle<Position>]) {
+        assert_eq!(triangles.len(), self.triangles.len());
+        for (dst, t) in self.triangles.iter_mut().zip(triangles) {
+            *dst = [t.x.into(), t.y.into(), t.z.into()];
+        }
+        // children always come after their parent
+        for n in (0..self.nodes.len()).rev() {
+            let Node { first, count, .. } = self.nodes[n];
+            self.nodes[n].bounds = if count > 0 {
+                self.range_bounds(first, count)
+            } else {
+                self.nodes[first].bounds.union(self.nodes[first + 1].bounds)
+            };
+        }
+    }
+
+    /// The box around all of the triangles, or `None` if there are none.
+    pub fn bounds(&self) -> Option<Aabb> {
+        self.nodes.first().map(|n| n.bounds)
+    }
+
+    /// Find the indices of the triangles whose bounding boxes overlap
+    /// `aabb`. This is a conservative test: the triangles themselves may
+    /// miss the box.
+    pub fn query_aabb(&self, aabb: &Aabb) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = match self.nodes.get(n) {
+                Some(node) if node.bounds.intersects(aabb) => node,
+                _ => continue,
+            };
+            if node.count == 0 {
+                stack.push(node.first);
+                stack.push(node.first + 1);
+                continue;
+            }
+            for &i in &self.order[node.first..node.first + node.count] {
+                let [a, b, c] = self.triangles[i];
+                let bounds = Aabb::new(a.into(), a.into()).grow(b.into()).grow(c.into());
+                if bounds.intersects(aabb) {
+                    found.push(i);
+                }
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+
+    /// Find the first triangle hit by the ray starting at `origin` going
+    /// along `direction`, which does not need to be of unit length. Both
+    /// sides of the triangles can be hit.
+    pub fn raycast(&self, origin: Position, direction: Position) -> Option<RayHit> {
+        let o = Vector3::from(origin);
+        let d = Vector3::from(direction);
+        let inv = Vector3::new(1. / d.x, 1. / d.y, 1. / d.z);
+
+        let mut best: Option<(usize, f32)> = None;
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = match self.nodes.get(n) {
+                Some(node) => node,
+                None => continue,
+            };
+            let limit = best.map_or(f32::INFINITY, |b| b.1);
+            if !ray_hits_box(o, inv, &node.bounds, limit) {
+                continue;
+            }
+            if node.count == 0 {
+                stack.push(node.first);
+                stack.push(node.first + 1);
+                continue;
+            }
+            for &i in &self.order[node.first..node.first + node.count] {
+                if let Some(t) = ray_triangle(o, d, &self.triangles[i]) {
+                    if best.is_none_or(|b| t < b.1) {
+                        best = Some((i, t));
+                    }
+                }
+            }
+        }
+        best.map(|(triangle, distance)| RayHit {
+            triangle,
+            distance,
+            point: (o + d * distance).into(),
+        })
+    }
+
+    /// Find the point on the triangles closest to `point`, or `None` if
+    /// the tree is empty.
+    pub fn nearest(&self, point: Position) -> Option<Nearest> {
+        let p = Vector3::from(point);
+        let mut best: Option<(usize, f32, Vector3<f32>)> = None;
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = match self.nodes.get(n) {
+                Some(node) => node,
+                None => continue,
+            };
+            if best.is_some_and(|b| box_distance2(p, &node.bounds) >= b.1) {
+                continue;
+            }
+            if node.count == 0 {
+                // visit the closer child first, it is pushed last
+                let (l, r) = (node.first, node.first + 1);
+                let dl = box_distance2(p, &self.nodes[l].bounds);
+                let dr = box_distance2(p, &self.nodes[r].bounds);
+                if dl < dr {
+                    stack.push(r);
+                    stack.push(l);
+                } else {
+                    stack.push(l);
+                    stack.push(r);
+                }
+                continue;
+            }
+            for &i in &self.order[node.first..node.first + node.count] {
+                let q = closest_point(p, &self.triangles[i]);
+                let d2 = (q - p).magnitude2();
+                if best.is_none_or(|b| d2 < b.1) {
+                    best = Some((i, d2, q));
+                }
+            }
+        }
+        best.map(|(triangle, d2, q)| Nearest {
+            triangle,
+            distance: d2.sqrt(),
+            point: q.into(),
+        })
+    }
+
+    fn range_bounds(&self, first: usize, count: usize) -> Aabb {
+        Aabb::from_points(
+            self.order[first..first + count]
+                .iter()
+                .flat_map(|&i| self.triangles[i].iter().map(|&v| v.into())),
+        )
+        .unwrap()
+    }
+}
+
+fn ray_hits_box(o: Vector3<f32>, inv: Vector3<f32>, b: &Aabb, limit: f32) -> bool {
+    let (min, max) = (Vector3::from(b.min), Vector3::from(b.max));
+    let (mut near, mut far) = (0f32, limit);
+    for axis in 0..3 {
+        if inv[axis].is_infinite() {
+            // parallel to the slab, the ray is either always in it or never
+            if o[axis] < min[axis] || o[axis] > max[axis] {
+                return false;
+            }
+            continue;
+        }
+        let t0 = (min[axis] - o[axis]) * inv[axis];
+        let t1 = (max[axis] - o[axis]) * inv[axis];
+        near = near.max(t0.min(t1));
+        far = far.min(t0.max(t1));
+    }
+    near <= far
+}
+
+// Moller-Trumbore
+fn ray_triangle(o: Vector3<f32>, d: Vector3<f32>, t: &[Vector3<f32>; 3]) -> Option<f32> {
+    let e1 = t[1] - t[0];
+    let e2 = t[2] - t[0];
+    let p = d.cross(e2);
+    let det = e1.dot(p);
+    if det.abs() < f32::MIN_POSITIVE {
+        return None;
+    }
+    let s = o - t[0];
+    let u = s.dot(p) / det;
+    if !(0. ..=1.).contains(&u) {
+        return None;
+    }
+    let q = s.cross(e1);
+    let v = d.dot(q) / det;
+    if v < 0. || u + v > 1. {
+        return None;
+    }
+    let dist = e2.dot(q) / det;
+    if dist >= 0. {
+        Some(dist)
+    } else {
+        None
+    }
+}
+
+fn box_distance2(p: Vector3<f32>, b: &Aabb) -> f32 {
+    let (min, max) = (Vector3::from(b.min), Vector3::from(b.max));
+    let mut d2 = 0.;
+    for axis in 0..3 {
+        let d = (min[axis] - p[axis]).max(p[axis] - max[axis]).max(0.);
+        d2 += d * d;
+    }
+    d2
+}
+
+// From Real-Time Collision Detection, by Christer Ericson
+fn closest_point(p: Vector3<f32>, t: &[Vector3<f32>; 3]) -> Vector3<f32> {
+    let [a, b, c] = *t;
+    let (ab, ac) = (b - a, c - a);
+
+    let ap = p - a;
+    let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+    if d1 <= 0. && d2 <= 0. {
+        return a;
+    }
+    let bp = p - b;
+    let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+    if d3 >= 0. && d4 <= d3 {
+        return b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0. && d1 >= 0. && d3 <= 0. {
+        return a + ab * (d1 / (d1 - d3));
+    }
+    let cp = p - c;
+    let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+    if d6 >= 0. && d5 <= d6 {
+        return c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0. && d2 >= 0. && d6 <= 0. {
+        return a + ac * (d2 / (d2 - d6));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0. && d4 - d3 >= 0. && d5 - d6 >= 0. {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+    let sum = va + vb + vc;
+    if sum <= 0. {
+        // degenerate triangle
+        return a;
+    }
+    a + ab * (vb / sum) + ac * (vc / sum)
+}
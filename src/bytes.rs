@@ -0,0 +1,23 @@
+//! Zero copy views of vertex buffers as bytes, for uploading them to the
+//! GPU.
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::Vertex;
+
+// `Vertex` is `repr(C)` and made of six `f32`, so it has no padding and
+// every bit pattern is a valid value.
+unsafe impl Zeroable for Vertex {}
+unsafe impl Pod for Vertex {}
+
+/// View a buffer of plain data, such as vertices or indices, as bytes.
+pub trait AsBytes {
+    /// The bytes of the buffer, in native endianness.
+    fn as_bytes(&self) -> &[u8];
+}
+
+impl<T: Pod> AsBytes for [T] {
+    fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(self)
+    }
+}
@@ -1,22 +1,40 @@
 use super::generators::{IndexedPolygon, SharedVertex};
-use super::Polygon::PolyTri;
-use super::{Polygon, Triangle};
+use super::Polygon::{PolyQuad, PolyTri};
+use super::{MapVertex, Polygon, Quad, Triangle};
 use crate::Vertex;
 use std::f32::consts::PI;
 
 /// Represents a circle in the XY plane with radius of 1, centered at (0, 0, 0)
+///
+/// The disk is a fan of triangles around the center, surrounded by rings
+/// of quads when it is subdivided.
 #[derive(Clone, Copy)]
 pub struct Circle {
-    u: usize,
+    idx: usize,
+    end: usize,
     sub_u: usize,
+    sub_r: usize,
 }
 
 impl Circle {
     /// Create a new sphere.
     /// `u` is the number of points around the circle, must be > 3
     pub fn new(u: usize) -> Self {
-        assert!(u > 3);
-        Circle { u: 1, sub_u: u }
+        Circle::subdivide(u, 1)
+    }
+
+    /// Create a new subdivided circle.
+    /// `u` is the number of points around the circle, must be > 3
+    /// `r` is the number of rings from the center to the edge, the first
+    /// one being the fan of triangles around the center.
+    pub fn subdivide(u: usize, r: usize) -> Self {
+        assert!(u > 3 && r > 0);
+        Circle {
+            idx: 0,
+            end: u * r,
+            sub_u: u,
+            sub_r: r,
+        }
     }
 
     fn vert(&self, u: usize) -> Vertex {
@@ -26,9 +44,11 @@ impl Circle {
                 normal: [0., 0., 1.].into(),
             }
         } else {
-            let u = ((u - 1) as f32 / self.sub_u as f32) * PI * 2.;
+            let (r, u) = ((u - 1) / self.sub_u + 1, (u - 1) % self.sub_u);
+            let r = r as f32 / self.sub_r as f32;
+            let u = (u as f32 / self.sub_u as f32) * PI * 2.;
 
-            let p = [u.cos(), u.sin(), 0.];
+            let p = [u.cos() * r, u.sin() * r, 0.];
             Vertex {
                 pos: p.into(),
                 normal: [0., 0., 1.].into(),
@@ -41,43 +61,63 @@ impl Iterator for Circle {
     type Item = Polygon<Vertex>;
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.sub_u, Some(self.sub_u))
+        let n = self.end - self.idx;
+        (n, Some(n))
     }
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.u > self.sub_u {
+        if self.idx < self.end {
+            self.idx += 1;
+            Some(
+                self.indexed_polygon(self.idx - 1)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
             None
-        } else if self.u == self.sub_u {
-            self.u += 1;
-            Some(PolyTri(Triangle::new(
-                self.vert(0),
-                self.vert(self.u - 1),
-                self.vert(1),
-            )))
+        }
+    }
+}
+
+impl DoubleEndedIterator for Circle {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            Some(
+                self.indexed_polygon(self.end)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
         } else {
-            self.u += 1;
-            Some(PolyTri(Triangle::new(
-                self.vert(0),
-                self.vert(self.u - 1),
-                self.vert(self.u),
-            )))
+            None
         }
     }
 }
 
+impl ExactSizeIterator for Circle {}
+
 impl SharedVertex<Vertex> for Circle {
     fn shared_vertex(&self, idx: usize) -> Vertex {
         self.vert(idx)
     }
 
     fn shared_vertex_count(&self) -> usize {
-        self.sub_u + 1
+        self.sub_u * self.sub_r + 1
     }
 }
 
 impl IndexedPolygon<Polygon<usize>> for Circle {
     fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
-        if idx == self.sub_u - 1 {
+        if idx >= self.sub_u {
+            // the quads between ring `r - 1` and ring `r`
+            let (r, u) = (idx / self.sub_u, idx % self.sub_u);
+            let u1 = (u + 1) % self.sub_u;
+            let ring = |r: usize, u: usize| 1 + (r - 1) * self.sub_u + u;
+            PolyQuad(Quad::new(
+                ring(r, u),
+                ring(r + 1, u),
+                ring(r + 1, u1),
+                ring(r, u1),
+            ))
+        } else if idx == self.sub_u - 1 {
             PolyTri(Triangle::new(0, self.sub_u, 1))
         } else {
             PolyTri(Triangle::new(
@@ -89,7 +129,7 @@ impl IndexedPolygon<Polygon<usize>> for Circle {
     }
 
     fn indexed_polygon_count(&self) -> usize {
-        self.sub_u
+        self.sub_u * self.sub_r
     }
 }
 
@@ -98,6 +138,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_circle() {
         let circle = Circle::new(8);
         assert_eq!((8, Some(8)), circle.size_hint());
@@ -114,4 +155,30 @@ mod tests {
         assert_eq!(PolyTri(Triangle { x: 0, y: 1, z: 2 }), polys[0]);
         assert_eq!(Some(&PolyTri(Triangle { x: 0, y: 8, z: 1 })), polys.last());
     }
+
+    #[test]
+    fn test_subdivided_circle() {
+        let circle = Circle::subdivide(6, 3);
+        assert_eq!((18, Some(18)), circle.size_hint());
+        assert_eq!(19, circle.shared_vertex_count());
+        let polys = circle.indexed_polygon_iter().collect::<Vec<_>>();
+        assert_eq!(
+            Circle::new(6).indexed_polygon_iter().collect::<Vec<_>>(),
+            polys[..6]
+        );
+        assert_eq!(PolyQuad(Quad::new(1, 7, 8, 2)), polys[6]);
+        assert_eq!(PolyQuad(Quad::new(12, 18, 13, 7)), polys[17]);
+
+        // the rings are evenly spaced, and every face points up
+        for (i, v) in circle.shared_vertex_iter().enumerate().skip(1) {
+            let r = (v.pos.x * v.pos.x + v.pos.y * v.pos.y).sqrt();
+            assert!((r - ((i - 1) / 6 + 1) as f32 / 3.).abs() < 1e-6);
+        }
+        for p in circle {
+            if let PolyQuad(q) = p {
+                let (a, b, c) = (q.x.pos, q.y.pos, q.z.pos);
+                assert!((b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x) > 0.);
+            }
+        }
+    }
 }
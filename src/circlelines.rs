@@ -0,0 +1,91 @@
+use super::generators::{IndexedPolygon, SharedVertex};
+use super::{Line, MapVertex};
+use crate::Vertex;
+use std::f32::consts::PI;
+
+/// Represents the outline of a circle in the XY plane with radius of 1,
+/// centered at (0, 0, 0), for drawing debug views.
+#[derive(Clone, Copy)]
+pub struct CircleLines {
+    idx: usize,
+    end: usize,
+    sub_u: usize,
+}
+
+impl CircleLines {
+    /// Create a new circle outline.
+    /// `u` is the number of points around the circle, must be > 2
+    pub fn new(u: usize) -> Self {
+        assert!(u > 2);
+        CircleLines {
+            idx: 0,
+            end: u,
+            sub_u: u,
+        }
+    }
+
+    fn vert(&self, u: usize) -> Vertex {
+        let u = (u as f32 / self.sub_u as f32) * PI * 2.;
+        Vertex {
+            pos: [u.cos(), u.sin(), 0.].into(),
+            normal: [0., 0., 1.].into(),
+        }
+    }
+}
+
+impl Iterator for CircleLines {
+    type Item = Line<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.idx;
+        (n, Some(n))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.idx += 1;
+            Some(
+                self.indexed_polygon(self.idx - 1)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl DoubleEndedIterator for CircleLines {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            Some(
+                self.indexed_polygon(self.end)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for CircleLines {}
+
+impl SharedVertex<Vertex> for CircleLines {
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        self.vert(idx)
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.sub_u
+    }
+}
+
+impl IndexedPolygon<Line<usize>> for CircleLines {
+    fn indexed_polygon(&self, idx: usize) -> Line<usize> {
+        Line::new(idx, (idx + 1) % self.sub_u)
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.sub_u
+    }
+}
@@ -0,0 +1,76 @@
+//! Removing the faces of an indexed mesh that cover no area or cover the
+//! same area twice, and the vertices that are not used.
+
+use std::collections::HashSet;
+
+use cgmath::Vector3;
+
+use crate::math;
+use crate::{Triangle, VertexAttributes};
+
+/// Remove the triangles of an indexed mesh that `validate` reports as
+/// degenerate or duplicate: those repeating a vertex index, those whose
+/// corners are collinear, and those using the same three vertices as an
+/// earlier triangle, whatever their winding.
+///
+/// The triangles left keep their order. With `compact`, the vertices no
+/// longer used by any triangle are removed too, see `compact`.
+///
+/// Returns the number of triangles removed.
+pub fn remove_degenerate_faces<V>(
+    vertices: &mut Vec<V>,
+    triangles: &mut Vec<Triangle<usize>>,
+    compact: bool,
+) -> usize
+where
+    V: VertexAttributes + Clone,
+{
+    let before = triangles.len();
+    let mut faces: HashSet<[usize; 3]> = HashSet::new();
+    triangles.retain(|t| {
+        if t.x == t.y || t.y == t.z || t.z == t.x {
+            return false;
+        }
+        let p = |i: usize| Vector3::from(vertices[i].position());
+        if math::is_degenerate(p(t.x), p(t.y), p(t.z)) {
+            return false;
+        }
+        let mut key = [t.x, t.y, t.z];
+        key.sort_unstable();
+        faces.insert(key)
+    });
+
+    if compact {
+        self::compact(vertices, triangles);
+    }
+    before - triangles.len()
+}
+
+/// Remove the vertices of an indexed mesh that no triangle uses. The
+/// vertices left keep their order, and the triangles are reindexed to
+/// match.
+///
+/// Returns, for every original vertex, its index in the compacted mesh,
+/// or `None` if it was removed, to remap other arrays of attributes the
+/// same way.
+pub fn compact<V>(vertices: &mut Vec<V>, triangles: &mut [Triangle<usize>]) -> Vec<Option<usize>> {
+    let mut remap = vec![None; vertices.len()];
+    for t in triangles.iter() {
+        for i in [t.x, t.y, t.z] {
+            remap[i] = Some(0);
+        }
+    }
+    for (kept, r) in remap.iter_mut().flatten().enumerate() {
+        *r = kept;
+    }
+    let mut i = 0;
+    vertices.retain(|_| {
+        i += 1;
+        remap[i - 1].is_some()
+    });
+    for t in triangles.iter_mut() {
+        let index = |i: usize| remap[i].unwrap();
+        *t = Triangle::new(index(t.x), index(t.y), index(t.z));
+    }
+    remap
+}
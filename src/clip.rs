@@ -0,0 +1,277 @@
+//! Clipping of polygon streams against a plane.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::iter::Fuse;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::analytic::{orthonormal_basis, Plane};
+use crate::math;
+use crate::planar::triangulate_polygon;
+use crate::Polygon::{PolyNGon, PolyQuad, PolyTri};
+use crate::{Polygon, Position, Quad, Triangle, Vertex};
+
+/// Cuts a stream of polygons with a plane, keeping only what lies on one
+/// side of it. Useful for cutaway views, and as a building block for
+/// constructive solid geometry.
+pub trait Clip: Sized {
+    /// Clip every polygon against the half-space in front of `plane`, the
+    /// side its normal points to. Polygons that straddle the plane are cut,
+    /// which can turn triangles and quads into polygons with more sides.
+    /// Polygons lying entirely behind or in the plane are dropped.
+    fn clip(self, plane: Plane) -> ClipIterator<Self>;
+}
+
+impl<T> Clip for T
+where
+    T: Iterator<Item = Polygon<Vertex>>,
+{
+    fn clip(self, plane: Plane) -> ClipIterator<T> {
+        ClipIterator {
+            // the cap is built after the source runs dry, which must then
+            // stay dry
+            source: self.fuse(),
+            plane,
+            cap: None,
+            buffer: VecDeque::new(),
+        }
+    }
+}
+
+/// An iterator that clips polygons against a plane
+pub struct ClipIterator<I> {
+    source: Fuse<I>,
+    plane: Plane,
+    // cut edges, from the key of their start to their end
+    cap: Option<BTreeMap<[u32; 3], [u32; 3]>>,
+    buffer: VecDeque<Polygon<Vertex>>,
+}
+
+impl<I> ClipIterator<I> {
+    /// Also close the cut with triangles lying in the plane, facing away
+    /// from the kept side. They are emitted once the source is exhausted,
+    /// and only make sense if the clipped surface is closed.
+    pub fn with_cap(mut self) -> Self {
+        self.cap = Some(BTreeMap::new());
+        self
+    }
+
+    fn clip_polygon(&mut self, polygon: Polygon<Vertex>) {
+        let input: Vec<Vertex> = polygon.as_vertices().collect();
+        let d: Vec<f32> = input
+            .iter()
+            .map(|v| self.plane.signed_distance(v.pos))
+            .collect();
+        if d.iter().all(|&d| d > 0.) {
+            self.buffer.push_back(polygon_from(input));
+            return;
+        }
+        if d.iter().all(|&d| d <= 0.) {
+            return;
+        }
+
+        // Sutherland-Hodgman, remembering which vertices are on the plane
+        let mut output: Vec<(Vertex, bool)> = Vec::with_capacity(input.len() + 1);
+        for i in 0..input.len() {
+            let j = (i + 1) % input.len();
+            if d[i] >= 0. {
+                output.push((input[i], d[i] == 0.));
+            }
+            if (d[i] > 0. && d[j] < 0.) || (d[i] < 0. && d[j] > 0.) {
+                output.push((intersect(&input[i], d[i], &input[j], d[j]), true));
+            }
+        }
+        if output.len() < 3 {
+            return;
+        }
+
+        if let Some(cap) = self.cap.as_mut() {
+            for i in 0..output.len() {
+                let (a, on_a) = output[i];
+                let (b, on_b) = output[(i + 1) % output.len()];
+                if on_a && on_b {
+                    // the cap runs along the cut the other way around
+                    let (from, to) = (math::position_key(b.pos), math::position_key(a.pos));
+                    if cap.get(&to) == Some(&from) {
+                        cap.remove(&to);
+                    } else {
+                        cap.insert(from, to);
+                    }
+                }
+            }
+        }
+        self.buffer
+            .push_back(polygon_from(output.into_iter().map(|(v, _)| v).collect()));
+    }
+
+    fn emit_cap(&mut self) {
+        let cap = match self.cap.take() {
+            Some(cap) => cap,
+            None => return,
+        };
+        let normal = -Vector3::from(self.plane.normal);
+        let (u, v) = orthonormal_basis(normal);
+        let project = |k: &[u32; 3]| {
+            let p = Vector3::from(position(k));
+            [p.dot(u), p.dot(v)]
+        };
+
+        let rings: Vec<Ring> = chain_loops(cap)
+            .into_iter()
+            .filter(|keys| keys.len() >= 3)
+            .map(|keys| {
+                let flat: Vec<[f32; 2]> = keys.iter().map(project).collect();
+                let area = area(&flat);
+                Ring { keys, flat, area }
+            })
+            .collect();
+
+        // counter clockwise rings are outlines, clockwise ones are holes,
+        // which belong to the smallest outline around them
+        let mut holes: Vec<Vec<usize>> = vec![Vec::new(); rings.len()];
+        for (h, hole) in rings.iter().enumerate().filter(|(_, r)| r.area < 0.) {
+            let owner = rings
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| r.area > 0. && contains(&r.flat, hole.flat[0]))
+                .min_by(|a, b| a.1.area.total_cmp(&b.1.area));
+            if let Some((o, _)) = owner {
+                holes[o].push(h);
+            }
+        }
+
+        for (o, outline) in rings.iter().enumerate().filter(|(_, r)| r.area > 0.) {
+            let mut keys = outline.keys.clone();
+            let flat: Vec<&[[f32; 2]]> = holes[o].iter().map(|&h| &rings[h].flat[..]).collect();
+            for &h in &holes[o] {
+                keys.extend_from_slice(&rings[h].keys);
+            }
+            let vertex = |i: usize| Vertex {
+                pos: position(&keys[i]),
+                normal: normal.into(),
+            };
+            for t in triangulate_polygon(&outline.flat, &flat) {
+                self.buffer.push_back(PolyTri(Triangle::new(
+                    vertex(t.x),
+                    vertex(t.y),
+                    vertex(t.z),
+                )));
+            }
+        }
+    }
+}
+
+impl<I> Iterator for ClipIterator<I>
+where
+    I: Iterator<Item = Polygon<Vertex>>,
+{
+    type Item = Polygon<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // polygons may be dropped, and the cap is unknown until the end
+        (self.buffer.len(), None)
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(p) = self.buffer.pop_front() {
+                return Some(p);
+            }
+
+            match self.source.next() {
+                Some(polygon) => self.clip_polygon(polygon),
+                None if self.cap.is_some() => self.emit_cap(),
+                None => return None,
+            }
+        }
+    }
+}
+
+/// Join directed segments, given as a map from their start to their end,
+/// into closed loops. Chains that do not close up are dropped.
+pub(crate) fn chain_loops<K: Ord + Copy>(mut next: BTreeMap<K, K>) -> Vec<Vec<K>> {
+    let mut loops = Vec::new();
+    while let Some((&start, _)) = next.iter().next() {
+        let mut ring = vec![start];
+        let mut at = start;
+        while let Some(to) = next.remove(&at) {
+            if to == start {
+                loops.push(ring);
+                break;
+            }
+            ring.push(to);
+            at = to;
+        }
+    }
+    loops
+}
+
+// A closed loop of the cut, projected in the plane
+struct Ring {
+    keys: Vec<[u32; 3]>,
+    flat: Vec<[f32; 2]>,
+    area: f32,
+}
+
+// Where the edge between `a` and `b` crosses the plane. The end points are
+// put in a fixed order first, so that the polygons on both sides of an
+// edge agree on the exact same point.
+fn intersect(a: &Vertex, da: f32, b: &Vertex, db: f32) -> Vertex {
+    let (a, da, b, db) = if math::position_key(a.pos) <= math::position_key(b.pos) {
+        (a, da, b, db)
+    } else {
+        (b, db, a, da)
+    };
+    let t = da / (da - db);
+    let (pa, pb) = (Vector3::from(a.pos), Vector3::from(b.pos));
+    let (na, nb) = (Vector3::from(a.normal), Vector3::from(b.normal));
+    let normal = math::try_normalize(na + (nb - na) * t).unwrap_or(na);
+    Vertex {
+        pos: (pa + (pb - pa) * t).into(),
+        normal: normal.into(),
+    }
+}
+
+fn polygon_from(vertices: Vec<Vertex>) -> Polygon<Vertex> {
+    match vertices.len() {
+        3 => PolyTri(Triangle::new(vertices[0], vertices[1], vertices[2])),
+        4 => PolyQuad(Quad::new(
+            vertices[0],
+            vertices[1],
+            vertices[2],
+            vertices[3],
+        )),
+        _ => PolyNGon(vertices.into_iter().collect()),
+    }
+}
+
+fn position(k: &[u32; 3]) -> Position {
+    [
+        f32::from_bits(k[0]),
+        f32::from_bits(k[1]),
+        f32::from_bits(k[2]),
+    ]
+    .into()
+}
+
+fn area(ring: &[[f32; 2]]) -> f32 {
+    let mut area = 0.;
+    for i in 0..ring.len() {
+        let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+        area += a[0] * b[1] - b[0] * a[1];
+    }
+    area * 0.5
+}
+
+fn contains(ring: &[[f32; 2]], p: [f32; 2]) -> bool {
+    let mut inside = false;
+    for i in 0..ring.len() {
+        let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+        if (a[1] > p[1]) != (b[1] > p[1])
+            && p[0] < a[0] + (p[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0])
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
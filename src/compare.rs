@@ -0,0 +1,190 @@
+//! Approximate comparison of indexed meshes, for golden tests that should
+//! not break over rounding or over the order vertices and triangles come
+//! in.
+//!
+//! ```
+//! use genmesh::compare::{compare, Tolerance};
+//! use genmesh::generators::{Cube, IndexedPolygon, SharedVertex};
+//! use genmesh::{Triangle, Triangulate, Vertex};
+//!
+//! let cube = Cube::new();
+//! let vertices: Vec<Vertex> = cube.shared_vertex_iter().collect();
+//! let triangles: Vec<Triangle<usize>> = cube.indexed_polygon_iter().triangulate().collect();
+//!
+//! // the same cube, listed backwards
+//! let reversed: Vec<Vertex> = vertices.iter().rev().cloned().collect();
+//! let last = vertices.len() - 1;
+//! let renumbered: Vec<Triangle<usize>> = triangles
+//!     .iter()
+//!     .rev()
+//!     .map(|t| Triangle::new(last - t.y, last - t.z, last - t.x))
+//!     .collect();
+//! let tolerance = Tolerance::default();
+//! assert_eq!(compare(&vertices, &triangles, &reversed, &renumbered, tolerance), Ok(()));
+//! ```
+
+use std::collections::HashMap;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::attributes::VertexAttributes;
+use crate::spatial::SpatialGrid;
+use crate::{Normal, Triangle};
+
+/// How far apart two vertices can be and still be the same.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance {
+    /// The largest distance between the positions
+    pub position: f32,
+    /// The largest angle between the normals, in radians
+    pub normal_angle: f32,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Tolerance {
+            position: 1e-5,
+            normal_angle: 1e-3,
+        }
+    }
+}
+
+/// The first difference `compare` found between two meshes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difference {
+    /// The meshes have different numbers of vertices.
+    VertexCount {
+        /// The number of vertices of the expected mesh
+        expected: usize,
+        /// The number of vertices of the actual mesh
+        actual: usize,
+    },
+    /// The meshes have different numbers of triangles.
+    TriangleCount {
+        /// The number of triangles of the expected mesh
+        expected: usize,
+        /// The number of triangles of the actual mesh
+        actual: usize,
+    },
+    /// This vertex of the expected mesh is not in the actual mesh, either
+    /// in position or in normal.
+    Vertex(usize),
+    /// This triangle of the expected mesh is not in the actual mesh, with
+    /// the same corners wound the same way.
+    Triangle(usize),
+}
+
+/// Check that two indexed meshes are the same within `tolerance`, up to
+/// the order of their vertices and triangles and the corner each triangle
+/// starts from.
+///
+/// Vertices are the same when their positions are within
+/// `tolerance.position` of each other, and their normals within
+/// `tolerance.normal_angle`, or neither has one. Vertices that are the
+/// same as each other within a mesh, as on the seams of flat shaded
+/// meshes, are interchangeable. Other attributes are not compared.
+pub fn compare<E, A>(
+    expected_vertices: &[E],
+    expected_triangles: &[Triangle<usize>],
+    actual_vertices: &[A],
+    actual_triangles: &[Triangle<usize>],
+    tolerance: Tolerance,
+) -> Result<(), Difference>
+where
+    E: VertexAttributes,
+    A: VertexAttributes,
+{
+    if expected_vertices.len() != actual_vertices.len() {
+        return Err(Difference::VertexCount {
+            expected: expected_vertices.len(),
+            actual: actual_vertices.len(),
+        });
+    }
+    if expected_triangles.len() != actual_triangles.len() {
+        return Err(Difference::TriangleCount {
+            expected: expected_triangles.len(),
+            actual: actual_triangles.len(),
+        });
+    }
+
+    // sort the vertices of both meshes into classes of the same vertex,
+    // and compare how many of each class the meshes have
+    let mut classes = Classes::new(tolerance);
+    let expected: Vec<usize> = expected_vertices.iter().map(|v| classes.class(v)).collect();
+    let actual: Vec<usize> = actual_vertices.iter().map(|v| classes.class(v)).collect();
+    let mut count = vec![0isize; classes.normals.len()];
+    for &c in &actual {
+        count[c] += 1;
+    }
+    for (i, &c) in expected.iter().enumerate() {
+        count[c] -= 1;
+        if count[c] < 0 {
+            return Err(Difference::Vertex(i));
+        }
+    }
+
+    let mut triangles: HashMap<[usize; 3], isize> = HashMap::new();
+    for t in actual_triangles {
+        *triangles.entry(key(t, &actual)).or_default() += 1;
+    }
+    for (i, t) in expected_triangles.iter().enumerate() {
+        let count = triangles.entry(key(t, &expected)).or_default();
+        *count -= 1;
+        if *count < 0 {
+            return Err(Difference::Triangle(i));
+        }
+    }
+    Ok(())
+}
+
+// The classes of a triangle's corners, starting from the lowest.
+fn key(t: &Triangle<usize>, class: &[usize]) -> [usize; 3] {
+    let c = [class[t.x], class[t.y], class[t.z]];
+    let first = (0..3).min_by_key(|&k| c[k]).unwrap();
+    [c[first], c[(first + 1) % 3], c[(first + 2) % 3]]
+}
+
+// Each vertex goes in the first class whose first vertex is the same as
+// it, or starts a new one.
+struct Classes {
+    tolerance: Tolerance,
+    grid: SpatialGrid,
+    normals: Vec<Option<Normal>>,
+}
+
+impl Classes {
+    fn new(tolerance: Tolerance) -> Self {
+        Classes {
+            tolerance,
+            grid: SpatialGrid::new(tolerance.position.max(1e-6)),
+            normals: Vec::new(),
+        }
+    }
+
+    fn class<V: VertexAttributes>(&mut self, v: &V) -> usize {
+        let normal = v.normal();
+        let found = self
+            .grid
+            .query_radius(v.position(), self.tolerance.position)
+            .into_iter()
+            .find(|&c| same_normal(self.normals[c], normal, self.tolerance.normal_angle));
+        found.unwrap_or_else(|| {
+            self.normals.push(normal);
+            self.grid.insert(v.position())
+        })
+    }
+}
+
+fn same_normal(a: Option<Normal>, b: Option<Normal>, angle: f32) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => {
+            let (a, b) = (Vector3::from(a), Vector3::from(b));
+            if a.magnitude2() == 0. || b.magnitude2() == 0. {
+                return a.magnitude2() == b.magnitude2();
+            }
+            a.cross(b).magnitude().atan2(a.dot(b)) <= angle
+        }
+        _ => false,
+    }
+}
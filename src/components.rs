@@ -0,0 +1,103 @@
+//! Finding the separate pieces of an indexed mesh.
+
+use crate::Triangle;
+
+/// Label the connected components of an indexed triangle mesh, the sets
+/// of triangles joined to each other through the vertices they share.
+///
+/// Returns the component of each triangle and the number of components.
+/// Components are numbered in the order of their first triangle. Pieces
+/// that only touch with separate vertices at the same position are apart,
+/// use `weld` first to join them.
+pub fn label_components(triangles: &[Triangle<usize>]) -> (Vec<usize>, usize) {
+    let count = triangles
+        .iter()
+        .map(|t| t.x.max(t.y).max(t.z) + 1)
+        .max()
+        .unwrap_or(0);
+    let mut parent: Vec<usize> = (0..count).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for t in triangles {
+        for (a, b) in [(t.x, t.y), (t.y, t.z)] {
+            let (a, b) = (root(&mut parent, a), root(&mut parent, b));
+            parent[a] = b;
+        }
+    }
+
+    let mut label = vec![usize::MAX; count];
+    let mut components = 0;
+    let labels = triangles
+        .iter()
+        .map(|t| {
+            let r = root(&mut parent, t.x);
+            if label[r] == usize::MAX {
+                label[r] = components;
+                components += 1;
+            }
+            label[r]
+        })
+        .collect();
+    (labels, components)
+}
+
+/// Split an indexed triangle mesh into a mesh for each of its connected
+/// components, see `label_components`, in the same order.
+///
+/// Each mesh only gets the vertices its triangles use, in the order they
+/// come in `vertices`, with the triangles reindexed to match.
+pub fn split_components<V: Clone>(
+    vertices: &[V],
+    triangles: &[Triangle<usize>],
+) -> Vec<(Vec<V>, Vec<Triangle<usize>>)> {
+    let (labels, count) = label_components(triangles);
+    let mut parts = vec![(Vec::new(), Vec::new()); count];
+    // the component of each vertex, if it is used, and where it goes
+    let mut component = vec![usize::MAX; vertices.len()];
+    for (t, &c) in triangles.iter().zip(&labels) {
+        for i in [t.x, t.y, t.z] {
+            component[i] = c;
+        }
+    }
+    let mut remap = vec![usize::MAX; vertices.len()];
+    for (i, v) in vertices.iter().enumerate() {
+        if let Some(part) = parts.get_mut(component[i]) {
+            remap[i] = part.0.len();
+            part.0.push(v.clone());
+        }
+    }
+    for (t, &c) in triangles.iter().zip(&labels) {
+        parts[c]
+            .1
+            .push(Triangle::new(remap[t.x], remap[t.y], remap[t.z]));
+    }
+    parts
+}
+
+/// Keep the triangles of the connected components of an indexed mesh
+/// that have at least `min_triangles` triangles, dropping the floating
+/// fragments left behind by CSG, marching cubes or scanning.
+///
+/// The vertices are left as they are, the ones that are no longer used
+/// stay in place.
+pub fn filter_components(
+    triangles: &[Triangle<usize>],
+    min_triangles: usize,
+) -> Vec<Triangle<usize>> {
+    let (labels, count) = label_components(triangles);
+    let mut sizes = vec![0; count];
+    for &c in &labels {
+        sizes[c] += 1;
+    }
+    triangles
+        .iter()
+        .zip(&labels)
+        .filter(|&(_, &c)| sizes[c] >= min_triangles)
+        .map(|(t, _)| t.clone())
+        .collect()
+}
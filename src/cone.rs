@@ -1,6 +1,7 @@
 use std::f32::consts::{self, FRAC_1_SQRT_2};
+use std::ops::Range;
 
-use super::generators::{IndexedPolygon, SharedVertex};
+use super::generators::{IndexedPolygon, PolygonGroup, SharedVertex};
 use super::{MapVertex, Polygon, Polygon::PolyTri, Triangle, Vertex};
 
 const TWO_PI: f32 = consts::PI * 2.;
@@ -17,8 +18,10 @@ enum VertexSection {
 /// The bottom will be a circle around [0, 0, -1] with a radius
 /// of 1, all coords on the bottom will follow the plan equation `-z-1=0`
 /// The tip of the cone will always be at coord [0, 0, 1]
+///
+/// The faces are in two groups: the side is 0 and the bottom 1.
 pub struct Cone {
-    u: usize,
+    range: Range<usize>,
     sub_u: usize,
 }
 
@@ -28,10 +31,13 @@ impl Cone {
     /// it must be greater then 1.
     pub fn new(u: usize) -> Self {
         assert!(u >= 2);
-        Cone { u: 0, sub_u: u }
+        Cone {
+            range: 0..u * 2,
+            sub_u: u,
+        }
     }
 
-    ///
+    /// build the vertex for a section of the cone
     fn vertex(&self, sec: VertexSection) -> Vertex {
         let divisions = TWO_PI / self.sub_u as f32;
 
@@ -101,20 +107,29 @@ impl Cone {
 impl Iterator for Cone {
     type Item = Polygon<Vertex>;
 
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
     fn next(&mut self) -> Option<Self::Item> {
-        if self.u < self.sub_u * 2 {
-            let idx = self.u;
-            self.u += 1;
-            Some(
-                self.indexed_polygon(idx)
-                    .map_vertex(|i| self.shared_vertex(i)),
-            )
-        } else {
-            None
-        }
+        self.range.next().map(|idx| {
+            self.indexed_polygon(idx)
+                .map_vertex(|i| self.shared_vertex(i))
+        })
     }
 }
 
+impl DoubleEndedIterator for Cone {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|idx| {
+            self.indexed_polygon(idx)
+                .map_vertex(|i| self.shared_vertex(i))
+        })
+    }
+}
+
+impl ExactSizeIterator for Cone {}
+
 impl SharedVertex<Vertex> for Cone {
     fn shared_vertex(&self, idx: usize) -> Vertex {
         self.vertex(self.rev_index(idx))
@@ -157,3 +172,17 @@ impl IndexedPolygon<Polygon<usize>> for Cone {
         self.sub_u * 2
     }
 }
+
+impl PolygonGroup for Cone {
+    fn polygon_group(&self, idx: usize) -> u32 {
+        if idx < self.sub_u {
+            0
+        } else {
+            1
+        }
+    }
+
+    fn polygon_group_count(&self) -> u32 {
+        2
+    }
+}
@@ -1,55 +1,262 @@
-use cgmath::{InnerSpace, Vector3};
-
-use super::generators::{IndexedPolygon, SharedVertex};
-use crate::{MapVertex, Normal, Polygon, Polygon::PolyQuad, Position, Quad, Vertex};
+use std::marker::PhantomData;
 use std::ops::Range;
 
+use cgmath::Vector3;
+
+use super::generators::{IndexedPolygon, PolygonGroup, SharedVertex};
+use crate::{
+    InsideOut, Normal, Polygon, Polygon::PolyQuad, Position, Quad, TexturedVertex, Vertex,
+};
+
+/// A face of a `Cube`, named after the direction it faces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CubeFace {
+    /// The face at x = 1
+    PosX,
+    /// The face at x = -1
+    NegX,
+    /// The face at y = 1
+    PosY,
+    /// The face at y = -1
+    NegY,
+    /// The face at z = 1
+    PosZ,
+    /// The face at z = -1
+    NegZ,
+}
+
+impl CubeFace {
+    /// All the faces, in the order the cube emits them.
+    pub const ALL: [CubeFace; 6] = [
+        CubeFace::PosX,
+        CubeFace::NegX,
+        CubeFace::PosY,
+        CubeFace::NegY,
+        CubeFace::PosZ,
+        CubeFace::NegZ,
+    ];
+
+    /// The face at offset `idx`, which is also its polygon group.
+    pub fn from_index(idx: usize) -> Option<CubeFace> {
+        CubeFace::ALL.get(idx).cloned()
+    }
+
+    /// The offset of the face among the faces of the cube.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The outward normal of the face.
+    pub fn normal(self) -> Normal {
+        match self {
+            CubeFace::PosX => [1., 0., 0.],
+            CubeFace::NegX => [-1., 0., 0.],
+            CubeFace::PosY => [0., 1., 0.],
+            CubeFace::NegY => [0., -1., 0.],
+            CubeFace::PosZ => [0., 0., 1.],
+            CubeFace::NegZ => [0., 0., -1.],
+        }
+        .into()
+    }
+
+    // the direction the v texture coordinate grows in: up the sides, and
+    // towards +y on the top and -y on the bottom
+    fn up(self) -> Vector3<f32> {
+        match self {
+            CubeFace::PosZ => Vector3::unit_y(),
+            CubeFace::NegZ => -Vector3::unit_y(),
+            _ => Vector3::unit_z(),
+        }
+    }
+
+    // the corner of each quad the face starts from, counting
+    // counter-clockwise from the one with the lowest `u` and `v`, as the
+    // unsubdivided cube always had them
+    fn first_corner(self) -> usize {
+        match self {
+            CubeFace::PosY => 2,
+            CubeFace::NegZ => 3,
+            _ => 1,
+        }
+    }
+
+    // the corners of the cell at column `i` and row `j`, from the first
+    fn ring(self, i: usize, j: usize, w: usize, h: usize) -> [(usize, usize); 4] {
+        let mut ring = [(i, j), (i + w, j), (i + w, j + h), (i, j + h)];
+        ring.rotate_left(self.first_corner());
+        ring
+    }
+}
+
 /// A perfect cube, centered at (0, 0, 0) with each face starting at 1/-1 away from the origin
+///
+/// Each face is its own group, in the order +x, -x, +y, -y, +z, -z, and
+/// has its own vertices, so that it is flat shaded. A subdivided cube cuts
+/// each face into a grid of quads, whose vertices are numbered row by row;
+/// the four vertices of a face that is not cut are numbered around it. A textured cube also maps the whole
+/// texture onto each face, as seen from outside the cube with `v` pointing
+/// up.
 #[derive(Clone)]
-pub struct Cube {
+pub struct Cube<V = Vertex> {
     range: Range<usize>,
+    sub: [usize; 3],
+    phantom_v: PhantomData<V>,
 }
 
 impl Cube {
     /// create a new cube generator
     pub fn new() -> Self {
-        Cube { range: 0..6 }
-    }
-
-    fn vert(&self, idx: usize) -> Position {
-        let x = if idx & 4 == 4 { 1. } else { -1. };
-        let y = if idx & 2 == 2 { 1. } else { -1. };
-        let z = if idx & 1 == 1 { 1. } else { -1. };
-        let result: Vector3<f32> = [x, y, z].into();
-        result.normalize().into()
-    }
-
-    fn face_indexed(&self, idx: usize) -> (Normal, Quad<usize>) {
-        match idx {
-            0 => ([1., 0., 0.].into(), Quad::new(0b110, 0b111, 0b101, 0b100)),
-            1 => ([-1., 0., 0.].into(), Quad::new(0b000, 0b001, 0b011, 0b010)),
-            2 => ([0., 1., 0.].into(), Quad::new(0b011, 0b111, 0b110, 0b010)),
-            3 => ([0., -1., 0.].into(), Quad::new(0b100, 0b101, 0b001, 0b000)),
-            4 => ([0., 0., 1.].into(), Quad::new(0b101, 0b111, 0b011, 0b001)),
-            5 => ([0., 0., -1.].into(), Quad::new(0b000, 0b010, 0b110, 0b100)),
-            idx => panic!("{} face is higher then 6", idx),
+        Cube::subdivided(1, 1, 1)
+    }
+
+    /// create a new cube generator, with the faces cut into `nx` quads
+    /// along the x axis, `ny` along the y axis and `nz` along the z axis.
+    pub fn subdivided(nx: usize, ny: usize, nz: usize) -> Self {
+        assert!(nx > 0 && ny > 0 && nz > 0);
+        let mut cube = Cube {
+            range: 0..0,
+            sub: [nx, ny, nz],
+            phantom_v: PhantomData,
+        };
+        cube.range = 0..cube.indexed_polygon_count();
+        cube
+    }
+
+    /// create a new cube generator emitting texture coordinates
+    pub fn textured() -> Cube<TexturedVertex> {
+        Cube::new().with_uvs()
+    }
+
+    /// emit texture coordinates along with the vertices
+    pub fn with_uvs(self) -> Cube<TexturedVertex> {
+        Cube {
+            range: self.range,
+            sub: self.sub,
+            phantom_v: PhantomData,
+        }
+    }
+}
+
+impl<V> Cube<V> {
+    // the number of quads of `face` along its right and up directions
+    fn grid(&self, face: CubeFace) -> (usize, usize) {
+        let [nx, ny, nz] = self.sub;
+        match face {
+            CubeFace::PosX | CubeFace::NegX => (ny, nz),
+            CubeFace::PosY | CubeFace::NegY => (nx, nz),
+            CubeFace::PosZ | CubeFace::NegZ => (nx, ny),
         }
     }
 
-    fn face(&self, idx: usize) -> Polygon<Vertex> {
-        let (no, quad) = self.face_indexed(idx);
-        PolyQuad(quad.map_vertex(|i| Vertex {
-            pos: self.vert(i),
-            normal: no,
-        }))
+    // the first shared vertex and the first polygon of `face`
+    fn offsets(&self, face: CubeFace) -> (usize, usize) {
+        CubeFace::ALL[..face.index()]
+            .iter()
+            .fold((0, 0), |(v, p), &f| {
+                let (a, b) = self.grid(f);
+                (v + (a + 1) * (b + 1), p + a * b)
+            })
+    }
+
+    // the face holding the polygon at `idx`, and the offset of the polygon
+    // in that face
+    fn polygon_face(&self, mut idx: usize) -> (CubeFace, usize) {
+        for &face in CubeFace::ALL.iter() {
+            let (a, b) = self.grid(face);
+            if idx < a * b {
+                return (face, idx);
+            }
+            idx -= a * b;
+        }
+        panic!("polygon index out of range")
+    }
+
+    // the offset among the vertices of `face` of the one at column `i` and
+    // row `j`
+    fn vertex_offset(&self, face: CubeFace, i: usize, j: usize) -> usize {
+        match self.grid(face) {
+            (1, 1) => face
+                .ring(0, 0, 1, 1)
+                .iter()
+                .position(|&c| c == (i, j))
+                .unwrap(),
+            (a, _) => j * (a + 1) + i,
+        }
+    }
+
+    // the column and row of the vertex at `offset` among those of `face`
+    fn vertex_cell(&self, face: CubeFace, offset: usize) -> (usize, usize) {
+        match self.grid(face) {
+            (1, 1) => face.ring(0, 0, 1, 1)[offset],
+            (a, _) => (offset % (a + 1), offset / (a + 1)),
+        }
+    }
+
+    /// The indices of the shared vertices at the corners of `face`, in
+    /// counter-clockwise order seen from outside.
+    pub fn face_indices(&self, face: CubeFace) -> Quad<usize> {
+        let (a, b) = self.grid(face);
+        let (base, _) = self.offsets(face);
+        let [p, q, r, s] = face
+            .ring(0, 0, a, b)
+            .map(|(i, j)| base + self.vertex_offset(face, i, j));
+        Quad::new(p, q, r, s)
+    }
+
+    /// Turn the cube inside out, to be seen from within like a skybox.
+    pub fn inside_out(self) -> InsideOut<Self> {
+        InsideOut::new(self)
     }
 }
 
-impl Iterator for Cube {
-    type Item = Polygon<Vertex>;
+impl<V: From<TexturedVertex>> Cube<V> {
+    // the vertex at column `i` and row `j` of the grid of `face`
+    fn vert(&self, face: CubeFace, i: usize, j: usize) -> V {
+        let (a, b) = self.grid(face);
+        let normal: Vector3<f32> = face.normal().into();
+        let up = face.up();
+        let right = up.cross(normal);
+        let uv = [i as f32 / a as f32, j as f32 / b as f32];
+        let pos = normal + right * (uv[0] * 2. - 1.) + up * (uv[1] * 2. - 1.);
+        // scaled like the corners of the unit cube once normalized
+        let pos: Position = (pos / 3f32.sqrt()).into();
+        TexturedVertex {
+            pos,
+            normal: normal.into(),
+            uv,
+        }
+        .into()
+    }
+
+    /// The vertices at the corners of `face`, in counter-clockwise order
+    /// seen from outside.
+    pub fn face(&self, face: CubeFace) -> Quad<V> {
+        let (a, b) = self.grid(face);
+        let [p, q, r, s] = face.ring(0, 0, a, b).map(|(i, j)| self.vert(face, i, j));
+        Quad::new(p, q, r, s)
+    }
 
-    fn next(&mut self) -> Option<Polygon<Vertex>> {
-        self.range.next().map(|idx| self.face(idx))
+    fn polygon(&self, idx: usize) -> Polygon<V> {
+        let (face, k) = self.polygon_face(idx);
+        let (a, _) = self.grid(face);
+        let [p, q, r, s] = face
+            .ring(k % a, k / a, 1, 1)
+            .map(|(i, j)| self.vert(face, i, j));
+        PolyQuad(Quad::new(p, q, r, s))
+    }
+}
+
+impl Default for Cube {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: From<TexturedVertex>> Iterator for Cube<V> {
+    type Item = Polygon<V>;
+
+    fn next(&mut self) -> Option<Polygon<V>> {
+        self.range.next().map(|idx| self.polygon(idx))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -57,38 +264,56 @@ impl Iterator for Cube {
     }
 }
 
-impl SharedVertex<Vertex> for Cube {
-    fn shared_vertex(&self, idx: usize) -> Vertex {
-        let (no, quad) = self.face_indexed(idx / 4);
-        let vid = match idx % 4 {
-            0 => quad.x,
-            1 => quad.y,
-            2 => quad.z,
-            3 => quad.w,
-            _ => unreachable!(),
-        };
-        Vertex {
-            pos: self.vert(vid),
-            normal: no,
+impl<V: From<TexturedVertex>> DoubleEndedIterator for Cube<V> {
+    fn next_back(&mut self) -> Option<Polygon<V>> {
+        self.range.next_back().map(|idx| self.polygon(idx))
+    }
+}
+
+impl<V: From<TexturedVertex>> ExactSizeIterator for Cube<V> {}
+
+impl<V: From<TexturedVertex>> SharedVertex<V> for Cube<V> {
+    fn shared_vertex(&self, mut idx: usize) -> V {
+        for &face in CubeFace::ALL.iter() {
+            let (a, b) = self.grid(face);
+            if idx < (a + 1) * (b + 1) {
+                let (i, j) = self.vertex_cell(face, idx);
+                return self.vert(face, i, j);
+            }
+            idx -= (a + 1) * (b + 1);
         }
+        panic!("vertex index out of range")
     }
 
     fn shared_vertex_count(&self) -> usize {
-        24
+        let [nx, ny, nz] = self.sub;
+        2 * ((nx + 1) * (ny + 1) + (ny + 1) * (nz + 1) + (nz + 1) * (nx + 1))
     }
 }
 
-impl IndexedPolygon<Polygon<usize>> for Cube {
+impl<V> IndexedPolygon<Polygon<usize>> for Cube<V> {
     fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
-        PolyQuad(Quad::new(
-            idx * 4 + 0,
-            idx * 4 + 1,
-            idx * 4 + 2,
-            idx * 4 + 3,
-        ))
+        let (face, k) = self.polygon_face(idx);
+        let (a, _) = self.grid(face);
+        let (base, _) = self.offsets(face);
+        let [p, q, r, s] = face
+            .ring(k % a, k / a, 1, 1)
+            .map(|(i, j)| base + self.vertex_offset(face, i, j));
+        PolyQuad(Quad::new(p, q, r, s))
     }
 
     fn indexed_polygon_count(&self) -> usize {
+        let [nx, ny, nz] = self.sub;
+        2 * (nx * ny + ny * nz + nz * nx)
+    }
+}
+
+impl<V> PolygonGroup for Cube<V> {
+    fn polygon_group(&self, idx: usize) -> u32 {
+        self.polygon_face(idx).0.index() as u32
+    }
+
+    fn polygon_group_count(&self) -> u32 {
         6
     }
 }
@@ -0,0 +1,96 @@
+//! Dropping the polygons of a stream that cannot be seen.
+
+use cgmath::Vector3;
+
+use crate::analytic::Plane;
+use crate::bounds::EmitPositions;
+use crate::math;
+use crate::View;
+
+/// Filters a stream of polygons down to those that may be visible, before
+/// they are baked, previewed or sent anywhere.
+pub trait Cull: Sized {
+    /// Keep only the polygons facing `view`, the way their vertices wind
+    /// counter clockwise around their normal. Polygons seen edge on, and
+    /// those with no area, are dropped.
+    fn cull_backfaces(self, view: View) -> CullIterator<Self>;
+
+    /// Keep only the polygons that are not entirely behind one of
+    /// `planes`, such as the six planes of a view frustum facing into it.
+    /// This is conservative: a polygon crossing the corner of the volume
+    /// from outside can be kept.
+    fn cull_outside<I>(self, planes: I) -> CullIterator<Self>
+    where
+        I: IntoIterator<Item = Plane>;
+}
+
+impl<T, P> Cull for T
+where
+    T: Iterator<Item = P>,
+    P: EmitPositions + Clone,
+{
+    fn cull_backfaces(self, view: View) -> CullIterator<T> {
+        CullIterator {
+            source: self,
+            test: Test::Backfaces(view),
+        }
+    }
+
+    fn cull_outside<I>(self, planes: I) -> CullIterator<T>
+    where
+        I: IntoIterator<Item = Plane>,
+    {
+        CullIterator {
+            source: self,
+            test: Test::Outside(planes.into_iter().collect()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Test {
+    Backfaces(View),
+    Outside(Vec<Plane>),
+}
+
+impl Test {
+    fn keeps<P: EmitPositions>(&self, polygon: P) -> bool {
+        let mut corners = Vec::new();
+        polygon.emit_positions(|p| corners.push(Vector3::from(p)));
+        match self {
+            Test::Backfaces(view) => match corners.first() {
+                Some(&first) => view.faces(first, math::newell_normal(&corners)),
+                None => false,
+            },
+            Test::Outside(planes) => planes.iter().all(|plane| {
+                corners
+                    .iter()
+                    .any(|&c| plane.signed_distance(c.into()) >= 0.)
+            }),
+        }
+    }
+}
+
+/// An iterator over the polygons of its source that pass a culling test
+#[derive(Clone, Debug)]
+pub struct CullIterator<I> {
+    source: I,
+    test: Test,
+}
+
+impl<I, P> Iterator for CullIterator<I>
+where
+    I: Iterator<Item = P>,
+    P: EmitPositions + Clone,
+{
+    type Item = P;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.source.size_hint().1)
+    }
+
+    fn next(&mut self) -> Option<P> {
+        let test = &self.test;
+        self.source.find(|p| test.keeps(p.clone()))
+    }
+}
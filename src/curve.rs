@@ -0,0 +1,456 @@
+//! Curves through space: polylines, Catmull-Rom splines and cubic Bézier
+//! curves, evaluated by parameter or by distance along them, and the
+//! frames that orient things along them.
+//!
+//! A curve resampled evenly along its length turns into lines ready to be
+//! wrapped in tubes:
+//!
+//! ```
+//! use genmesh::curve::{CatmullRom, Curve};
+//! use genmesh::Tubes;
+//!
+//! let path = CatmullRom::new(vec![
+//!     [0., 0., 0.].into(),
+//!     [1., 1., 0.].into(),
+//!     [2., 0., 1.].into(),
+//!     [3., 1., 1.].into(),
+//! ]);
+//! let polyline = path.resample(33);
+//! let tube: Vec<_> = polyline.lines().tubes(0.1, 8).collect();
+//! assert_eq!(tube.len(), 32 * 8);
+//! ```
+
+use cgmath::{InnerSpace, Matrix3, Matrix4, Vector3};
+use mint::ColumnMatrix4;
+
+use crate::analytic::orthonormal_basis;
+use crate::math;
+use crate::{Line, Normal, Position, Vertex};
+
+/// A curve, going from `point(0.)` to `point(1.)`.
+pub trait Curve {
+    /// The point of the curve at parameter `t`, from 0 to 1.
+    fn point(&self, t: f32) -> Position;
+
+    /// The derivative of the curve with respect to its parameter at `t`.
+    /// Its length is how fast the curve goes by there.
+    fn derivative(&self, t: f32) -> mint::Vector3<f32>;
+
+    /// The table mapping distances along the curve to its parameter.
+    fn arc_length(&self) -> ArcLength {
+        ArcLength::new(self, 256)
+    }
+
+    /// The length of the curve.
+    fn length(&self) -> f32 {
+        self.arc_length().length()
+    }
+
+    /// The point `distance` along the curve from its start, clamped to
+    /// its ends.
+    fn point_at_length(&self, distance: f32) -> Position {
+        self.point(self.arc_length().parameter(distance))
+    }
+
+    /// `count` points evenly spaced along the curve, from its start to its
+    /// end, as a polyline.
+    fn resample(&self, count: usize) -> Polyline {
+        assert!(count > 1);
+        let table = self.arc_length();
+        let step = table.length() / (count - 1) as f32;
+        let points = (0..count)
+            .map(|i| match i {
+                0 => self.point(0.),
+                _ if i == count - 1 => self.point(1.),
+                _ => self.point(table.parameter(i as f32 * step)),
+            })
+            .collect();
+        Polyline::new(points)
+    }
+
+    /// Points along the curve no more than `spacing` apart, evenly spaced
+    /// from its start to its end, as a polyline.
+    fn resample_by_length(&self, spacing: f32) -> Polyline {
+        assert!(spacing > 0.);
+        let segments = (self.length() / spacing).ceil().max(1.) as usize;
+        self.resample(segments + 1)
+    }
+}
+
+/// The distances along a curve at a series of increasing parameters, to
+/// find the parameter at a given distance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArcLength {
+    parameters: Vec<f32>,
+    distances: Vec<f32>,
+}
+
+impl ArcLength {
+    /// Measure `curve` by adding up the lengths of the `samples` chords
+    /// between evenly spaced parameters.
+    pub fn new<C: Curve + ?Sized>(curve: &C, samples: usize) -> Self {
+        let samples = samples.max(1);
+        ArcLength::from_parameters(curve, (0..=samples).map(|i| i as f32 / samples as f32))
+    }
+
+    // measure along chords between the given parameters, in order
+    fn from_parameters<C, I>(curve: &C, parameters: I) -> Self
+    where
+        C: Curve + ?Sized,
+        I: Iterator<Item = f32>,
+    {
+        let parameters: Vec<f32> = parameters.collect();
+        let mut distances = Vec::with_capacity(parameters.len());
+        let mut total = 0.;
+        let mut last = Vector3::from(curve.point(parameters[0]));
+        for &t in &parameters {
+            let p = Vector3::from(curve.point(t));
+            total += (p - last).magnitude();
+            distances.push(total);
+            last = p;
+        }
+        ArcLength {
+            parameters,
+            distances,
+        }
+    }
+
+    /// The length of the curve.
+    pub fn length(&self) -> f32 {
+        *self.distances.last().unwrap()
+    }
+
+    /// The parameter `distance` along the curve, interpolated between
+    /// the samples, and clamped to the ends of the curve.
+    pub fn parameter(&self, distance: f32) -> f32 {
+        let i = self.distances.partition_point(|&d| d < distance);
+        if i == 0 {
+            return self.parameters[0];
+        }
+        if i == self.distances.len() {
+            return *self.parameters.last().unwrap();
+        }
+        let (d0, d1) = (self.distances[i - 1], self.distances[i]);
+        let (t0, t1) = (self.parameters[i - 1], self.parameters[i]);
+        if d1 > d0 {
+            t0 + (t1 - t0) * (distance - d0) / (d1 - d0)
+        } else {
+            t0
+        }
+    }
+}
+
+/// Straight segments between points, each taking an equal share of the
+/// parameter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polyline {
+    points: Vec<Position>,
+}
+
+impl Polyline {
+    /// The polyline through `points`, of which there must be at least one.
+    pub fn new(points: Vec<Position>) -> Self {
+        assert!(!points.is_empty());
+        Polyline { points }
+    }
+
+    /// The points of the polyline.
+    pub fn points(&self) -> &[Position] {
+        &self.points
+    }
+
+    /// The segments of the polyline, as lines between vertices whose
+    /// normal is the direction of the segment, for instance to wrap them
+    /// with `Tubes`.
+    pub fn lines(&self) -> impl Iterator<Item = Line<Vertex>> + '_ {
+        self.points.windows(2).map(|w| {
+            let (a, b) = (Vector3::from(w[0]), Vector3::from(w[1]));
+            let normal = math::try_normalize(b - a)
+                .unwrap_or_else(|| Vector3::new(0., 0., 0.))
+                .into();
+            Line::new(Vertex { pos: w[0], normal }, Vertex { pos: w[1], normal })
+        })
+    }
+
+    // the segment `t` falls in, and how far along it
+    fn locate(&self, t: f32) -> (usize, f32) {
+        let segments = self.points.len() - 1;
+        let x = t.clamp(0., 1.) * segments as f32;
+        let i = (x.floor() as usize).min(segments.saturating_sub(1));
+        (i, x - i as f32)
+    }
+}
+
+impl Curve for Polyline {
+    fn point(&self, t: f32) -> Position {
+        if self.points.len() == 1 {
+            return self.points[0];
+        }
+        let (i, f) = self.locate(t);
+        let (a, b) = (
+            Vector3::from(self.points[i]),
+            Vector3::from(self.points[i + 1]),
+        );
+        (a + (b - a) * f).into()
+    }
+
+    fn derivative(&self, t: f32) -> mint::Vector3<f32> {
+        if self.points.len() == 1 {
+            return [0.; 3].into();
+        }
+        let (i, _) = self.locate(t);
+        let (a, b) = (
+            Vector3::from(self.points[i]),
+            Vector3::from(self.points[i + 1]),
+        );
+        ((b - a) * (self.points.len() - 1) as f32).into()
+    }
+
+    // straight segments are measured exactly at their ends
+    fn arc_length(&self) -> ArcLength {
+        let segments = self.points.len().saturating_sub(1).max(1);
+        ArcLength::from_parameters(self, (0..=segments).map(|i| i as f32 / segments as f32))
+    }
+}
+
+/// A smooth curve through all of its points, each span between two
+/// points taking an equal share of the parameter.
+///
+/// The tangent at each point is parallel to the line between the points
+/// before and after it; the ends get the tangent of the chord to their
+/// only neighbor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatmullRom {
+    points: Vec<Position>,
+}
+
+impl CatmullRom {
+    /// The spline through `points`, of which there must be at least two.
+    pub fn new(points: Vec<Position>) -> Self {
+        assert!(points.len() > 1);
+        CatmullRom { points }
+    }
+
+    /// The points the spline goes through.
+    pub fn points(&self) -> &[Position] {
+        &self.points
+    }
+
+    // the span `t` falls in, its four control points, and how far along
+    // it `t` is
+    fn span(&self, t: f32) -> ([Vector3<f32>; 4], f32) {
+        let n = self.points.len();
+        let x = t.clamp(0., 1.) * (n - 1) as f32;
+        let i = (x.floor() as usize).min(n - 2);
+        let p = |k: usize| Vector3::from(self.points[k]);
+        let (p1, p2) = (p(i), p(i + 1));
+        // mirror the end points to make up the missing neighbors
+        let p0 = if i > 0 { p(i - 1) } else { p1 * 2. - p2 };
+        let p3 = if i + 2 < n { p(i + 2) } else { p2 * 2. - p1 };
+        ([p0, p1, p2, p3], x - i as f32)
+    }
+}
+
+impl Curve for CatmullRom {
+    fn point(&self, t: f32) -> Position {
+        let ([p0, p1, p2, p3], u) = self.span(t);
+        let (u2, u3) = (u * u, u * u * u);
+        ((p1 * 2.
+            + (p2 - p0) * u
+            + (p0 * 2. - p1 * 5. + p2 * 4. - p3) * u2
+            + (p1 * 3. - p0 - p2 * 3. + p3) * u3)
+            * 0.5)
+            .into()
+    }
+
+    fn derivative(&self, t: f32) -> mint::Vector3<f32> {
+        let ([p0, p1, p2, p3], u) = self.span(t);
+        let spans = (self.points.len() - 1) as f32;
+        (((p2 - p0)
+            + (p0 * 2. - p1 * 5. + p2 * 4. - p3) * (2. * u)
+            + (p1 * 3. - p0 - p2 * 3. + p3) * (3. * u * u))
+            * (0.5 * spans))
+            .into()
+    }
+
+    fn arc_length(&self) -> ArcLength {
+        ArcLength::new(self, 32 * (self.points.len() - 1))
+    }
+}
+
+/// A cubic Bézier curve, from its first control point to its last, pulled
+/// towards the two in between.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CubicBezier {
+    /// The control points
+    pub points: [Position; 4],
+}
+
+impl CubicBezier {
+    /// The curve with the given control points.
+    pub fn new(p0: Position, p1: Position, p2: Position, p3: Position) -> Self {
+        CubicBezier {
+            points: [p0, p1, p2, p3],
+        }
+    }
+}
+
+impl Curve for CubicBezier {
+    fn point(&self, t: f32) -> Position {
+        let [p0, p1, p2, p3] = self.points.map(Vector3::from);
+        let s = 1. - t;
+        (p0 * (s * s * s) + p1 * (3. * s * s * t) + p2 * (3. * s * t * t) + p3 * (t * t * t)).into()
+    }
+
+    fn derivative(&self, t: f32) -> mint::Vector3<f32> {
+        let [p0, p1, p2, p3] = self.points.map(Vector3::from);
+        let s = 1. - t;
+        ((p1 - p0) * (3. * s * s) + (p2 - p1) * (6. * s * t) + (p3 - p2) * (3. * t * t)).into()
+    }
+}
+
+/// A point along a path, with the directions of the path there, to orient
+/// instances, cameras or cross sections along it.
+///
+/// The three directions are unit vectors at right angles to each other,
+/// with `normal`, `binormal` and `tangent` making a right handed frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frame {
+    /// Where the frame is
+    pub position: Position,
+    /// The direction the path goes in
+    pub tangent: Normal,
+    /// The first direction across the path
+    pub normal: Normal,
+    /// The second direction across the path, completing the frame
+    pub binormal: Normal,
+}
+
+impl Frame {
+    /// Frames at each of `points`, turned along the path as little as
+    /// possible: the normal only turns as much as the tangent forces it
+    /// to, so the frames never spin about the path. This is the frame to
+    /// use for tubes and other sweeps.
+    ///
+    /// The tangent at each point is the direction from the point before
+    /// it to the point after it, or to or from its only neighbor at the
+    /// ends. The first normal is picked arbitrarily across the first
+    /// tangent. The normals are carried from one point to the next with
+    /// the double reflection method of Wang et al.
+    pub fn parallel_transport(points: &[Position]) -> Vec<Frame> {
+        let n = points.len();
+        let p = |i: usize| Vector3::from(points[i]);
+        // points on top of their neighbors keep the tangent before them,
+        // or the first one found if they are at the start
+        let tangents: Vec<Option<Vector3<f32>>> = (0..n)
+            .map(|i| math::try_normalize(p((i + 1).min(n - 1)) - p(i.saturating_sub(1))))
+            .collect();
+        let mut last = tangents
+            .iter()
+            .flatten()
+            .next()
+            .cloned()
+            .unwrap_or_else(Vector3::unit_z);
+        let tangents: Vec<Vector3<f32>> = tangents
+            .into_iter()
+            .map(|t| {
+                last = t.unwrap_or(last);
+                last
+            })
+            .collect();
+
+        let mut frames = Vec::with_capacity(n);
+        let mut normal = match tangents.first() {
+            Some(&t) => orthonormal_basis(t).0,
+            None => return frames,
+        };
+        for i in 0..n {
+            if i > 0 {
+                let v1 = p(i) - p(i - 1);
+                let c1 = v1.magnitude2();
+                let (r, t) = if c1 > 0. {
+                    (
+                        normal - v1 * (2. / c1 * v1.dot(normal)),
+                        tangents[i - 1] - v1 * (2. / c1 * v1.dot(tangents[i - 1])),
+                    )
+                } else {
+                    (normal, tangents[i - 1])
+                };
+                let v2 = tangents[i] - t;
+                let c2 = v2.magnitude2();
+                normal = if c2 > 0. {
+                    r - v2 * (2. / c2 * v2.dot(r))
+                } else {
+                    r
+                };
+                // keep rounding errors from building up
+                normal = math::try_normalize(normal - tangents[i] * normal.dot(tangents[i]))
+                    .unwrap_or_else(|| orthonormal_basis(tangents[i]).0);
+            }
+            frames.push(Frame::new(points[i], tangents[i], normal));
+        }
+        frames
+    }
+
+    /// `count` Frenet frames at evenly spaced parameters along `curve`,
+    /// whose normal points to the center of the curve's bending, from its
+    /// first and second derivatives, the latter estimated from the change
+    /// of the first.
+    ///
+    /// Where the curve runs straight the normal is kept from the frame
+    /// before, or picked arbitrarily at the start. The normal flips over
+    /// where the curve changes the way it bends, so sweeps look better
+    /// with `parallel_transport`.
+    pub fn frenet<C: Curve + ?Sized>(curve: &C, count: usize) -> Vec<Frame> {
+        assert!(count > 1);
+        let h = 1e-3;
+        let mut frames: Vec<Frame> = Vec::with_capacity(count);
+        for i in 0..count {
+            let t = i as f32 / (count - 1) as f32;
+            let d1 = Vector3::from(curve.derivative(t));
+            let (a, b) = ((t - h).max(0.), (t + h).min(1.));
+            let d2 =
+                (Vector3::from(curve.derivative(b)) - Vector3::from(curve.derivative(a))) / (b - a);
+            let tangent = math::try_normalize(d1)
+                .or_else(|| frames.last().map(|f| Vector3::from(f.tangent)))
+                .unwrap_or_else(Vector3::unit_z);
+            // with too little bending to tell its direction, there is none
+            let bend = d1.cross(d2);
+            let binormal = math::try_normalize(bend)
+                .filter(|_| bend.magnitude2() > 1e-8 * d1.magnitude2() * d2.magnitude2());
+            let normal = match binormal {
+                Some(b) => b.cross(tangent),
+                None => {
+                    let last = frames.last().map(|f| Vector3::from(f.normal));
+                    let last = last.unwrap_or_else(|| orthonormal_basis(tangent).0);
+                    math::try_normalize(last - tangent * last.dot(tangent))
+                        .unwrap_or_else(|| orthonormal_basis(tangent).0)
+                }
+            };
+            frames.push(Frame::new(curve.point(t), tangent, normal));
+        }
+        frames
+    }
+
+    fn new(position: Position, tangent: Vector3<f32>, normal: Vector3<f32>) -> Frame {
+        Frame {
+            position,
+            tangent: tangent.into(),
+            normal: normal.into(),
+            binormal: tangent.cross(normal).into(),
+        }
+    }
+
+    /// The transform taking the x, y and z axes to the normal, binormal
+    /// and tangent, and the origin to the position.
+    pub fn matrix(&self) -> ColumnMatrix4<f32> {
+        let rotation = Matrix3::from_cols(
+            Vector3::from(self.normal),
+            Vector3::from(self.binormal),
+            Vector3::from(self.tangent),
+        );
+        let mut m = Matrix4::from(rotation);
+        m.w = Vector3::from(self.position).extend(1.);
+        m.into()
+    }
+}
@@ -1,14 +1,17 @@
-use super::generators::{IndexedPolygon, SharedVertex};
+use super::generators::{IndexedPolygon, PolygonGroup, SharedVertex};
 use super::{Polygon, Quad, Triangle};
 use crate::{Normal, Position, Vertex};
 use std::f32::consts::PI;
 
 /// Represents a cylinder with radius of 1, height of 2,
 /// and centered at (0, 0, 0) pointing up (to 0, 0, 1).
+///
+/// The faces are in three groups: the side is 0, the bottom cap 1 and the
+/// top cap 2.
 #[derive(Clone, Copy)]
 pub struct Cylinder {
-    u: usize,
-    h: isize,
+    idx: usize,
+    end: usize,
     sub_u: usize,
     sub_h: isize,
 }
@@ -43,13 +46,7 @@ impl Cylinder {
     /// Create a new cylinder.
     /// `u` is the number of points across the radius.
     pub fn new(u: usize) -> Self {
-        assert!(u > 1);
-        Cylinder {
-            u: 0,
-            h: -1,
-            sub_u: u,
-            sub_h: 1,
-        }
+        Cylinder::subdivide(u, 1)
     }
 
     /// Create a new subdivided cylinder.
@@ -58,8 +55,8 @@ impl Cylinder {
     pub fn subdivide(u: usize, h: usize) -> Self {
         assert!(u > 1 && h > 0);
         Cylinder {
-            u: 0,
-            h: -1,
+            idx: 0,
+            end: (2 + h) * u,
             sub_u: u,
             sub_h: h as isize,
         }
@@ -84,49 +81,65 @@ impl Cylinder {
             normal: normal.into(),
         }
     }
-}
-
-impl Iterator for Cylinder {
-    type Item = Polygon<Vertex>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.u == self.sub_u {
-            if self.h >= self.sub_h {
-                return None;
-            }
-            self.u = 0;
-            self.h += 1;
-        }
 
-        let u = self.u;
-        self.u += 1;
+    // the polygon at `idx`, going around each ring from the bottom cap
+    // to the top cap
+    fn polygon(&self, idx: usize) -> Polygon<Vertex> {
+        let u = idx % self.sub_u;
+        let h = (idx / self.sub_u) as isize - 1;
         // mathematically, reaching `u + 1 == sub_u` should trivially resolve,
         // because sin(2pi) == sin(0), but rounding errors go in the way.
-        let u1 = self.u % self.sub_u;
+        let u1 = (u + 1) % self.sub_u;
 
-        Some(if self.h < 0 {
-            let x = self.vert(u, self.h);
-            let y = self.vert(u1, self.h);
+        if h < 0 {
+            let x = self.vert(u, h);
+            let y = self.vert(u1, h);
             Polygon::PolyTri(Triangle::new(x, BOT, y))
-        } else if self.h == self.sub_h {
-            let x = self.vert(u, self.h + 1);
-            let y = self.vert(u1, self.h + 1);
+        } else if h == self.sub_h {
+            let x = self.vert(u, h + 1);
+            let y = self.vert(u1, h + 1);
             Polygon::PolyTri(Triangle::new(x, y, TOP))
         } else {
-            let x = self.vert(u, self.h);
-            let y = self.vert(u1, self.h);
-            let z = self.vert(u1, self.h + 1);
-            let w = self.vert(u, self.h + 1);
+            let x = self.vert(u, h);
+            let y = self.vert(u1, h);
+            let z = self.vert(u1, h + 1);
+            let w = self.vert(u, h + 1);
             Polygon::PolyQuad(Quad::new(x, y, z, w))
-        })
+        }
+    }
+}
+
+impl Iterator for Cylinder {
+    type Item = Polygon<Vertex>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.idx += 1;
+            Some(self.polygon(self.idx - 1))
+        } else {
+            None
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let n = self.sub_u * (1 + self.sub_h - self.h) as usize - self.u;
+        let n = self.end - self.idx;
         (n, Some(n))
     }
 }
 
+impl DoubleEndedIterator for Cylinder {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            Some(self.polygon(self.end))
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Cylinder {}
+
 impl SharedVertex<Vertex> for Cylinder {
     fn shared_vertex(&self, idx: usize) -> Vertex {
         if idx == 0 {
@@ -176,3 +189,20 @@ impl IndexedPolygon<Polygon<usize>> for Cylinder {
         (2 + self.sub_h) as usize * self.sub_u
     }
 }
+
+impl PolygonGroup for Cylinder {
+    fn polygon_group(&self, idx: usize) -> u32 {
+        let h = (idx / self.sub_u) as isize - 1;
+        if h < 0 {
+            1
+        } else if h == self.sub_h {
+            2
+        } else {
+            0
+        }
+    }
+
+    fn polygon_group_count(&self) -> u32 {
+        3
+    }
+}
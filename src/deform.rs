@@ -0,0 +1,194 @@
+//! Twisting, bending and tapering, the simple deformations of modeling
+//! packages, applied vertex by vertex.
+
+use cgmath::{Matrix, Matrix3, SquareMatrix, Vector3};
+
+use crate::math;
+use crate::{MapVertex, Vertex};
+
+/// One of the coordinate axes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// The x axis
+    X,
+    /// The y axis
+    Y,
+    /// The z axis
+    Z,
+}
+
+impl Axis {
+    // the other two axes, in the order that makes a right handed frame
+    // with this one last
+    pub(crate) fn frame(self) -> [usize; 3] {
+        match self {
+            Axis::X => [1, 2, 0],
+            Axis::Y => [2, 0, 1],
+            Axis::Z => [0, 1, 2],
+        }
+    }
+}
+
+/// A deformation of space that varies along an axis.
+///
+/// Each one moves the vertices, and turns their normals with the inverse
+/// transpose of the derivative of the deformation at the vertex, so that
+/// they stay correct without recomputing them from the faces. The
+/// deformations are measured from the origin; move the mesh first to pick
+/// where they start.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Deformer {
+    /// Rotate about the axis, by `angle` radians for each unit along it.
+    Twist {
+        /// The axis to twist about
+        axis: Axis,
+        /// The angle per unit of length, in radians
+        angle: f32,
+    },
+    /// Curl the axis into a circle of radius `1 / curvature`, towards the
+    /// next axis over: the x axis towards y, y towards z and z towards x.
+    /// Lengths along the axis are kept where it crosses the origin.
+    Bend {
+        /// The axis to bend
+        axis: Axis,
+        /// The inverse of the radius of the bend
+        curvature: f32,
+    },
+    /// Scale across the axis, by `1 + factor` times the distance along it.
+    Taper {
+        /// The axis to taper along
+        axis: Axis,
+        /// The change of scale per unit of length
+        factor: f32,
+    },
+}
+
+impl Deformer {
+    /// Deform a single vertex.
+    pub fn apply(&self, v: Vertex) -> Vertex {
+        let axis = match *self {
+            Deformer::Twist { axis, .. }
+            | Deformer::Bend { axis, .. }
+            | Deformer::Taper { axis, .. } => axis,
+        };
+        let [a, b, c] = axis.frame();
+        let local = |p: Vector3<f32>| Vector3::new(p[a], p[b], p[c]);
+        let world = |l: Vector3<f32>| {
+            let mut p = Vector3::new(0., 0., 0.);
+            p[a] = l.x;
+            p[b] = l.y;
+            p[c] = l.z;
+            p
+        };
+        let (pos, jacobian) = self.local(local(Vector3::from(v.pos)));
+        let normal = jacobian
+            .invert()
+            .map(|inv| inv.transpose() * local(Vector3::from(v.normal)))
+            .and_then(math::try_normalize)
+            .map(world)
+            .unwrap_or_else(|| Vector3::from(v.normal));
+        Vertex {
+            pos: world(pos).into(),
+            normal: normal.into(),
+        }
+    }
+
+    // the deformed position of `p`, in the frame with the axis along z,
+    // and the derivative of the deformation there
+    fn local(&self, p: Vector3<f32>) -> (Vector3<f32>, Matrix3<f32>) {
+        let (u, v, w) = (p.x, p.y, p.z);
+        match *self {
+            Deformer::Twist { angle, .. } => {
+                let (s, c) = (angle * w).sin_cos();
+                let (u1, v1) = (u * c - v * s, u * s + v * c);
+                (
+                    Vector3::new(u1, v1, w),
+                    // cgmath matrices are built from columns
+                    Matrix3::new(c, s, 0., -s, c, 0., -angle * v1, angle * u1, 1.),
+                )
+            }
+            Deformer::Bend { curvature, .. } => {
+                if curvature == 0. {
+                    return (p, Matrix3::identity());
+                }
+                let (s, c) = (curvature * w).sin_cos();
+                let r = 1. / curvature - u;
+                let k = 1. - curvature * u;
+                (
+                    Vector3::new(1. / curvature - r * c, v, r * s),
+                    Matrix3::new(c, 0., -s, 0., 1., 0., k * s, 0., k * c),
+                )
+            }
+            Deformer::Taper { factor, .. } => {
+                let s = 1. + factor * w;
+                (
+                    Vector3::new(u * s, v * s, w),
+                    Matrix3::new(s, 0., 0., 0., s, 0., factor * u, factor * v, 1.),
+                )
+            }
+        }
+    }
+}
+
+/// Deforms a stream of polygons.
+pub trait Deform: Sized {
+    /// Deform every vertex with `deformer`.
+    fn deform(self, deformer: Deformer) -> DeformIterator<Self>;
+
+    /// Rotate about `axis` by `angle` radians per unit along it, see
+    /// `Deformer::Twist`.
+    fn twist(self, axis: Axis, angle: f32) -> DeformIterator<Self> {
+        self.deform(Deformer::Twist { axis, angle })
+    }
+
+    /// Curl `axis` into a circle of radius `1 / curvature`, see
+    /// `Deformer::Bend`.
+    fn bend(self, axis: Axis, curvature: f32) -> DeformIterator<Self> {
+        self.deform(Deformer::Bend { axis, curvature })
+    }
+
+    /// Scale across `axis` by `1 + factor` times the distance along it,
+    /// see `Deformer::Taper`.
+    fn taper(self, axis: Axis, factor: f32) -> DeformIterator<Self> {
+        self.deform(Deformer::Taper { axis, factor })
+    }
+}
+
+impl<I, P> Deform for I
+where
+    I: Iterator<Item = P>,
+    P: MapVertex<Vertex, Vertex, Output = P>,
+{
+    fn deform(self, deformer: Deformer) -> DeformIterator<I> {
+        DeformIterator {
+            source: self,
+            deformer,
+        }
+    }
+}
+
+/// An iterator deforming the polygons of its source
+#[derive(Clone)]
+pub struct DeformIterator<I> {
+    source: I,
+    deformer: Deformer,
+}
+
+impl<I, P> Iterator for DeformIterator<I>
+where
+    I: Iterator<Item = P>,
+    P: MapVertex<Vertex, Vertex, Output = P>,
+{
+    type Item = P;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.source.size_hint()
+    }
+
+    fn next(&mut self) -> Option<P> {
+        let deformer = self.deformer;
+        self.source
+            .next()
+            .map(|p| p.map_vertex(|v| deformer.apply(v)))
+    }
+}
@@ -0,0 +1,72 @@
+//! Ordering polygons from back to front, for drawing transparent meshes.
+
+use cgmath::{InnerSpace, Vector3, Zero};
+
+use crate::bounds::EmitPositions;
+use crate::{Position, Triangle, View};
+
+/// Sorts a stream of polygons by their distance from a viewer.
+pub trait DepthSort: Iterator + Sized {
+    /// Collect the polygons and stream them back again from the farthest
+    /// from `view` to the nearest, so that blending them in that order
+    /// draws transparent surfaces right.
+    ///
+    /// Polygons are compared by the average of their vertices: its
+    /// distance from the viewpoint of `View::Point`, or how far along the
+    /// direction of `View::Direction` it is. Polygons at the same depth
+    /// keep their order.
+    fn sort_by_depth(self, view: View) -> std::vec::IntoIter<Self::Item>;
+}
+
+impl<T, P> DepthSort for T
+where
+    T: Iterator<Item = P>,
+    P: EmitPositions + Clone,
+{
+    fn sort_by_depth(self, view: View) -> std::vec::IntoIter<P> {
+        let mut polygons: Vec<(f32, P)> =
+            self.map(|p| (depth(view, center(p.clone())), p)).collect();
+        polygons.sort_by(|a, b| b.0.total_cmp(&a.0));
+        polygons
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+/// Reorder the `triangles` of an indexed mesh from the farthest from
+/// `view` to the nearest, as `DepthSort::sort_by_depth` does, to draw the
+/// index buffer back to front.
+pub fn sort_indices_by_depth(
+    positions: &[Position],
+    triangles: &mut [Triangle<usize>],
+    view: View,
+) {
+    let p = |i: usize| Vector3::from(positions[i]);
+    let mut sorted: Vec<(f32, Triangle<usize>)> = triangles
+        .iter()
+        .map(|t| (depth(view, (p(t.x) + p(t.y) + p(t.z)) / 3.), t.clone()))
+        .collect();
+    sorted.sort_by(|a, b| b.0.total_cmp(&a.0));
+    for (t, (_, sorted)) in triangles.iter_mut().zip(sorted) {
+        *t = sorted;
+    }
+}
+
+fn center<P: EmitPositions>(polygon: P) -> Vector3<f32> {
+    let (mut sum, mut count) = (Vector3::zero(), 0);
+    polygon.emit_positions(|p| {
+        sum += Vector3::from(p);
+        count += 1;
+    });
+    sum / count.max(1) as f32
+}
+
+// larger is farther
+fn depth(view: View, point: Vector3<f32>) -> f32 {
+    match view {
+        View::Point(eye) => (point - Vector3::from(eye)).magnitude2(),
+        View::Direction(direction) => point.dot(direction.into()),
+    }
+}
@@ -0,0 +1,117 @@
+//! Moving vertices along their normals, the usual way to roughen the
+//! smooth generators into rocks, asteroids or terrain.
+
+use cgmath::Vector3;
+
+use crate::{
+    MapVertex, Neighbors, Normal, NormalFallback, Position, Triangle, Vertex, VertexAttributes,
+};
+
+/// Something made of vertices that can be pushed along their normals:
+/// a single `Vertex`, or any polygon of them.
+pub trait Displaceable {
+    /// Move every vertex by `offset(pos, normal)` along its normal.
+    fn displaced<F>(self, offset: &mut F) -> Self
+    where
+        F: FnMut(Position, Normal) -> f32;
+}
+
+impl Displaceable for Vertex {
+    fn displaced<F>(self, offset: &mut F) -> Self
+    where
+        F: FnMut(Position, Normal) -> f32,
+    {
+        let d = offset(self.pos, self.normal);
+        let n = Vector3::from(self.normal);
+        Vertex {
+            pos: (Vector3::from(self.pos) + n * d).into(),
+            normal: self.normal,
+        }
+    }
+}
+
+impl<P> Displaceable for P
+where
+    P: MapVertex<Vertex, Vertex, Output = P>,
+{
+    fn displaced<F>(self, offset: &mut F) -> Self
+    where
+        F: FnMut(Position, Normal) -> f32,
+    {
+        self.map_vertex(|v| v.displaced(offset))
+    }
+}
+
+/// Displaces a stream of vertices or polygons.
+pub trait Displace: Sized {
+    /// Move every vertex along its normal by the distance returned by
+    /// `offset(pos, normal)`, typically a noise or height function.
+    ///
+    /// The normals are left untouched; for an indexed mesh, use
+    /// `recompute_normals` once the vertices are displaced. On a polygon
+    /// stream, vertices that are shared but have different normals, like
+    /// the corners of a cube, move apart and open cracks.
+    fn displace<F>(self, offset: F) -> DisplaceIterator<Self, F>
+    where
+        F: FnMut(Position, Normal) -> f32;
+}
+
+impl<I> Displace for I
+where
+    I: Iterator,
+    I::Item: Displaceable,
+{
+    fn displace<F>(self, offset: F) -> DisplaceIterator<I, F>
+    where
+        F: FnMut(Position, Normal) -> f32,
+    {
+        DisplaceIterator {
+            source: self,
+            offset,
+        }
+    }
+}
+
+/// An iterator moving the vertices of its source along their normals
+pub struct DisplaceIterator<I, F> {
+    source: I,
+    offset: F,
+}
+
+impl<I, F> Iterator for DisplaceIterator<I, F>
+where
+    I: Iterator,
+    I::Item: Displaceable,
+    F: FnMut(Position, Normal) -> f32,
+{
+    type Item = I::Item;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.source.size_hint()
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = &mut self.offset;
+        self.source.next().map(|p| p.displaced(offset))
+    }
+}
+
+/// Replace the normals of an indexed mesh by the average normal of the
+/// triangles around each vertex, as after a displacement.
+///
+/// `fallback` decides what degenerate triangles contribute; vertices that
+/// still end up without a direction, including unused ones, keep their
+/// previous normal.
+pub fn recompute_normals<V: VertexAttributes>(
+    vertices: &mut [V],
+    triangles: &[Triangle<usize>],
+    fallback: NormalFallback,
+) {
+    let positions: Vec<Position> = vertices.iter().map(|v| v.position()).collect();
+    let neighbors = Neighbors::new(positions, triangles.to_vec());
+    for (i, v) in vertices.iter_mut().enumerate() {
+        if let Some(n) = neighbors.normal_for_vertex_with(i, |&p| p, fallback) {
+            v.set_normal(n);
+        }
+    }
+}
@@ -1,10 +1,12 @@
+use std::ops::Range;
+
 use cgmath::{InnerSpace, Vector3};
 
 use crate::generators::{IndexedPolygon, SharedVertex};
-use crate::{NGon, Polygon, Polygon::PolyNGon, Vertex};
+use crate::{ConstNGon, MapVertex, Polygon, Polygon::PolyNGon, Vertex};
 
-const PHI: f32 = 1.618033988749895; // (5 ^ 0.5 + 1) * 0.5
-const CONJPHI: f32 = 0.6180339887498948; // 1 / PHI
+const PHI: f32 = 1.618_034; // (5 ^ 0.5 + 1) * 0.5
+const CONJPHI: f32 = 0.618_034; // 1 / PHI
 
 // 20 vertices
 const VERTICES: [[f32; 3]; 20] = [
@@ -48,13 +50,15 @@ const FACES: [[usize; 5]; 12] = [
 
 /// Platonic dodecahedron, made of pentagons
 pub struct Dodecahedron {
-    i: usize,
+    range: Range<usize>,
 }
 
 impl Dodecahedron {
     /// Create a unit Dodecahedron
     pub fn new() -> Self {
-        Self { i: 0 }
+        Self {
+            range: 0..FACES.len(),
+        }
     }
 
     fn vert(&self, index: usize) -> Vertex {
@@ -68,32 +72,36 @@ impl Dodecahedron {
     }
 }
 
+impl Default for Dodecahedron {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Iterator for Dodecahedron {
     type Item = Polygon<Vertex>;
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (FACES.len(), Some(FACES.len()))
+        self.range.size_hint()
     }
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.i == FACES.len() {
-            return None;
-        }
-
-        let mut result = NGon::new();
-
-        let face = FACES[self.i];
-        result.add_vertex(self.vert(face[0]));
-        result.add_vertex(self.vert(face[1]));
-        result.add_vertex(self.vert(face[2]));
-        result.add_vertex(self.vert(face[3]));
-        result.add_vertex(self.vert(face[4]));
-        self.i += 1;
+        self.range
+            .next()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vert(i)))
+    }
+}
 
-        Some(PolyNGon(result))
+impl DoubleEndedIterator for Dodecahedron {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range
+            .next_back()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vert(i)))
     }
 }
 
+impl ExactSizeIterator for Dodecahedron {}
+
 impl SharedVertex<Vertex> for Dodecahedron {
     fn shared_vertex_count(&self) -> usize {
         VERTICES.len()
@@ -110,15 +118,6 @@ impl IndexedPolygon<Polygon<usize>> for Dodecahedron {
     }
 
     fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
-        let mut result = NGon::new();
-
-        let face = FACES[idx];
-        result.add_vertex(face[0]);
-        result.add_vertex(face[1]);
-        result.add_vertex(face[2]);
-        result.add_vertex(face[3]);
-        result.add_vertex(face[4]);
-
-        PolyNGon(result)
+        PolyNGon(ConstNGon::new(FACES[idx]).into())
     }
 }
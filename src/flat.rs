@@ -0,0 +1,88 @@
+//! Generators made of flat shaded polygons, built once up front.
+
+use std::ops::Range;
+
+use cgmath::Vector3;
+
+use crate::math;
+use crate::Polygon::{PolyQuad, PolyTri};
+use crate::{MapVertex, Polygon, Quad, Triangle, Vertex};
+
+// Polygons with their own vertices, all with the normal of the polygon.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FlatFaces {
+    pub(crate) range: Range<usize>,
+    pub(crate) vertices: Vec<Vertex>,
+    pub(crate) polygons: Vec<Polygon<usize>>,
+}
+
+impl FlatFaces {
+    // add a triangle or a quad, wound counter clockwise seen from outside
+    pub(crate) fn add(&mut self, corners: &[Vector3<f32>]) {
+        let normal = math::try_normalize(math::newell_normal(corners))
+            .unwrap_or_else(|| Vector3::new(0., 0., 0.));
+        let base = self.vertices.len();
+        self.vertices.extend(corners.iter().map(|&c| Vertex {
+            pos: c.into(),
+            normal: normal.into(),
+        }));
+        self.polygons.push(match corners.len() {
+            3 => PolyTri(Triangle::new(base, base + 1, base + 2)),
+            _ => PolyQuad(Quad::new(base, base + 1, base + 2, base + 3)),
+        });
+        self.range = 0..self.polygons.len();
+    }
+
+    pub(crate) fn polygon(&self, idx: usize) -> Polygon<Vertex> {
+        self.polygons[idx].clone().map_vertex(|i| self.vertices[i])
+    }
+}
+
+// The generator traits for a type keeping its polygons in a `faces` field.
+macro_rules! flat_faces_generator {
+    ($name:ident) => {
+        impl Iterator for $name {
+            type Item = $crate::Polygon<$crate::Vertex>;
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.faces.range.size_hint()
+            }
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let idx = self.faces.range.next()?;
+                Some(self.faces.polygon(idx))
+            }
+        }
+
+        impl DoubleEndedIterator for $name {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let idx = self.faces.range.next_back()?;
+                Some(self.faces.polygon(idx))
+            }
+        }
+
+        impl ExactSizeIterator for $name {}
+
+        impl $crate::generators::SharedVertex<$crate::Vertex> for $name {
+            fn shared_vertex(&self, idx: usize) -> $crate::Vertex {
+                self.faces.vertices[idx]
+            }
+
+            fn shared_vertex_count(&self) -> usize {
+                self.faces.vertices.len()
+            }
+        }
+
+        impl $crate::generators::IndexedPolygon<$crate::Polygon<usize>> for $name {
+            fn indexed_polygon(&self, idx: usize) -> $crate::Polygon<usize> {
+                self.faces.polygons[idx].clone()
+            }
+
+            fn indexed_polygon_count(&self) -> usize {
+                self.faces.polygons.len()
+            }
+        }
+    };
+}
+
+pub(crate) use flat_faces_generator;
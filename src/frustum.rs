@@ -0,0 +1,188 @@
+use cgmath::{InnerSpace, Matrix4, Vector3, Vector4};
+use mint::ColumnMatrix4;
+
+use super::generators::{IndexedPolygon, SharedVertex};
+use super::{Line, MapVertex, Polygon, Polygon::PolyQuad, Position, Quad, Vertex};
+use crate::math;
+
+// the corners of each face, as in `Cube`, numbered with a bit for each of
+// right (4), top (2) and far (1)
+const FACES: [[usize; 4]; 6] = [
+    [0b110, 0b111, 0b101, 0b100],
+    [0b000, 0b001, 0b011, 0b010],
+    [0b011, 0b111, 0b110, 0b010],
+    [0b100, 0b101, 0b001, 0b000],
+    [0b101, 0b111, 0b011, 0b001],
+    [0b000, 0b010, 0b110, 0b100],
+];
+
+// the corners at each end of the edges: around the near plane, around the
+// far plane, then from near to far
+const EDGES: [[usize; 2]; 12] = [
+    [0b000, 0b100],
+    [0b100, 0b110],
+    [0b110, 0b010],
+    [0b010, 0b000],
+    [0b001, 0b101],
+    [0b101, 0b111],
+    [0b111, 0b011],
+    [0b011, 0b001],
+    [0b000, 0b001],
+    [0b100, 0b101],
+    [0b110, 0b111],
+    [0b010, 0b011],
+];
+
+/// The volume seen by a camera, a truncated pyramid between its near and
+/// far planes, for drawing cameras and shadow cascades in debug views.
+///
+/// The six faces are quads with their own vertices, so that they are flat
+/// shaded, in the order right, left, top, bottom, far, near. The edges are
+/// available separately for drawing the frustum as lines.
+#[derive(Clone, Copy, Debug)]
+pub struct Frustum {
+    idx: usize,
+    end: usize,
+    corners: [Position; 8],
+    // whether the corners are mirrored compared to the cube
+    flipped: bool,
+}
+
+impl Frustum {
+    /// Create the frustum of a perspective camera at (0, 0, 0), looking
+    /// down -z with y up. `fov_y` is the vertical field of view in radians,
+    /// `aspect` the width divided by the height, and `near` and `far` the
+    /// distances to the clipping planes.
+    pub fn new(fov_y: f32, aspect: f32, near: f32, far: f32) -> Self {
+        assert!(fov_y > 0. && aspect > 0. && 0. < near && near < far);
+        let tan = (fov_y / 2.).tan();
+        Frustum::from_corners(|x, y, z| {
+            let d = if z > 0. { far } else { near };
+            [x * d * tan * aspect, y * d * tan, -d]
+        })
+    }
+
+    /// Create a frustum from the inverse of a projection matrix, which
+    /// maps the normalized device coordinates, from -1 to 1 on each axis
+    /// with -1 as near along z, back to view space.
+    pub fn from_inverse_projection<M>(inverse: M) -> Self
+    where
+        M: Into<ColumnMatrix4<f32>>,
+    {
+        let m = Matrix4::from(inverse.into());
+        Frustum::from_corners(|x, y, z| {
+            let p = m * Vector4::new(x, y, z, 1.);
+            (p.truncate() / p.w).into()
+        })
+    }
+
+    fn from_corners<F: Fn(f32, f32, f32) -> [f32; 3]>(corner: F) -> Self {
+        let sign = |bit: bool| if bit { 1. } else { -1. };
+        let mut corners = [Position::from([0.; 3]); 8];
+        for (i, c) in corners.iter_mut().enumerate() {
+            *c = corner(sign(i & 4 != 0), sign(i & 2 != 0), sign(i & 1 != 0)).into();
+        }
+        // the cube corners are right handed; a mirroring mapping has to
+        // reverse the faces to keep them facing out
+        let v = |i: usize| Vector3::from(corners[i]);
+        let flipped = (v(4) - v(0)).cross(v(2) - v(0)).dot(v(1) - v(0)) < 0.;
+        Frustum {
+            idx: 0,
+            end: 6,
+            corners,
+            flipped,
+        }
+    }
+
+    /// The corners of the frustum, numbered with a bit for each of right
+    /// (4), top (2) and far (1).
+    pub fn corners(&self) -> [Position; 8] {
+        self.corners
+    }
+
+    /// The twelve edges of the frustum.
+    pub fn edges(&self) -> [Line<Position>; 12] {
+        EDGES.map(|[a, b]| Line::new(self.corners[a], self.corners[b]))
+    }
+
+    fn face(&self, idx: usize) -> [usize; 4] {
+        let [a, b, c, d] = FACES[idx];
+        if self.flipped {
+            [a, d, c, b]
+        } else {
+            [a, b, c, d]
+        }
+    }
+
+    fn normal(&self, idx: usize) -> Vector3<f32> {
+        // Newell's method, for quads that are slightly skewed
+        let face = self.face(idx);
+        let mut n = Vector3::new(0., 0., 0.);
+        for k in 0..4 {
+            let a = Vector3::from(self.corners[face[k]]);
+            let b = Vector3::from(self.corners[face[(k + 1) % 4]]);
+            n += a.cross(b);
+        }
+        math::try_normalize(n).unwrap_or(n)
+    }
+}
+
+impl Iterator for Frustum {
+    type Item = Polygon<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.idx;
+        (n, Some(n))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.idx += 1;
+            Some(
+                self.indexed_polygon(self.idx - 1)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl DoubleEndedIterator for Frustum {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            Some(
+                self.indexed_polygon(self.end)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Frustum {}
+
+impl SharedVertex<Vertex> for Frustum {
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        Vertex {
+            pos: self.corners[self.face(idx / 4)[idx % 4]],
+            normal: self.normal(idx / 4).into(),
+        }
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        24
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for Frustum {
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        PolyQuad(Quad::new(idx * 4, idx * 4 + 1, idx * 4 + 2, idx * 4 + 3))
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        6
+    }
+}
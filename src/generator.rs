@@ -41,6 +41,14 @@ impl<'a, T: SharedVertex<V>, V> Iterator for SharedVertexIterator<'a, T, V> {
     }
 }
 
+impl<'a, T: SharedVertex<V>, V> DoubleEndedIterator for SharedVertexIterator<'a, T, V> {
+    fn next_back(&mut self) -> Option<V> {
+        self.idx.next_back().map(|idx| self.base.shared_vertex(idx))
+    }
+}
+
+impl<'a, T: SharedVertex<V>, V> ExactSizeIterator for SharedVertexIterator<'a, T, V> {}
+
 /// The `IndexedPolygon` trait is used with the `SharedVertex` trait in order to build
 /// a mesh. `IndexedPolygon` calculates each polygon face required to build an implementors mesh.
 /// each face is always returned in indexed form that points to the correct vertice supplied
@@ -80,3 +88,75 @@ impl<'a, T: IndexedPolygon<V>, V> Iterator for IndexedPolygonIterator<'a, T, V>
         self.idx.next().map(|idx| self.base.indexed_polygon(idx))
     }
 }
+
+impl<'a, T: IndexedPolygon<V>, V> DoubleEndedIterator for IndexedPolygonIterator<'a, T, V> {
+    fn next_back(&mut self) -> Option<V> {
+        self.idx
+            .next_back()
+            .map(|idx| self.base.indexed_polygon(idx))
+    }
+}
+
+impl<'a, T: IndexedPolygon<V>, V> ExactSizeIterator for IndexedPolygonIterator<'a, T, V> {}
+
+/// The `PolygonGroup` trait is used with the `IndexedPolygon` trait to tag
+/// each face of a mesh with a group, such as a material, so that the faces
+/// can be split into separate draw calls. Groups are numbered from 0.
+pub trait PolygonGroup: Sized {
+    /// return the group of the polygon at offset `i`
+    fn polygon_group(&self, i: usize) -> u32;
+
+    /// return the number of groups the polygons are spread over
+    fn polygon_group_count(&self) -> u32;
+
+    /// create an iterator that will return the group of each face in the
+    /// source mesh, along with the face itself
+    fn grouped_polygon_iter<'a, V>(&'a self) -> GroupedPolygonIterator<'a, Self, V>
+    where
+        Self: IndexedPolygon<V>,
+    {
+        GroupedPolygonIterator {
+            base: self,
+            idx: 0..self.indexed_polygon_count(),
+            phantom_v: PhantomData,
+        }
+    }
+}
+
+/// An iterator that yields the indices of the mesh with their group
+pub struct GroupedPolygonIterator<'a, T: 'a, V> {
+    base: &'a T,
+    idx: Range<usize>,
+    phantom_v: PhantomData<V>,
+}
+
+impl<'a, T: IndexedPolygon<V> + PolygonGroup, V> GroupedPolygonIterator<'a, T, V> {
+    fn get(&self, idx: usize) -> (u32, V) {
+        (self.base.polygon_group(idx), self.base.indexed_polygon(idx))
+    }
+}
+
+impl<'a, T: IndexedPolygon<V> + PolygonGroup, V> Iterator for GroupedPolygonIterator<'a, T, V> {
+    type Item = (u32, V);
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.idx.size_hint()
+    }
+
+    fn next(&mut self) -> Option<(u32, V)> {
+        self.idx.next().map(|idx| self.get(idx))
+    }
+}
+
+impl<'a, T: IndexedPolygon<V> + PolygonGroup, V> DoubleEndedIterator
+    for GroupedPolygonIterator<'a, T, V>
+{
+    fn next_back(&mut self) -> Option<(u32, V)> {
+        self.idx.next_back().map(|idx| self.get(idx))
+    }
+}
+
+impl<'a, T: IndexedPolygon<V> + PolygonGroup, V> ExactSizeIterator
+    for GroupedPolygonIterator<'a, T, V>
+{
+}
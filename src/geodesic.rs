@@ -0,0 +1,149 @@
+//! Distances measured across the surface of a mesh.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::{Position, Triangle};
+
+/// How `geodesic_distances` measures paths across the mesh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GeodesicMethod {
+    /// The shortest paths along the edges of the mesh. Fast, but paths
+    /// that would cut across triangles zigzag along their edges instead,
+    /// so distances come out too long, the more so in the directions that
+    /// no edge follows.
+    Dijkstra,
+    /// Fast marching: a front grows out of the seeds and crosses each
+    /// triangle as a straight line, which gets much closer to the true
+    /// distance. Where the front would reach a corner without crossing
+    /// the opposite edge, as it can next to obtuse angles, the distance
+    /// falls back to the edges.
+    FastMarching,
+}
+
+// a vertex reached at some distance, reversed so that the `BinaryHeap`
+// pops the closest first
+#[derive(Clone, Copy, PartialEq)]
+struct Reached {
+    distance: f32,
+    vertex: usize,
+}
+
+impl Eq for Reached {}
+
+impl PartialOrd for Reached {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Reached {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then_with(|| other.vertex.cmp(&self.vertex))
+    }
+}
+
+/// Compute the distance from the nearest of the `seeds` to every vertex
+/// of an indexed triangle mesh, going across its surface. The distances
+/// drive effects that spread over a surface, like growth, burning or
+/// ripples, and select the region within some distance of a point.
+///
+/// The mesh has to share its vertices along its edges, see `weld`. The
+/// vertices that cannot be reached from any seed get an infinite distance.
+/// Panics if a seed or a corner of a triangle is not the index of one of
+/// the `positions`.
+pub fn geodesic_distances(
+    positions: &[Position],
+    triangles: &[Triangle<usize>],
+    seeds: &[usize],
+    method: GeodesicMethod,
+) -> Vec<f32> {
+    let p = |i: usize| Vector3::from(positions[i]);
+    let mut faces_of: Vec<Vec<usize>> = vec![Vec::new(); positions.len()];
+    for (f, t) in triangles.iter().enumerate() {
+        for i in [t.x, t.y, t.z] {
+            faces_of[i].push(f);
+        }
+    }
+
+    let mut distance = vec![f32::INFINITY; positions.len()];
+    let mut done = vec![false; positions.len()];
+    let mut heap = BinaryHeap::new();
+    for &s in seeds {
+        distance[s] = 0.;
+        heap.push(Reached {
+            distance: 0.,
+            vertex: s,
+        });
+    }
+    while let Some(Reached {
+        distance: d,
+        vertex: v,
+    }) = heap.pop()
+    {
+        if done[v] || d > distance[v] {
+            continue;
+        }
+        done[v] = true;
+        for &f in &faces_of[v] {
+            let t = &triangles[f];
+            let corners = [t.x, t.y, t.z];
+            for &x in &corners {
+                if x == v || done[x] {
+                    continue;
+                }
+                let mut best = d + (p(x) - p(v)).magnitude();
+                // the third corner, already reached, lets the front cross
+                // the triangle
+                let third = corners.iter().find(|&&o| o != v && o != x);
+                if let (GeodesicMethod::FastMarching, Some(&o)) = (method, third) {
+                    if done[o] {
+                        if let Some(through) = cross(p(v), d, p(o), distance[o], p(x)) {
+                            best = best.min(through);
+                        }
+                    }
+                }
+                if best < distance[x] {
+                    distance[x] = best;
+                    heap.push(Reached {
+                        distance: best,
+                        vertex: x,
+                    });
+                }
+            }
+        }
+    }
+    distance
+}
+
+// the distance to `c` of a straight front reaching `a` at distance `da`
+// and `b` at `db`, if it comes into the triangle through the edge between
+// them; the front is unfolded into the plane of the triangle, where it
+// comes from a virtual source on the other side of the edge
+fn cross(a: Vector3<f32>, da: f32, b: Vector3<f32>, db: f32, c: Vector3<f32>) -> Option<f32> {
+    let ab = b - a;
+    let len = ab.magnitude();
+    if len <= 0. {
+        return None;
+    }
+    let e = ab / len;
+    let ac = c - a;
+    let (cx, cy) = (ac.dot(e), (ac - e * ac.dot(e)).magnitude());
+    let sx = (da * da - db * db + len * len) / (2. * len);
+    let sy2 = da * da - sx * sx;
+    if sy2 < 0. || cy <= 0. {
+        return None;
+    }
+    let sy = -sy2.sqrt();
+    // where the line from the source to `c` crosses the edge
+    let x = sx + (cx - sx) * (-sy / (cy - sy));
+    if !(0. ..=len).contains(&x) {
+        return None;
+    }
+    Some((cx - sx).hypot(cy - sy))
+}
@@ -0,0 +1,260 @@
+//! Export of indexed meshes to glTF 2.0.
+//!
+//! Meshes can be written either as a `.glb` binary file, or as a `.gltf`
+//! JSON file with the geometry embedded as a base64 data URI, so that the
+//! result is always a single self-contained file.
+
+use std::io::{self, Write};
+
+use crate::attributes::custom_values;
+use crate::{Triangle, Vertex, VertexAttributes};
+
+// component types and buffer view targets from the specification
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// An indexed triangle mesh ready to be written as glTF.
+///
+/// Normals, texture coordinates and colors are written when the vertices
+/// have them, and their custom values as the scalar attributes
+/// `_CUSTOM_0`, `_CUSTOM_1` and so on. glTF has no room for empty meshes,
+/// so the mesh needs at least one triangle to be written.
+#[derive(Debug)]
+pub struct GltfMesh<'a, V = Vertex> {
+    vertices: &'a [V],
+    triangles: &'a [Triangle<usize>],
+    uvs: Option<&'a [[f32; 2]]>,
+}
+
+impl<'a, V> Clone for GltfMesh<'a, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, V> Copy for GltfMesh<'a, V> {}
+
+impl<'a, V: VertexAttributes> GltfMesh<'a, V> {
+    /// Describe a mesh made of `vertices`, and of `triangles` indexing
+    /// into them.
+    pub fn new(vertices: &'a [V], triangles: &'a [Triangle<usize>]) -> Self {
+        GltfMesh {
+            vertices,
+            triangles,
+            uvs: None,
+        }
+    }
+
+    /// Also write texture coordinates, one per vertex, in place of those of
+    /// the vertices. Writing the mesh fails if there are not as many of
+    /// them as vertices.
+    pub fn with_uvs(mut self, uvs: &'a [[f32; 2]]) -> Self {
+        self.uvs = Some(uvs);
+        self
+    }
+
+    /// Serialize the mesh into the bytes of a `.glb` file.
+    pub fn to_glb(&self) -> io::Result<Vec<u8>> {
+        let (json, mut bin) = self.encode(None)?;
+        let mut json = json.into_bytes();
+        while json.len() % 4 != 0 {
+            json.push(b' ');
+        }
+        while bin.len() % 4 != 0 {
+            bin.push(0);
+        }
+
+        let total = 12 + 8 + json.len() + 8 + bin.len();
+        let mut out = Vec::with_capacity(total);
+        out.extend_from_slice(b"glTF");
+        out.extend_from_slice(&2u32.to_le_bytes());
+        out.extend_from_slice(&(total as u32).to_le_bytes());
+        out.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        out.extend_from_slice(b"JSON");
+        out.extend_from_slice(&json);
+        out.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+        out.extend_from_slice(b"BIN\0");
+        out.extend_from_slice(&bin);
+        Ok(out)
+    }
+
+    /// Serialize the mesh into the text of a `.gltf` file.
+    pub fn to_gltf(&self) -> io::Result<String> {
+        let (_, bin) = self.encode(None)?;
+        let uri = format!("data:application/octet-stream;base64,{}", base64(&bin));
+        Ok(self.encode(Some(&uri))?.0)
+    }
+
+    /// Write the mesh as a `.glb` file.
+    pub fn write_glb<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.to_glb()?)
+    }
+
+    /// Write the mesh as a `.gltf` file.
+    pub fn write_gltf<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.to_gltf()?.as_bytes())
+    }
+
+    // Build the JSON document and the binary buffer it describes. The
+    // buffer is referred to by `uri`, or is the binary chunk of a `.glb`.
+    fn encode(&self, uri: Option<&str>) -> io::Result<(String, Vec<u8>)> {
+        // every buffer view must hold at least one byte
+        if self.triangles.is_empty() {
+            return Err(invalid("glTF meshes need at least one triangle"));
+        }
+        if self.uvs.is_some_and(|uvs| uvs.len() != self.vertices.len()) {
+            return Err(invalid("the mesh needs one texture coordinate per vertex"));
+        }
+        let mut bin = Vec::new();
+        let mut views = Vec::new();
+        let mut accessors = Vec::new();
+        let mut attributes = Vec::new();
+        let count = self.vertices.len();
+
+        let mut add = |bin: &mut Vec<u8>, data: Vec<f32>, kind: &str, name: &str, extra: &str| {
+            let offset = bin.len();
+            for x in data {
+                bin.extend_from_slice(&x.to_le_bytes());
+            }
+            views.push(format!(
+                r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{}}}"#,
+                offset,
+                bin.len() - offset,
+                ARRAY_BUFFER
+            ));
+            attributes.push(format!(r#""{}":{}"#, name, accessors.len()));
+            accessors.push(format!(
+                r#"{{"bufferView":{},"componentType":{},"count":{},"type":"{}"{}}}"#,
+                views.len() - 1,
+                FLOAT,
+                count,
+                kind,
+                extra
+            ));
+        };
+
+        // positions need their bounds
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for v in self.vertices {
+            let p = v.position();
+            let p = [p.x, p.y, p.z];
+            // the bounds go in the JSON, which has no room for NaN or infinity
+            if !p.iter().all(|c| c.is_finite()) {
+                return Err(invalid("glTF positions must be finite"));
+            }
+            for i in 0..3 {
+                min[i] = min[i].min(p[i]);
+                max[i] = max[i].max(p[i]);
+            }
+        }
+        let bounds = format!(
+            r#","min":[{},{},{}],"max":[{},{},{}]"#,
+            min[0], min[1], min[2], max[0], max[1], max[2]
+        );
+        let positions = self
+            .vertices
+            .iter()
+            .flat_map(|v| {
+                let p = v.position();
+                [p.x, p.y, p.z]
+            })
+            .collect();
+        add(&mut bin, positions, "VEC3", "POSITION", &bounds);
+        // the other attributes are written only if every vertex has them
+        let normals: Option<Vec<_>> = self.vertices.iter().map(|v| v.normal()).collect();
+        if let Some(normals) = normals {
+            let normals = normals.iter().flat_map(|n| [n.x, n.y, n.z]).collect();
+            add(&mut bin, normals, "VEC3", "NORMAL", "");
+        }
+        let uvs = match self.uvs {
+            Some(uvs) => Some(uvs.to_vec()),
+            None => self.vertices.iter().map(|v| v.uv()).collect(),
+        };
+        if let Some(uvs) = uvs {
+            let uvs = uvs.iter().flat_map(|uv| [uv[0], uv[1]]).collect();
+            add(&mut bin, uvs, "VEC2", "TEXCOORD_0", "");
+        }
+        let colors: Option<Vec<_>> = self.vertices.iter().map(|v| v.color()).collect();
+        if let Some(colors) = colors {
+            let colors = colors.iter().flat_map(|c| c.iter().cloned()).collect();
+            add(&mut bin, colors, "VEC4", "COLOR_0", "");
+        }
+        let (custom, values) = custom_values(self.vertices)
+            .ok_or_else(|| invalid("the vertices have different numbers of custom values"))?;
+        for k in 0..custom {
+            let values = values.iter().skip(k).step_by(custom).cloned().collect();
+            add(&mut bin, values, "SCALAR", &format!("_CUSTOM_{}", k), "");
+        }
+
+        let offset = bin.len();
+        for t in self.triangles {
+            for i in [t.x, t.y, t.z] {
+                if i >= count || i > u32::MAX as usize {
+                    return Err(invalid("vertex index out of range"));
+                }
+                bin.extend_from_slice(&(i as u32).to_le_bytes());
+            }
+        }
+        views.push(format!(
+            r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{}}}"#,
+            offset,
+            bin.len() - offset,
+            ELEMENT_ARRAY_BUFFER
+        ));
+        let indices = accessors.len();
+        accessors.push(format!(
+            r#"{{"bufferView":{},"componentType":{},"count":{},"type":"SCALAR"}}"#,
+            views.len() - 1,
+            UNSIGNED_INT,
+            self.triangles.len() * 3
+        ));
+
+        let uri = match uri {
+            Some(uri) => format!(r#","uri":"{}""#, uri),
+            None => String::new(),
+        };
+        let json = format!(
+            concat!(
+                r#"{{"asset":{{"version":"2.0","generator":"genmesh"}},"#,
+                r#""scene":0,"scenes":[{{"nodes":[0]}}],"nodes":[{{"mesh":0}}],"#,
+                r#""meshes":[{{"primitives":[{{"attributes":{{{}}},"indices":{},"mode":4}}]}}],"#,
+                r#""accessors":[{}],"bufferViews":[{}],"buffers":[{{"byteLength":{}{}}}]}}"#
+            ),
+            attributes.join(","),
+            indices,
+            accessors.join(","),
+            views.join(","),
+            bin.len(),
+            uri
+        );
+        Ok((json, bin))
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+fn base64(data: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).cloned().unwrap_or(0),
+            chunk.get(2).cloned().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for k in 0..4 {
+            if k <= chunk.len() {
+                out.push(TABLE[(n >> (18 - 6 * k) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
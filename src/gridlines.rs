@@ -0,0 +1,109 @@
+use super::generators::{IndexedPolygon, SharedVertex};
+use super::{Line, MapVertex};
+use crate::Vertex;
+
+/// Represents a grid of lines in the XZ plane, centered at (0, 0, 0), for
+/// drawing the ground of debug views.
+#[derive(Clone, Copy)]
+pub struct GridLines {
+    idx: usize,
+    end: usize,
+    extent: f32,
+    spacing: f32,
+    // lines on each side of the axis in each direction
+    half: usize,
+}
+
+impl GridLines {
+    /// Create a new grid.
+    /// `extent` is the distance from the center to the edges of the grid.
+    /// `spacing` is the distance between two lines, one line of each
+    /// direction going through the center.
+    pub fn new(extent: f32, spacing: f32) -> Self {
+        assert!(extent > 0. && spacing > 0.);
+        // allow for rounding errors when the extent is a multiple of the
+        // spacing
+        let half = (extent / spacing * (1. + f32::EPSILON * 4.)).floor() as usize;
+        GridLines {
+            idx: 0,
+            end: 2 * (2 * half + 1),
+            extent,
+            spacing,
+            half,
+        }
+    }
+
+    fn vert(&self, idx: usize) -> Vertex {
+        let per_dir = 2 * self.half + 1;
+        let line = idx / 2;
+        let along = [-self.extent, self.extent][idx % 2];
+        let across = ((line % per_dir) as f32 - self.half as f32) * self.spacing;
+        // lines along x come first, then lines along z
+        let pos = if line < per_dir {
+            [along, 0., across]
+        } else {
+            [across, 0., along]
+        };
+        Vertex {
+            pos: pos.into(),
+            normal: [0., 1., 0.].into(),
+        }
+    }
+}
+
+impl Iterator for GridLines {
+    type Item = Line<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.idx;
+        (n, Some(n))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.idx += 1;
+            Some(
+                self.indexed_polygon(self.idx - 1)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl DoubleEndedIterator for GridLines {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            Some(
+                self.indexed_polygon(self.end)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for GridLines {}
+
+impl SharedVertex<Vertex> for GridLines {
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        self.vert(idx)
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        4 * (2 * self.half + 1)
+    }
+}
+
+impl IndexedPolygon<Line<usize>> for GridLines {
+    fn indexed_polygon(&self, idx: usize) -> Line<usize> {
+        Line::new(2 * idx, 2 * idx + 1)
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        2 * (2 * self.half + 1)
+    }
+}
@@ -0,0 +1,83 @@
+//! Closing the open boundaries of a mesh.
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::analytic::orthonormal_basis;
+use crate::math;
+use crate::triangulate::ear_clip;
+use crate::{Neighbors, Triangle, Vertex};
+
+/// How `fill_holes` triangulates each hole.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HoleFill {
+    /// A fan of triangles from the first vertex of the hole. Cheap, but
+    /// only correct for convex holes.
+    Fan,
+    /// Ear clipping of the hole projected onto its average plane, which
+    /// copes with concave holes.
+    EarClip,
+    /// A fan around a new vertex added at the centroid of the hole.
+    Centroid,
+}
+
+/// Close every open boundary loop of an indexed triangle mesh.
+///
+/// The new triangles are appended to `triangles`, wound to face the same
+/// way as the surrounding faces. With `HoleFill::Centroid` a vertex is
+/// also appended to `vertices` for each hole, whose normal is the
+/// direction the patch faces. Returns the number of holes filled.
+pub fn fill_holes(
+    vertices: &mut Vec<Vertex>,
+    triangles: &mut Vec<Triangle<usize>>,
+    mode: HoleFill,
+) -> usize {
+    let loops = {
+        let neighbors = Neighbors::new(Vec::<()>::new(), std::mem::take(triangles));
+        let loops = neighbors.boundary_loops();
+        *triangles = neighbors.split().1;
+        loops
+    };
+
+    for hole in &loops {
+        // boundary loops follow the winding of the faces around them, the
+        // patch has to go the other way around
+        let hole: Vec<usize> = hole.iter().rev().cloned().collect();
+        let positions: Vec<Vector3<f32>> = hole
+            .iter()
+            .map(|&i| Vector3::from(vertices[i].pos))
+            .collect();
+
+        match mode {
+            HoleFill::Fan => {
+                for i in 1..hole.len() - 1 {
+                    triangles.push(Triangle::new(hole[0], hole[i], hole[i + 1]));
+                }
+            }
+            HoleFill::EarClip => {
+                let (u, v) = orthonormal_basis(math::newell_normal(&positions));
+                let projected: Vec<[f32; 2]> =
+                    positions.iter().map(|p| [p.dot(u), p.dot(v)]).collect();
+                for [a, b, c] in ear_clip(&projected) {
+                    triangles.push(Triangle::new(hole[a], hole[b], hole[c]));
+                }
+            }
+            HoleFill::Centroid => {
+                let sum = positions
+                    .iter()
+                    .fold(Vector3::new(0., 0., 0.), |a, &b| a + b);
+                let normal = math::try_normalize(math::newell_normal(&positions))
+                    .unwrap_or_else(Vector3::unit_z);
+                let center = vertices.len();
+                vertices.push(Vertex {
+                    pos: (sum / hole.len() as f32).into(),
+                    normal: normal.into(),
+                });
+                for i in 0..hole.len() {
+                    let next = hole[(i + 1) % hole.len()];
+                    triangles.push(Triangle::new(center, hole[i], next));
+                }
+            }
+        }
+    }
+    loops.len()
+}
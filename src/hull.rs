@@ -0,0 +1,208 @@
+//! Convex hulls of point clouds.
+
+use std::collections::HashMap;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::math;
+use crate::{Position, Triangle, Vertex};
+
+struct Face {
+    corners: [usize; 3],
+    normal: Vector3<f64>,
+    offset: f64,
+    outside: Vec<usize>,
+    alive: bool,
+}
+
+impl Face {
+    fn new(points: &[Vector3<f64>], corners: [usize; 3]) -> Self {
+        let [a, b, c] = corners;
+        let normal = (points[b] - points[a])
+            .cross(points[c] - points[a])
+            .normalize();
+        Face {
+            corners,
+            normal,
+            offset: normal.dot(points[a]),
+            outside: Vec::new(),
+            alive: true,
+        }
+    }
+
+    fn distance(&self, p: Vector3<f64>) -> f64 {
+        self.normal.dot(p) - self.offset
+    }
+}
+
+/// Compute the convex hull of `points` with the quickhull algorithm.
+///
+/// The hull is returned as an indexed triangle mesh, wound to face
+/// outward. Only the points on the hull are kept, each with the average
+/// of the normals of the faces around it. Coplanar faces are not merged,
+/// so flat sides are made of several triangles.
+///
+/// If the points do not span a volume, because there are fewer than four
+/// of them or they are all on a plane, the hull is empty.
+pub fn convex_hull(points: &[Position]) -> (Vec<Vertex>, Vec<Triangle<usize>>) {
+    let p: Vec<Vector3<f64>> = points
+        .iter()
+        .map(|p| Vector3::new(p.x as f64, p.y as f64, p.z as f64))
+        .collect();
+    let faces = match quickhull(&p) {
+        Some(faces) => faces,
+        None => return (Vec::new(), Vec::new()),
+    };
+
+    let mut remap = vec![None; points.len()];
+    let mut vertices: Vec<Vertex> = Vec::new();
+    let mut normals: Vec<Vector3<f64>> = Vec::new();
+    let mut triangles = Vec::new();
+    for f in faces.iter().filter(|f| f.alive) {
+        let [a, b, c] = f.corners;
+        let area = (p[b] - p[a]).cross(p[c] - p[a]).magnitude();
+        let mut index = |i: usize| {
+            let v = *remap[i].get_or_insert_with(|| {
+                vertices.push(Vertex {
+                    pos: points[i],
+                    normal: [0.; 3].into(),
+                });
+                normals.push(Vector3::new(0., 0., 0.));
+                vertices.len() - 1
+            });
+            normals[v] += f.normal * area;
+            v
+        };
+        triangles.push(Triangle::new(index(a), index(b), index(c)));
+    }
+    for (v, n) in vertices.iter_mut().zip(normals) {
+        let n = Vector3::new(n.x as f32, n.y as f32, n.z as f32);
+        v.normal = math::try_normalize(n)
+            .unwrap_or_else(Vector3::unit_z)
+            .into();
+    }
+    (vertices, triangles)
+}
+
+fn quickhull(p: &[Vector3<f64>]) -> Option<Vec<Face>> {
+    if p.len() < 4 {
+        return None;
+    }
+    let extent = p
+        .iter()
+        .fold(0f64, |m, v| m.max(v.x.abs()).max(v.y.abs()).max(v.z.abs()));
+    let eps = extent.max(f64::MIN_POSITIVE) * 1e-9;
+
+    // the initial tetrahedron: two far apart points, the point farthest
+    // from the line through them, and the point farthest from that plane
+    let min_x = (0..p.len()).min_by(|&a, &b| p[a].x.total_cmp(&p[b].x))?;
+    let max_x = (0..p.len()).max_by(|&a, &b| p[a].x.total_cmp(&p[b].x))?;
+    let (a, b) = if (p[max_x] - p[min_x]).magnitude() > eps {
+        (min_x, max_x)
+    } else {
+        let far = (0..p.len()).max_by(|&i, &j| {
+            (p[i] - p[0])
+                .magnitude2()
+                .total_cmp(&(p[j] - p[0]).magnitude2())
+        })?;
+        (0, far)
+    };
+    let dir = (p[b] - p[a]).normalize();
+    let line_distance = |i: usize| {
+        let d = p[i] - p[a];
+        (d - dir * d.dot(dir)).magnitude()
+    };
+    let c = (0..p.len()).max_by(|&i, &j| line_distance(i).total_cmp(&line_distance(j)))?;
+    if line_distance(c) <= eps {
+        return None;
+    }
+    let normal = (p[b] - p[a]).cross(p[c] - p[a]).normalize();
+    let plane_distance = |i: usize| normal.dot(p[i] - p[a]);
+    let d = (0..p.len())
+        .max_by(|&i, &j| plane_distance(i).abs().total_cmp(&plane_distance(j).abs()))?;
+    if plane_distance(d).abs() <= eps {
+        return None;
+    }
+
+    let mut faces = if plane_distance(d) > 0. {
+        vec![[a, c, b], [a, b, d], [b, c, d], [c, a, d]]
+    } else {
+        vec![[a, b, c], [a, d, b], [b, d, c], [c, d, a]]
+    }
+    .into_iter()
+    .map(|corners| Face::new(p, corners))
+    .collect::<Vec<_>>();
+
+    let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+    for (i, f) in faces.iter().enumerate() {
+        let [x, y, z] = f.corners;
+        for &e in &[(x, y), (y, z), (z, x)] {
+            edges.insert(e, i);
+        }
+    }
+
+    let used = [a, b, c, d];
+    for i in (0..p.len()).filter(|i| !used.contains(i)) {
+        if let Some(f) = faces.iter_mut().find(|f| f.distance(p[i]) > eps) {
+            f.outside.push(i);
+        }
+    }
+
+    while let Some(start) = faces.iter().position(|f| f.alive && !f.outside.is_empty()) {
+        let apex = *faces[start]
+            .outside
+            .iter()
+            .max_by(|&&i, &&j| {
+                faces[start]
+                    .distance(p[i])
+                    .total_cmp(&faces[start].distance(p[j]))
+            })
+            .unwrap();
+
+        // find the faces seen from the apex, and the horizon around them
+        let mut visible = vec![start];
+        faces[start].alive = false;
+        let mut horizon = Vec::new();
+        let mut k = 0;
+        while k < visible.len() {
+            let [x, y, z] = faces[visible[k]].corners;
+            for &(u, v) in &[(x, y), (y, z), (z, x)] {
+                let n = edges[&(v, u)];
+                if !faces[n].alive {
+                    continue;
+                }
+                if faces[n].distance(p[apex]) > eps {
+                    faces[n].alive = false;
+                    visible.push(n);
+                } else {
+                    horizon.push((u, v));
+                }
+            }
+            k += 1;
+        }
+
+        let mut orphans = Vec::new();
+        for &f in &visible {
+            orphans.append(&mut faces[f].outside);
+            let [x, y, z] = faces[f].corners;
+            for e in &[(x, y), (y, z), (z, x)] {
+                edges.remove(e);
+            }
+        }
+
+        let first = faces.len();
+        for (u, v) in horizon {
+            let i = faces.len();
+            faces.push(Face::new(p, [u, v, apex]));
+            edges.insert((u, v), i);
+            edges.insert((v, apex), i);
+            edges.insert((apex, u), i);
+        }
+        for i in orphans.into_iter().filter(|&i| i != apex) {
+            if let Some(f) = faces[first..].iter_mut().find(|f| f.distance(p[i]) > eps) {
+                f.outside.push(i);
+            }
+        }
+    }
+    Some(faces)
+}
@@ -1,18 +1,30 @@
 //! Icosahedral sphere
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::PI;
+use std::marker::PhantomData;
+use std::ops::Range;
 
 use cgmath::{InnerSpace, Vector3};
 
 use crate::generators::{IndexedPolygon, SharedVertex};
-use crate::{Polygon, Polygon::PolyTri, Triangle, Vertex};
+use crate::subdivide::split_triangle;
+use crate::{InsideOut, MapVertex, Polygon, Polygon::PolyTri, TexturedVertex, Triangle, Vertex};
 
 /// Icosahedral sphere with radius 1, centered at (0., 0., 0.)
+///
+/// A textured icosphere has spherical texture coordinates, `u` going
+/// around the z axis from the +x axis and `v` from the south pole to the
+/// north pole. The vertices on the seam where `u` wraps around, and at the
+/// poles, are duplicated so that no face stretches across the texture.
 #[derive(Clone, Debug)]
-pub struct IcoSphere {
-    i: usize,
+pub struct IcoSphere<V = Vertex> {
+    range: Range<usize>,
     vertices: Vec<[f32; 3]>,
     faces: Vec<[usize; 3]>,
+    // empty unless the sphere is textured
+    uvs: Vec<[f32; 2]>,
+    phantom_v: PhantomData<V>,
 }
 
 // The vertices of a regular icosahedron can be visualised as lying at the corner points of 3
@@ -24,8 +36,8 @@ pub struct IcoSphere {
 // get a unit sphere, therefore we need to normalize the vector (0, 1, (1 + sqrt(5)) / 2). This
 // gives us the values below. These values are the half dimensions of the orthogonal rectangles
 // from which we get the corner points that define a unit icosahedral sphere.
-const T: f32 = 0.85065080835204;
-const X: f32 = 0.5257311121191336;
+const T: f32 = 0.850_650_8;
+const X: f32 = 0.525_731_1;
 
 const VERTICES: [[f32; 3]; 12] = [
     // corners of the rectangle in the XY plane
@@ -76,9 +88,11 @@ impl IcoSphere {
     /// Create a unit sphere with 20 faces and 12 vertices.
     pub fn new() -> Self {
         Self {
-            i: 0,
+            range: 0..FACES.len(),
             vertices: VERTICES.to_vec(),
             faces: FACES.to_vec(),
+            uvs: Vec::new(),
+            phantom_v: PhantomData,
         }
     }
 
@@ -99,20 +113,107 @@ impl IcoSphere {
         }
 
         Self {
-            i: 0,
+            range: 0..faces.len(),
             vertices,
             faces,
+            uvs: Vec::new(),
+            phantom_v: PhantomData,
         }
     }
 
-    fn vert(&self, index: usize) -> Vertex {
-        Vertex {
+    /// Emit texture coordinates along with the vertices, duplicating the
+    /// vertices on the seam and at the poles as needed.
+    pub fn with_uvs(self) -> IcoSphere<TexturedVertex> {
+        let mut vertices = self.vertices;
+        let mut faces = self.faces;
+        let mut uvs: Vec<[f32; 2]> = vertices.iter().map(|&p| spherical_uv(p)).collect();
+        // copies of the vertices on the seam, with `u` past 1
+        let mut wrapped = HashMap::new();
+        let mut used_poles = HashSet::new();
+
+        for face in &mut faces {
+            let poles = face.map(|i| is_pole(vertices[i]));
+            let us: Vec<f32> = (0..3)
+                .filter(|&k| !poles[k])
+                .map(|k| uvs[face[k]][0])
+                .collect();
+            let min = us.iter().cloned().fold(1., f32::min);
+            let max = us.iter().cloned().fold(0., f32::max);
+            for k in 0..3 {
+                let i = face[k];
+                if max - min > 0.5 && !poles[k] && uvs[i][0] < 0.5 {
+                    face[k] = *wrapped.entry(i).or_insert_with(|| {
+                        vertices.push(vertices[i]);
+                        uvs.push([uvs[i][0] + 1., uvs[i][1]]);
+                        vertices.len() - 1
+                    });
+                }
+            }
+            // poles get their own vertex in each face, with `u` in the
+            // middle of the others
+            for k in 0..3 {
+                if poles[k] {
+                    let i = face[k];
+                    let (a, b) = (face[(k + 1) % 3], face[(k + 2) % 3]);
+                    let uv = [(uvs[a][0] + uvs[b][0]) / 2., uvs[i][1]];
+                    if used_poles.insert(i) {
+                        uvs[i] = uv;
+                    } else {
+                        vertices.push(vertices[i]);
+                        uvs.push(uv);
+                        face[k] = vertices.len() - 1;
+                    }
+                }
+            }
+        }
+
+        IcoSphere {
+            range: self.range,
+            vertices,
+            faces,
+            uvs,
+            phantom_v: PhantomData,
+        }
+    }
+}
+
+impl<V: From<TexturedVertex>> IcoSphere<V> {
+    /// Turn the sphere inside out, to be seen from within like an
+    /// environment dome.
+    pub fn inside_out(self) -> InsideOut<Self> {
+        InsideOut::new(self)
+    }
+
+    fn vert(&self, index: usize) -> V {
+        TexturedVertex {
             pos: self.vertices[index].into(),
             normal: self.vertices[index].into(),
+            uv: self.uvs.get(index).cloned().unwrap_or([0., 0.]),
         }
+        .into()
+    }
+}
+
+fn is_pole(p: [f32; 3]) -> bool {
+    p[0] * p[0] + p[1] * p[1] < 1e-12
+}
+
+fn spherical_uv(p: [f32; 3]) -> [f32; 2] {
+    let mut u = p[1].atan2(p[0]) / (2. * PI);
+    if u < 0. {
+        u += 1.;
+    }
+    [u, 0.5 + p[2].clamp(-1., 1.).asin() / PI]
+}
+
+impl Default for IcoSphere {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
+// New vertices are only ever appended, existing ones keep their index.
+// `ProgressiveIcoSphere` relies on this to share one vertex buffer between levels.
 fn subdivide_impl(
     mut vertices: Vec<[f32; 3]>,
     faces: Vec<[usize; 3]>,
@@ -135,10 +236,8 @@ fn subdivide_impl(
                 vertices.push(new);
             }
         }
-        new_faces.push([face[0], mid[0], mid[2]]);
-        new_faces.push([face[1], mid[1], mid[0]]);
-        new_faces.push([face[2], mid[2], mid[1]]);
-        new_faces.push([mid[0], mid[1], mid[2]]);
+        new_faces.extend_from_slice(&split_triangle(*face, mid));
+        new_faces.push(mid);
     }
     (vertices, new_faces)
 }
@@ -149,39 +248,41 @@ fn new_point(start: [f32; 3], end: [f32; 3]) -> [f32; 3] {
         .into()
 }
 
-impl Iterator for IcoSphere {
-    type Item = Polygon<Vertex>;
+impl<V: From<TexturedVertex>> Iterator for IcoSphere<V> {
+    type Item = Polygon<V>;
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.faces.len(), Some(self.faces.len()))
+        self.range.size_hint()
     }
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.i == self.faces.len() {
-            return None;
-        }
-
-        let face = self.faces[self.i];
-        let x = self.vert(face[0]);
-        let y = self.vert(face[1]);
-        let z = self.vert(face[2]);
-        self.i += 1;
+        self.range
+            .next()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vert(i)))
+    }
+}
 
-        Some(PolyTri(Triangle::new(x, y, z)))
+impl<V: From<TexturedVertex>> DoubleEndedIterator for IcoSphere<V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range
+            .next_back()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vert(i)))
     }
 }
 
-impl SharedVertex<Vertex> for IcoSphere {
+impl<V: From<TexturedVertex>> ExactSizeIterator for IcoSphere<V> {}
+
+impl<V: From<TexturedVertex>> SharedVertex<V> for IcoSphere<V> {
     fn shared_vertex_count(&self) -> usize {
         self.vertices.len()
     }
 
-    fn shared_vertex(&self, idx: usize) -> Vertex {
+    fn shared_vertex(&self, idx: usize) -> V {
         self.vert(idx)
     }
 }
 
-impl IndexedPolygon<Polygon<usize>> for IcoSphere {
+impl<V> IndexedPolygon<Polygon<usize>> for IcoSphere<V> {
     fn indexed_polygon_count(&self) -> usize {
         self.faces.len()
     }
@@ -194,3 +295,112 @@ impl IndexedPolygon<Polygon<usize>> for IcoSphere {
         ))
     }
 }
+
+/// Icosahedral sphere keeping the faces of every subdivision level.
+///
+/// Subdivision only appends vertices, so the vertices used by each level are
+/// a prefix of the vertices of the next one. All the levels can therefore
+/// share a single vertex buffer (the `SharedVertex` implementation of this
+/// type), each with its own index buffer (see `level`), and switching level
+/// of detail only means switching index buffers.
+#[derive(Clone, Debug)]
+pub struct ProgressiveIcoSphere {
+    vertices: Vec<[f32; 3]>,
+    levels: Vec<Vec<[usize; 3]>>,
+    vertex_counts: Vec<usize>,
+}
+
+impl ProgressiveIcoSphere {
+    /// Create a unit sphere with levels 0 (the plain icosahedron) to
+    /// `subdivides` included, where level N has 20 * 4^N faces.
+    pub fn new(subdivides: usize) -> Self {
+        let mut vertices = VERTICES.to_vec();
+        let mut levels = vec![FACES.to_vec()];
+        let mut vertex_counts = vec![vertices.len()];
+
+        for _ in 0..subdivides {
+            let faces = levels.last().unwrap().clone();
+            let (v, f) = subdivide_impl(vertices, faces);
+            vertices = v;
+            vertex_counts.push(vertices.len());
+            levels.push(f);
+        }
+
+        Self {
+            vertices,
+            levels,
+            vertex_counts,
+        }
+    }
+
+    /// Number of levels of detail, the coarsest being level 0.
+    pub fn level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Access a single level of detail. Its shared vertices are the first
+    /// vertices of the whole sphere, and its indices point into them.
+    pub fn level(&self, level: usize) -> IcoSphereLevel<'_> {
+        assert!(level < self.levels.len());
+        IcoSphereLevel {
+            sphere: self,
+            level,
+        }
+    }
+
+    fn vert(&self, index: usize) -> Vertex {
+        Vertex {
+            pos: self.vertices[index].into(),
+            normal: self.vertices[index].into(),
+        }
+    }
+}
+
+impl SharedVertex<Vertex> for ProgressiveIcoSphere {
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        self.vert(idx)
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for ProgressiveIcoSphere {
+    fn indexed_polygon_count(&self) -> usize {
+        self.levels.last().unwrap().len()
+    }
+
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        self.level(self.levels.len() - 1).indexed_polygon(idx)
+    }
+}
+
+/// A single level of detail of a `ProgressiveIcoSphere`.
+#[derive(Clone, Copy, Debug)]
+pub struct IcoSphereLevel<'a> {
+    sphere: &'a ProgressiveIcoSphere,
+    level: usize,
+}
+
+impl<'a> SharedVertex<Vertex> for IcoSphereLevel<'a> {
+    fn shared_vertex_count(&self) -> usize {
+        self.sphere.vertex_counts[self.level]
+    }
+
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        debug_assert!(idx < self.shared_vertex_count());
+        self.sphere.vert(idx)
+    }
+}
+
+impl<'a> IndexedPolygon<Polygon<usize>> for IcoSphereLevel<'a> {
+    fn indexed_polygon_count(&self) -> usize {
+        self.sphere.levels[self.level].len()
+    }
+
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        let face = self.sphere.levels[self.level][idx];
+        PolyTri(Triangle::new(face[0], face[1], face[2]))
+    }
+}
@@ -0,0 +1,48 @@
+//! Integer types for index buffers.
+
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// An integer type index buffers can be made of.
+///
+/// Meshes are built with `usize` indices internally, which are narrowed to
+/// the index type when they are written out. Narrowing is checked, so that
+/// a mesh with too many vertices for 16-bit indices is caught rather than
+/// silently wrapped around.
+pub trait IndexType: Copy + Debug + Eq + Hash + Ord + Send + Sync + 'static {
+    /// The largest value of the type, which graphics APIs conventionally
+    /// use as the primitive restart index.
+    const MAX: Self;
+
+    /// Convert an index, or return `None` if it does not fit.
+    fn try_from_usize(index: usize) -> Option<Self>;
+
+    /// Widen the index back.
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_index_type {
+    ($($t:ty),*) => {
+        $(
+            impl IndexType for $t {
+                const MAX: Self = <$t>::MAX;
+
+                fn try_from_usize(index: usize) -> Option<Self> {
+                    <$t>::try_from(index).ok()
+                }
+
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_index_type!(u16, u32, usize);
+
+/// Whether every index of a buffer over `count` vertices fits in `I`.
+pub(crate) fn fits<I: IndexType>(count: usize) -> bool {
+    count == 0 || I::try_from_usize(count - 1).is_some()
+}
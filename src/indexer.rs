@@ -1,3 +1,5 @@
+use crate::IndexType;
+
 /// A trait defining how to defined an Indexer. An indexer is a object
 /// that collects verticies and emits indexes for the vertex. The intent
 /// is that an Indexer can find redundent vertexes and deduplicate them
@@ -5,6 +7,15 @@
 pub trait Indexer<T> {
     /// convert a vertex into an index.
     fn index(&mut self, v: T) -> usize;
+
+    /// convert a vertex into an index of type `I`, or return `None` if the
+    /// index does not fit in it. The vertex is recorded either way.
+    fn index_as<I: IndexType>(&mut self, v: T) -> Option<I>
+    where
+        Self: Sized,
+    {
+        I::try_from_usize(self.index(v))
+    }
 }
 
 /// An `LruIndexer` is useful for creating indexed steam from a stream of
@@ -31,7 +42,7 @@ impl<T, F: FnMut(usize, T)> LruIndexer<T, F> {
             index: 0,
             max: size,
             cache: Vec::new(),
-            emit: emit,
+            emit,
         }
     }
 }
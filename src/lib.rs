@@ -18,50 +18,234 @@ extern crate cgmath;
 extern crate mint;
 
 pub use crate::poly::{
-    EmitLines, Line, Lines, MapToVertices, MapVertex, NGon, Polygon, Quad, Triangle,
-    VertexIterator, VertexStreamIterator, Vertices,
+    ConstNGon, EmitLines, Line, Lines, MapToVertices, MapVertex, NGon, Polygon, Quad, Triangle,
+    UniqueLines, UniqueLinesIterator, VertexIterator, VertexStreamIterator, Vertices,
 };
 
 pub use crate::triangulate::{EmitTriangles, Triangulate, TriangulateIterator};
 
+pub use crate::attributes::VertexAttributes;
+
+pub use crate::bevel::bevel;
+
+pub use crate::bounds::{indexed_bounding_box, indexed_bounding_sphere, Bounds, EmitPositions};
+
+#[cfg(feature = "bytemuck")]
+pub use crate::bytes::AsBytes;
+
+pub use crate::cleanup::{compact, remove_degenerate_faces};
+
+pub use crate::clip::{Clip, ClipIterator};
+
+pub use crate::components::{filter_components, label_components, split_components};
+
+pub use crate::cull::{Cull, CullIterator};
+
+pub use crate::deform::{Axis, Deform, DeformIterator, Deformer};
+
+pub use crate::depth::{sort_indices_by_depth, DepthSort};
+
+pub use crate::displace::{recompute_normals, Displace, DisplaceIterator, Displaceable};
+
+pub use crate::geodesic::{geodesic_distances, GeodesicMethod};
+
+pub use crate::holes::{fill_holes, HoleFill};
+
+pub use crate::hull::convex_hull;
+
+pub use crate::index::IndexType;
+
 pub use crate::indexer::{Indexer, LruIndexer};
 
-pub use crate::neighbors::Neighbors;
+pub use crate::measure::{indexed_centroid, indexed_surface_area, indexed_volume, Measure};
+
+pub use crate::mesh::{Mesh, MeshBuilder};
+
+pub use crate::metrics::{curvature, triangle_quality, Curvature, TriangleQuality};
+
+pub use crate::morph::MorphTargets;
+
+pub use crate::neighbors::{Neighbors, NormalFallback};
+
+pub use crate::normal_lines::{NormalLines, NormalLinesIterator};
+
+pub use crate::occlusion::bake_ao;
+
+pub use crate::offset::offset;
+
+pub use crate::assembly::{explode_offsets, explode_parts, ExplodeMode};
+
+pub use crate::planar::{triangulate_2d, triangulate_polygon};
+
+pub use crate::progressive::{EdgeCollapse, ProgressiveMesh};
+
+pub use crate::proxy::{capsule_proxy, sphere_proxy};
+
+pub use crate::quadrangulate::quadrangulate;
 
+pub use crate::repeat::{Repeat, RepeatIterator};
+
+pub use crate::sample::SampleSurface;
+
+pub use crate::scene::{Scene, SceneBuilder, SubMesh};
+
+pub use crate::silhouette::{silhouette, View};
+
+pub use crate::skin::{skin_weights, Bone, Skin, SkinIterator, SkinnedVertex, MAX_INFLUENCES};
+
+pub use crate::slice::slice;
+
+pub use crate::solidify::solidify;
+
+pub use crate::strip::{line_strip_indices, triangle_strip_indices};
+
+pub use crate::subdivide::{Subdivide, SubdivideIterator, SubdivideToEdgeLengthIterator};
+
+pub use crate::terrain::TerrainVertex;
+
+pub use crate::transfer::{transfer_attributes, Transferred};
+
+pub use crate::turtle::{expand_lsystem, Turtle};
+
+pub use crate::unshare::unshare;
+
+pub use crate::weld::weld;
+
+pub use crate::winding::{
+    make_winding_consistent, FlipWinding, InsideOut, ReverseWinding, ReverseWindingIterator,
+};
+
+pub use crate::wireframe::{Tubes, TubesIterator};
+
+pub mod analytic;
+mod assembly;
+mod attributes;
+mod bevel;
+mod bounds;
+pub mod buffer;
+pub mod bvh;
+#[cfg(feature = "bytemuck")]
+mod bytes;
+mod cleanup;
+mod clip;
+pub mod compare;
+mod components;
+mod cull;
+pub mod curve;
+mod deform;
+mod depth;
+mod displace;
+mod flat;
 mod generator;
+mod geodesic;
+#[cfg(feature = "gltf")]
+pub mod gltf;
+mod holes;
+mod hull;
+mod index;
 mod indexer;
+mod math;
+mod measure;
+mod mesh;
+mod metrics;
+mod morph;
 mod neighbors;
+pub mod noise;
+mod normal_lines;
+#[cfg(feature = "obj")]
+pub mod obj;
+mod occlusion;
+mod offset;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod planar;
+#[cfg(feature = "ply")]
+pub mod ply;
 mod poly;
+mod progressive;
+mod proxy;
+mod quadrangulate;
+mod queue;
+mod repeat;
+mod sample;
+pub mod scatter;
+mod scene;
+pub mod sdf;
+mod silhouette;
+pub mod simplify;
+mod skin;
+mod slice;
+mod solidify;
+pub mod spatial;
+#[cfg(feature = "stl")]
+pub mod stl;
+mod strip;
+mod subdivide;
+#[cfg(feature = "text")]
+pub mod text;
+mod transfer;
 mod triangulate;
+mod turtle;
+mod unshare;
+pub mod uv;
+pub mod validate;
+mod weld;
+mod winding;
+mod wireframe;
 
+mod annulus;
+mod boxframe;
 mod circle;
+mod circlelines;
 mod cone;
 mod cube;
 mod cylinder;
 mod dodecahedron;
+mod frustum;
+mod gridlines;
 mod icosphere;
 mod octahedron;
 mod plane;
+mod quadsphere;
+mod rock;
 mod sphere;
+mod stairs;
+mod terrain;
 mod tetrahedron;
 mod torus;
 
 /// A collection of utilties that can be used to build
 /// meshes programmatically.
 pub mod generators {
+    pub use crate::annulus::Annulus;
+    pub use crate::boxframe::BoxFrame;
     pub use crate::circle::Circle;
+    pub use crate::circlelines::CircleLines;
     pub use crate::cone::Cone;
-    pub use crate::cube::Cube;
+    pub use crate::cube::{Cube, CubeFace};
     pub use crate::cylinder::Cylinder;
     pub use crate::dodecahedron::Dodecahedron;
+    pub use crate::frustum::Frustum;
     pub use crate::generator::{
-        IndexedPolygon, IndexedPolygonIterator, SharedVertex, SharedVertexIterator,
+        GroupedPolygonIterator, IndexedPolygon, IndexedPolygonIterator, PolygonGroup, SharedVertex,
+        SharedVertexIterator,
     };
-    pub use crate::icosphere::IcoSphere;
+    pub use crate::gridlines::GridLines;
+    pub use crate::icosphere::{IcoSphere, IcoSphereLevel, ProgressiveIcoSphere};
     pub use crate::octahedron::Octahedron;
+    #[cfg(feature = "parallel")]
+    pub use crate::parallel::{
+        ParIndexedPolygon, ParIndexedPolygonIterator, ParSharedVertex, ParSharedVertexIterator,
+    };
     pub use crate::plane::Plane;
+    pub use crate::quadsphere::QuadSphere;
+    pub use crate::rock::{Rock, Roughness};
     pub use crate::sphere::SphereUv;
+    pub use crate::stairs::{Ramp, Stairs};
+    pub use crate::terrain::{ChunkEdge, HydraulicErosion, Terrain, TerrainChunk, TerrainChunks};
     pub use crate::tetrahedron::Tetrahedron;
+    #[cfg(feature = "text")]
+    pub use crate::text::TextMesh;
     pub use crate::torus::Torus;
 }
 
@@ -71,9 +255,32 @@ pub type Position = mint::Vector3<f32>;
 pub type Normal = mint::Vector3<f32>;
 /// Common vertex type.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
 pub struct Vertex {
     /// Vertex position
     pub pos: Position,
     /// Vertex normal
     pub normal: Normal,
 }
+
+/// Vertex type with texture coordinates, emitted by the generators that
+/// can map a texture onto their surface.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+pub struct TexturedVertex {
+    /// Vertex position
+    pub pos: Position,
+    /// Vertex normal
+    pub normal: Normal,
+    /// Vertex texture coordinates
+    pub uv: [f32; 2],
+}
+
+impl From<TexturedVertex> for Vertex {
+    fn from(v: TexturedVertex) -> Self {
+        Vertex {
+            pos: v.pos,
+            normal: v.normal,
+        }
+    }
+}
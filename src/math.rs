@@ -0,0 +1,338 @@
+//! Small linear algebra helpers shared by the analysis passes.
+
+use cgmath::{InnerSpace, Matrix, Matrix3, Matrix4, SquareMatrix, Vector3, Vector4};
+
+use crate::{Position, Vertex};
+
+/// An affine transform applied to whole vertices: positions go through
+/// the matrix, normals through its inverse transpose.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct VertexTransform {
+    matrix: Matrix4<f32>,
+    normal: Matrix3<f32>,
+    flips: bool,
+}
+
+impl VertexTransform {
+    pub(crate) fn new(matrix: Matrix4<f32>) -> Self {
+        let linear = Matrix3::from_cols(
+            matrix.x.truncate(),
+            matrix.y.truncate(),
+            matrix.z.truncate(),
+        );
+        let normal = linear.invert().map(|m| m.transpose()).unwrap_or(linear);
+        VertexTransform {
+            matrix,
+            normal,
+            flips: linear.determinant() < 0.,
+        }
+    }
+
+    /// Whether the transform mirrors space, in which case the winding of
+    /// the polygons has to be reversed to keep them facing outward.
+    pub(crate) fn flips_winding(&self) -> bool {
+        self.flips
+    }
+
+    pub(crate) fn apply(&self, v: Vertex) -> Vertex {
+        let p = self.matrix * Vector4::new(v.pos.x, v.pos.y, v.pos.z, 1.);
+        let n = self.normal * Vector3::from(v.normal);
+        let n = try_normalize(n).unwrap_or(n);
+        Vertex {
+            pos: (p.truncate() / p.w).into(),
+            normal: n.into(),
+        }
+    }
+}
+
+/// Scale `v` to unit length, or return `None` if it is too short to have
+/// a meaningful direction (including zero, infinite and NaN vectors).
+pub(crate) fn try_normalize(v: Vector3<f32>) -> Option<Vector3<f32>> {
+    let len = v.magnitude();
+    if len > f32::MIN_POSITIVE && len.is_finite() {
+        Some(v / len)
+    } else {
+        None
+    }
+}
+
+/// Newell's method: the area weighted normal of a possibly non planar
+/// loop, twice its area long.
+pub(crate) fn newell_normal(points: &[Vector3<f32>]) -> Vector3<f32> {
+    let mut n = Vector3::new(0., 0., 0.);
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        n += a.cross(b);
+    }
+    n
+}
+
+/// The outer product `a bᵀ`.
+pub(crate) fn outer(a: Vector3<f32>, b: Vector3<f32>) -> Matrix3<f32> {
+    Matrix3::from_cols(a * b.x, a * b.y, a * b.z)
+}
+
+/// The bits of the coordinates of `p`, to find the vertices at exactly
+/// the same position through a hash map. `-0` and `+0` are the same.
+pub(crate) fn position_key(p: Position) -> [u32; 3] {
+    [p.x, p.y, p.z].map(|c| (c + 0.).to_bits())
+}
+
+/// Whether the corners `a`, `b` and `c` lie on a line, relative to the
+/// lengths of the sides, so that the triangle they make has no area.
+pub(crate) fn is_degenerate(a: Vector3<f32>, b: Vector3<f32>, c: Vector3<f32>) -> bool {
+    let (ab, ac) = (b - a, c - a);
+    ab.cross(ac).magnitude2() <= 1e-12 * ab.magnitude2() * ac.magnitude2()
+}
+
+/// Compute the covariance matrix of `points` around their mean.
+/// Returns the mean together with the matrix, or `None` if there are no points.
+pub(crate) fn covariance<I>(points: I) -> Option<(Vector3<f32>, [[f64; 3]; 3])>
+where
+    I: Iterator<Item = Vector3<f32>> + Clone,
+{
+    let mut n = 0usize;
+    let mut sum = [0f64; 3];
+    for p in points.clone() {
+        n += 1;
+        sum[0] += p.x as f64;
+        sum[1] += p.y as f64;
+        sum[2] += p.z as f64;
+    }
+    if n == 0 {
+        return None;
+    }
+    let mean = [sum[0] / n as f64, sum[1] / n as f64, sum[2] / n as f64];
+
+    let mut cov = [[0f64; 3]; 3];
+    for p in points {
+        let d = [
+            p.x as f64 - mean[0],
+            p.y as f64 - mean[1],
+            p.z as f64 - mean[2],
+        ];
+        for i in 0..3 {
+            for j in 0..3 {
+                cov[i][j] += d[i] * d[j];
+            }
+        }
+    }
+    for row in cov.iter_mut() {
+        for c in row.iter_mut() {
+            *c /= n as f64;
+        }
+    }
+
+    let mean = Vector3::new(mean[0] as f32, mean[1] as f32, mean[2] as f32);
+    Some((mean, cov))
+}
+
+/// Eigen decomposition of a symmetric 3x3 matrix using Jacobi rotations.
+///
+/// Returns the eigenvalues sorted from largest to smallest, and the
+/// matching unit eigenvectors, which form a right-handed basis.
+#[allow(clippy::needless_range_loop)]
+pub(crate) fn symmetric_eigen(m: [[f64; 3]; 3]) -> ([f64; 3], [Vector3<f32>; 3]) {
+    let mut a = m;
+    let mut v = [[1f64, 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+
+    for _ in 0..32 {
+        let off = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+        if off < 1e-24 {
+            break;
+        }
+        for &(p, q) in &[(0, 1), (0, 2), (1, 2)] {
+            if a[p][q].abs() < 1e-30 {
+                continue;
+            }
+            let theta = (a[q][q] - a[p][p]) / (2. * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+            let c = 1. / (t * t + 1.).sqrt();
+            let s = t * c;
+
+            // a = J^T a J
+            for k in 0..3 {
+                let akp = a[k][p];
+                let akq = a[k][q];
+                a[k][p] = c * akp - s * akq;
+                a[k][q] = s * akp + c * akq;
+            }
+            for k in 0..3 {
+                let apk = a[p][k];
+                let aqk = a[q][k];
+                a[p][k] = c * apk - s * aqk;
+                a[q][k] = s * apk + c * aqk;
+            }
+            // v = v J
+            for row in v.iter_mut() {
+                let vp = row[p];
+                let vq = row[q];
+                row[p] = c * vp - s * vq;
+                row[q] = s * vp + c * vq;
+            }
+        }
+    }
+
+    let mut order = [0, 1, 2];
+    order.sort_by(|&i, &j| a[j][j].partial_cmp(&a[i][i]).unwrap());
+
+    let column = |i: usize| Vector3::new(v[0][i] as f32, v[1][i] as f32, v[2][i] as f32);
+    let x = column(order[0]);
+    let y = column(order[1]);
+    let z = x.cross(y);
+    (
+        [
+            a[order[0]][order[0]],
+            a[order[1]][order[1]],
+            a[order[2]][order[2]],
+        ],
+        [x, y, z],
+    )
+}
+
+type Ball = ([f64; 3], f64);
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn ball_contains(ball: &Ball, p: [f64; 3]) -> bool {
+    let d = sub(p, ball.0);
+    dot(d, d).sqrt() <= ball.1 * (1. + 1e-9) + 1e-9
+}
+
+fn ball_2(a: [f64; 3], b: [f64; 3]) -> Ball {
+    let c = [
+        (a[0] + b[0]) * 0.5,
+        (a[1] + b[1]) * 0.5,
+        (a[2] + b[2]) * 0.5,
+    ];
+    let d = sub(a, c);
+    (c, dot(d, d).sqrt())
+}
+
+fn ball_3(a: [f64; 3], b: [f64; 3], c: [f64; 3]) -> Ball {
+    let ab = sub(b, a);
+    let ac = sub(c, a);
+    let n = cross(ab, ac);
+    let nn = dot(n, n);
+    if nn < 1e-18 {
+        // collinear, the two farthest points define the ball
+        let candidates = [ball_2(a, b), ball_2(a, c), ball_2(b, c)];
+        return candidates
+            .iter()
+            .cloned()
+            .fold(candidates[0], |m, x| if x.1 > m.1 { x } else { m });
+    }
+    let t0 = cross(n, ab);
+    let t1 = cross(ac, n);
+    let (lab, lac) = (dot(ab, ab), dot(ac, ac));
+    let offset = [
+        (t0[0] * lac + t1[0] * lab) / (2. * nn),
+        (t0[1] * lac + t1[1] * lab) / (2. * nn),
+        (t0[2] * lac + t1[2] * lab) / (2. * nn),
+    ];
+    (
+        [a[0] + offset[0], a[1] + offset[1], a[2] + offset[2]],
+        dot(offset, offset).sqrt(),
+    )
+}
+
+fn ball_4(a: [f64; 3], b: [f64; 3], c: [f64; 3], d: [f64; 3]) -> Ball {
+    let ab = sub(b, a);
+    let ac = sub(c, a);
+    let ad = sub(d, a);
+    let det = dot(ab, cross(ac, ad));
+    if det.abs() < 1e-18 {
+        // coplanar, fall back to the largest circumscribed circle
+        let candidates = [
+            ball_3(a, b, c),
+            ball_3(a, b, d),
+            ball_3(a, c, d),
+            ball_3(b, c, d),
+        ];
+        return candidates
+            .iter()
+            .cloned()
+            .fold(candidates[0], |m, x| if x.1 > m.1 { x } else { m });
+    }
+    let (lab, lac, lad) = (dot(ab, ab), dot(ac, ac), dot(ad, ad));
+    let x = cross(ac, ad);
+    let y = cross(ad, ab);
+    let z = cross(ab, ac);
+    let offset = [
+        (x[0] * lab + y[0] * lac + z[0] * lad) / (2. * det),
+        (x[1] * lab + y[1] * lac + z[1] * lad) / (2. * det),
+        (x[2] * lab + y[2] * lac + z[2] * lad) / (2. * det),
+    ];
+    (
+        [a[0] + offset[0], a[1] + offset[1], a[2] + offset[2]],
+        dot(offset, offset).sqrt(),
+    )
+}
+
+/// Compute the minimal ball enclosing all of `points` (Welzl's algorithm,
+/// in its iterative form). The points are visited in a scrambled but
+/// deterministic order to keep the expected running time linear.
+pub(crate) fn min_enclosing_ball(points: &[Vector3<f32>]) -> Option<(Vector3<f32>, f32)> {
+    if points.is_empty() {
+        return None;
+    }
+
+    let mut p: Vec<[f64; 3]> = points
+        .iter()
+        .map(|v| [v.x as f64, v.y as f64, v.z as f64])
+        .collect();
+    // Fisher-Yates with a small LCG, so results are reproducible
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    for i in (1..p.len()).rev() {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        let j = ((state >> 33) % (i as u64 + 1)) as usize;
+        p.swap(i, j);
+    }
+
+    let mut ball: Ball = (p[0], 0.);
+    for i in 1..p.len() {
+        if ball_contains(&ball, p[i]) {
+            continue;
+        }
+        ball = (p[i], 0.);
+        for j in 0..i {
+            if ball_contains(&ball, p[j]) {
+                continue;
+            }
+            ball = ball_2(p[i], p[j]);
+            for k in 0..j {
+                if ball_contains(&ball, p[k]) {
+                    continue;
+                }
+                ball = ball_3(p[i], p[j], p[k]);
+                for l in 0..k {
+                    if !ball_contains(&ball, p[l]) {
+                        ball = ball_4(p[i], p[j], p[k], p[l]);
+                    }
+                }
+            }
+        }
+    }
+
+    let (c, r) = ball;
+    Some((
+        Vector3::new(c[0] as f32, c[1] as f32, c[2] as f32),
+        r as f32,
+    ))
+}
@@ -0,0 +1,160 @@
+//! Surface area, volume and center of mass of meshes.
+
+use cgmath::{InnerSpace, Vector3, Zero};
+
+use crate::bounds::EmitPositions;
+use crate::math;
+use crate::{Normal, Position, Triangle};
+
+// Running sums over the triangles of a mesh, in double precision since
+// the volume terms of opposite sides of a mesh mostly cancel out.
+struct Sums {
+    area: f64,
+    volume: f64,
+    moment: Vector3<f64>,
+}
+
+impl Sums {
+    fn new() -> Self {
+        Sums {
+            area: 0.,
+            volume: 0.,
+            moment: Vector3::zero(),
+        }
+    }
+
+    fn add(&mut self, a: Vector3<f64>, b: Vector3<f64>, c: Vector3<f64>) {
+        let cross = (b - a).cross(c - a);
+        self.area += cross.magnitude() * 0.5;
+        // signed volume of the tetrahedron between the triangle and the
+        // origin, and its centroid
+        let v = a.dot(b.cross(c)) / 6.;
+        self.volume += v;
+        self.moment += (a + b + c) * (v / 4.);
+    }
+
+    fn add_polygon<P: EmitPositions>(&mut self, polygon: P) {
+        let mut corners: Vec<Vector3<f64>> = Vec::new();
+        polygon.emit_positions(|p| corners.push(Vector3::new(p.x as f64, p.y as f64, p.z as f64)));
+        for i in 2..corners.len() {
+            self.add(corners[0], corners[i - 1], corners[i]);
+        }
+    }
+
+    fn centroid(&self) -> Option<Position> {
+        if self.volume.abs() < 1e-12 {
+            return None;
+        }
+        let c = self.moment / self.volume;
+        Some([c.x as f32, c.y as f32, c.z as f32].into())
+    }
+}
+
+/// Terminal adapters measuring a stream of polygons. Polygons with more
+/// than three sides are measured as fans of triangles around their first
+/// vertex.
+pub trait Measure: Sized {
+    /// Compute the total area of the polygons.
+    fn surface_area(self) -> f32;
+
+    /// Compute the volume enclosed by the polygons, as the sum of the
+    /// signed volumes of the tetrahedra joining each triangle to the
+    /// origin. It is only meaningful for closed meshes, and is positive
+    /// when the polygons face outward.
+    fn volume(self) -> f32;
+
+    /// Compute the center of mass of the solid enclosed by the polygons,
+    /// assuming a uniform density. Returns `None` if the enclosed volume
+    /// is zero.
+    fn centroid(self) -> Option<Position>;
+
+    /// Compute the unit normal of each polygon, the direction its
+    /// vertices wind counter clockwise around. Polygons with no area get
+    /// a zero normal.
+    fn face_normals(self) -> Vec<Normal>;
+
+    /// Compute the center of each polygon, as the average of its
+    /// vertices. That is the centroid of triangles and regular polygons.
+    fn face_centroids(self) -> Vec<Position>;
+}
+
+impl<T, P> Measure for T
+where
+    T: Iterator<Item = P>,
+    P: EmitPositions,
+{
+    fn surface_area(self) -> f32 {
+        sums(self).area as f32
+    }
+
+    fn volume(self) -> f32 {
+        sums(self).volume as f32
+    }
+
+    fn centroid(self) -> Option<Position> {
+        sums(self).centroid()
+    }
+
+    fn face_normals(self) -> Vec<Normal> {
+        self.map(|p| {
+            let corners = corners(p);
+            math::try_normalize(math::newell_normal(&corners))
+                .unwrap_or_else(Vector3::zero)
+                .into()
+        })
+        .collect()
+    }
+
+    fn face_centroids(self) -> Vec<Position> {
+        self.map(|p| {
+            let corners = corners(p);
+            let sum = corners.iter().fold(Vector3::zero(), |sum, &c| sum + c);
+            (sum / corners.len().max(1) as f32).into()
+        })
+        .collect()
+    }
+}
+
+fn corners<P: EmitPositions>(polygon: P) -> Vec<Vector3<f32>> {
+    let mut corners = Vec::new();
+    polygon.emit_positions(|p| corners.push(Vector3::from(p)));
+    corners
+}
+
+/// Compute the total area of the `triangles` of an indexed mesh.
+pub fn indexed_surface_area(positions: &[Position], triangles: &[Triangle<usize>]) -> f32 {
+    indexed(positions, triangles).surface_area()
+}
+
+/// Compute the volume enclosed by the `triangles` of an indexed mesh,
+/// see `Measure::volume`.
+pub fn indexed_volume(positions: &[Position], triangles: &[Triangle<usize>]) -> f32 {
+    indexed(positions, triangles).volume()
+}
+
+/// Compute the center of mass of the solid enclosed by the `triangles` of
+/// an indexed mesh, see `Measure::centroid`.
+pub fn indexed_centroid(positions: &[Position], triangles: &[Triangle<usize>]) -> Option<Position> {
+    indexed(positions, triangles).centroid()
+}
+
+fn sums<I>(polygons: I) -> Sums
+where
+    I: Iterator,
+    I::Item: EmitPositions,
+{
+    let mut sums = Sums::new();
+    for p in polygons {
+        sums.add_polygon(p);
+    }
+    sums
+}
+
+fn indexed<'a>(
+    positions: &'a [Position],
+    triangles: &'a [Triangle<usize>],
+) -> impl Iterator<Item = Triangle<Position>> + 'a {
+    triangles
+        .iter()
+        .map(move |t| Triangle::new(positions[t.x], positions[t.y], positions[t.z]))
+}
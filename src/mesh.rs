@@ -0,0 +1,335 @@
+//! A ready to render indexed triangle mesh.
+
+use cgmath::Matrix4;
+use mint::ColumnMatrix4;
+
+use crate::generators::{IndexedPolygon, SharedVertex};
+use crate::index::fits;
+use crate::scene::transformed;
+use crate::{
+    EmitTriangles, IndexType, Indexer, LruIndexer, Normal, Polygon, Position, Triangle, Vertex,
+    VertexAttributes,
+};
+
+/// An indexed triangle mesh, with each vertex attribute in its own buffer.
+///
+/// This is the usual last step of a pipeline, turning a generator or a
+/// stream of polygons into buffers for a graphics API. Any vertex type
+/// implementing `VertexAttributes` can be collected, keeping the attributes
+/// it has. Every vertex must have the same attributes, so that each buffer
+/// is either empty or holds one value for each vertex; the constructors
+/// panic otherwise.
+///
+/// The indices are `u32` by default; other index types, like `u16` for
+/// compact GPU buffers, go through the `try_` constructors, which check
+/// that every vertex can be indexed.
+///
+/// ```
+/// use genmesh::generators::Cube;
+/// use genmesh::Mesh;
+///
+/// let mesh = Mesh::from_generator(&Cube::new());
+/// assert_eq!(mesh.vertex_count(), 24);
+/// assert_eq!(mesh.triangle_count(), 12);
+/// assert_eq!(mesh.interleaved().len(), 24 * 6);
+///
+/// let small: Mesh<u16> = Mesh::try_from_generator(&Cube::new()).unwrap();
+/// assert_eq!(small.triangles().collect::<Vec<_>>(), mesh.triangles().collect::<Vec<_>>());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mesh<I = u32> {
+    /// Position of each vertex
+    pub positions: Vec<Position>,
+    /// Normals, either empty or one for each vertex
+    pub normals: Vec<Normal>,
+    /// Texture coordinates, either empty or one for each vertex
+    pub uvs: Vec<[f32; 2]>,
+    /// Colors, either empty or one for each vertex
+    pub colors: Vec<[f32; 4]>,
+    /// The other values of the vertices, the same number for each vertex,
+    /// one vertex after the other
+    pub custom: Vec<f32>,
+    /// Triangle list, three indices into the vertices for each triangle
+    pub indices: Vec<I>,
+}
+
+impl<I> Default for Mesh<I> {
+    fn default() -> Self {
+        Mesh {
+            positions: Vec::new(),
+            normals: Vec::new(),
+            uvs: Vec::new(),
+            colors: Vec::new(),
+            custom: Vec::new(),
+            indices: Vec::new(),
+        }
+    }
+}
+
+impl Mesh {
+    /// Collect the shared vertices and the triangulated indexed polygons
+    /// of `generator`.
+    pub fn from_generator<G, V>(generator: &G) -> Self
+    where
+        G: SharedVertex<V> + IndexedPolygon<Polygon<usize>>,
+        V: VertexAttributes,
+    {
+        Mesh::try_from_generator(generator).expect("too many vertices for 32-bit indices")
+    }
+
+    /// Triangulate a stream of polygons, merging equal vertices with an
+    /// `LruIndexer` remembering the last `cache_size` vertices.
+    pub fn from_polygons_with_indexer<T, P>(polygons: T, cache_size: usize) -> Self
+    where
+        T: IntoIterator<Item = P>,
+        P: EmitTriangles,
+        P::Vertex: VertexAttributes + PartialEq + Clone,
+    {
+        Mesh::try_from_polygons_with_indexer(polygons, cache_size)
+            .expect("too many vertices for 32-bit indices")
+    }
+}
+
+impl<I: IndexType> Mesh<I> {
+    /// Like `from_generator`, for any index type. Returns `None` if the
+    /// generator has more vertices than `I` can index.
+    pub fn try_from_generator<G, V>(generator: &G) -> Option<Self>
+    where
+        G: SharedVertex<V> + IndexedPolygon<Polygon<usize>>,
+        V: VertexAttributes,
+    {
+        if !fits::<I>(generator.shared_vertex_count()) {
+            return None;
+        }
+        let mut mesh = Mesh::default();
+        for v in generator.shared_vertex_iter() {
+            mesh.push_vertex(&v);
+        }
+        for poly in generator.indexed_polygon_iter() {
+            poly.emit_triangles(|t| mesh.push_triangle(t));
+        }
+        Some(mesh)
+    }
+
+    /// Like `from_polygons_with_indexer`, for any index type. Returns `None`
+    /// if the polygons have more distinct vertices than `I` can index.
+    pub fn try_from_polygons_with_indexer<T, P>(polygons: T, cache_size: usize) -> Option<Self>
+    where
+        T: IntoIterator<Item = P>,
+        P: EmitTriangles,
+        P::Vertex: VertexAttributes + PartialEq + Clone,
+    {
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+        {
+            let mut indexer = LruIndexer::new(cache_size, |_, v| vertices.push(v));
+            for poly in polygons {
+                poly.emit_triangles(|t| {
+                    triangles.push(Triangle::new(
+                        indexer.index(t.x),
+                        indexer.index(t.y),
+                        indexer.index(t.z),
+                    ))
+                });
+            }
+        }
+        if !fits::<I>(vertices.len()) {
+            return None;
+        }
+
+        let mut mesh = Mesh::default();
+        for v in &vertices {
+            mesh.push_vertex(v);
+        }
+        for t in triangles {
+            mesh.push_triangle(t);
+        }
+        Some(mesh)
+    }
+
+    /// Set the texture coordinates, one for each vertex.
+    pub fn with_uvs(mut self, uvs: Vec<[f32; 2]>) -> Self {
+        assert_eq!(uvs.len(), self.positions.len());
+        self.uvs = uvs;
+        self
+    }
+
+    /// The number of vertices.
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// The number of other values of each vertex.
+    pub fn custom_count(&self) -> usize {
+        self.custom
+            .len()
+            .checked_div(self.positions.len())
+            .unwrap_or(0)
+    }
+
+    /// The number of triangles.
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// The vertices, with their position and normal side by side. Normals
+    /// are zero if the mesh has none.
+    pub fn vertices(&self) -> Vec<Vertex> {
+        let zero = Normal::from([0.; 3]);
+        self.positions
+            .iter()
+            .enumerate()
+            .map(|(i, &pos)| Vertex {
+                pos,
+                normal: self.normals.get(i).cloned().unwrap_or(zero),
+            })
+            .collect()
+    }
+
+    /// The triangles, as indices into the vertices.
+    pub fn triangles(&self) -> impl Iterator<Item = Triangle<usize>> + '_ {
+        self.indices
+            .chunks(3)
+            .map(|t| Triangle::new(t[0].to_usize(), t[1].to_usize(), t[2].to_usize()))
+    }
+
+    /// All of the attributes in a single buffer: for each vertex its
+    /// position, then its normal, texture coordinates and color, for those
+    /// the mesh has, and its other values.
+    pub fn interleaved(&self) -> Vec<f32> {
+        let mut out = Vec::new();
+        let custom = self.custom_count();
+        for (i, p) in self.positions.iter().enumerate() {
+            out.extend_from_slice(&[p.x, p.y, p.z]);
+            if let Some(n) = self.normals.get(i) {
+                out.extend_from_slice(&[n.x, n.y, n.z]);
+            }
+            if let Some(uv) = self.uvs.get(i) {
+                out.extend_from_slice(uv);
+            }
+            if let Some(color) = self.colors.get(i) {
+                out.extend_from_slice(color);
+            }
+            out.extend_from_slice(&self.custom[i * custom..(i + 1) * custom]);
+        }
+        out
+    }
+
+    /// The positions, as a flat buffer of three floats each.
+    pub fn position_buffer(&self) -> Vec<f32> {
+        self.positions
+            .iter()
+            .flat_map(|p| [p.x, p.y, p.z])
+            .collect()
+    }
+
+    /// The normals, as a flat buffer of three floats each.
+    pub fn normal_buffer(&self) -> Vec<f32> {
+        self.normals.iter().flat_map(|n| [n.x, n.y, n.z]).collect()
+    }
+
+    /// The texture coordinates, as a flat buffer of two floats each.
+    pub fn uv_buffer(&self) -> Vec<f32> {
+        self.uvs.iter().flat_map(|uv| [uv[0], uv[1]]).collect()
+    }
+
+    /// The colors, as a flat buffer of four floats each.
+    pub fn color_buffer(&self) -> Vec<f32> {
+        self.colors.iter().flat_map(|c| c.iter().cloned()).collect()
+    }
+
+    fn push_vertex<V: VertexAttributes>(&mut self, v: &V) {
+        let count = self.positions.len();
+        push_attribute(&mut self.normals, count, v.normal(), "normals");
+        push_attribute(&mut self.uvs, count, v.uv(), "texture coordinates");
+        push_attribute(&mut self.colors, count, v.color(), "colors");
+        let custom = v.custom();
+        assert!(
+            count == 0 || self.custom.len() == count * custom.len(),
+            "the vertices have different numbers of custom values"
+        );
+        self.custom.extend_from_slice(custom);
+        self.positions.push(v.position());
+    }
+
+    fn push_triangle(&mut self, t: Triangle<usize>) {
+        let index = |i: usize| I::try_from_usize(i).expect("index out of range");
+        self.indices
+            .extend_from_slice(&[index(t.x), index(t.y), index(t.z)]);
+    }
+}
+
+// Add the attribute of the vertex after the `count` vertices already in
+// the mesh, checking they all have it or none does.
+fn push_attribute<T>(buffer: &mut Vec<T>, count: usize, value: Option<T>, name: &str) {
+    let had = if count == 0 {
+        value.is_some()
+    } else {
+        buffer.len() == count
+    };
+    assert_eq!(
+        had,
+        value.is_some(),
+        "only some of the vertices have {}",
+        name
+    );
+    buffer.extend(value);
+}
+
+/// Merges the meshes of many generators, each with its own transform,
+/// into a single `Mesh`.
+///
+/// ```
+/// use genmesh::generators::SphereUv;
+/// use genmesh::{Mesh, MeshBuilder};
+///
+/// let ball = |scale: f32, height: f32| {
+///     [
+///         [scale, 0., 0., 0.],
+///         [0., scale, 0., 0.],
+///         [0., 0., scale, 0.],
+///         [0., 0., height, 1.],
+///     ]
+/// };
+/// let sphere = SphereUv::new(12, 8);
+/// let snowman = MeshBuilder::new()
+///     .add(&sphere, ball(1., 0.))
+///     .add(&sphere, ball(0.7, 1.5))
+///     .add(&sphere, ball(0.5, 2.6))
+///     .build();
+/// assert_eq!(snowman.triangle_count(), 3 * Mesh::from_generator(&sphere).triangle_count());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MeshBuilder {
+    mesh: Mesh,
+}
+
+impl MeshBuilder {
+    /// Create an empty mesh builder.
+    pub fn new() -> Self {
+        MeshBuilder::default()
+    }
+
+    /// Add the mesh of `generator`, moved by `transform`, with the normals
+    /// and winding handled as in `SceneBuilder::add`.
+    pub fn add<G, M>(mut self, generator: &G, transform: M) -> Self
+    where
+        G: SharedVertex<Vertex> + IndexedPolygon<Polygon<usize>>,
+        M: Into<ColumnMatrix4<f32>>,
+    {
+        let base = self.mesh.vertex_count();
+        let (vertices, triangles) = transformed(generator, Matrix4::from(transform.into()), base);
+        for v in &vertices {
+            self.mesh.push_vertex(v);
+        }
+        for t in triangles {
+            self.mesh.push_triangle(t);
+        }
+        self
+    }
+
+    /// Finish the mesh.
+    pub fn build(self) -> Mesh {
+        self.mesh
+    }
+}
@@ -0,0 +1,165 @@
+//! Local measures of the shape of a mesh and the quality of its
+//! triangles, for adaptive remeshing and for visualization.
+
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::{Position, Triangle};
+
+/// The curvature of a surface at a vertex, see `curvature`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Curvature {
+    /// The product of the two principal curvatures: positive on domes,
+    /// negative on saddles and zero where the surface can be unrolled
+    /// flat
+    pub gaussian: f32,
+    /// The average of the two principal curvatures, positive where the
+    /// surface bulges towards its normal like the outside of a sphere
+    pub mean: f32,
+}
+
+/// Estimate the curvature of an indexed triangle mesh at each of its
+/// vertices, with the discrete operators of Meyer, Desbrun, Schröder and
+/// Barr: the angle deficit for the Gaussian curvature and the cotangent
+/// Laplacian for the mean curvature, both divided by the mixed Voronoi
+/// area around the vertex.
+///
+/// The mesh has to share its vertices along its edges, see `weld`, and be
+/// wound consistently, which gives the sign of the mean curvature. The
+/// curvature is left at zero for the vertices on open boundaries, where
+/// it is not defined, and for those that no triangle uses.
+pub fn curvature(positions: &[Position], triangles: &[Triangle<usize>]) -> Vec<Curvature> {
+    let p = |i: usize| Vector3::from(positions[i]);
+    let count = positions.len();
+    let mut angles = vec![0f32; count];
+    let mut areas = vec![0f32; count];
+    let mut laplacian = vec![Vector3::new(0., 0., 0.); count];
+    let mut normals = vec![Vector3::new(0., 0., 0.); count];
+    let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+
+    for t in triangles {
+        let corners = [t.x, t.y, t.z];
+        let normal = (p(t.y) - p(t.x)).cross(p(t.z) - p(t.x));
+        let area = normal.magnitude() / 2.;
+        if area <= 0. {
+            continue;
+        }
+        for k in 0..3 {
+            let (a, b, c) = (corners[k], corners[(k + 1) % 3], corners[(k + 2) % 3]);
+            let key = (a.min(b), a.max(b));
+            *edges.entry(key).or_insert(0) += 1;
+            normals[a] += normal;
+
+            // the angle at `a`, and the cotangent of that at `c`, which
+            // weighs the edge from `a` to `b`
+            let (ab, ac) = (p(b) - p(a), p(c) - p(a));
+            angles[a] += ab.angle(ac).0;
+            let (ca, cb) = (p(a) - p(c), p(b) - p(c));
+            let cot = ca.dot(cb) / ca.cross(cb).magnitude();
+            laplacian[a] += (p(a) - p(b)) * cot;
+            laplacian[b] += (p(b) - p(a)) * cot;
+        }
+
+        // the mixed area, the part of the triangle closest to each corner
+        // unless it is obtuse
+        let obtuse = (0..3).find(|&k| {
+            let (a, b, c) = (corners[k], corners[(k + 1) % 3], corners[(k + 2) % 3]);
+            (p(b) - p(a)).dot(p(c) - p(a)) < 0.
+        });
+        for k in 0..3 {
+            let (a, b, c) = (corners[k], corners[(k + 1) % 3], corners[(k + 2) % 3]);
+            areas[a] += match obtuse {
+                Some(o) if o == k => area / 2.,
+                Some(_) => area / 4.,
+                None => {
+                    let cot = |o: usize, x: usize, y: usize| {
+                        let (u, v) = (p(x) - p(o), p(y) - p(o));
+                        u.dot(v) / u.cross(v).magnitude()
+                    };
+                    ((p(b) - p(a)).magnitude2() * cot(c, a, b)
+                        + (p(c) - p(a)).magnitude2() * cot(b, c, a))
+                        / 8.
+                }
+            };
+        }
+    }
+
+    let mut boundary = vec![false; count];
+    for (&(a, b), &n) in &edges {
+        if n == 1 {
+            boundary[a] = true;
+            boundary[b] = true;
+        }
+    }
+    (0..count)
+        .map(|i| {
+            if boundary[i] || areas[i] <= 0. {
+                return Curvature::default();
+            }
+            // the Laplacian is twice the mean curvature times the normal,
+            // with the edges weighted by half the sum of their cotangents
+            let h = laplacian[i] / (4. * areas[i]);
+            let sign = if h.dot(normals[i]) < 0. { -1. } else { 1. };
+            Curvature {
+                gaussian: (2. * PI - angles[i]) / areas[i],
+                mean: sign * h.magnitude(),
+            }
+        })
+        .collect()
+}
+
+/// The shape of a triangle, see `triangle_quality`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriangleQuality {
+    /// The radius of the circle through the corners over twice that of
+    /// the circle inside the triangle: `1` for equilateral triangles,
+    /// growing without bound as they get thinner, infinite for those
+    /// with no area
+    pub aspect_ratio: f32,
+    /// The smallest angle of the triangle, in radians, at most `PI / 3`
+    pub min_angle: f32,
+}
+
+/// Measure the shape of each triangle of an indexed mesh. Thin triangles
+/// shade badly and slow down simulations, remeshing passes split or
+/// flip them away.
+pub fn triangle_quality(
+    positions: &[Position],
+    triangles: &[Triangle<usize>],
+) -> Vec<TriangleQuality> {
+    triangles
+        .iter()
+        .map(|t| {
+            let [a, b, c] = [t.x, t.y, t.z].map(|i| Vector3::from(positions[i]));
+            let (la, lb, lc) = (
+                (c - b).magnitude(),
+                (a - c).magnitude(),
+                (b - a).magnitude(),
+            );
+            let area = (b - a).cross(c - a).magnitude() / 2.;
+            let s = (la + lb + lc) / 2.;
+            let aspect_ratio = if area > 0. {
+                // R = abc / 4A and r = A / s
+                la * lb * lc * s / (8. * area * area)
+            } else {
+                f32::INFINITY
+            };
+            let angle = |u: Vector3<f32>, v: Vector3<f32>| {
+                if u.magnitude2() > 0. && v.magnitude2() > 0. {
+                    u.angle(v).0
+                } else {
+                    0.
+                }
+            };
+            let min_angle = angle(b - a, c - a)
+                .min(angle(c - b, a - b))
+                .min(angle(a - c, b - c));
+            TriangleQuality {
+                aspect_ratio,
+                min_angle,
+            }
+        })
+        .collect()
+}
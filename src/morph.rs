@@ -0,0 +1,138 @@
+//! Blend shapes between generators with the same topology.
+
+use cgmath::Vector3;
+
+use crate::generators::{IndexedPolygon, SharedVertex};
+use crate::math;
+use crate::{Mesh, Normal, Polygon, Position, VertexAttributes};
+
+/// A base mesh and the differences to one or more target shapes, the
+/// buffers GPU morphing blends together with one weight per target.
+///
+/// The targets are generators with the same vertices and polygons as the
+/// base, only in other places, like a `QuadSphere` and a
+/// `Cube::subdivided` cut as finely, which morph between a sphere and a
+/// cube.
+///
+/// ```
+/// use genmesh::generators::{Cube, QuadSphere};
+/// use genmesh::MorphTargets;
+///
+/// let mut morph = MorphTargets::new(&QuadSphere::new(4));
+/// assert_eq!(morph.add_target(&Cube::subdivided(4, 4, 4)), Some(0));
+/// assert_eq!(morph.add_target(&Cube::new()), None);
+///
+/// let halfway = morph.blend(&[0.5]);
+/// assert_eq!(halfway.vertex_count(), morph.base.vertex_count());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MorphTargets {
+    /// The base shape, with its indices and any other attributes
+    pub base: Mesh,
+    /// For each target, how far each vertex moves from the base
+    pub position_deltas: Vec<Vec<Position>>,
+    /// For each target, how much each normal changes, empty unless both
+    /// the base and the target have normals
+    pub normal_deltas: Vec<Vec<Normal>>,
+}
+
+impl MorphTargets {
+    /// Start from the shape of `base`, with no targets.
+    pub fn new<G, V>(base: &G) -> Self
+    where
+        G: SharedVertex<V> + IndexedPolygon<Polygon<usize>>,
+        V: VertexAttributes,
+    {
+        MorphTargets {
+            base: Mesh::from_generator(base),
+            position_deltas: Vec::new(),
+            normal_deltas: Vec::new(),
+        }
+    }
+
+    /// Add the shape of `target` as a new target, returning its index, or
+    /// `None` if it does not have the same vertices and triangles as the
+    /// base.
+    pub fn add_target<G, V>(&mut self, target: &G) -> Option<usize>
+    where
+        G: SharedVertex<V> + IndexedPolygon<Polygon<usize>>,
+        V: VertexAttributes,
+    {
+        if target.shared_vertex_count() != self.base.vertex_count() {
+            return None;
+        }
+        let target: Mesh = Mesh::from_generator(target);
+        if target.indices != self.base.indices {
+            return None;
+        }
+        let delta = |a: &[mint::Vector3<f32>], b: &[mint::Vector3<f32>]| {
+            a.iter()
+                .zip(b)
+                .map(|(&a, &b)| (Vector3::from(a) - Vector3::from(b)).into())
+                .collect::<Vec<_>>()
+        };
+        let normals = if target.normals.len() == self.base.normals.len() {
+            delta(&target.normals, &self.base.normals)
+        } else {
+            Vec::new()
+        };
+        self.position_deltas
+            .push(delta(&target.positions, &self.base.positions));
+        self.normal_deltas.push(normals);
+        Some(self.position_deltas.len() - 1)
+    }
+
+    /// The number of targets.
+    pub fn target_count(&self) -> usize {
+        self.position_deltas.len()
+    }
+
+    /// The position deltas of `target`, as a flat buffer of three floats
+    /// each.
+    pub fn position_delta_buffer(&self, target: usize) -> Vec<f32> {
+        self.position_deltas[target]
+            .iter()
+            .flat_map(|p| [p.x, p.y, p.z])
+            .collect()
+    }
+
+    /// The normal deltas of `target`, as a flat buffer of three floats
+    /// each.
+    pub fn normal_delta_buffer(&self, target: usize) -> Vec<f32> {
+        self.normal_deltas[target]
+            .iter()
+            .flat_map(|n| [n.x, n.y, n.z])
+            .collect()
+    }
+
+    /// Blend the targets on the CPU: the base with each target's deltas
+    /// added `weights[i]` times, the targets with no weight left alone.
+    /// The blended normals are normalized again.
+    pub fn blend(&self, weights: &[f32]) -> Mesh {
+        let mut mesh = self.base.clone();
+        let add = |values: &mut [mint::Vector3<f32>], deltas: &[mint::Vector3<f32>], w: f32| {
+            for (v, d) in values.iter_mut().zip(deltas) {
+                *v = (Vector3::from(*v) + Vector3::from(*d) * w).into();
+            }
+        };
+        let mut bent = false;
+        for (k, &w) in weights.iter().enumerate().take(self.target_count()) {
+            if w == 0. {
+                continue;
+            }
+            add(&mut mesh.positions, &self.position_deltas[k], w);
+            if !self.normal_deltas[k].is_empty() {
+                add(&mut mesh.normals, &self.normal_deltas[k], w);
+                bent = true;
+            }
+        }
+        if bent {
+            for n in &mut mesh.normals {
+                if let Some(unit) = math::try_normalize(Vector3::from(*n)) {
+                    *n = unit.into();
+                }
+            }
+        }
+        mesh
+    }
+}
@@ -1,9 +1,10 @@
 //! This is a utility to search out and work in the mesh as a whole rather
 //! then polygon by polygon.
 
-use cgmath::{InnerSpace, Vector3};
+use cgmath::Vector3;
 use std::collections::{HashMap, HashSet};
 
+use crate::math;
 use crate::poly::{EmitLines, Line, Triangle};
 use crate::Normal;
 
@@ -26,23 +27,17 @@ impl<T> Neighbors<T> {
 
         for (i, p) in polygons.iter().enumerate() {
             p.clone().emit_lines(|line| {
-                shares_vertex
-                    .entry(line.x.clone())
-                    .or_insert(Vec::new())
-                    .push(i);
-                shares_vertex
-                    .entry(line.y.clone())
-                    .or_insert(Vec::new())
-                    .push(i);
+                // every corner starts exactly one line of the triangle
+                shares_vertex.entry(line.x).or_insert(Vec::new()).push(i);
                 shares_edge.entry(line).or_insert(Vec::new()).push(i);
             });
         }
 
         Neighbors {
-            vertices: vertices,
-            shares_vertex: shares_vertex,
-            shares_edge: shares_edge,
-            polygons: polygons,
+            vertices,
+            shares_vertex,
+            shares_edge,
+            polygons,
         }
     }
 
@@ -64,35 +59,177 @@ impl<T> Neighbors<T> {
         self.polygons.get(i).map(|x| {
             let mut v = HashSet::new();
             x.clone().emit_lines(|line| {
-                self.shares_edge.get(&line).map(|x| {
-                    for &i in x {
-                        v.insert(i);
-                    }
-                });
+                for i in self.faces_sharing_edge(&line) {
+                    v.insert(i);
+                }
             });
             v.remove(&i);
             v
         })
     }
 
+    /// looks up the index of every polygon that uses the edge between
+    /// `edge.x` and `edge.y`, in either direction.
+    pub fn faces_sharing_edge(&self, edge: &Line<usize>) -> Vec<usize> {
+        let flipped = Line::new(edge.y, edge.x);
+        let mut faces = Vec::new();
+        for e in &[edge, &flipped] {
+            if let Some(x) = self.shares_edge.get(e) {
+                faces.extend(x.iter().cloned());
+            }
+        }
+        faces
+    }
+
+    /// looks up every vertex connected to vertex `t` by an edge,
+    /// sorted by index.
+    pub fn vertices_adjacent_to_vertex(&self, t: usize) -> Vec<usize> {
+        let mut adjacent: Vec<usize> = self
+            .shares_vertex
+            .get(&t)
+            .into_iter()
+            .flatten()
+            .flat_map(|&face| {
+                let Triangle { x, y, z } = self.polygons[face];
+                vec![x, y, z]
+            })
+            .filter(|&v| v != t)
+            .collect();
+        adjacent.sort_unstable();
+        adjacent.dedup();
+        adjacent
+    }
+
+    /// looks up every edge connected to vertex `t`, each line
+    /// starting at `t`.
+    pub fn edges_of_vertex(&self, t: usize) -> Vec<Line<usize>> {
+        self.vertices_adjacent_to_vertex(t)
+            .into_iter()
+            .map(|v| Line::new(t, v))
+            .collect()
+    }
+
+    /// enumerates the edges that belong to a single polygon, which are the
+    /// edges along the open boundaries of the mesh. Each edge follows the
+    /// winding of the polygon it belongs to; they are listed in polygon order.
+    pub fn boundary_edges(&self) -> Vec<Line<usize>> {
+        let mut edges = Vec::new();
+        for p in &self.polygons {
+            p.clone().emit_lines(|line| {
+                if self.faces_sharing_edge(&line).len() == 1 {
+                    edges.push(line);
+                }
+            });
+        }
+        edges
+    }
+
+    /// finds the open boundaries of the mesh, as closed cycles of vertex
+    /// indices. Each loop follows the winding of the polygons along it, and
+    /// the first vertex is not repeated at the end. Where several boundaries
+    /// touch at a single vertex they are split into separate loops. Chains
+    /// that cannot be closed, because the winding of the polygons around
+    /// them is inconsistent, are not reported.
+    pub fn boundary_loops(&self) -> Vec<Vec<usize>> {
+        let edges = self.boundary_edges();
+        let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (i, e) in edges.iter().enumerate() {
+            outgoing.entry(e.x).or_default().push(i);
+        }
+
+        let mut used = vec![false; edges.len()];
+        let mut loops = Vec::new();
+        for first in 0..edges.len() {
+            if used[first] {
+                continue;
+            }
+            used[first] = true;
+            let start = edges[first].x;
+            let mut path = vec![start];
+            let mut current = edges[first].y;
+
+            let closed = loop {
+                if current == start {
+                    break true;
+                }
+                let next = outgoing
+                    .get(&current)
+                    .and_then(|out| out.iter().find(|&&e| !used[e]));
+                match next {
+                    Some(&e) => {
+                        used[e] = true;
+                        path.push(current);
+                        current = edges[e].y;
+                    }
+                    None => break false,
+                }
+            };
+            if !closed {
+                continue;
+            }
+
+            // a vertex visited twice means two loops touching at a corner
+            let mut seen: HashMap<usize, usize> = HashMap::new();
+            let mut stack: Vec<usize> = Vec::new();
+            for v in path {
+                if let Some(&at) = seen.get(&v) {
+                    let inner: Vec<usize> = stack.drain(at..).collect();
+                    for u in &inner {
+                        seen.remove(u);
+                    }
+                    loops.push(inner);
+                }
+                seen.insert(v, stack.len());
+                stack.push(v);
+            }
+            loops.push(stack);
+        }
+        loops
+    }
+
     /// Calculate the normal for face. This is a `flat` shading
     ///
     /// You must supply a function that can be used to lookup
     /// The position which is needed to calculate the normal
-    pub fn normal_for_face<F>(&self, i: usize, mut f: F) -> Normal
+    ///
+    /// Degenerate faces use `NormalFallback::default()`, and end up
+    /// facing +Z if none of their neighbors has a normal either.
+    pub fn normal_for_face<F>(&self, i: usize, f: F) -> Normal
     where
         F: FnMut(&T) -> Normal,
     {
-        let Triangle { x, y, z } = self.polygons[i];
-
-        let x = Vector3::from(f(&self.vertices[x]));
-        let y = Vector3::from(f(&self.vertices[y]));
-        let z = Vector3::from(f(&self.vertices[z]));
-
-        let a = z - x;
-        let b = z - y;
+        self.normal_for_face_with(i, f, NormalFallback::default())
+            .unwrap_or_else(|| [0., 0., 1.].into())
+    }
 
-        a.cross(b).normalize().into()
+    /// Calculate the normal for face, using `fallback` if the face has
+    /// no area. Returns `None` if the fallback cannot provide a normal.
+    pub fn normal_for_face_with<F>(
+        &self,
+        i: usize,
+        mut f: F,
+        fallback: NormalFallback,
+    ) -> Option<Normal>
+    where
+        F: FnMut(&T) -> Normal,
+    {
+        if let Some(n) = self.face_normal(i, &mut f) {
+            return Some(n.into());
+        }
+        match fallback {
+            NormalFallback::Skip => None,
+            NormalFallback::Up(up) => Some(up),
+            NormalFallback::NeighborAverage => {
+                let mut neighbors: Vec<usize> = self.polygon_neighbors(i)?.into_iter().collect();
+                // summing in a fixed order keeps the result reproducible
+                neighbors.sort_unstable();
+                let sum = neighbors
+                    .into_iter()
+                    .filter_map(|n| self.face_normal(n, &mut f))
+                    .fold(Vector3::new(0., 0., 0.), |a, b| a + b);
+                math::try_normalize(sum).map(Into::into)
+            }
+        }
     }
 
     /// Calculate the normal for an vertex based on the average
@@ -100,16 +237,72 @@ impl<T> Neighbors<T> {
     ///
     /// You must supply a function that can be used to lookup
     /// The position which is needed to calculate the normal
-    pub fn normal_for_vertex<F>(&self, i: usize, mut f: F) -> Normal
+    ///
+    /// Degenerate faces are handled as in `normal_for_face`; a vertex that
+    /// still gets no usable direction faces +Z.
+    pub fn normal_for_vertex<F>(&self, i: usize, f: F) -> Normal
+    where
+        F: FnMut(&T) -> Normal,
+    {
+        self.normal_for_vertex_with(i, f, NormalFallback::default())
+            .unwrap_or_else(|| [0., 0., 1.].into())
+    }
+
+    /// Calculate the normal for an vertex, using `fallback` for the
+    /// degenerate faces around it. With `NormalFallback::Up` the up vector
+    /// is also used when the faces cancel out or the vertex is unused;
+    /// otherwise `None` is returned in those cases.
+    pub fn normal_for_vertex_with<F>(
+        &self,
+        i: usize,
+        mut f: F,
+        fallback: NormalFallback,
+    ) -> Option<Normal>
     where
         F: FnMut(&T) -> Normal,
     {
         let mut normal = Vector3::new(0f32, 0., 0.);
 
-        for &face in &self.shares_vertex[&i] {
-            normal += Vector3::from(self.normal_for_face(face, &mut f));
+        for &face in self.shares_vertex.get(&i).into_iter().flatten() {
+            if let Some(n) = self.normal_for_face_with(face, &mut f, fallback) {
+                normal += Vector3::from(n);
+            }
+        }
+
+        match (math::try_normalize(normal), fallback) {
+            (Some(n), _) => Some(n.into()),
+            (None, NormalFallback::Up(up)) => Some(up),
+            (None, _) => None,
         }
+    }
+
+    // unit normal of face `i`, or `None` if it has no area
+    fn face_normal<F>(&self, i: usize, mut f: F) -> Option<Vector3<f32>>
+    where
+        F: FnMut(&T) -> Normal,
+    {
+        let Triangle { x, y, z } = self.polygons[i];
 
-        normal.normalize().into()
+        let x = Vector3::from(f(&self.vertices[x]));
+        let y = Vector3::from(f(&self.vertices[y]));
+        let z = Vector3::from(f(&self.vertices[z]));
+
+        let a = z - x;
+        let b = z - y;
+
+        math::try_normalize(a.cross(b))
     }
 }
+
+/// What to use as the normal of a face that has no area, and thus no
+/// direction of its own.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NormalFallback {
+    /// Give the face no normal, and leave it out of vertex normals.
+    Skip,
+    /// Use the given direction.
+    Up(Normal),
+    /// Use the average normal of the faces sharing an edge with it.
+    #[default]
+    NeighborAverage,
+}
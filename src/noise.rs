@@ -0,0 +1,233 @@
+//! Smooth pseudo random noise, to feed `Displace` and the generators of
+//! rocks and terrain without bringing in a separate noise crate.
+//!
+//! Every function is deterministic: the same point and `seed` always give
+//! the same value, and different seeds give unrelated noise. Values are
+//! within `[-1, 1]`, and features are about one unit across, so points are
+//! usually scaled by a frequency first.
+//!
+//! ```
+//! use genmesh::generators::IcoSphere;
+//! use genmesh::noise::Fbm;
+//! use genmesh::Displace;
+//!
+//! let noise = Fbm::default();
+//! let lumpy: Vec<_> = IcoSphere::subdivide(3)
+//!     .displace(|p, _| 0.2 * noise.sample3(p))
+//!     .collect();
+//! ```
+
+use std::f32::consts::{FRAC_1_SQRT_2, SQRT_2};
+
+use crate::Position;
+
+/// The noise `Fbm` layers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Basis {
+    /// `value2` and `value3`: blocky, cheap
+    Value,
+    /// `perlin2` and `perlin3`: rounder features, with no grid showing
+    Perlin,
+}
+
+/// Fractal Brownian motion: a sum of `octaves` layers of noise, each
+/// `lacunarity` times finer than the one before and `persistence` times
+/// as strong, scaled back to `[-1, 1]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fbm {
+    /// The noise each layer is made of
+    pub basis: Basis,
+    /// The frequency of the coarsest layer
+    pub frequency: f32,
+    /// The number of layers
+    pub octaves: usize,
+    /// How much finer each layer is than the one before, usually 2
+    pub lacunarity: f32,
+    /// The strength of each layer relative to the one before
+    pub persistence: f32,
+    /// The seed of the first layer; each layer has its own
+    pub seed: u32,
+}
+
+impl Default for Fbm {
+    fn default() -> Self {
+        Fbm {
+            basis: Basis::Perlin,
+            frequency: 1.,
+            octaves: 4,
+            lacunarity: 2.,
+            persistence: 0.5,
+            seed: 0,
+        }
+    }
+}
+
+impl Fbm {
+    /// Sample the noise at a 2D point.
+    pub fn sample2(&self, p: [f32; 2]) -> f32 {
+        self.sum(|frequency, seed| {
+            let p = [p[0] * frequency, p[1] * frequency];
+            match self.basis {
+                Basis::Value => value2(p, seed),
+                Basis::Perlin => perlin2(p, seed),
+            }
+        })
+    }
+
+    /// Sample the noise at a 3D point.
+    pub fn sample3(&self, p: Position) -> f32 {
+        self.sum(|frequency, seed| {
+            let p = [p.x * frequency, p.y * frequency, p.z * frequency].into();
+            match self.basis {
+                Basis::Value => value3(p, seed),
+                Basis::Perlin => perlin3(p, seed),
+            }
+        })
+    }
+
+    fn sum<F>(&self, mut layer: F) -> f32
+    where
+        F: FnMut(f32, u32) -> f32,
+    {
+        let (mut sum, mut total) = (0., 0.);
+        let (mut frequency, mut amplitude) = (self.frequency, 1.);
+        for octave in 0..self.octaves {
+            let seed = self
+                .seed
+                .wrapping_add((octave as u32).wrapping_mul(0x9e37_79b9));
+            sum += amplitude * layer(frequency, seed);
+            total += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.persistence;
+        }
+        if total > 0. {
+            sum / total
+        } else {
+            0.
+        }
+    }
+}
+
+/// Value noise at a 2D point: random values at the corners of a unit
+/// grid, blended smoothly in between.
+pub fn value2(p: [f32; 2], seed: u32) -> f32 {
+    let cell = p.map(f32::floor);
+    let t = [p[0] - cell[0], p[1] - cell[1]].map(smoothstep);
+    let cell = cell.map(|c| c as i32);
+    let corner = |dx: i32, dy: i32| unit(hash(&[cell[0] + dx, cell[1] + dy], seed));
+    let x0 = lerp(corner(0, 0), corner(1, 0), t[0]);
+    let x1 = lerp(corner(0, 1), corner(1, 1), t[0]);
+    lerp(x0, x1, t[1])
+}
+
+/// Value noise at a 3D point: random values at the corners of a unit
+/// grid, blended smoothly in between.
+pub fn value3(p: Position, seed: u32) -> f32 {
+    let cell = [p.x.floor(), p.y.floor(), p.z.floor()];
+    let t = [p.x - cell[0], p.y - cell[1], p.z - cell[2]].map(smoothstep);
+    let cell = cell.map(|c| c as i32);
+    let corner =
+        |dx: i32, dy: i32, dz: i32| unit(hash(&[cell[0] + dx, cell[1] + dy, cell[2] + dz], seed));
+    let x00 = lerp(corner(0, 0, 0), corner(1, 0, 0), t[0]);
+    let x10 = lerp(corner(0, 1, 0), corner(1, 1, 0), t[0]);
+    let x01 = lerp(corner(0, 0, 1), corner(1, 0, 1), t[0]);
+    let x11 = lerp(corner(0, 1, 1), corner(1, 1, 1), t[0]);
+    lerp(lerp(x00, x10, t[1]), lerp(x01, x11, t[1]), t[2])
+}
+
+/// Perlin gradient noise at a 2D point: random slopes at the corners of
+/// a unit grid, blended smoothly in between. It is zero at the corners.
+pub fn perlin2(p: [f32; 2], seed: u32) -> f32 {
+    let cell = p.map(f32::floor);
+    let f = [p[0] - cell[0], p[1] - cell[1]];
+    let t = f.map(fade);
+    let cell = cell.map(|c| c as i32);
+    let corner = |dx: i32, dy: i32| {
+        let h = hash(&[cell[0] + dx, cell[1] + dy], seed);
+        let (x, y) = (f[0] - dx as f32, f[1] - dy as f32);
+        // eight directions around the circle
+        let (gx, gy) = GRADIENTS_2D[(h >> 29) as usize];
+        gx * x + gy * y
+    };
+    let x0 = lerp(corner(0, 0), corner(1, 0), t[0]);
+    let x1 = lerp(corner(0, 1), corner(1, 1), t[0]);
+    // the largest value is half the diagonal of a cell
+    (lerp(x0, x1, t[1]) * SQRT_2).clamp(-1., 1.)
+}
+
+/// Perlin gradient noise at a 3D point: random slopes at the corners of
+/// a unit grid, blended smoothly in between. It is zero at the corners.
+pub fn perlin3(p: Position, seed: u32) -> f32 {
+    let cell = [p.x.floor(), p.y.floor(), p.z.floor()];
+    let f = [p.x - cell[0], p.y - cell[1], p.z - cell[2]];
+    let t = f.map(fade);
+    let cell = cell.map(|c| c as i32);
+    let corner = |dx: i32, dy: i32, dz: i32| {
+        let h = hash(&[cell[0] + dx, cell[1] + dy, cell[2] + dz], seed);
+        let (x, y, z) = (f[0] - dx as f32, f[1] - dy as f32, f[2] - dz as f32);
+        // towards the middle of one of the twelve edges of a cube
+        let g = GRADIENTS_3D[(h % 12) as usize];
+        g[0] * x + g[1] * y + g[2] * z
+    };
+    let x00 = lerp(corner(0, 0, 0), corner(1, 0, 0), t[0]);
+    let x10 = lerp(corner(0, 1, 0), corner(1, 1, 0), t[0]);
+    let x01 = lerp(corner(0, 0, 1), corner(1, 0, 1), t[0]);
+    let x11 = lerp(corner(0, 1, 1), corner(1, 1, 1), t[0]);
+    lerp(lerp(x00, x10, t[1]), lerp(x01, x11, t[1]), t[2]).clamp(-1., 1.)
+}
+
+const GRADIENTS_2D: [(f32, f32); 8] = [
+    (1., 0.),
+    (-1., 0.),
+    (0., 1.),
+    (0., -1.),
+    (FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (-FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+    (-FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+];
+
+const GRADIENTS_3D: [[f32; 3]; 12] = [
+    [1., 1., 0.],
+    [-1., 1., 0.],
+    [1., -1., 0.],
+    [-1., -1., 0.],
+    [1., 0., 1.],
+    [-1., 0., 1.],
+    [1., 0., -1.],
+    [-1., 0., -1.],
+    [0., 1., 1.],
+    [0., -1., 1.],
+    [0., 1., -1.],
+    [0., -1., -1.],
+];
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn smoothstep(t: f32) -> f32 {
+    t * t * (3. - 2. * t)
+}
+
+// smoother than `smoothstep`, so that the slopes of gradient noise have
+// no creases at the edges of the cells
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6. - 15.) + 10.)
+}
+
+// a hash in [-1, 1]
+fn unit(h: u32) -> f32 {
+    h as f32 / u32::MAX as f32 * 2. - 1.
+}
+
+fn hash(cell: &[i32], seed: u32) -> u32 {
+    let mut h = seed ^ 0x2545_f491;
+    for &c in cell {
+        h = (h ^ c as u32).wrapping_mul(0x2710_0001);
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x85eb_ca6b);
+        h ^= h >> 13;
+    }
+    h
+}
@@ -0,0 +1,108 @@
+//! Lines showing the normals of a stream of vertices, for checking them
+//! by eye in a renderer.
+
+use cgmath::Vector3;
+
+use crate::attributes::VertexAttributes;
+use crate::math;
+use crate::{Line, Normal, Vertex};
+
+// what `normal_lines` gives for the tangent of every vertex
+type NoTangent<T> = fn(&T) -> Option<Normal>;
+
+/// Draws the normals of a stream of vertices as lines.
+pub trait NormalLines: Iterator + Sized {
+    /// Stream a line from the position of each vertex to `length` along
+    /// its normal. Vertices without a normal are skipped.
+    ///
+    /// The normal of both ends of each line is the direction of the line.
+    fn normal_lines(self, length: f32) -> NormalLinesIterator<Self, NoTangent<Self::Item>>;
+
+    /// Stream a line along the normal of each vertex as `normal_lines`
+    /// does, each followed by a line `length` along the tangent `tangent`
+    /// gives for the vertex, if it gives one, which is drawn even for
+    /// vertices without a normal.
+    fn normal_and_tangent_lines<F>(self, length: f32, tangent: F) -> NormalLinesIterator<Self, F>
+    where
+        F: FnMut(&Self::Item) -> Option<Normal>;
+}
+
+impl<I> NormalLines for I
+where
+    I: Iterator,
+    I::Item: VertexAttributes,
+{
+    fn normal_lines(self, length: f32) -> NormalLinesIterator<Self, NoTangent<Self::Item>> {
+        self.normal_and_tangent_lines(length, |_| None)
+    }
+
+    fn normal_and_tangent_lines<F>(self, length: f32, tangent: F) -> NormalLinesIterator<Self, F>
+    where
+        F: FnMut(&Self::Item) -> Option<Normal>,
+    {
+        NormalLinesIterator {
+            source: self,
+            length,
+            tangent,
+            pending: None,
+        }
+    }
+}
+
+/// An iterator over the lines showing the normals of its source
+pub struct NormalLinesIterator<I, F> {
+    source: I,
+    length: f32,
+    tangent: F,
+    // the tangent line of the last vertex, not streamed yet
+    pending: Option<Line<Vertex>>,
+}
+
+impl<I, F> NormalLinesIterator<I, F> {
+    fn line(&self, from: Vector3<f32>, direction: Vector3<f32>) -> Line<Vertex> {
+        let normal = math::try_normalize(direction)
+            .unwrap_or_else(|| Vector3::new(0., 0., 0.))
+            .into();
+        let to = from + direction * self.length;
+        Line::new(
+            Vertex {
+                pos: from.into(),
+                normal,
+            },
+            Vertex {
+                pos: to.into(),
+                normal,
+            },
+        )
+    }
+}
+
+impl<I, F> Iterator for NormalLinesIterator<I, F>
+where
+    I: Iterator,
+    I::Item: VertexAttributes,
+    F: FnMut(&I::Item) -> Option<Normal>,
+{
+    type Item = Line<Vertex>;
+
+    fn next(&mut self) -> Option<Line<Vertex>> {
+        if let Some(line) = self.pending.take() {
+            return Some(line);
+        }
+        loop {
+            let v = self.source.next()?;
+            let from = Vector3::from(v.position());
+            if let Some(tangent) = (self.tangent)(&v) {
+                self.pending = Some(self.line(from, tangent.into()));
+            }
+            match v.normal() {
+                Some(normal) => return Some(self.line(from, normal.into())),
+                None => {
+                    if let Some(line) = self.pending.take() {
+                        return Some(line);
+                    }
+                }
+            }
+        }
+    }
+}
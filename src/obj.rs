@@ -0,0 +1,86 @@
+//! Export of indexed meshes to the Wavefront OBJ format.
+//!
+//! OBJ faces can have any number of sides, so quads and other polygons
+//! are written as they are instead of being cut into triangles.
+
+use std::io::{self, Write};
+
+use crate::poly::EmitVertices;
+use crate::{Polygon, VertexAttributes};
+
+/// Write `vertices`, and the `polygons` indexing into them, as an OBJ
+/// file. Normals and texture coordinates are written if every vertex has
+/// them.
+pub fn write_obj<W: Write, V: VertexAttributes>(
+    writer: W,
+    vertices: &[V],
+    polygons: &[Polygon<usize>],
+) -> io::Result<()> {
+    write(writer, vertices, None, polygons)
+}
+
+/// Write `vertices` with their texture coordinates `uvs`, one per vertex,
+/// and the `polygons` indexing into them, as an OBJ file.
+pub fn write_obj_with_uvs<W: Write, V: VertexAttributes>(
+    writer: W,
+    vertices: &[V],
+    uvs: &[[f32; 2]],
+    polygons: &[Polygon<usize>],
+) -> io::Result<()> {
+    assert_eq!(uvs.len(), vertices.len());
+    write(writer, vertices, Some(uvs), polygons)
+}
+
+fn write<W: Write, V: VertexAttributes>(
+    writer: W,
+    vertices: &[V],
+    uvs: Option<&[[f32; 2]]>,
+    polygons: &[Polygon<usize>],
+) -> io::Result<()> {
+    let uvs = match uvs {
+        Some(uvs) => Some(uvs.to_vec()),
+        None => vertices.iter().map(|v| v.uv()).collect(),
+    };
+    let normals: Option<Vec<_>> = vertices.iter().map(|v| v.normal()).collect();
+
+    let mut w = io::BufWriter::new(writer);
+    writeln!(w, "# genmesh")?;
+    for v in vertices {
+        let p = v.position();
+        writeln!(w, "v {} {} {}", p.x, p.y, p.z)?;
+    }
+    if let Some(uvs) = &uvs {
+        for uv in uvs {
+            writeln!(w, "vt {} {}", uv[0], uv[1])?;
+        }
+    }
+    if let Some(normals) = &normals {
+        for n in normals {
+            writeln!(w, "vn {} {} {}", n.x, n.y, n.z)?;
+        }
+    }
+
+    for p in polygons {
+        let mut line = String::from("f");
+        let mut result = Ok(());
+        p.clone().emit_vertices(|i| {
+            if i >= vertices.len() {
+                result = Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "vertex index out of range",
+                ));
+            }
+            // OBJ indices start at 1
+            let i = i + 1;
+            line += &match (&uvs, &normals) {
+                (Some(_), Some(_)) => format!(" {}/{}/{}", i, i, i),
+                (Some(_), None) => format!(" {}/{}", i, i),
+                (None, Some(_)) => format!(" {}//{}", i, i),
+                (None, None) => format!(" {}", i),
+            };
+        });
+        result?;
+        writeln!(w, "{}", line)?;
+    }
+    w.flush()
+}
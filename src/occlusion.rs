@@ -0,0 +1,95 @@
+//! Ambient occlusion baked into the vertices of a mesh.
+
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use cgmath::{InnerSpace, Vector3, Zero};
+
+use crate::analytic::orthonormal_basis;
+use crate::bvh::Bvh;
+use crate::math;
+use crate::{Triangle, VertexAttributes};
+
+/// Compute how much of the sky each vertex of an indexed mesh is hidden
+/// from by the mesh itself, for cheap soft shadows in creases and
+/// corners without any lighting at run time.
+///
+/// From every vertex, `samples` rays are cast over the hemisphere around
+/// its normal, more of them close to the normal as with diffuse light,
+/// and any triangle hit within `max_distance` counts as occluding. The
+/// directions follow the same spiral at every vertex, so the result does
+/// not change from one bake to the next. Vertices with no normal use the
+/// average normal of the triangles around them.
+///
+/// Returns, for every vertex, the fraction of the rays that were blocked,
+/// from `0` in the open to `1` when entirely enclosed; lighting is
+/// usually multiplied by one minus it.
+pub fn bake_ao<V>(
+    vertices: &[V],
+    triangles: &[Triangle<usize>],
+    samples: usize,
+    max_distance: f32,
+) -> Vec<f32>
+where
+    V: VertexAttributes,
+{
+    let p = |i: usize| Vector3::from(vertices[i].position());
+    // the average normal of the triangles around each position, whether
+    // or not they share the vertex there
+    let key = |i: usize| math::position_key(vertices[i].position());
+    let mut around: HashMap<[u32; 3], Vector3<f32>> = HashMap::new();
+    for t in triangles {
+        let n = (p(t.y) - p(t.x)).cross(p(t.z) - p(t.x));
+        for i in [t.x, t.y, t.z] {
+            *around.entry(key(i)).or_insert_with(Vector3::zero) += n;
+        }
+    }
+    let around = |i: usize| around.get(&key(i)).cloned().and_then(math::try_normalize);
+    let bvh = Bvh::build(
+        &triangles
+            .iter()
+            .map(|t| Triangle::new(p(t.x).into(), p(t.y).into(), p(t.z).into()))
+            .collect::<Vec<_>>(),
+    );
+    let size = bvh
+        .bounds()
+        .map_or(0., |b| Vector3::from(b.size()).magnitude());
+    // rays start just off the surface, away from all the triangles
+    // around their vertex, so that they neither hit them straight away
+    // nor slip through them in creases
+    let bias = size * 1e-5;
+
+    // cosine weighted directions, on a Fibonacci spiral around +z
+    let golden = PI * (3. - 5f32.sqrt());
+    let directions: Vec<Vector3<f32>> = (0..samples)
+        .map(|k| {
+            let r = ((k as f32 + 0.5) / samples as f32).sqrt();
+            let (s, c) = (k as f32 * golden).sin_cos();
+            Vector3::new(r * c, r * s, (1. - r * r).sqrt())
+        })
+        .collect();
+
+    (0..vertices.len())
+        .map(|i| {
+            let normal = vertices[i]
+                .normal()
+                .and_then(|n| math::try_normalize(Vector3::from(n)))
+                .or_else(|| around(i));
+            let n = match normal {
+                Some(n) if samples > 0 => n,
+                _ => return 0.,
+            };
+            let (u, v) = orthonormal_basis(n);
+            let origin = p(i) + around(i).unwrap_or(n) * bias;
+            let blocked = directions
+                .iter()
+                .filter(|d| {
+                    let ray = (u * d.x + v * d.y + n * d.z) * max_distance;
+                    bvh.raycast(origin.into(), ray.into())
+                        .is_some_and(|hit| hit.distance <= 1.)
+                })
+                .count();
+            blocked as f32 / samples as f32
+        })
+        .collect()
+}
@@ -1,10 +1,13 @@
+use std::f32::consts::SQRT_2;
+use std::ops::Range;
+
 use cgmath::{InnerSpace, Vector3};
 
 use crate::generators::{IndexedPolygon, SharedVertex};
-use crate::{Polygon, Polygon::PolyTri, Triangle, Vertex};
+use crate::{MapVertex, Polygon, Polygon::PolyTri, Triangle, Vertex};
 
 // from Paul Bourke: http://paulbourke.net/geometry/platonic/
-const A: f32 = 1. / (2. * 1.4142135623730951);
+const A: f32 = 1. / (2. * SQRT_2);
 const B: f32 = 0.5;
 
 const VERTICES: [[f32; 3]; 6] = [
@@ -29,13 +32,15 @@ const FACES: [[usize; 3]; 8] = [
 
 /// a platonic octahedron solid
 pub struct Octahedron {
-    i: usize,
+    range: Range<usize>,
 }
 
 impl Octahedron {
     /// create a simple octahedron with normalized vertices
     pub fn new() -> Self {
-        Self { i: 0 }
+        Self {
+            range: 0..FACES.len(),
+        }
     }
 
     fn vert(&self, idx: usize) -> Vertex {
@@ -47,27 +52,36 @@ impl Octahedron {
     }
 }
 
+impl Default for Octahedron {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Iterator for Octahedron {
     type Item = Polygon<Vertex>;
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (FACES.len(), Some(FACES.len()))
+        self.range.size_hint()
     }
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.i == FACES.len() {
-            return None;
-        }
-
-        let face = FACES[self.i];
-        let va = self.vert(face[0]);
-        let vb = self.vert(face[1]);
-        let vc = self.vert(face[2]);
+        self.range
+            .next()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vert(i)))
+    }
+}
 
-        Some(PolyTri(Triangle::new(va, vb, vc)))
+impl DoubleEndedIterator for Octahedron {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range
+            .next_back()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vert(i)))
     }
 }
 
+impl ExactSizeIterator for Octahedron {}
+
 impl SharedVertex<Vertex> for Octahedron {
     fn shared_vertex_count(&self) -> usize {
         VERTICES.len()
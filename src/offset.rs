@@ -0,0 +1,88 @@
+//! Growing and shrinking meshes by moving their surface along its normals.
+
+use std::collections::HashMap;
+
+use cgmath::{InnerSpace, Matrix3, SquareMatrix, Vector3};
+
+use crate::math;
+use crate::{Triangle, Vertex};
+
+// How far a corner may move, in multiples of the distance, so that needle
+// sharp corners do not shoot out into spikes.
+const MAX_STRETCH: f32 = 3.;
+
+/// Move the surface of an indexed triangle mesh `distance` outwards, or
+/// inwards if it is negative, for outlines, shells and collision margins.
+///
+/// Vertices at the same position move together, so flat shaded meshes
+/// with separate vertices for each face stay closed. Each position moves
+/// so that the planes of the triangles around it move by `distance` as
+/// nearly as possible, which keeps the faces of sharp corners, like those
+/// of a cube, flat and parallel to where they were instead of bulging or
+/// folding over each other, and is the same as moving along the normal on
+/// smooth surfaces. The corners too sharp for that are held to three
+/// times the distance. Normals are left as they are, and positions not
+/// used by any triangle do not move.
+///
+/// This does not handle features smaller than the distance: shrinking a
+/// mesh by more than its thickness turns it inside out.
+pub fn offset(vertices: &mut [Vertex], triangles: &[Triangle<usize>], distance: f32) {
+    let mut groups: HashMap<[u32; 3], usize> = HashMap::new();
+    let group: Vec<usize> = vertices
+        .iter()
+        .map(|v| {
+            let next = groups.len();
+            *groups.entry(math::position_key(v.pos)).or_insert(next)
+        })
+        .collect();
+
+    // the planes around each position, weighted by the angle of the
+    // triangles at it, as normal matrices and right hand sides
+    let mut normals = vec![Matrix3::from_value(0.); groups.len()];
+    let mut targets = vec![Vector3::new(0., 0., 0.); groups.len()];
+    let mut sums = vec![Vector3::new(0., 0., 0.); groups.len()];
+    let mut weights = vec![0f32; groups.len()];
+    for t in triangles {
+        let corners = [t.x, t.y, t.z];
+        let p = corners.map(|i| Vector3::from(vertices[i].pos));
+        let n = match math::try_normalize((p[1] - p[0]).cross(p[2] - p[0])) {
+            Some(n) => n,
+            None => continue,
+        };
+        for k in 0..3 {
+            let a = p[(k + 1) % 3] - p[k];
+            let b = p[(k + 2) % 3] - p[k];
+            let w = a.angle(b).0;
+            if !w.is_finite() {
+                continue;
+            }
+            let g = group[corners[k]];
+            normals[g] += math::outer(n, n) * w;
+            targets[g] += n * (w * distance);
+            sums[g] += n * w;
+            weights[g] += w;
+        }
+    }
+
+    let moves: Vec<Option<Vector3<f32>>> = (0..groups.len())
+        .map(|g| {
+            let average = math::try_normalize(sums[g])?;
+            // a little pull towards the average normal settles the
+            // directions along which the planes do not constrain the move
+            let pull = 1e-4 * weights[g];
+            let m = normals[g] + Matrix3::identity() * pull;
+            let d = m.invert()? * (targets[g] + average * (pull * distance));
+            let limit = MAX_STRETCH * distance.abs();
+            Some(if d.magnitude() > limit {
+                d.normalize_to(limit)
+            } else {
+                d
+            })
+        })
+        .collect();
+    for (v, &g) in vertices.iter_mut().zip(group.iter()) {
+        if let Some(d) = moves[g] {
+            v.pos = (Vector3::from(v.pos) + d).into();
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Parallel versions of the generator iterators, built on rayon.
+
+use std::marker::PhantomData;
+
+use rayon::iter::plumbing::{Consumer, ProducerCallback, UnindexedConsumer};
+use rayon::prelude::*;
+
+use crate::generators::{IndexedPolygon, SharedVertex};
+
+/// Parallel counterpart of `SharedVertex::shared_vertex_iter`.
+pub trait ParSharedVertex<V: Send>: SharedVertex<V> + Sync {
+    /// create a parallel iterator that returns each shared vertex that is
+    /// required to build the mesh.
+    fn par_shared_vertices(&self) -> ParSharedVertexIterator<'_, Self, V> {
+        ParSharedVertexIterator {
+            base: self,
+            count: self.shared_vertex_count(),
+            phantom_v: PhantomData,
+        }
+    }
+}
+
+impl<T: SharedVertex<V> + Sync, V: Send> ParSharedVertex<V> for T {}
+
+/// Parallel counterpart of `IndexedPolygon::indexed_polygon_iter`.
+pub trait ParIndexedPolygon<V: Send>: IndexedPolygon<V> + Sync {
+    /// create a parallel iterator that will return a polygon for each face
+    /// in the source mesh
+    fn par_indexed_polygons(&self) -> ParIndexedPolygonIterator<'_, Self, V> {
+        ParIndexedPolygonIterator {
+            base: self,
+            count: self.indexed_polygon_count(),
+            phantom_v: PhantomData,
+        }
+    }
+}
+
+impl<T: IndexedPolygon<V> + Sync, V: Send> ParIndexedPolygon<V> for T {}
+
+/// A parallel iterator that yields the shared vertices of the mesh
+pub struct ParSharedVertexIterator<'a, T: 'a, V> {
+    base: &'a T,
+    count: usize,
+    phantom_v: PhantomData<fn() -> V>,
+}
+
+impl<'a, T: SharedVertex<V> + Sync, V: Send> ParallelIterator
+    for ParSharedVertexIterator<'a, T, V>
+{
+    type Item = V;
+
+    fn drive_unindexed<C: UnindexedConsumer<V>>(self, consumer: C) -> C::Result {
+        self.drive(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.count)
+    }
+}
+
+impl<'a, T: SharedVertex<V> + Sync, V: Send> IndexedParallelIterator
+    for ParSharedVertexIterator<'a, T, V>
+{
+    fn len(&self) -> usize {
+        self.count
+    }
+
+    fn drive<C: Consumer<V>>(self, consumer: C) -> C::Result {
+        let base = self.base;
+        (0..self.count)
+            .into_par_iter()
+            .map(|i| base.shared_vertex(i))
+            .drive(consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<V>>(self, callback: CB) -> CB::Output {
+        let base = self.base;
+        (0..self.count)
+            .into_par_iter()
+            .map(|i| base.shared_vertex(i))
+            .with_producer(callback)
+    }
+}
+
+/// A parallel iterator that yields the indices of the mesh
+pub struct ParIndexedPolygonIterator<'a, T: 'a, V> {
+    base: &'a T,
+    count: usize,
+    phantom_v: PhantomData<fn() -> V>,
+}
+
+impl<'a, T: IndexedPolygon<V> + Sync, V: Send> ParallelIterator
+    for ParIndexedPolygonIterator<'a, T, V>
+{
+    type Item = V;
+
+    fn drive_unindexed<C: UnindexedConsumer<V>>(self, consumer: C) -> C::Result {
+        self.drive(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.count)
+    }
+}
+
+impl<'a, T: IndexedPolygon<V> + Sync, V: Send> IndexedParallelIterator
+    for ParIndexedPolygonIterator<'a, T, V>
+{
+    fn len(&self) -> usize {
+        self.count
+    }
+
+    fn drive<C: Consumer<V>>(self, consumer: C) -> C::Result {
+        let base = self.base;
+        (0..self.count)
+            .into_par_iter()
+            .map(|i| base.indexed_polygon(i))
+            .drive(consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<V>>(self, callback: CB) -> CB::Output {
+        let base = self.base;
+        (0..self.count)
+            .into_par_iter()
+            .map(|i| base.indexed_polygon(i))
+            .with_producer(callback)
+    }
+}
@@ -0,0 +1,201 @@
+//! Triangulation of points and outlines in the plane.
+
+use std::collections::HashSet;
+
+use crate::triangulate::ear_clip;
+use crate::Triangle;
+
+/// Compute the Delaunay triangulation of a set of 2D points, with the
+/// Bowyer-Watson algorithm.
+///
+/// The triangles index into `points` and are wound counter clockwise.
+/// Together they cover the convex hull of the points, and no point lies
+/// inside the circumcircle of any triangle. Repeated points are ignored,
+/// only the first copy is used.
+pub fn triangulate_2d(points: &[[f32; 2]]) -> Vec<Triangle<usize>> {
+    let n = points.len();
+    if n < 3 {
+        return Vec::new();
+    }
+    let mut p: Vec<[f64; 2]> = points.iter().map(|p| [p[0] as f64, p[1] as f64]).collect();
+
+    // a triangle around everything, far enough not to bend the hull
+    let (mut min, mut max) = ([f64::MAX; 2], [f64::MIN; 2]);
+    for q in &p {
+        for i in 0..2 {
+            min[i] = min[i].min(q[i]);
+            max[i] = max[i].max(q[i]);
+        }
+    }
+    let size = (max[0] - min[0]).max(max[1] - min[1]).max(1e-9) * 1e4;
+    let mid = [(min[0] + max[0]) * 0.5, (min[1] + max[1]) * 0.5];
+    p.push([mid[0] - size, mid[1] - size]);
+    p.push([mid[0] + size, mid[1] - size]);
+    p.push([mid[0], mid[1] + size]);
+
+    let mut triangles: Vec<[usize; 3]> = vec![[n, n + 1, n + 2]];
+    let mut seen = HashSet::new();
+    for i in 0..n {
+        if !seen.insert((p[i][0].to_bits(), p[i][1].to_bits())) {
+            continue;
+        }
+
+        let mut boundary: Vec<(usize, usize)> = Vec::new();
+        triangles.retain(|&[a, b, c]| {
+            if !in_circle(p[a], p[b], p[c], p[i]) {
+                return true;
+            }
+            for &(u, v) in &[(a, b), (b, c), (c, a)] {
+                // an edge shared by two removed triangles is inside the cavity
+                match boundary.iter().position(|&e| e == (v, u)) {
+                    Some(k) => {
+                        boundary.swap_remove(k);
+                    }
+                    None => boundary.push((u, v)),
+                }
+            }
+            false
+        });
+        for (u, v) in boundary {
+            triangles.push([u, v, i]);
+        }
+    }
+
+    triangles
+        .into_iter()
+        .filter(|t| t.iter().all(|&v| v < n))
+        .map(|[a, b, c]| Triangle::new(a, b, c))
+        .collect()
+}
+
+/// Triangulate a simple polygon with holes, such as a glyph outline or
+/// the cap of an extrusion.
+///
+/// `outer` is the boundary of the polygon and `holes` the boundaries of
+/// the holes inside of it, in any winding. The triangles index into the
+/// points of `outer` followed by those of each hole in turn, and are wound
+/// counter clockwise. Each hole is joined to the rest of the outline by a
+/// bridge to the nearest vertex it can see, then the whole outline is cut
+/// into triangles by ear clipping.
+pub fn triangulate_polygon<H>(outer: &[[f32; 2]], holes: &[H]) -> Vec<Triangle<usize>>
+where
+    H: AsRef<[[f32; 2]]>,
+{
+    let mut points: Vec<[f32; 2]> = Vec::new();
+    let mut loops: Vec<Vec<usize>> = Vec::new();
+    let mut add = |ring: &[[f32; 2]], ccw: bool| {
+        let start = points.len();
+        points.extend_from_slice(ring);
+        let mut ring: Vec<usize> = (start..points.len()).collect();
+        if (signed_area(&points, &ring) > 0.) != ccw {
+            ring.reverse();
+        }
+        ring
+    };
+    let mut merged = add(outer, true);
+    for hole in holes {
+        let ring = add(hole.as_ref(), false);
+        if ring.len() >= 3 {
+            loops.push(ring);
+        }
+    }
+    if merged.len() < 3 {
+        return Vec::new();
+    }
+
+    // rightmost holes first, their bridges are the least likely to be in
+    // the way of the others
+    let rightmost = |ring: &Vec<usize>| ring.iter().map(|&i| points[i][0]).fold(f32::MIN, f32::max);
+    loops.sort_by(|a, b| rightmost(b).total_cmp(&rightmost(a)));
+
+    while !loops.is_empty() {
+        let hole = loops.remove(0);
+        let m = (0..hole.len())
+            .max_by(|&a, &b| points[hole[a]][0].total_cmp(&points[hole[b]][0]))
+            .unwrap();
+        let pm = points[hole[m]];
+
+        // edges that a bridge must not cross
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        for ring in std::iter::once(&merged)
+            .chain(loops.iter())
+            .chain(Some(&hole))
+        {
+            for k in 0..ring.len() {
+                edges.push((ring[k], ring[(k + 1) % ring.len()]));
+            }
+        }
+
+        let distance = |i: usize| {
+            let q = points[merged[i]];
+            (q[0] - pm[0]).powi(2) + (q[1] - pm[1]).powi(2)
+        };
+        let mut candidates: Vec<usize> = (0..merged.len())
+            // vertices that are already the end of a bridge appear twice
+            .filter(|&i| merged.iter().filter(|&&v| v == merged[i]).count() == 1)
+            .collect();
+        candidates.sort_by(|&a, &b| distance(a).total_cmp(&distance(b)));
+        let bridge = candidates.into_iter().find(|&i| {
+            let (a, b) = (merged[i], hole[m]);
+            edges.iter().all(|&(u, v)| {
+                u == a || v == a || u == b || v == b || !crosses(&points, (a, b), (u, v))
+            })
+        });
+        let p = match bridge {
+            Some(p) => p,
+            // the hole is not inside the outline, leave it out
+            None => continue,
+        };
+
+        // walk to the hole, around it, and back
+        let mut spliced = Vec::with_capacity(merged.len() + hole.len() + 2);
+        spliced.extend_from_slice(&merged[..=p]);
+        for k in 0..=hole.len() {
+            spliced.push(hole[(m + k) % hole.len()]);
+        }
+        spliced.extend_from_slice(&merged[p..]);
+        merged = spliced;
+    }
+
+    let ring: Vec<[f32; 2]> = merged.iter().map(|&i| points[i]).collect();
+    ear_clip(&ring)
+        .into_iter()
+        .map(|[a, b, c]| Triangle::new(merged[a], merged[b], merged[c]))
+        .collect()
+}
+
+fn signed_area(points: &[[f32; 2]], ring: &[usize]) -> f32 {
+    let mut area = 0.;
+    for k in 0..ring.len() {
+        let a = points[ring[k]];
+        let b = points[ring[(k + 1) % ring.len()]];
+        area += a[0] * b[1] - b[0] * a[1];
+    }
+    area * 0.5
+}
+
+// whether the segments `s` and `t` properly cross each other
+fn crosses(points: &[[f32; 2]], s: (usize, usize), t: (usize, usize)) -> bool {
+    let orient = |a: [f32; 2], b: [f32; 2], c: [f32; 2]| {
+        (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+    };
+    let (a, b) = (points[s.0], points[s.1]);
+    let (c, d) = (points[t.0], points[t.1]);
+    let (d1, d2) = (orient(a, b, c), orient(a, b, d));
+    let (d3, d4) = (orient(c, d, a), orient(c, d, b));
+    d1 * d2 <= 0. && d3 * d4 <= 0. && !(d1 == 0. && d2 == 0.)
+}
+
+// whether `d` is strictly inside the circumcircle of the counter clockwise
+// triangle `abc`
+fn in_circle(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> bool {
+    let row = |p: [f64; 2]| {
+        let (x, y) = (p[0] - d[0], p[1] - d[1]);
+        (x, y, x * x + y * y)
+    };
+    let (ax, ay, aa) = row(a);
+    let (bx, by, bb) = row(b);
+    let (cx, cy, cc) = row(c);
+    let det = ax * (by * cc - bb * cy) - ay * (bx * cc - bb * cx) + aa * (bx * cy - by * cx);
+    det > 0.
+}
@@ -3,23 +3,21 @@ use super::{Polygon, Polygon::PolyQuad, Quad};
 use crate::Vertex;
 
 /// Represents a 2D plane with origin of (0, 0), from 1 to -1
+/// unless created with an explicit size.
 #[derive(Clone, Copy)]
 pub struct Plane {
     subdivide_x: usize,
     subdivide_y: usize,
-    x: usize,
-    y: usize,
+    width: f32,
+    height: f32,
+    idx: usize,
+    end: usize,
 }
 
 impl Plane {
     /// create a new cube generator
     pub fn new() -> Plane {
-        Plane {
-            subdivide_x: 1,
-            subdivide_y: 1,
-            x: 0,
-            y: 0,
-        }
+        Plane::subdivide(1, 1)
     }
 
     /// create a subdivided plane. This can be used to build
@@ -27,54 +25,88 @@ impl Plane {
     /// `x` is the number of subdivisions in the x axis
     /// `y` is the number of subdivisions in the y axis
     pub fn subdivide(x: usize, y: usize) -> Plane {
+        Plane::with_size(2., 2., x, y)
+    }
+
+    /// create a subdivided plane of a given physical size, still centered
+    /// on the origin. Unlike scaling a unit plane afterwards, this keeps
+    /// the cells of the grid at the requested size.
+    /// `width` is the extent of the plane along the x axis
+    /// `height` is the extent of the plane along the y axis
+    /// `x` is the number of subdivisions in the x axis
+    /// `y` is the number of subdivisions in the y axis
+    pub fn with_size(width: f32, height: f32, x: usize, y: usize) -> Plane {
         assert!(x > 0 && y > 0);
+        assert!(width > 0. && height > 0.);
         Plane {
             subdivide_x: x,
             subdivide_y: y,
-            x: 0,
-            y: 0,
+            width,
+            height,
+            idx: 0,
+            end: x * y,
         }
     }
 
     fn vert(&self, x: usize, y: usize) -> Vertex {
         let sx = self.subdivide_x as f32;
         let sy = self.subdivide_y as f32;
-        let x = (2. / sx) * x as f32 - 1.;
-        let y = (2. / sy) * y as f32 - 1.;
+        let x = (x as f32 / sx - 0.5) * self.width;
+        let y = (y as f32 / sy - 0.5) * self.height;
         Vertex {
             pos: [x, y, 0.0].into(),
             normal: [0., 0., 1.].into(),
         }
     }
+
+    fn quad(&self, idx: usize) -> Polygon<Vertex> {
+        let (x, y) = (idx % self.subdivide_x, idx / self.subdivide_x);
+        PolyQuad(Quad::new(
+            self.vert(x, y),
+            self.vert(x + 1, y),
+            self.vert(x + 1, y + 1),
+            self.vert(x, y + 1),
+        ))
+    }
+}
+
+impl Default for Plane {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Iterator for Plane {
     type Item = Polygon<Vertex>;
 
     fn next(&mut self) -> Option<Polygon<Vertex>> {
-        if self.x == self.subdivide_x {
-            self.y += 1;
-            if self.y >= self.subdivide_y {
-                return None;
-            }
-            self.x = 0;
+        if self.idx < self.end {
+            self.idx += 1;
+            Some(self.quad(self.idx - 1))
+        } else {
+            None
         }
-
-        let x = self.vert(self.x, self.y);
-        let y = self.vert(self.x + 1, self.y);
-        let z = self.vert(self.x + 1, self.y + 1);
-        let w = self.vert(self.x, self.y + 1);
-        self.x += 1;
-
-        Some(PolyQuad(Quad::new(x, y, z, w)))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let n = (self.subdivide_y - self.y) * self.subdivide_x + (self.subdivide_x - self.x);
+        let n = self.end - self.idx;
         (n, Some(n))
     }
 }
 
+impl DoubleEndedIterator for Plane {
+    fn next_back(&mut self) -> Option<Polygon<Vertex>> {
+        if self.idx < self.end {
+            self.end -= 1;
+            Some(self.quad(self.end))
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Plane {}
+
 impl SharedVertex<Vertex> for Plane {
     fn shared_vertex(&self, idx: usize) -> Vertex {
         let y = idx / (self.subdivide_x + 1);
@@ -120,4 +152,20 @@ fn test_shared_vertex_count() {
     let plane = Plane::subdivide(4, 4);
     assert_eq!(plane.shared_vertex_count(), 25);
     assert_eq!(plane.indexed_polygon_count(), 16);
+
+    let plane = Plane::with_size(4., 1., 8, 2);
+    assert_eq!(plane.shared_vertex_count(), 27);
+    assert_eq!(plane.indexed_polygon_count(), 16);
+}
+
+#[test]
+fn test_sized_vertices() {
+    let plane = Plane::with_size(4., 1., 8, 2);
+    let first = plane.shared_vertex(0);
+    let last = plane.shared_vertex(plane.shared_vertex_count() - 1);
+    assert_eq!(first.pos, [-2., -0.5, 0.].into());
+    assert_eq!(last.pos, [2., 0.5, 0.].into());
+    // cells are square, 0.5 units wide
+    assert_eq!(plane.shared_vertex(1).pos, [-1.5, -0.5, 0.].into());
+    assert_eq!(plane.shared_vertex(9).pos, [-2., 0., 0.].into());
 }
@@ -0,0 +1,246 @@
+//! Export of meshes to the PLY format, with custom vertex properties.
+//!
+//! Unlike the other exporters, the vertex layout is not fixed: any number
+//! of named per-vertex properties can be added, such as colors, texture
+//! coordinates or simulation results, next to the positions and normals.
+
+use std::io::{self, Write};
+
+use crate::attributes::custom_values;
+use crate::poly::EmitVertices;
+use crate::{Polygon, VertexAttributes};
+
+/// The values of one or more vertex properties, of one of the scalar
+/// types of PLY.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlyValues {
+    /// `uchar` values
+    U8(Vec<u8>),
+    /// `int` values
+    I32(Vec<i32>),
+    /// `uint` values
+    U32(Vec<u32>),
+    /// `float` values
+    F32(Vec<f32>),
+    /// `double` values
+    F64(Vec<f64>),
+}
+
+impl PlyValues {
+    fn len(&self) -> usize {
+        match self {
+            PlyValues::U8(v) => v.len(),
+            PlyValues::I32(v) => v.len(),
+            PlyValues::U32(v) => v.len(),
+            PlyValues::F32(v) => v.len(),
+            PlyValues::F64(v) => v.len(),
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            PlyValues::U8(_) => "uchar",
+            PlyValues::I32(_) => "int",
+            PlyValues::U32(_) => "uint",
+            PlyValues::F32(_) => "float",
+            PlyValues::F64(_) => "double",
+        }
+    }
+
+    fn write_ascii<W: Write>(&self, w: &mut W, i: usize) -> io::Result<()> {
+        match self {
+            PlyValues::U8(v) => write!(w, "{}", v[i]),
+            PlyValues::I32(v) => write!(w, "{}", v[i]),
+            PlyValues::U32(v) => write!(w, "{}", v[i]),
+            PlyValues::F32(v) => write!(w, "{}", v[i]),
+            PlyValues::F64(v) => write!(w, "{}", v[i]),
+        }
+    }
+
+    fn write_binary<W: Write>(&self, w: &mut W, i: usize) -> io::Result<()> {
+        match self {
+            PlyValues::U8(v) => w.write_all(&[v[i]]),
+            PlyValues::I32(v) => w.write_all(&v[i].to_le_bytes()),
+            PlyValues::U32(v) => w.write_all(&v[i].to_le_bytes()),
+            PlyValues::F32(v) => w.write_all(&v[i].to_le_bytes()),
+            PlyValues::F64(v) => w.write_all(&v[i].to_le_bytes()),
+        }
+    }
+}
+
+/// Describes the vertices and faces of a PLY file, and writes it.
+#[derive(Clone, Debug)]
+pub struct PlyWriter<'a> {
+    vertex_count: usize,
+    // names of the properties, and their values interleaved per vertex
+    properties: Vec<(Vec<String>, PlyValues)>,
+    faces: &'a [Polygon<usize>],
+    // a problem with the vertices, reported when writing the file
+    error: Option<&'static str>,
+}
+
+impl<'a> PlyWriter<'a> {
+    /// Describe `vertex_count` vertices without any property yet.
+    pub fn new(vertex_count: usize) -> Self {
+        PlyWriter {
+            vertex_count,
+            properties: Vec::new(),
+            faces: &[],
+            error: None,
+        }
+    }
+
+    /// Describe `vertices`, with their positions as the `x`, `y` and `z`
+    /// properties. Normals are added as `nx`, `ny` and `nz`, texture
+    /// coordinates as `s` and `t`, and colors as `red`, `green`, `blue` and
+    /// `alpha`, if every vertex has them. The custom values of the vertices
+    /// follow as `custom_0`, `custom_1` and so on; writing the file fails
+    /// if the vertices have different numbers of them.
+    pub fn from_vertices<V: VertexAttributes>(vertices: &[V]) -> Self {
+        let pos = vertices
+            .iter()
+            .flat_map(|v| {
+                let p = v.position();
+                [p.x, p.y, p.z]
+            })
+            .collect();
+        let mut writer =
+            PlyWriter::new(vertices.len()).properties(&["x", "y", "z"], PlyValues::F32(pos));
+        let normals: Option<Vec<_>> = vertices.iter().map(|v| v.normal()).collect();
+        if let Some(normals) = normals {
+            let normals = normals.iter().flat_map(|n| [n.x, n.y, n.z]).collect();
+            writer = writer.properties(&["nx", "ny", "nz"], PlyValues::F32(normals));
+        }
+        let uvs: Option<Vec<_>> = vertices.iter().map(|v| v.uv()).collect();
+        if let Some(uvs) = uvs {
+            let uvs = uvs.iter().flat_map(|uv| [uv[0], uv[1]]).collect();
+            writer = writer.properties(&["s", "t"], PlyValues::F32(uvs));
+        }
+        let colors: Option<Vec<_>> = vertices.iter().map(|v| v.color()).collect();
+        if let Some(colors) = colors {
+            let colors = colors.iter().flat_map(|c| c.iter().cloned()).collect();
+            writer = writer.properties(&["red", "green", "blue", "alpha"], PlyValues::F32(colors));
+        }
+        match custom_values(vertices) {
+            Some((0, _)) => {}
+            Some((count, custom)) => {
+                let names: Vec<String> = (0..count).map(|k| format!("custom_{}", k)).collect();
+                let names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+                writer = writer.properties(&names, PlyValues::F32(custom));
+            }
+            None => writer.error = Some("the vertices have different numbers of custom values"),
+        }
+        writer
+    }
+
+    /// Add a property with one value per vertex.
+    pub fn property(self, name: &str, values: PlyValues) -> Self {
+        self.properties(&[name], values)
+    }
+
+    /// Add several properties of the same type at once, such as the
+    /// `red`, `green` and `blue` components of a color. `values` holds the
+    /// values of all the properties of the first vertex, followed by those
+    /// of the second vertex, and so on.
+    pub fn properties(mut self, names: &[&str], values: PlyValues) -> Self {
+        assert_eq!(values.len(), names.len() * self.vertex_count);
+        self.properties
+            .push((names.iter().map(|s| s.to_string()).collect(), values));
+        self
+    }
+
+    /// Set the faces, as polygons indexing into the vertices.
+    pub fn faces(mut self, faces: &'a [Polygon<usize>]) -> Self {
+        self.faces = faces;
+        self
+    }
+
+    /// Write the file in the text form of PLY.
+    pub fn write_ascii<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(writer);
+        self.write_header(&mut w, "ascii")?;
+        for i in 0..self.vertex_count {
+            let mut first = true;
+            for (names, values) in &self.properties {
+                for k in 0..names.len() {
+                    if !first {
+                        write!(w, " ")?;
+                    }
+                    first = false;
+                    values.write_ascii(&mut w, i * names.len() + k)?;
+                }
+            }
+            writeln!(w)?;
+        }
+        for f in self.faces {
+            let indices = self.face_indices(f)?;
+            write!(w, "{}", indices.len())?;
+            for i in indices {
+                write!(w, " {}", i)?;
+            }
+            writeln!(w)?;
+        }
+        w.flush()
+    }
+
+    /// Write the file in the little endian binary form of PLY.
+    pub fn write_binary<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(writer);
+        self.write_header(&mut w, "binary_little_endian")?;
+        for i in 0..self.vertex_count {
+            for (names, values) in &self.properties {
+                for k in 0..names.len() {
+                    values.write_binary(&mut w, i * names.len() + k)?;
+                }
+            }
+        }
+        for f in self.faces {
+            let indices = self.face_indices(f)?;
+            w.write_all(&[indices.len() as u8])?;
+            for i in indices {
+                w.write_all(&i.to_le_bytes())?;
+            }
+        }
+        w.flush()
+    }
+
+    fn write_header<W: Write>(&self, w: &mut W, format: &str) -> io::Result<()> {
+        if let Some(error) = self.error {
+            return Err(invalid(error));
+        }
+        writeln!(w, "ply")?;
+        writeln!(w, "format {} 1.0", format)?;
+        writeln!(w, "comment genmesh")?;
+        writeln!(w, "element vertex {}", self.vertex_count)?;
+        for (names, values) in &self.properties {
+            for name in names {
+                writeln!(w, "property {} {}", values.type_name(), name)?;
+            }
+        }
+        writeln!(w, "element face {}", self.faces.len())?;
+        writeln!(w, "property list uchar int vertex_indices")?;
+        writeln!(w, "end_header")
+    }
+
+    fn face_indices(&self, face: &Polygon<usize>) -> io::Result<Vec<i32>> {
+        let mut indices = Vec::new();
+        face.clone().emit_vertices(|i| indices.push(i));
+        if indices.len() > u8::MAX as usize {
+            return Err(invalid("PLY faces have at most 255 vertices"));
+        }
+        indices
+            .into_iter()
+            .map(|i| {
+                if i < self.vertex_count && i <= i32::MAX as usize {
+                    Ok(i as i32)
+                } else {
+                    Err(invalid("vertex index out of range"))
+                }
+            })
+            .collect()
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
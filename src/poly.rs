@@ -1,6 +1,12 @@
-use std::collections::VecDeque;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::iter::FromIterator;
 use std::marker::PhantomData;
 
+use smallvec::SmallVec;
+
+use crate::queue::SmallQueue;
+
 /// Represents a line
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Line<T> {
@@ -13,7 +19,7 @@ pub struct Line<T> {
 impl<T> Line<T> {
     /// Create a new line using point x and y
     pub fn new(x: T, y: T) -> Self {
-        Line { x: x, y: y }
+        Line { x, y }
     }
 }
 
@@ -65,23 +71,70 @@ impl<T> Triangle<T> {
 }
 
 /// An arbitrary-length polygon
+///
+/// Up to eight vertices are stored inline, so only larger polygons
+/// allocate.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct NGon<T> {
     /// the list of vertices of the polygon
-    pub verts: VecDeque<T>,
+    pub verts: SmallVec<[T; 8]>,
 }
 
 impl<T> NGon<T> {
     /// create a new, empty `NGon` polygon
     pub fn new() -> Self {
         NGon {
-            verts: VecDeque::new(),
+            verts: SmallVec::new(),
+        }
+    }
+
+    /// create a new, empty `NGon` polygon with room for `capacity`
+    /// vertices
+    pub fn with_capacity(capacity: usize) -> Self {
+        NGon {
+            verts: SmallVec::with_capacity(capacity),
         }
     }
 
     /// add a vertex to the NGon
     pub fn add_vertex(&mut self, vert: T) {
-        self.verts.push_back(vert);
+        self.verts.push(vert);
+    }
+}
+
+impl<T> FromIterator<T> for NGon<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        NGon {
+            verts: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// A polygon with a number of vertices known at compile time, stored in
+/// an array. Faces of a fixed arity, like the pentagons of a dodecahedron,
+/// can use it to avoid allocating entirely.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ConstNGon<T, const N: usize> {
+    /// the vertices of the polygon
+    pub verts: [T; N],
+}
+
+impl<T, const N: usize> ConstNGon<T, N> {
+    /// create a new polygon from its vertices
+    pub fn new(verts: [T; N]) -> Self {
+        ConstNGon { verts }
+    }
+}
+
+impl<T, const N: usize> From<ConstNGon<T, N>> for NGon<T> {
+    fn from(ngon: ConstNGon<T, N>) -> Self {
+        IntoIterator::into_iter(ngon.verts).collect()
+    }
+}
+
+impl<T> Default for NGon<T> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -156,6 +209,17 @@ impl<T> EmitVertices<T> for NGon<T> {
     }
 }
 
+impl<T, const N: usize> EmitVertices<T> for ConstNGon<T, N> {
+    fn emit_vertices<F>(self, mut emit: F)
+    where
+        F: FnMut(T),
+    {
+        for v in IntoIterator::into_iter(self.verts) {
+            emit(v);
+        }
+    }
+}
+
 impl<T> EmitVertices<T> for Polygon<T> {
     fn emit_vertices<F>(self, emit: F)
     where
@@ -173,22 +237,26 @@ impl<T> EmitVertices<T> for Polygon<T> {
 
 /// An iterator over vertices which have been extracted from a polygon
 pub struct VertexIterator<V> {
-    buffer: VecDeque<V>,
+    buffer: SmallQueue<V>,
 }
 
 impl<T> Polygon<T> {
     /// extract vertices from the polygon directly, without requiring
     /// an iterator of polygons to transform into an iterator of vertices
     pub fn as_vertices(self) -> VertexIterator<T> {
-        let mut buffer = VecDeque::new();
+        let mut buffer = SmallQueue::new();
         self.emit_vertices(|v| buffer.push_back(v));
-        VertexIterator { buffer: buffer }
+        VertexIterator { buffer }
     }
 }
 
 impl<T> Iterator for VertexIterator<T> {
     type Item = T;
 
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.buffer.len(), Some(self.buffer.len()))
+    }
+
     fn next(&mut self) -> Option<T> {
         self.buffer.pop_front()
     }
@@ -206,7 +274,7 @@ impl<V, P: EmitVertices<V>, T: Iterator<Item = P>> Vertices<T, V> for T {
     fn vertices(self) -> VertexStreamIterator<T, V> {
         VertexStreamIterator {
             source: self,
-            buffer: VecDeque::new(),
+            buffer: SmallQueue::new(),
         }
     }
 }
@@ -215,7 +283,7 @@ impl<V, P: EmitVertices<V>, T: Iterator<Item = P>> Vertices<T, V> for T {
 /// an iterator of the individual verticies of those polygons
 pub struct VertexStreamIterator<SRC, V> {
     source: SRC,
-    buffer: VecDeque<V>,
+    buffer: SmallQueue<V>,
 }
 
 impl<V, U: EmitVertices<V>, SRC: Iterator<Item = U>> Iterator for VertexStreamIterator<SRC, V> {
@@ -223,9 +291,8 @@ impl<V, U: EmitVertices<V>, SRC: Iterator<Item = U>> Iterator for VertexStreamIt
 
     fn next(&mut self) -> Option<V> {
         loop {
-            match self.buffer.pop_front() {
-                Some(v) => return Some(v),
-                None => (),
+            if let Some(v) = self.buffer.pop_front() {
+                return Some(v);
             }
 
             match self.source.next() {
@@ -310,6 +377,19 @@ impl<T: Clone, U> MapVertex<T, U> for NGon<T> {
     }
 }
 
+impl<T: Clone, U, const N: usize> MapVertex<T, U> for ConstNGon<T, N> {
+    type Output = ConstNGon<U, N>;
+
+    fn map_vertex<F>(self, map: F) -> ConstNGon<U, N>
+    where
+        F: FnMut(T) -> U,
+    {
+        ConstNGon {
+            verts: self.verts.map(map),
+        }
+    }
+}
+
 impl<T: Clone, U> MapVertex<T, U> for Polygon<T> {
     type Output = Polygon<U>;
 
@@ -366,15 +446,8 @@ pub struct MapToVerticesIter<SRC, T, U, F: FnMut(T) -> U> {
     phantom: PhantomData<(T, U)>,
 }
 
-impl<
-        'a,
-        P,
-        POut: MapVertex<T, U, Output = P>,
-        SRC: Iterator<Item = POut>,
-        T,
-        U,
-        F: FnMut(T) -> U,
-    > Iterator for MapToVerticesIter<SRC, T, U, F>
+impl<P, POut: MapVertex<T, U, Output = P>, SRC: Iterator<Item = POut>, T, U, F: FnMut(T) -> U>
+    Iterator for MapToVerticesIter<SRC, T, U, F>
 {
     type Item = P;
 
@@ -445,6 +518,22 @@ impl<T: Clone> EmitLines for NGon<T> {
     }
 }
 
+impl<T: Clone, const N: usize> EmitLines for ConstNGon<T, N> {
+    type Vertex = T;
+
+    fn emit_lines<E>(self, mut emit: E)
+    where
+        E: FnMut(Line<T>),
+    {
+        for i in 0..N {
+            emit(Line::new(
+                self.verts[i].clone(),
+                self.verts[(i + 1) % N].clone(),
+            ));
+        }
+    }
+}
+
 impl<T: Clone> EmitLines for Polygon<T> {
     type Vertex = T;
 
@@ -481,7 +570,7 @@ where
     fn lines(self) -> LinesIterator<T, V> {
         LinesIterator {
             source: self,
-            buffer: VecDeque::new(),
+            buffer: SmallQueue::new(),
         }
     }
 }
@@ -489,7 +578,7 @@ where
 /// An iterator that turns Polygons into an Iterator of Lines
 pub struct LinesIterator<I, V> {
     source: I,
-    buffer: VecDeque<Line<V>>,
+    buffer: SmallQueue<Line<V>>,
 }
 
 impl<I, P, V> Iterator for LinesIterator<I, V>
@@ -501,14 +590,13 @@ where
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (n, _) = self.source.size_hint();
-        (n, None)
+        (n + self.buffer.len(), None)
     }
 
     fn next(&mut self) -> Option<Line<V>> {
         loop {
-            match self.buffer.pop_front() {
-                Some(v) => return Some(v),
-                None => (),
+            if let Some(v) = self.buffer.pop_front() {
+                return Some(v);
             }
 
             match self.source.next() {
@@ -518,3 +606,61 @@ where
         }
     }
 }
+
+/// Creates an `UniqueLinesIterator` from another Iterator
+pub trait UniqueLines: Sized {
+    /// The type of each point in the lines
+    type Vertex;
+
+    /// Convert the iterator into an iterator of lines, where every
+    /// undirected edge is only emitted the first time it is found.
+    /// Edges shared between two polygons of an indexed mesh are
+    /// usually traversed once in each direction, so this is what
+    /// a wireframe index buffer needs.
+    fn unique_lines(self) -> UniqueLinesIterator<Self, Self::Vertex>;
+}
+
+impl<T, P, V> UniqueLines for T
+where
+    T: Iterator<Item = P>,
+    P: EmitLines<Vertex = V>,
+    V: Clone + Eq + Hash,
+{
+    type Vertex = V;
+
+    fn unique_lines(self) -> UniqueLinesIterator<T, V> {
+        UniqueLinesIterator {
+            source: self.lines(),
+            seen: HashSet::new(),
+        }
+    }
+}
+
+/// An iterator that turns Polygons into an Iterator of distinct Lines
+pub struct UniqueLinesIterator<I, V> {
+    source: LinesIterator<I, V>,
+    seen: HashSet<Line<V>>,
+}
+
+impl<I, P, V> Iterator for UniqueLinesIterator<I, V>
+where
+    I: Iterator<Item = P>,
+    P: EmitLines<Vertex = V>,
+    V: Clone + Eq + Hash,
+{
+    type Item = Line<V>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
+    fn next(&mut self) -> Option<Line<V>> {
+        for line in &mut self.source {
+            let flipped = Line::new(line.y.clone(), line.x.clone());
+            if !self.seen.contains(&flipped) && self.seen.insert(line.clone()) {
+                return Some(line);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,442 @@
+//! Progressive meshes: a triangle mesh together with the sequence of edge
+//! collapses that simplifies it, from which any intermediate level of detail
+//! can be rebuilt.
+//!
+//! Collapses are half-edge collapses: vertex `from` is merged into vertex `to`,
+//! which keeps its position. The vertex buffer is therefore the same for every
+//! level of detail, only the index buffer changes.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::io::{self, Read, Write};
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::math;
+use crate::{Position, Triangle};
+
+/// A single half-edge collapse, merging vertex `from` into vertex `to`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EdgeCollapse {
+    /// The vertex that disappears
+    pub from: usize,
+    /// The vertex that `from` is merged into
+    pub to: usize,
+}
+
+/// A triangle mesh and the ordered record of the collapses simplifying it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProgressiveMesh {
+    triangles: Vec<Triangle<usize>>,
+    collapses: Vec<EdgeCollapse>,
+}
+
+impl ProgressiveMesh {
+    /// Create a progressive mesh from full resolution `triangles` and a
+    /// previously recorded list of `collapses`, for example one loaded with
+    /// `read_collapses`.
+    ///
+    /// Panics if a collapse merges a vertex the triangles do not use, or
+    /// one an earlier collapse already merged.
+    pub fn new(triangles: Vec<Triangle<usize>>, collapses: Vec<EdgeCollapse>) -> Self {
+        let mesh = ProgressiveMesh {
+            triangles,
+            collapses,
+        };
+        if let Err(message) = check_collapses(mesh.vertex_count(), &mesh.collapses) {
+            panic!("{}", message);
+        }
+        mesh
+    }
+
+    /// Build a progressive mesh by repeatedly collapsing the shortest edge of
+    /// the mesh, as long as doing so keeps the mesh manifold, does not change
+    /// the outline of its open boundaries and does not flip any triangle.
+    pub fn build(positions: &[Position], triangles: Vec<Triangle<usize>>) -> Self {
+        let mut collapser = Collapser::new(positions, &triangles);
+        let collapses = collapser.run(
+            |from, to| (Vector3::from(positions[from]) - Vector3::from(positions[to])).magnitude(),
+            |_, _| {},
+            0,
+            f32::INFINITY,
+        );
+        ProgressiveMesh::new(triangles, collapses)
+    }
+
+    /// The full resolution triangles.
+    pub fn triangles(&self) -> &[Triangle<usize>] {
+        &self.triangles
+    }
+
+    /// The recorded collapses, in the order they are applied.
+    pub fn collapses(&self) -> &[EdgeCollapse] {
+        &self.collapses
+    }
+
+    /// Rebuild the triangles of the mesh once the first `collapses` collapses
+    /// of the record have been applied. `0` gives back the full resolution
+    /// mesh, `self.collapses().len()` the coarsest one.
+    pub fn triangles_at(&self, collapses: usize) -> Vec<Triangle<usize>> {
+        let collapses = &self.collapses[..collapses.min(self.collapses.len())];
+        let remap = resolve_collapses(self.vertex_count(), collapses);
+        self.triangles
+            .iter()
+            .map(|t| Triangle::new(remap[t.x], remap[t.y], remap[t.z]))
+            .filter(|t| t.x != t.y && t.y != t.z && t.z != t.x)
+            .collect()
+    }
+
+    /// Rebuild the triangles of the most detailed level that has no more
+    /// than `count` triangles, or of the coarsest level if none does.
+    pub fn triangles_for_count(&self, count: usize) -> Vec<Triangle<usize>> {
+        // collapses only ever remove triangles, so the levels are sorted
+        let (mut lo, mut hi) = (0, self.collapses.len());
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if self.triangles_at(mid).len() <= count {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        self.triangles_at(lo)
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.triangles
+            .iter()
+            .map(|t| t.x.max(t.y).max(t.z) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Serialize the collapse record as a little-endian binary blob.
+    pub fn write_collapses<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&(self.collapses.len() as u32).to_le_bytes())?;
+        for c in &self.collapses {
+            writer.write_all(&(c.from as u32).to_le_bytes())?;
+            writer.write_all(&(c.to as u32).to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Read a collapse record written by `write_collapses`, for a mesh of
+    /// `vertex_count` vertices. Records merging vertices the mesh does not
+    /// have, or vertices already merged by an earlier collapse, are
+    /// rejected as invalid data.
+    pub fn read_collapses<R: Read>(
+        mut reader: R,
+        vertex_count: usize,
+    ) -> io::Result<Vec<EdgeCollapse>> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a collapse record",
+            ));
+        }
+        let count = read_u32(&mut reader)? as usize;
+        let mut collapses = Vec::with_capacity(count.min(1 << 20));
+        for _ in 0..count {
+            let from = read_u32(&mut reader)? as usize;
+            let to = read_u32(&mut reader)? as usize;
+            collapses.push(EdgeCollapse { from, to });
+        }
+        check_collapses(vertex_count, &collapses)
+            .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))?;
+        Ok(collapses)
+    }
+}
+
+const MAGIC: &[u8; 4] = b"GMPM";
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+// Check that every collapse merges a vertex that is still there into
+// another one still there, so that the chains of merges always end.
+fn check_collapses(vertex_count: usize, collapses: &[EdgeCollapse]) -> Result<(), &'static str> {
+    let mut merged = vec![false; vertex_count];
+    for c in collapses {
+        if c.from >= vertex_count || c.to >= vertex_count {
+            return Err("vertex index out of range");
+        }
+        if c.from == c.to {
+            return Err("collapse of a vertex into itself");
+        }
+        if merged[c.from] || merged[c.to] {
+            return Err("collapse of an already merged vertex");
+        }
+        merged[c.from] = true;
+    }
+    Ok(())
+}
+
+/// Compute, for every vertex, the vertex it ends up merged into once all
+/// of `collapses` are applied.
+pub(crate) fn resolve_collapses(vertex_count: usize, collapses: &[EdgeCollapse]) -> Vec<usize> {
+    let mut remap: Vec<usize> = (0..vertex_count).collect();
+    for c in collapses {
+        remap[c.from] = c.to;
+    }
+    // later collapses may move the target of an earlier one, follow the chains
+    for i in 0..vertex_count {
+        let mut v = i;
+        while remap[v] != v {
+            v = remap[v];
+        }
+        remap[i] = v;
+    }
+    remap
+}
+
+#[derive(PartialEq)]
+struct Candidate {
+    cost: f32,
+    from: usize,
+    to: usize,
+    stamp: (u32, u32),
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    // reversed, so that the `BinaryHeap` pops the cheapest collapse first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .partial_cmp(&self.cost)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| (other.from, other.to).cmp(&(self.from, self.to)))
+    }
+}
+
+/// Greedy half-edge collapse engine, shared by the progressive mesh builder
+/// and the simplification pass.
+pub(crate) struct Collapser<'a> {
+    positions: &'a [Position],
+    faces: Vec<Option<[usize; 3]>>,
+    vertex_faces: Vec<Vec<usize>>,
+    stamps: Vec<u32>,
+    alive: usize,
+}
+
+impl<'a> Collapser<'a> {
+    pub(crate) fn new(positions: &'a [Position], triangles: &[Triangle<usize>]) -> Self {
+        let mut vertex_faces = vec![Vec::new(); positions.len()];
+        let mut faces = Vec::with_capacity(triangles.len());
+        for (i, t) in triangles.iter().enumerate() {
+            if t.x == t.y || t.y == t.z || t.z == t.x {
+                faces.push(None);
+                continue;
+            }
+            for &v in &[t.x, t.y, t.z] {
+                vertex_faces[v].push(i);
+            }
+            faces.push(Some([t.x, t.y, t.z]));
+        }
+        let alive = faces.iter().filter(|f| f.is_some()).count();
+        Collapser {
+            positions,
+            faces,
+            vertex_faces,
+            stamps: vec![0; positions.len()],
+            alive,
+        }
+    }
+
+    fn neighbors(&self, v: usize) -> Vec<usize> {
+        let mut result: Vec<usize> = self.vertex_faces[v]
+            .iter()
+            .filter_map(|&f| self.faces[f])
+            .flat_map(|f| f.to_vec())
+            .filter(|&n| n != v)
+            .collect();
+        result.sort_unstable();
+        result.dedup();
+        result
+    }
+
+    fn edge_faces(&self, a: usize, b: usize) -> usize {
+        self.vertex_faces[a]
+            .iter()
+            .filter_map(|&f| self.faces[f])
+            .filter(|f| f.contains(&b))
+            .count()
+    }
+
+    fn boundary_neighbors(&self, v: usize) -> Vec<usize> {
+        self.neighbors(v)
+            .into_iter()
+            .filter(|&n| self.edge_faces(v, n) == 1)
+            .collect()
+    }
+
+    fn normal(&self, f: [usize; 3]) -> Vector3<f32> {
+        let a = Vector3::from(self.positions[f[0]]);
+        let b = Vector3::from(self.positions[f[1]]);
+        let c = Vector3::from(self.positions[f[2]]);
+        (b - a).cross(c - a)
+    }
+
+    fn can_collapse(&self, from: usize, to: usize) -> bool {
+        let shared = self.edge_faces(from, to);
+        if shared == 0 || shared > 2 {
+            return false;
+        }
+
+        // link condition: the only vertices adjacent to both ends of the edge
+        // are the opposite corners of the triangles using it
+        let nf = self.neighbors(from);
+        let nt = self.neighbors(to);
+        let common = nf.iter().filter(|v| nt.binary_search(v).is_ok()).count();
+        if common != shared {
+            return false;
+        }
+
+        // keep the outline of the boundaries: a boundary vertex may only
+        // slide along a straight stretch of the boundary
+        let boundary = self.boundary_neighbors(from);
+        if !boundary.is_empty() {
+            if shared != 1 || boundary.len() != 2 {
+                return false;
+            }
+            let p = |i: usize| Vector3::from(self.positions[i]);
+            if !math::is_degenerate(p(from), p(boundary[0]), p(boundary[1])) {
+                return false;
+            }
+        }
+        // never collapse the last triangles of a tiny closed component
+        if shared == 2 && nf.len() <= 3 && nt.len() <= 3 {
+            return false;
+        }
+
+        // the triangles moving with `from` must not flip over
+        for &fi in &self.vertex_faces[from] {
+            let f = match self.faces[fi] {
+                Some(f) if !f.contains(&to) => f,
+                _ => continue,
+            };
+            let before = self.normal(f);
+            let mut moved = f;
+            for v in moved.iter_mut() {
+                if *v == from {
+                    *v = to;
+                }
+            }
+            let after = self.normal(moved);
+            if after.magnitude2() == 0. || before.dot(after) <= 0. {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn collapse(&mut self, from: usize, to: usize) {
+        let faces = std::mem::take(&mut self.vertex_faces[from]);
+        for fi in faces {
+            let f = match self.faces[fi] {
+                Some(f) => f,
+                None => continue,
+            };
+            if f.contains(&to) {
+                self.faces[fi] = None;
+                self.alive -= 1;
+            } else {
+                let mut moved = f;
+                for v in moved.iter_mut() {
+                    if *v == from {
+                        *v = to;
+                    }
+                }
+                self.faces[fi] = Some(moved);
+                self.vertex_faces[to].push(fi);
+            }
+        }
+        let faces = &self.faces;
+        self.vertex_faces[to].retain(|&f| faces[f].is_some());
+        self.stamps[from] += 1;
+        self.stamps[to] += 1;
+    }
+
+    /// Collapse edges in order of increasing `cost(from, to)` until at most
+    /// `target` triangles are left, or no collapse is cheaper than `max_cost`.
+    /// `collapsed` is called after every collapse, so callers can update
+    /// their own per-vertex data before neighboring costs are recomputed.
+    pub(crate) fn run<C, A>(
+        &mut self,
+        mut cost: C,
+        mut collapsed: A,
+        target: usize,
+        max_cost: f32,
+    ) -> Vec<EdgeCollapse>
+    where
+        C: FnMut(usize, usize) -> f32,
+        A: FnMut(usize, usize),
+    {
+        let mut heap = BinaryHeap::new();
+        for v in 0..self.positions.len() {
+            self.push_candidates(v, &mut heap, &mut cost);
+        }
+
+        let mut record = Vec::new();
+        while self.alive > target {
+            let c = match heap.pop() {
+                Some(c) => c,
+                None => break,
+            };
+            if c.cost > max_cost {
+                break;
+            }
+            if c.stamp != (self.stamps[c.from], self.stamps[c.to])
+                || !self.can_collapse(c.from, c.to)
+            {
+                continue;
+            }
+
+            self.collapse(c.from, c.to);
+            collapsed(c.from, c.to);
+            record.push(EdgeCollapse {
+                from: c.from,
+                to: c.to,
+            });
+
+            let mut touched = self.neighbors(c.to);
+            touched.push(c.to);
+            for &v in &touched {
+                self.stamps[v] += 1;
+            }
+            for v in touched {
+                self.push_candidates(v, &mut heap, &mut cost);
+            }
+        }
+        record
+    }
+
+    fn push_candidates<C>(&self, v: usize, heap: &mut BinaryHeap<Candidate>, cost: &mut C)
+    where
+        C: FnMut(usize, usize) -> f32,
+    {
+        for n in self.neighbors(v) {
+            for &(from, to) in &[(v, n), (n, v)] {
+                heap.push(Candidate {
+                    cost: cost(from, to),
+                    from,
+                    to,
+                    stamp: (self.stamps[from], self.stamps[to]),
+                });
+            }
+        }
+    }
+}
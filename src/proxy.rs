@@ -0,0 +1,77 @@
+//! Simple collision and debug proxies fitted to a mesh, and the generators
+//! to draw them with.
+
+use cgmath::Vector3;
+
+use crate::analytic::{orthonormal_basis, Capsule, Sphere};
+use crate::generators::SphereUv;
+use crate::math;
+use crate::{MapToVertices, Polygon, Position, Vertex};
+
+impl Sphere {
+    /// Build a mesh of the sphere from a `SphereUv` generator.
+    /// `u` and `v` are the resolution of the `SphereUv`.
+    pub fn mesh(&self, u: usize, v: usize) -> impl Iterator<Item = Polygon<Vertex>> {
+        let center = Vector3::from(self.center);
+        let radius = self.radius;
+        SphereUv::new(u, v).vertex(move |v| Vertex {
+            pos: (center + Vector3::from(v.pos) * radius).into(),
+            normal: v.normal,
+        })
+    }
+}
+
+impl Capsule {
+    /// Build a mesh of the capsule from a `SphereUv` generator, whose two
+    /// hemispheres are pulled apart to the ends of the capsule's segment.
+    ///
+    /// `u` and `v` are the resolution of the `SphereUv`; `v` is rounded up to
+    /// an odd number so that a band of quads spans the cylindrical part.
+    pub fn mesh(&self, u: usize, v: usize) -> impl Iterator<Item = Polygon<Vertex>> {
+        let v = v | 1;
+        let a = Vector3::from(self.a);
+        let b = Vector3::from(self.b);
+        let center = (a + b) * 0.5;
+        let half = (b - a) * 0.5;
+        let axis = math::try_normalize(half).unwrap_or_else(Vector3::unit_z);
+        let (x, y) = orthonormal_basis(axis);
+        let radius = self.radius;
+
+        let rotate = move |p: Position| x * p.x + y * p.y + axis * p.z;
+        SphereUv::new(u, v.max(3)).vertex(move |v| {
+            let side = if v.pos.z > 0. { half } else { -half };
+            Vertex {
+                pos: (center + side + rotate(v.pos) * radius).into(),
+                normal: rotate(v.normal).into(),
+            }
+        })
+    }
+}
+
+/// Compute the minimal sphere enclosing `points`, and a mesh of it with the
+/// given `SphereUv` resolution.
+/// Returns `None` if there are no points.
+pub fn sphere_proxy<I>(
+    points: I,
+    u: usize,
+    v: usize,
+) -> Option<(Sphere, impl Iterator<Item = Polygon<Vertex>>)>
+where
+    I: IntoIterator<Item = Position>,
+{
+    Sphere::enclosing(points).map(|s| (s, s.mesh(u, v)))
+}
+
+/// Fit a capsule around `points`, and build a mesh of it with the given
+/// `SphereUv` resolution.
+/// Returns `None` if there are no points.
+pub fn capsule_proxy<I>(
+    points: I,
+    u: usize,
+    v: usize,
+) -> Option<(Capsule, impl Iterator<Item = Polygon<Vertex>>)>
+where
+    I: IntoIterator<Item = Position>,
+{
+    Capsule::fit(points).map(|c| (c, c.mesh(u, v)))
+}
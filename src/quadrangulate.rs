@@ -0,0 +1,92 @@
+//! Pairing the triangles of a mesh back into quads.
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::math;
+use crate::Polygon::{PolyQuad, PolyTri};
+use crate::{Line, Neighbors, Polygon, Position, Quad, Triangle};
+
+/// Merge pairs of neighboring triangles into quads, for subdivision
+/// schemes and exporters that work best on quads.
+///
+/// Two triangles are merged if they share an edge that no other triangle
+/// uses, go along it in opposite directions, are no more than `max_angle`
+/// radians from being coplanar, and make a convex quad. The pairs are
+/// picked greedily, starting with those making the squarest quads, so a
+/// triangulated grid turns back into its original quads.
+///
+/// Each quad takes the place of the first of its two triangles, the
+/// triangles left alone keep theirs.
+pub fn quadrangulate(
+    positions: &[Position],
+    triangles: &[Triangle<usize>],
+    max_angle: f32,
+) -> Vec<Polygon<usize>> {
+    let neighbors = Neighbors::new(Vec::<()>::new(), triangles.to_vec());
+    let min_cos = max_angle.cos();
+    let p = |i: usize| Vector3::from(positions[i]);
+    let normal =
+        |t: &Triangle<usize>| math::try_normalize((p(t.y) - p(t.x)).cross(p(t.z) - p(t.x)));
+
+    // every pair that could be merged, with how far the quad is from
+    // having square corners
+    let mut candidates: Vec<(f32, usize, usize, Quad<usize>)> = Vec::new();
+    for (i, t) in triangles.iter().enumerate() {
+        for (a, b, c) in [(t.x, t.y, t.z), (t.y, t.z, t.x), (t.z, t.x, t.y)] {
+            let shared = neighbors.faces_sharing_edge(&Line::new(a, b));
+            if shared.len() != 2 {
+                continue;
+            }
+            let j = if shared[0] == i { shared[1] } else { shared[0] };
+            if j <= i {
+                continue;
+            }
+            let u = &triangles[j];
+            // the corner of the other triangle across the edge, which has
+            // to go along it from `b` to `a`
+            let d = match (u.x, u.y, u.z) {
+                (x, y, z) if (x, y) == (b, a) => z,
+                (x, y, z) if (y, z) == (b, a) => x,
+                (x, y, z) if (z, x) == (b, a) => y,
+                _ => continue,
+            };
+            let (n1, n2) = match (normal(t), normal(u)) {
+                (Some(n1), Some(n2)) => (n1, n2),
+                _ => continue,
+            };
+            if n1.dot(n2) < min_cos {
+                continue;
+            }
+            let quad = Quad::new(b, c, a, d);
+            let corners = [quad.x, quad.y, quad.z, quad.w];
+            let n = n1 + n2;
+            let mut convex = true;
+            let mut squareness: f32 = 0.;
+            for k in 0..4 {
+                let prev = p(corners[(k + 3) % 4]) - p(corners[k]);
+                let next = p(corners[(k + 1) % 4]) - p(corners[k]);
+                convex &= next.cross(prev).dot(n) > 0.;
+                let cos = prev.normalize().dot(next.normalize());
+                squareness = squareness.max(cos.abs());
+            }
+            if convex {
+                candidates.push((squareness, i, j, quad));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut merged: Vec<Option<Polygon<usize>>> =
+        triangles.iter().map(|t| Some(PolyTri(t.clone()))).collect();
+    let mut used = vec![false; triangles.len()];
+    for (_, i, j, quad) in candidates {
+        if used[i] || used[j] {
+            continue;
+        }
+        used[i] = true;
+        used[j] = true;
+        merged[i] = Some(PolyQuad(quad));
+        merged[j] = None;
+    }
+    merged.into_iter().flatten().collect()
+}
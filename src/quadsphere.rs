@@ -0,0 +1,110 @@
+//! Spherified cube
+
+use std::marker::PhantomData;
+
+use crate::generators::{Cube, IndexedPolygon, PolygonGroup, SharedVertex};
+use crate::{MapVertex, Polygon, TexturedVertex, Vertex};
+
+/// Sphere with radius 1, centered at (0., 0., 0.), made by projecting a
+/// subdivided cube onto it.
+///
+/// The faces are quads of nearly the same size all over the sphere, in six
+/// patches which are the faces of the cube. Each patch is a polygon group
+/// with its own vertices, in the order of `CubeFace`, so that a textured
+/// quad sphere maps the whole texture onto each patch like a cube map.
+#[derive(Clone)]
+pub struct QuadSphere<V = Vertex> {
+    cube: Cube<TexturedVertex>,
+    phantom_v: PhantomData<V>,
+}
+
+impl QuadSphere {
+    /// Create a quad sphere, with each of the six patches cut into
+    /// `n` by `n` quads.
+    pub fn new(n: usize) -> Self {
+        QuadSphere {
+            cube: Cube::subdivided(n, n, n).with_uvs(),
+            phantom_v: PhantomData,
+        }
+    }
+
+    /// Emit texture coordinates along with the vertices.
+    pub fn with_uvs(self) -> QuadSphere<TexturedVertex> {
+        QuadSphere {
+            cube: self.cube,
+            phantom_v: PhantomData,
+        }
+    }
+}
+
+// Move a vertex of the cube onto the sphere. Rather than normalizing the
+// position, which crowds the quads at the middle of the faces, this uses
+// the mapping which keeps them evenly spread:
+// http://mathproofs.blogspot.com/2005/07/mapping-cube-to-sphere.html
+fn spherify<V: From<TexturedVertex>>(v: TexturedVertex) -> V {
+    // the cube has its corners on the unit sphere
+    let s = 3f32.sqrt();
+    let (x, y, z) = (v.pos.x * s, v.pos.y * s, v.pos.z * s);
+    let (x2, y2, z2) = (x * x, y * y, z * z);
+    let p = [
+        x * (1. - y2 / 2. - z2 / 2. + y2 * z2 / 3.).max(0.).sqrt(),
+        y * (1. - z2 / 2. - x2 / 2. + z2 * x2 / 3.).max(0.).sqrt(),
+        z * (1. - x2 / 2. - y2 / 2. + x2 * y2 / 3.).max(0.).sqrt(),
+    ];
+    TexturedVertex {
+        pos: p.into(),
+        normal: p.into(),
+        uv: v.uv,
+    }
+    .into()
+}
+
+impl<V: From<TexturedVertex>> Iterator for QuadSphere<V> {
+    type Item = Polygon<V>;
+
+    fn next(&mut self) -> Option<Polygon<V>> {
+        self.cube.next().map(|p| p.map_vertex(spherify))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cube.size_hint()
+    }
+}
+
+impl<V: From<TexturedVertex>> DoubleEndedIterator for QuadSphere<V> {
+    fn next_back(&mut self) -> Option<Polygon<V>> {
+        self.cube.next_back().map(|p| p.map_vertex(spherify))
+    }
+}
+
+impl<V: From<TexturedVertex>> ExactSizeIterator for QuadSphere<V> {}
+
+impl<V: From<TexturedVertex>> SharedVertex<V> for QuadSphere<V> {
+    fn shared_vertex(&self, idx: usize) -> V {
+        spherify(self.cube.shared_vertex(idx))
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.cube.shared_vertex_count()
+    }
+}
+
+impl<V> IndexedPolygon<Polygon<usize>> for QuadSphere<V> {
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        self.cube.indexed_polygon(idx)
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.cube.indexed_polygon_count()
+    }
+}
+
+impl<V> PolygonGroup for QuadSphere<V> {
+    fn polygon_group(&self, idx: usize) -> u32 {
+        self.cube.polygon_group(idx)
+    }
+
+    fn polygon_group_count(&self) -> u32 {
+        6
+    }
+}
@@ -0,0 +1,52 @@
+//! A first in, first out queue that avoids the heap for small polygons.
+
+use std::collections::VecDeque;
+
+// enough for the vertices or lines of a quad, or the triangles of a pentagon
+const INLINE: usize = 4;
+
+/// Queue storing its first few items inline. Triangles and quads never
+/// break down into more than `INLINE` items, so streams of them run
+/// without allocating; larger polygons spill over onto the heap.
+pub(crate) struct SmallQueue<T> {
+    inline: [Option<T>; INLINE],
+    head: usize,
+    len: usize,
+    // only used once the inline slots are full, and drained after them
+    spill: VecDeque<T>,
+}
+
+impl<T> SmallQueue<T> {
+    pub(crate) fn new() -> Self {
+        SmallQueue {
+            inline: [None, None, None, None],
+            head: 0,
+            len: 0,
+            spill: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len + self.spill.len()
+    }
+
+    pub(crate) fn push_back(&mut self, value: T) {
+        if self.len < INLINE && self.spill.is_empty() {
+            self.inline[(self.head + self.len) % INLINE] = Some(value);
+            self.len += 1;
+        } else {
+            self.spill.push_back(value);
+        }
+    }
+
+    pub(crate) fn pop_front(&mut self) -> Option<T> {
+        if self.len > 0 {
+            let value = self.inline[self.head].take();
+            self.head = (self.head + 1) % INLINE;
+            self.len -= 1;
+            value
+        } else {
+            self.spill.pop_front()
+        }
+    }
+}
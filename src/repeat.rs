@@ -0,0 +1,122 @@
+//! Repeating a stream of polygons, for fences, stairs, propellers and
+//! gear teeth built from a single piece.
+
+use std::f32::consts::PI;
+
+use cgmath::{Matrix4, Rad, SquareMatrix};
+use mint::ColumnMatrix4;
+
+use crate::math::{self, VertexTransform};
+use crate::{FlipWinding, MapVertex, Vertex};
+
+/// Repeats a stream of polygons, each copy moved one step further than
+/// the one before.
+pub trait Repeat: Sized {
+    /// Stream the polygons `count` times: as they are, then moved by
+    /// `step`, then moved by `step` twice, and so on.
+    ///
+    /// Normals go through the inverse transpose of each transform, and
+    /// the copies that end up mirrored have their winding reversed so
+    /// that they keep facing out.
+    fn array<M>(self, count: usize, step: M) -> RepeatIterator<Self>
+    where
+        M: Into<ColumnMatrix4<f32>>;
+
+    /// Stream the polygons `count` times, spread evenly around the full
+    /// turn about `axis`, which goes through the origin. To spread them
+    /// over part of a turn, use `array` with a rotation as the step.
+    /// Panics if the axis is zero.
+    fn radial_array<A>(self, count: usize, axis: A) -> RepeatIterator<Self>
+    where
+        A: Into<mint::Vector3<f32>>;
+}
+
+impl<I, P> Repeat for I
+where
+    I: Iterator<Item = P> + Clone,
+    P: FlipWinding + MapVertex<Vertex, Vertex, Output = P>,
+{
+    fn array<M>(self, count: usize, step: M) -> RepeatIterator<I>
+    where
+        M: Into<ColumnMatrix4<f32>>,
+    {
+        RepeatIterator {
+            current: self.clone(),
+            source: self,
+            remaining: count,
+            step: Matrix4::from(step.into()),
+            matrix: Matrix4::identity(),
+            transform: VertexTransform::new(Matrix4::identity()),
+        }
+    }
+
+    fn radial_array<A>(self, count: usize, axis: A) -> RepeatIterator<I>
+    where
+        A: Into<mint::Vector3<f32>>,
+    {
+        let axis = math::try_normalize(axis.into().into())
+            .expect("the axis of a radial array must not be zero");
+        let angle = Rad(2. * PI / count.max(1) as f32);
+        self.array(count, Matrix4::from_axis_angle(axis, angle))
+    }
+}
+
+/// An iterator over the copies of the polygons of its source
+#[derive(Clone)]
+pub struct RepeatIterator<I> {
+    source: I,
+    current: I,
+    // the copies left, including the current one
+    remaining: usize,
+    step: Matrix4<f32>,
+    matrix: Matrix4<f32>,
+    transform: VertexTransform,
+}
+
+impl<I, P> Iterator for RepeatIterator<I>
+where
+    I: Iterator<Item = P> + Clone,
+    P: FlipWinding + MapVertex<Vertex, Vertex, Output = P>,
+{
+    type Item = P;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remaining == 0 {
+            return (0, Some(0));
+        }
+        let (lo, hi) = self.current.size_hint();
+        let (all_lo, all_hi) = self.source.size_hint();
+        let copies = self.remaining - 1;
+        (
+            lo.saturating_add(all_lo.saturating_mul(copies)),
+            hi.zip(all_hi)
+                .and_then(|(hi, all)| all.checked_mul(copies)?.checked_add(hi)),
+        )
+    }
+
+    fn next(&mut self) -> Option<P> {
+        while self.remaining > 0 {
+            if let Some(p) = self.current.next() {
+                let transform = &self.transform;
+                let p = p.map_vertex(|v| transform.apply(v));
+                return Some(if transform.flips_winding() {
+                    p.flip_winding()
+                } else {
+                    p
+                });
+            }
+            self.remaining -= 1;
+            self.matrix = self.step * self.matrix;
+            self.transform = VertexTransform::new(self.matrix);
+            self.current = self.source.clone();
+        }
+        None
+    }
+}
+
+impl<I, P> ExactSizeIterator for RepeatIterator<I>
+where
+    I: ExactSizeIterator<Item = P> + Clone,
+    P: FlipWinding + MapVertex<Vertex, Vertex, Output = P>,
+{
+}
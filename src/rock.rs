@@ -0,0 +1,130 @@
+//! Rocks and asteroids: lumpy spheres
+
+use std::ops::Range;
+
+use cgmath::Vector3;
+
+use crate::generators::{IcoSphere, IndexedPolygon, SharedVertex};
+use crate::noise::{Basis, Fbm};
+use crate::{recompute_normals, MapVertex, NormalFallback, Polygon, Triangle, Triangulate, Vertex};
+
+/// How lumpy a `Rock` is.
+///
+/// The surface is pushed in and out by `Fbm` of `octaves` layers of value
+/// noise, each twice as fine as the one before and `persistence` times as
+/// strong.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Roughness {
+    /// The largest distance the surface moves in or out, as a fraction
+    /// of the radius
+    pub amplitude: f32,
+    /// The number of bumps of the coarsest layer across the radius
+    pub frequency: f32,
+    /// The number of layers of noise
+    pub octaves: usize,
+    /// The strength of each layer relative to the one before
+    pub persistence: f32,
+}
+
+impl Default for Roughness {
+    fn default() -> Self {
+        Roughness {
+            amplitude: 0.3,
+            frequency: 1.5,
+            octaves: 4,
+            persistence: 0.5,
+        }
+    }
+}
+
+/// A rock of radius about 1, centered at (0., 0., 0.): a subdivided
+/// `IcoSphere` with its vertices moved in and out by noise, and smooth
+/// normals recomputed from the faces.
+///
+/// The same `seed` always gives the same rock, and different seeds give
+/// unrelated ones.
+#[derive(Clone, Debug)]
+pub struct Rock {
+    range: Range<usize>,
+    vertices: Vec<Vertex>,
+    faces: Vec<Triangle<usize>>,
+}
+
+impl Rock {
+    /// Create a rock from an `IcoSphere` subdivided `subdivides` times,
+    /// with the default `Roughness`.
+    pub fn new(subdivides: usize, seed: u32) -> Self {
+        Self::with_roughness(subdivides, seed, Roughness::default())
+    }
+
+    /// Create a rock from an `IcoSphere` subdivided `subdivides` times,
+    /// as lumpy as `roughness` says.
+    pub fn with_roughness(subdivides: usize, seed: u32, roughness: Roughness) -> Self {
+        let sphere = IcoSphere::subdivide(subdivides);
+        let mut vertices: Vec<Vertex> = sphere.shared_vertex_iter().collect();
+        let faces: Vec<Triangle<usize>> = sphere.indexed_polygon_iter().triangulate().collect();
+        let noise = Fbm {
+            basis: Basis::Value,
+            frequency: roughness.frequency,
+            octaves: roughness.octaves,
+            lacunarity: 2.,
+            persistence: roughness.persistence,
+            seed,
+        };
+        for v in &mut vertices {
+            let p = Vector3::from(v.pos);
+            let r = 1. + roughness.amplitude * noise.sample3(v.pos);
+            v.pos = (p * r).into();
+        }
+        recompute_normals(&mut vertices, &faces, NormalFallback::default());
+        Self {
+            range: 0..faces.len(),
+            vertices,
+            faces,
+        }
+    }
+}
+
+impl Iterator for Rock {
+    type Item = Polygon<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range
+            .next()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vertices[i]))
+    }
+}
+
+impl DoubleEndedIterator for Rock {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range
+            .next_back()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vertices[i]))
+    }
+}
+
+impl ExactSizeIterator for Rock {}
+
+impl SharedVertex<Vertex> for Rock {
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        self.vertices[idx]
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for Rock {
+    fn indexed_polygon_count(&self) -> usize {
+        self.faces.len()
+    }
+
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        Polygon::PolyTri(self.faces[idx].clone())
+    }
+}
@@ -0,0 +1,173 @@
+//! Random sampling of points on the surface of meshes.
+
+use std::f64::consts::PI;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::math;
+use crate::spatial::SpatialGrid;
+use crate::triangulate::EmitTriangles;
+use crate::{Triangle, Vertex};
+
+/// Terminal adapters picking random points on a stream of polygons.
+///
+/// Randomness comes from `rng`, which must return numbers uniformly
+/// distributed in `[0, 1)`; any random number generator can be wrapped
+/// in a closure to provide them.
+pub trait SampleSurface: Sized {
+    /// Pick `n` points uniformly by area on the polygons. The normal of
+    /// each point is interpolated from the vertices of its polygon.
+    /// Returns no points if the polygons have no area.
+    fn sample_surface<R>(self, n: usize, rng: R) -> Vec<Vertex>
+    where
+        R: FnMut() -> f32;
+
+    /// Pick points on the polygons such that no two of them are closer
+    /// than `min_distance`, giving an even covering without clumps.
+    ///
+    /// Candidates are thrown uniformly by area and kept if they are far
+    /// enough from those kept so far; thirty candidates are thrown for
+    /// every disk of radius `min_distance` that fits on the surface, which
+    /// leaves few gaps large enough for another point. The throwing stops
+    /// early once a thousand candidates in a row have been rejected, as
+    /// the surface is then nearly full.
+    fn poisson_disk<R>(self, min_distance: f32, rng: R) -> Vec<Vertex>
+    where
+        R: FnMut() -> f32;
+}
+
+impl<T, P> SampleSurface for T
+where
+    T: Iterator<Item = P>,
+    P: EmitTriangles<Vertex = Vertex>,
+{
+    fn sample_surface<R>(self, n: usize, mut rng: R) -> Vec<Vertex>
+    where
+        R: FnMut() -> f32,
+    {
+        let surface = Surface::new(self);
+        if surface.total <= 0. {
+            return Vec::new();
+        }
+        (0..n).map(|_| surface.sample(&mut rng)).collect()
+    }
+
+    fn poisson_disk<R>(self, min_distance: f32, mut rng: R) -> Vec<Vertex>
+    where
+        R: FnMut() -> f32,
+    {
+        assert!(min_distance > 0.);
+        let surface = Surface::new(self);
+        if surface.total <= 0. {
+            return Vec::new();
+        }
+        surface.poisson_disk(min_distance, &mut rng)
+    }
+}
+
+// Triangles of a surface, with the running total of their areas to pick
+// them proportionally to their size.
+pub(crate) struct Surface {
+    triangles: Vec<Triangle<Vertex>>,
+    cumulative: Vec<f64>,
+    pub(crate) total: f64,
+}
+
+impl Surface {
+    pub(crate) fn new<I>(polygons: I) -> Self
+    where
+        I: Iterator,
+        I::Item: EmitTriangles<Vertex = Vertex>,
+    {
+        let mut triangles = Vec::new();
+        for p in polygons {
+            p.emit_triangles(|t| triangles.push(t));
+        }
+        let mut total = 0f64;
+        let cumulative = triangles
+            .iter()
+            .map(|t| {
+                total += area(t) as f64;
+                total
+            })
+            .collect();
+        Surface {
+            triangles,
+            cumulative,
+            total,
+        }
+    }
+
+    pub(crate) fn sample<R>(&self, rng: &mut R) -> Vertex
+    where
+        R: FnMut() -> f32,
+    {
+        let target = rng() as f64 * self.total;
+        let i = self
+            .cumulative
+            .partition_point(|&c| c <= target)
+            .min(self.triangles.len() - 1);
+        point_in(&self.triangles[i], rng(), rng())
+    }
+
+    // Dart throwing, keeping the candidates far enough from those kept so
+    // far, found through a grid of cells `min_distance` wide.
+    pub(crate) fn poisson_disk<R>(&self, min_distance: f32, rng: &mut R) -> Vec<Vertex>
+    where
+        R: FnMut() -> f32,
+    {
+        // the rejections in a row after which the surface counts as full
+        const MAX_REJECTIONS: usize = 1000;
+
+        let r = min_distance as f64;
+        let darts = (30. * self.total / (PI * r * r)).ceil() as usize;
+
+        let mut points: Vec<Vertex> = Vec::new();
+        let mut grid = SpatialGrid::new(min_distance);
+        let mut rejections = 0;
+        for _ in 0..darts {
+            let candidate = self.sample(rng);
+            if grid.query_radius(candidate.pos, min_distance).is_empty() {
+                grid.insert(candidate.pos);
+                points.push(candidate);
+                rejections = 0;
+            } else {
+                rejections += 1;
+                if rejections == MAX_REJECTIONS {
+                    break;
+                }
+            }
+        }
+        points
+    }
+}
+
+fn area(t: &Triangle<Vertex>) -> f32 {
+    let (a, b, c) = (
+        Vector3::from(t.x.pos),
+        Vector3::from(t.y.pos),
+        Vector3::from(t.z.pos),
+    );
+    (b - a).cross(c - a).magnitude() * 0.5
+}
+
+// Map two uniform numbers to a uniformly distributed point of a triangle.
+fn point_in(t: &Triangle<Vertex>, r1: f32, r2: f32) -> Vertex {
+    let s = r1.sqrt();
+    let (u, v, w) = (1. - s, s * (1. - r2), s * r2);
+    let (a, b, c) = (
+        Vector3::from(t.x.pos),
+        Vector3::from(t.y.pos),
+        Vector3::from(t.z.pos),
+    );
+    let n = Vector3::from(t.x.normal) * u
+        + Vector3::from(t.y.normal) * v
+        + Vector3::from(t.z.normal) * w;
+    let normal = math::try_normalize(n)
+        .or_else(|| math::try_normalize((b - a).cross(c - a)))
+        .unwrap_or_else(Vector3::unit_z);
+    Vertex {
+        pos: (a * u + b * v + c * w).into(),
+        normal: normal.into(),
+    }
+}
@@ -0,0 +1,121 @@
+//! Placing instances of a model over a surface, as transforms for GPU
+//! instancing rather than merged geometry.
+//!
+//! ```
+//! use genmesh::generators::Plane;
+//! use genmesh::scatter::{Placement, Scatter};
+//!
+//! let mut seed = 1u32;
+//! let rng = move || {
+//!     seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+//!     (seed >> 8) as f32 / (1 << 24) as f32
+//! };
+//! let instances = Plane::new().scatter(Placement::Count(100), (0.5, 1.5), rng);
+//! assert_eq!(instances.len(), 100);
+//! let matrices: Vec<mint::ColumnMatrix4<f32>> =
+//!     instances.iter().map(|i| i.matrix()).collect();
+//! # let _ = matrices;
+//! ```
+
+use std::f32::consts::PI;
+
+use cgmath::{Matrix4, Quaternion, Rad, Rotation3, Vector3};
+use mint::ColumnMatrix4;
+
+use crate::sample::Surface;
+use crate::triangulate::EmitTriangles;
+use crate::{Position, Vertex};
+
+/// How many instances to place on a surface.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Placement {
+    /// Exactly this many, uniformly by area.
+    Count(usize),
+    /// This many per unit of area, uniformly by area, rounded to the
+    /// nearest whole number over the whole surface.
+    Density(f32),
+    /// As many as fit with no two closer than this distance, see
+    /// `SampleSurface::poisson_disk`.
+    Spacing(f32),
+}
+
+/// Where an instance goes, and how it is turned and scaled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Instance {
+    /// The point of the surface the instance stands on
+    pub position: Position,
+    /// Turns the z axis of the model onto the normal of the surface, after
+    /// spinning it about that axis
+    pub rotation: mint::Quaternion<f32>,
+    /// The uniform scale of the model
+    pub scale: f32,
+}
+
+impl Instance {
+    /// The transform of the instance, scaling, then rotating, then moving
+    /// the model into place.
+    pub fn matrix(&self) -> ColumnMatrix4<f32> {
+        let m = Matrix4::from_translation(Vector3::from(self.position))
+            * Matrix4::from(Quaternion::from(self.rotation))
+            * Matrix4::from_scale(self.scale);
+        m.into()
+    }
+}
+
+/// Terminal adapter placing instances on a stream of polygons.
+///
+/// Randomness comes from `rng`, as for `SampleSurface`.
+pub trait Scatter: Sized {
+    /// Place instances on the polygons as `placement` asks. Each stands
+    /// on a random point of the surface, with the z axis of the model
+    /// along the normal there, spun by a random angle about it, and
+    /// scaled by a random factor between the two ends of `scale`.
+    /// Returns no instances if the polygons have no area.
+    fn scatter<R>(self, placement: Placement, scale: (f32, f32), rng: R) -> Vec<Instance>
+    where
+        R: FnMut() -> f32;
+}
+
+impl<T, P> Scatter for T
+where
+    T: Iterator<Item = P>,
+    P: EmitTriangles<Vertex = Vertex>,
+{
+    fn scatter<R>(self, placement: Placement, scale: (f32, f32), mut rng: R) -> Vec<Instance>
+    where
+        R: FnMut() -> f32,
+    {
+        let surface = Surface::new(self);
+        if surface.total <= 0. {
+            return Vec::new();
+        }
+        let points = match placement {
+            Placement::Count(n) => (0..n).map(|_| surface.sample(&mut rng)).collect(),
+            Placement::Density(density) => {
+                assert!(density >= 0.);
+                let n = (surface.total * density as f64).round() as usize;
+                (0..n).map(|_| surface.sample(&mut rng)).collect()
+            }
+            Placement::Spacing(min_distance) => {
+                assert!(min_distance > 0.);
+                surface.poisson_disk(min_distance, &mut rng)
+            }
+        };
+        points
+            .into_iter()
+            .map(|v| {
+                let normal = Vector3::from(v.normal);
+                // turning half way round has no single arc, any axis across
+                // z will do
+                let align =
+                    Quaternion::from_arc(Vector3::unit_z(), normal, Some(Vector3::unit_x()));
+                let spin = Quaternion::from_angle_z(Rad(2. * PI * rng()));
+                Instance {
+                    position: v.pos,
+                    rotation: (align * spin).into(),
+                    scale: scale.0 + (scale.1 - scale.0) * rng(),
+                }
+            })
+            .collect()
+    }
+}
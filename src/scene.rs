@@ -0,0 +1,141 @@
+//! Assembly of many generators into a single scene.
+
+use std::collections::BTreeMap;
+
+use cgmath::Matrix4;
+use mint::ColumnMatrix4;
+
+use crate::generators::{IndexedPolygon, SharedVertex};
+use crate::math::VertexTransform;
+use crate::{EmitTriangles, Polygon, Triangle, Vertex};
+
+/// Collects generators, each with its own transform and material, and
+/// bakes them into a single indexed mesh.
+///
+/// ```
+/// use genmesh::generators::{Cube, SphereUv};
+/// use genmesh::SceneBuilder;
+///
+/// let identity = [[1., 0., 0., 0.], [0., 1., 0., 0.], [0., 0., 1., 0.], [0., 0., 0., 1.]];
+/// let lifted = [[1., 0., 0., 0.], [0., 1., 0., 0.], [0., 0., 1., 0.], [0., 0., 2., 1.]];
+/// let scene = SceneBuilder::new()
+///     .add(&Cube::new(), identity, 0)
+///     .add(&SphereUv::new(8, 8), lifted, 1)
+///     .build();
+/// assert_eq!(scene.triangles.len(), scene.materials.len());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SceneBuilder {
+    scene: Scene,
+}
+
+impl SceneBuilder {
+    /// Create an empty scene builder.
+    pub fn new() -> Self {
+        SceneBuilder::default()
+    }
+
+    /// Add the mesh of `generator`, moved by `transform` and tagged with
+    /// `material`. Normals are transformed by the inverse transpose of the
+    /// transform, and mirroring transforms also reverse the winding so the
+    /// faces keep pointing outward.
+    pub fn add<G, M>(mut self, generator: &G, transform: M, material: u32) -> Self
+    where
+        G: SharedVertex<Vertex> + IndexedPolygon<Polygon<usize>>,
+        M: Into<ColumnMatrix4<f32>>,
+    {
+        let base = self.scene.vertices.len();
+        let (vertices, triangles) = transformed(generator, Matrix4::from(transform.into()), base);
+        self.scene.vertices.extend(vertices);
+        self.scene
+            .materials
+            .extend(triangles.iter().map(|_| material));
+        self.scene.triangles.extend(triangles);
+        self
+    }
+
+    /// Finish the scene.
+    pub fn build(self) -> Scene {
+        self.scene
+    }
+}
+
+/// The vertices of `generator` moved by `transform`, and its triangles
+/// with their indices offset by `base`, as `SceneBuilder::add` and
+/// `MeshBuilder::add` append them.
+pub(crate) fn transformed<G>(
+    generator: &G,
+    transform: Matrix4<f32>,
+    base: usize,
+) -> (Vec<Vertex>, Vec<Triangle<usize>>)
+where
+    G: SharedVertex<Vertex> + IndexedPolygon<Polygon<usize>>,
+{
+    let transform = VertexTransform::new(transform);
+    let vertices = generator
+        .shared_vertex_iter()
+        .map(|v| transform.apply(v))
+        .collect();
+    let mut triangles = Vec::new();
+    for poly in generator.indexed_polygon_iter() {
+        poly.emit_triangles(|t| {
+            triangles.push(if transform.flips_winding() {
+                Triangle::new(t.x + base, t.z + base, t.y + base)
+            } else {
+                Triangle::new(t.x + base, t.y + base, t.z + base)
+            })
+        });
+    }
+    (vertices, triangles)
+}
+
+/// A merged indexed triangle mesh with a material tag per triangle.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scene {
+    /// Vertices of all the parts of the scene
+    pub vertices: Vec<Vertex>,
+    /// Triangles indexing into `vertices`
+    pub triangles: Vec<Triangle<usize>>,
+    /// Material of each triangle, in the same order as `triangles`
+    pub materials: Vec<u32>,
+}
+
+/// The part of a scene using a single material.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubMesh {
+    /// Material shared by all the triangles
+    pub material: u32,
+    /// Vertices used by the triangles
+    pub vertices: Vec<Vertex>,
+    /// Triangles indexing into `vertices`
+    pub triangles: Vec<Triangle<usize>>,
+}
+
+impl Scene {
+    /// Split the scene into one self-contained mesh per material, sorted by
+    /// material. Each sub-mesh only keeps the vertices it uses.
+    pub fn split_by_material(&self) -> Vec<SubMesh> {
+        let mut groups: BTreeMap<u32, (Vec<Option<usize>>, SubMesh)> = BTreeMap::new();
+        for (t, &material) in self.triangles.iter().zip(self.materials.iter()) {
+            let (remap, sub) = groups.entry(material).or_insert_with(|| {
+                (
+                    vec![None; self.vertices.len()],
+                    SubMesh {
+                        material,
+                        vertices: Vec::new(),
+                        triangles: Vec::new(),
+                    },
+                )
+            });
+            let mut index = |i: usize| {
+                *remap[i].get_or_insert_with(|| {
+                    sub.vertices.push(self.vertices[i]);
+                    sub.vertices.len() - 1
+                })
+            };
+            let t = Triangle::new(index(t.x), index(t.y), index(t.z));
+            sub.triangles.push(t);
+        }
+        groups.into_iter().map(|(_, (_, sub))| sub).collect()
+    }
+}
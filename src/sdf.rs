@@ -0,0 +1,500 @@
+//! Polygonization of implicit surfaces, described by a signed distance
+//! function (or any field that is negative inside and positive outside).
+
+use std::collections::HashMap;
+
+use cgmath::Vector3;
+
+use crate::generators::{IndexedPolygon, SharedVertex};
+use crate::math;
+use crate::Polygon::{PolyQuad, PolyTri};
+use crate::{FlipWinding, MapVertex, Polygon, Position, Quad, Triangle, Triangulate, Vertex};
+
+/// The surface where a field crosses zero, extracted with marching cubes.
+///
+/// The field is sampled on a regular grid over a box; the surface is made
+/// of triangles facing the positive side of the field, with normals taken
+/// from its gradient. Faces of a grid cell whose corners are ambiguous are
+/// resolved from the average of the corners, consistently between
+/// neighboring cells, so the surface is closed wherever it does not leave
+/// the box.
+///
+/// ```
+/// use genmesh::sdf::MarchingCubes;
+///
+/// let sphere = MarchingCubes::new(
+///     |p| (p.x * p.x + p.y * p.y + p.z * p.z).sqrt() - 1.,
+///     [-1.5; 3].into(),
+///     [1.5; 3].into(),
+///     [16; 3],
+/// );
+/// assert!(sphere.count() > 0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MarchingCubes {
+    idx: usize,
+    end: usize,
+    vertices: Vec<Vertex>,
+    triangles: Vec<Triangle<usize>>,
+}
+
+// the corners of a cell are numbered `x + 2 * y + 4 * z`, the faces list
+// them counter clockwise as seen from outside of the cell
+const FACES: [[usize; 4]; 6] = [
+    [0, 4, 6, 2],
+    [1, 3, 7, 5],
+    [0, 1, 5, 4],
+    [2, 6, 7, 3],
+    [0, 2, 3, 1],
+    [4, 5, 7, 6],
+];
+
+impl MarchingCubes {
+    /// Polygonize the surface `field(p) == 0` inside the box from `min` to
+    /// `max`, sampled on a grid of `resolution` cells along each axis.
+    pub fn new<F>(field: F, min: Position, max: Position, resolution: [usize; 3]) -> Self
+    where
+        F: Fn(Position) -> f32,
+    {
+        let grid = Grid::new(&field, min, max, resolution);
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+        // one vertex per crossed grid edge, keyed by the grid point the edge
+        // starts from and its axis
+        let mut crossings: HashMap<(usize, usize), usize> = HashMap::new();
+
+        for i in 0..resolution[0] {
+            for j in 0..resolution[1] {
+                for k in 0..resolution[2] {
+                    let corner = |c: usize| (i + (c & 1), j + ((c >> 1) & 1), k + ((c >> 2) & 1));
+                    let value = |c: usize| {
+                        let (x, y, z) = corner(c);
+                        grid.value(x, y, z)
+                    };
+                    let inside: Vec<bool> = (0..8).map(|c| value(c) < 0.).collect();
+                    if inside.iter().all(|&x| x) || inside.iter().all(|&x| !x) {
+                        continue;
+                    }
+
+                    let mut vertex = |a: usize, b: usize| {
+                        let (a, b) = if a < b { (a, b) } else { (b, a) };
+                        let (x, y, z) = corner(a);
+                        let axis = (b - a).trailing_zeros() as usize;
+                        *crossings
+                            .entry((grid.index(x, y, z), axis))
+                            .or_insert_with(|| {
+                                let (bx, by, bz) = corner(b);
+                                let pos = grid.crossing((x, y, z), (bx, by, bz));
+                                vertices.push(Vertex {
+                                    pos: pos.into(),
+                                    normal: [0.; 3].into(),
+                                });
+                                vertices.len() - 1
+                            })
+                    };
+
+                    // trace the crossing segments on each face, then chain
+                    // them into closed loops. Each segment runs from where
+                    // the face boundary goes inside to where it comes back
+                    // out, which winds the loops to face the outside. On an
+                    // ambiguous face, the inside corners are kept apart if
+                    // the center of the face is outside.
+                    let mut next: HashMap<usize, usize> = HashMap::new();
+                    for face in &FACES {
+                        let mut crossed = Vec::with_capacity(4);
+                        for e in 0..4 {
+                            let (a, b) = (face[e], face[(e + 1) % 4]);
+                            if inside[a] != inside[b] {
+                                crossed.push((vertex(a, b), inside[b]));
+                            }
+                        }
+                        let separate =
+                            crossed.len() == 4 && face.iter().map(|&c| value(c)).sum::<f32>() >= 0.;
+                        let n = crossed.len();
+                        for (e, &(v, enters)) in crossed.iter().enumerate() {
+                            if enters {
+                                let exit = if separate {
+                                    crossed[(e + 1) % n].0
+                                } else {
+                                    crossed[(e + n - 1) % n].0
+                                };
+                                next.insert(v, exit);
+                            }
+                        }
+                    }
+
+                    while let Some((&start, _)) = next.iter().min_by_key(|(&k, _)| k) {
+                        let mut polygon = vec![start];
+                        let mut current = next.remove(&start).unwrap();
+                        while current != start {
+                            polygon.push(current);
+                            current = match next.remove(&current) {
+                                Some(v) => v,
+                                None => break,
+                            };
+                        }
+                        for w in 1..polygon.len().saturating_sub(1) {
+                            triangles.push(Triangle::new(polygon[0], polygon[w], polygon[w + 1]));
+                        }
+                    }
+                }
+            }
+        }
+
+        gradient_normals(&field, &grid, &mut vertices, &triangles);
+
+        MarchingCubes {
+            idx: 0,
+            end: triangles.len(),
+            vertices,
+            triangles,
+        }
+    }
+}
+
+impl Iterator for MarchingCubes {
+    type Item = Polygon<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.idx;
+        (n, Some(n))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.idx += 1;
+            Some(
+                self.indexed_polygon(self.idx - 1)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl DoubleEndedIterator for MarchingCubes {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            Some(
+                self.indexed_polygon(self.end)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for MarchingCubes {}
+
+impl SharedVertex<Vertex> for MarchingCubes {
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        self.vertices[idx]
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for MarchingCubes {
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        PolyTri(self.triangles[idx].clone())
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.triangles.len()
+    }
+}
+
+/// The surface where a field crosses zero, extracted with naive surface
+/// nets.
+///
+/// Each grid cell the surface goes through gets a single vertex, at the
+/// average of the points where the field crosses the edges of the cell,
+/// and each crossed grid edge gets a quad joining the four cells around
+/// it. The result is made of quads of fairly even size, which suits
+/// subdivision better than the slivers of `MarchingCubes`, at the cost of
+/// rounding off sharp features. Quads are not necessarily planar.
+///
+/// The sampling works as for `MarchingCubes`.
+#[derive(Clone, Debug)]
+pub struct SurfaceNets {
+    idx: usize,
+    end: usize,
+    vertices: Vec<Vertex>,
+    quads: Vec<Quad<usize>>,
+}
+
+// the twelve edges of a cell, as pairs of corners
+const EDGES: [(usize, usize); 12] = [
+    (0, 1),
+    (2, 3),
+    (4, 5),
+    (6, 7),
+    (0, 2),
+    (1, 3),
+    (4, 6),
+    (5, 7),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+impl SurfaceNets {
+    /// Polygonize the surface `field(p) == 0` inside the box from `min` to
+    /// `max`, sampled on a grid of `resolution` cells along each axis.
+    pub fn new<F>(field: F, min: Position, max: Position, resolution: [usize; 3]) -> Self
+    where
+        F: Fn(Position) -> f32,
+    {
+        let grid = Grid::new(&field, min, max, resolution);
+        let [rx, ry, rz] = resolution;
+        let cell_index = |i: usize, j: usize, k: usize| (i * ry + j) * rz + k;
+
+        let mut vertices = Vec::new();
+        let mut cells = vec![None; rx * ry * rz];
+        for i in 0..rx {
+            for j in 0..ry {
+                for k in 0..rz {
+                    let corner = |c: usize| (i + (c & 1), j + ((c >> 1) & 1), k + ((c >> 2) & 1));
+                    let inside = |c: usize| {
+                        let (x, y, z) = corner(c);
+                        grid.value(x, y, z) < 0.
+                    };
+                    let mut sum = Vector3::new(0., 0., 0.);
+                    let mut count = 0;
+                    for &(a, b) in &EDGES {
+                        if inside(a) != inside(b) {
+                            sum += grid.crossing(corner(a), corner(b));
+                            count += 1;
+                        }
+                    }
+                    if count > 0 {
+                        cells[cell_index(i, j, k)] = Some(vertices.len());
+                        vertices.push(Vertex {
+                            pos: (sum / count as f32).into(),
+                            normal: [0.; 3].into(),
+                        });
+                    }
+                }
+            }
+        }
+
+        // a quad around every crossed grid edge that has cells on all sides
+        let mut quads = Vec::new();
+        for i in 0..=rx {
+            for j in 0..=ry {
+                for k in 0..=rz {
+                    let p = [i, j, k];
+                    for axis in 0..3 {
+                        let (b, c) = ((axis + 1) % 3, (axis + 2) % 3);
+                        if p[axis] == resolution[axis]
+                            || p[b] == 0
+                            || p[c] == 0
+                            || p[b] == resolution[b]
+                            || p[c] == resolution[c]
+                        {
+                            continue;
+                        }
+                        let mut q = p;
+                        q[axis] += 1;
+                        let from = grid.value(i, j, k) < 0.;
+                        if from == (grid.value(q[0], q[1], q[2]) < 0.) {
+                            continue;
+                        }
+
+                        let cell = |db: usize, dc: usize| {
+                            let mut at = p;
+                            at[b] -= db;
+                            at[c] -= dc;
+                            cells[cell_index(at[0], at[1], at[2])]
+                                .expect("a crossed edge has a vertex in each cell around it")
+                        };
+                        // counter clockwise around `axis`, so facing along it
+                        let quad = Quad::new(cell(1, 1), cell(0, 1), cell(0, 0), cell(1, 0));
+                        quads.push(if from { quad } else { quad.flip_winding() });
+                    }
+                }
+            }
+        }
+
+        let triangles: Vec<Triangle<usize>> = quads.iter().cloned().triangulate().collect();
+        gradient_normals(&field, &grid, &mut vertices, &triangles);
+
+        SurfaceNets {
+            idx: 0,
+            end: quads.len(),
+            vertices,
+            quads,
+        }
+    }
+}
+
+impl Iterator for SurfaceNets {
+    type Item = Polygon<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.idx;
+        (n, Some(n))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.idx += 1;
+            Some(
+                self.indexed_polygon(self.idx - 1)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl DoubleEndedIterator for SurfaceNets {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            Some(
+                self.indexed_polygon(self.end)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for SurfaceNets {}
+
+impl SharedVertex<Vertex> for SurfaceNets {
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        self.vertices[idx]
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for SurfaceNets {
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        PolyQuad(self.quads[idx].clone())
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.quads.len()
+    }
+}
+
+// the field sampled at the corners of the cells of a regular grid
+struct Grid {
+    min: Vector3<f32>,
+    cell: Vector3<f32>,
+    resolution: [usize; 3],
+    samples: Vec<f32>,
+}
+
+impl Grid {
+    fn new<F>(field: &F, min: Position, max: Position, resolution: [usize; 3]) -> Self
+    where
+        F: Fn(Position) -> f32,
+    {
+        assert!(resolution.iter().all(|&r| r > 0));
+        let min = Vector3::from(min);
+        let size = Vector3::from(max) - min;
+        let mut grid = Grid {
+            min,
+            cell: Vector3::new(
+                size.x / resolution[0] as f32,
+                size.y / resolution[1] as f32,
+                size.z / resolution[2] as f32,
+            ),
+            resolution,
+            samples: Vec::new(),
+        };
+        let [nx, ny, nz] = [resolution[0] + 1, resolution[1] + 1, resolution[2] + 1];
+        grid.samples.reserve(nx * ny * nz);
+        for i in 0..nx {
+            for j in 0..ny {
+                for k in 0..nz {
+                    let value = field(grid.point(i, j, k).into());
+                    grid.samples.push(value);
+                }
+            }
+        }
+        grid
+    }
+
+    fn point(&self, i: usize, j: usize, k: usize) -> Vector3<f32> {
+        self.min
+            + Vector3::new(
+                i as f32 * self.cell.x,
+                j as f32 * self.cell.y,
+                k as f32 * self.cell.z,
+            )
+    }
+
+    fn index(&self, i: usize, j: usize, k: usize) -> usize {
+        (i * (self.resolution[1] + 1) + j) * (self.resolution[2] + 1) + k
+    }
+
+    fn value(&self, i: usize, j: usize, k: usize) -> f32 {
+        self.samples[self.index(i, j, k)]
+    }
+
+    // where the field crosses zero between two grid points, assuming it
+    // changes sign between them
+    fn crossing(&self, a: (usize, usize, usize), b: (usize, usize, usize)) -> Vector3<f32> {
+        let va = self.value(a.0, a.1, a.2);
+        let vb = self.value(b.0, b.1, b.2);
+        let pa = self.point(a.0, a.1, a.2);
+        let pb = self.point(b.0, b.1, b.2);
+        pa + (pb - pa) * (va / (va - vb))
+    }
+}
+
+// set the normals from the gradient of the field, by central differences
+fn gradient_normals<F>(
+    field: &F,
+    grid: &Grid,
+    vertices: &mut [Vertex],
+    triangles: &[Triangle<usize>],
+) where
+    F: Fn(Position) -> f32,
+{
+    let h = grid.cell.x.min(grid.cell.y).min(grid.cell.z) * 0.5;
+    let mut missing = Vec::new();
+    for (i, v) in vertices.iter_mut().enumerate() {
+        let p = Vector3::from(v.pos);
+        let diff = |d: Vector3<f32>| field((p + d).into()) - field((p - d).into());
+        let gradient = Vector3::new(
+            diff(Vector3::unit_x() * h),
+            diff(Vector3::unit_y() * h),
+            diff(Vector3::unit_z() * h),
+        );
+        match math::try_normalize(gradient) {
+            Some(n) => v.normal = n.into(),
+            None => missing.push(i),
+        }
+    }
+    // flat spots of the field fall back to the faces around the vertex
+    if !missing.is_empty() {
+        let mut sums = vec![Vector3::new(0., 0., 0.); vertices.len()];
+        for t in triangles {
+            let p = |i: usize| Vector3::from(vertices[i].pos);
+            let n = (p(t.y) - p(t.x)).cross(p(t.z) - p(t.x));
+            for &c in &[t.x, t.y, t.z] {
+                sums[c] += n;
+            }
+        }
+        for i in missing {
+            let n = math::try_normalize(sums[i]).unwrap_or_else(Vector3::unit_z);
+            vertices[i].normal = n.into();
+        }
+    }
+}
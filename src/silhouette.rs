@@ -0,0 +1,74 @@
+//! The outlines of a mesh as seen from a viewpoint.
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::attributes::VertexAttributes;
+use crate::poly::{EmitLines, Line};
+use crate::{Neighbors, Normal, Position};
+
+/// Where a mesh is seen from, to tell its front faces from its back faces.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum View {
+    /// A perspective view from a point, such as a camera or a point light.
+    Point(Position),
+    /// A parallel view looking along a direction, such as a directional
+    /// light. The direction needs not be normalized.
+    Direction(Normal),
+}
+
+impl View {
+    // whether a surface at `point` facing `normal` faces the viewer;
+    // surfaces seen edge on, and those with no normal, do not
+    pub(crate) fn faces(&self, point: Vector3<f32>, normal: Vector3<f32>) -> bool {
+        let towards = match *self {
+            View::Point(eye) => Vector3::from(eye) - point,
+            View::Direction(direction) => -Vector3::from(direction),
+        };
+        normal.dot(towards) > 0.
+    }
+}
+
+/// Find the silhouette of an indexed triangle mesh seen from `view`: the
+/// edges between a triangle facing the viewer and one facing away, for
+/// toon outlines and the sides of shadow volumes.
+///
+/// Each edge is given once, as indices into `neighbors.vertices`, and goes
+/// the way it goes around its front facing triangle, so extruding it away
+/// from the viewer gives quads that all face outwards. Open boundary edges
+/// of front facing triangles are part of the silhouette too.
+pub fn silhouette<'a, V>(
+    neighbors: &'a Neighbors<V>,
+    view: View,
+) -> impl Iterator<Item = Line<usize>> + 'a
+where
+    V: VertexAttributes,
+{
+    let p = move |i: usize| Vector3::from(neighbors.vertices[i].position());
+    let front: Vec<bool> = neighbors
+        .polygons
+        .iter()
+        .map(|t| view.faces(p(t.x), (p(t.y) - p(t.x)).cross(p(t.z) - p(t.x))))
+        .collect();
+
+    neighbors
+        .polygons
+        .iter()
+        .enumerate()
+        .flat_map(move |(i, t)| {
+            let mut edges = Vec::new();
+            if !front[i] {
+                return edges;
+            }
+            t.clone().emit_lines(|line| {
+                let others: Vec<usize> = neighbors
+                    .faces_sharing_edge(&line)
+                    .into_iter()
+                    .filter(|&f| f != i)
+                    .collect();
+                if others.is_empty() || others.iter().any(|&f| !front[f]) {
+                    edges.push(line);
+                }
+            });
+            edges
+        })
+}
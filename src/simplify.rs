@@ -0,0 +1,139 @@
+//! Decimation of triangle meshes, driven by the quadric error metric of
+//! Garland and Heckbert.
+//!
+//! Every vertex accumulates the planes of the triangles around it. The
+//! error of moving a vertex is the sum of its squared distances to those
+//! planes, so flat regions are simplified first and sharp features last.
+//!
+//! Collapses are half-edge collapses, as in `ProgressiveMesh`: the vertices
+//! that are kept do not move, and the vertex buffer can be reused as is.
+
+use std::cell::RefCell;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::progressive::{resolve_collapses, Collapser};
+use crate::{EdgeCollapse, Position, ProgressiveMesh, Triangle};
+
+/// Simplify a triangle mesh until it has at most `target` triangles, or
+/// until any further collapse would exceed `max_error`.
+///
+/// `max_error` is a distance: the root of the summed squared distances
+/// from the merged vertex to the planes of the original triangles around
+/// it. Use `0` as `target` to only stop on the error, and `f32::INFINITY`
+/// as `max_error` to only stop on the count.
+///
+/// The mesh stays manifold, its open boundaries keep their outline and no
+/// triangle is flipped, which may leave more triangles than asked for.
+/// The returned triangles index into the same `positions`.
+pub fn simplify(
+    positions: &[Position],
+    triangles: &[Triangle<usize>],
+    target: usize,
+    max_error: f32,
+) -> Vec<Triangle<usize>> {
+    let collapses = collapse(positions, triangles, target, max_error);
+    let remap = resolve_collapses(positions.len(), &collapses);
+    triangles
+        .iter()
+        .map(|t| Triangle::new(remap[t.x], remap[t.y], remap[t.z]))
+        .filter(|t| t.x != t.y && t.y != t.z && t.z != t.x)
+        .collect()
+}
+
+/// Record the full sequence of quadric error collapses of a mesh as a
+/// `ProgressiveMesh`, from which a whole chain of levels of detail can
+/// be extracted with `ProgressiveMesh::triangles_for_count`.
+pub fn progressive_mesh(
+    positions: &[Position],
+    triangles: Vec<Triangle<usize>>,
+) -> ProgressiveMesh {
+    let collapses = collapse(positions, &triangles, 0, f32::INFINITY);
+    ProgressiveMesh::new(triangles, collapses)
+}
+
+fn collapse(
+    positions: &[Position],
+    triangles: &[Triangle<usize>],
+    target: usize,
+    max_error: f32,
+) -> Vec<EdgeCollapse> {
+    let mut quadrics = vec![Quadric::default(); positions.len()];
+    for t in triangles {
+        let a = Vector3::from(positions[t.x]);
+        let b = Vector3::from(positions[t.y]);
+        let c = Vector3::from(positions[t.z]);
+        let n = (b - a).cross(c - a);
+        if n.magnitude2() == 0. {
+            continue;
+        }
+        let q = Quadric::plane(n.normalize(), a);
+        for &v in &[t.x, t.y, t.z] {
+            quadrics[v].add(&q);
+        }
+    }
+
+    let mut collapser = Collapser::new(positions, triangles);
+    let quadrics = RefCell::new(quadrics);
+    collapser.run(
+        |from, to| {
+            let q = quadrics.borrow();
+            let mut sum = q[from];
+            sum.add(&q[to]);
+            sum.error(Vector3::from(positions[to])).max(0.).sqrt() as f32
+        },
+        |from, to| {
+            let mut q = quadrics.borrow_mut();
+            let merged = q[from];
+            q[to].add(&merged);
+        },
+        target,
+        max_error,
+    )
+}
+
+/// A symmetric 4x4 matrix measuring the squared distance of a point to a
+/// set of planes, stored as its upper triangle.
+#[derive(Clone, Copy, Debug, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    // the plane through `p` with unit normal `n`
+    fn plane(n: Vector3<f32>, p: Vector3<f32>) -> Self {
+        let (a, b, c) = (n.x as f64, n.y as f64, n.z as f64);
+        let d = -(a * p.x as f64 + b * p.y as f64 + c * p.z as f64);
+        Quadric([
+            a * a,
+            a * b,
+            a * c,
+            a * d,
+            b * b,
+            b * c,
+            b * d,
+            c * c,
+            c * d,
+            d * d,
+        ])
+    }
+
+    fn add(&mut self, other: &Quadric) {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a += b;
+        }
+    }
+
+    fn error(&self, p: Vector3<f32>) -> f64 {
+        let q = &self.0;
+        let (x, y, z) = (p.x as f64, p.y as f64, p.z as f64);
+        q[0] * x * x
+            + 2. * q[1] * x * y
+            + 2. * q[2] * x * z
+            + 2. * q[3] * x
+            + q[4] * y * y
+            + 2. * q[5] * y * z
+            + 2. * q[6] * y
+            + q[7] * z * z
+            + 2. * q[8] * z
+            + q[9]
+    }
+}
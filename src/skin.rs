@@ -0,0 +1,198 @@
+//! Skinning weights from bone segments, to rig generated meshes.
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::{MapVertex, Normal, Position, Vertex, VertexAttributes};
+
+/// The most bones that can move a single vertex, the usual limit of GPU
+/// skinning.
+pub const MAX_INFLUENCES: usize = 4;
+
+/// A vertex moved by up to four bones, for skeletal animation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+pub struct SkinnedVertex {
+    /// Vertex position
+    pub pos: Position,
+    /// Vertex normal
+    pub normal: Normal,
+    /// The bones moving the vertex, by index
+    pub joints: [u16; MAX_INFLUENCES],
+    /// How much each of the bones moves the vertex, adding up to one,
+    /// zero for the unused slots
+    pub weights: [f32; MAX_INFLUENCES],
+}
+
+impl VertexAttributes for SkinnedVertex {
+    fn position(&self) -> Position {
+        self.pos
+    }
+
+    fn normal(&self) -> Option<Normal> {
+        Some(self.normal)
+    }
+
+    fn set_normal(&mut self, normal: Normal) {
+        self.normal = normal;
+    }
+}
+
+impl From<SkinnedVertex> for Vertex {
+    fn from(v: SkinnedVertex) -> Self {
+        Vertex {
+            pos: v.pos,
+            normal: v.normal,
+        }
+    }
+}
+
+/// A bone, as the segment from its head to its tail, with the distance
+/// over which it pulls the vertices around it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bone {
+    /// Where the bone starts, at its joint with its parent
+    pub head: Position,
+    /// Where the bone ends
+    pub tail: Position,
+    /// How far from the segment the bone still moves vertices
+    pub radius: f32,
+}
+
+impl Bone {
+    /// Create a bone from `head` to `tail` reaching `radius` around it.
+    pub fn new<P: Into<Position>>(head: P, tail: P, radius: f32) -> Self {
+        Bone {
+            head: head.into(),
+            tail: tail.into(),
+            radius,
+        }
+    }
+
+    /// The distance from `point` to the segment of the bone.
+    pub fn distance<P: Into<Position>>(&self, point: P) -> f32 {
+        let (a, b) = (Vector3::from(self.head), Vector3::from(self.tail));
+        let p = Vector3::from(point.into());
+        let ab = b - a;
+        let t = if ab.magnitude2() > 0. {
+            ((p - a).dot(ab) / ab.magnitude2()).clamp(0., 1.)
+        } else {
+            0.
+        };
+        (p - (a + ab * t)).magnitude()
+    }
+
+    /// How much the bone pulls `point`, before weighing it against the
+    /// other bones: one on the segment, falling off smoothly to zero at
+    /// `radius` from it, like the capsules of envelope rigging.
+    pub fn influence<P: Into<Position>>(&self, point: P) -> f32 {
+        if self.radius <= 0. {
+            return 0.;
+        }
+        let x = 1. - (self.distance(point) / self.radius).min(1.);
+        x * x * (3. - 2. * x)
+    }
+}
+
+/// The joints and weights of a vertex at `point`: the bones with the
+/// largest influence on it, at most `MAX_INFLUENCES` of them, with their
+/// weights scaled to add up to one. A vertex out of the reach of every
+/// bone goes entirely to the closest one.
+///
+/// Bones are numbered by their place in `bones`, which must not hold more
+/// than `u16::MAX` of them.
+pub fn skin_weights<P: Into<Position>>(
+    point: P,
+    bones: &[Bone],
+) -> ([u16; MAX_INFLUENCES], [f32; MAX_INFLUENCES]) {
+    assert!(bones.len() <= u16::MAX as usize);
+    let point = point.into();
+    let mut joints = [0; MAX_INFLUENCES];
+    let mut weights = [0.; MAX_INFLUENCES];
+    let mut influences: Vec<(usize, f32)> = bones
+        .iter()
+        .map(|b| b.influence(point))
+        .enumerate()
+        .filter(|&(_, w)| w > 0.)
+        .collect();
+    if influences.is_empty() {
+        let closest = bones
+            .iter()
+            .map(|b| b.distance(point))
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((i, _)) = closest {
+            joints[0] = i as u16;
+            weights[0] = 1.;
+        }
+        return (joints, weights);
+    }
+    influences.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    influences.truncate(MAX_INFLUENCES);
+    let total: f32 = influences.iter().map(|i| i.1).sum();
+    for (k, &(i, w)) in influences.iter().enumerate() {
+        joints[k] = i as u16;
+        weights[k] = w / total;
+    }
+    (joints, weights)
+}
+
+/// Rigs a stream of polygons to a skeleton.
+pub trait Skin: Sized {
+    /// Give every vertex the joints and weights of the bones around it,
+    /// see `skin_weights`.
+    fn skin(self, bones: &[Bone]) -> SkinIterator<Self>;
+}
+
+impl<I, P> Skin for I
+where
+    I: Iterator<Item = P>,
+    P: MapVertex<Vertex, SkinnedVertex>,
+{
+    fn skin(self, bones: &[Bone]) -> SkinIterator<I> {
+        SkinIterator {
+            source: self,
+            bones: bones.to_vec(),
+        }
+    }
+}
+
+/// An iterator rigging the polygons of its source
+#[derive(Clone)]
+pub struct SkinIterator<I> {
+    source: I,
+    bones: Vec<Bone>,
+}
+
+impl<I, P> Iterator for SkinIterator<I>
+where
+    I: Iterator<Item = P>,
+    P: MapVertex<Vertex, SkinnedVertex>,
+{
+    type Item = P::Output;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.source.size_hint()
+    }
+
+    fn next(&mut self) -> Option<P::Output> {
+        let bones = &self.bones;
+        self.source.next().map(|p| {
+            p.map_vertex(|v| {
+                let (joints, weights) = skin_weights(v.pos, bones);
+                SkinnedVertex {
+                    pos: v.pos,
+                    normal: v.normal,
+                    joints,
+                    weights,
+                }
+            })
+        })
+    }
+}
+
+impl<I, P> ExactSizeIterator for SkinIterator<I>
+where
+    I: ExactSizeIterator<Item = P>,
+    P: MapVertex<Vertex, SkinnedVertex>,
+{
+}
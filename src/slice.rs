@@ -0,0 +1,87 @@
+//! Planar cross-sections of indexed meshes.
+
+use std::collections::BTreeMap;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::analytic::Plane;
+use crate::clip::chain_loops;
+use crate::{Normal, Position, Triangle};
+
+/// Intersect a closed triangle mesh with parallel planes, and return the
+/// outlines of every cross-section.
+///
+/// The planes all face `normal`, and are placed at each of `distances`
+/// from the origin along it, as with `Plane::new`. For every plane the
+/// result holds a list of closed polylines, which wind counter clockwise
+/// around the inside of the mesh when seen from the side `normal` points
+/// to, so holes wind clockwise. The last point of a polyline is not
+/// repeated. Pieces of the cut that do not close up, where the mesh has
+/// boundaries, are left out.
+pub fn slice(
+    positions: &[Position],
+    triangles: &[Triangle<usize>],
+    normal: Normal,
+    distances: &[f32],
+) -> Vec<Vec<Vec<Position>>> {
+    distances
+        .iter()
+        .map(|&distance| slice_one(positions, triangles, Plane::new(normal, distance)))
+        .collect()
+}
+
+fn slice_one(
+    positions: &[Position],
+    triangles: &[Triangle<usize>],
+    plane: Plane,
+) -> Vec<Vec<Position>> {
+    let d: Vec<f32> = positions
+        .iter()
+        .map(|&p| plane.signed_distance(p))
+        .collect();
+    // vertices in the plane count as being above it, so every triangle
+    // is crossed by either none or two of its edges
+    let above = |i: usize| d[i] >= 0.;
+    let edge = |a: usize, b: usize| (a.min(b), a.max(b));
+
+    let mut next = BTreeMap::new();
+    for t in triangles {
+        let v = [t.x, t.y, t.z];
+        let mut exit = None;
+        let mut enter = None;
+        for k in 0..3 {
+            let (a, b) = (v[k], v[(k + 1) % 3]);
+            match (above(a), above(b)) {
+                (true, false) => exit = Some(edge(a, b)),
+                (false, true) => enter = Some(edge(a, b)),
+                _ => {}
+            }
+        }
+        if let (Some(exit), Some(enter)) = (exit, enter) {
+            next.insert(exit, enter);
+        }
+    }
+
+    let point = |(a, b): (usize, usize)| {
+        let (pa, pb) = (Vector3::from(positions[a]), Vector3::from(positions[b]));
+        let t = d[a] / (d[a] - d[b]);
+        pa + (pb - pa) * t
+    };
+    chain_loops(next)
+        .into_iter()
+        .map(|ring| {
+            let mut points: Vec<Vector3<f32>> = Vec::with_capacity(ring.len());
+            for p in ring.into_iter().map(point) {
+                // vertices lying in the plane are reached from two edges
+                if points.last().is_none_or(|&q| (p - q).magnitude2() > 0.) {
+                    points.push(p);
+                }
+            }
+            if points.len() > 1 && points[0] == points[points.len() - 1] {
+                points.pop();
+            }
+            points.into_iter().map(Into::into).collect()
+        })
+        .filter(|ring: &Vec<Position>| ring.len() >= 3)
+        .collect()
+}
@@ -0,0 +1,89 @@
+//! Turning open surfaces into solids with a thickness.
+
+use cgmath::Vector3;
+
+use crate::math;
+use crate::{Neighbors, Triangle, Vertex};
+
+/// Give the surface of an indexed triangle mesh a `thickness`, turning
+/// open surfaces like planes, lathes and text caps into closed solids that
+/// can be printed or used in CSG.
+///
+/// A copy of the surface is added `thickness` behind it, against the
+/// normals of its vertices, facing the other way. A negative thickness
+/// builds it in front instead, and turns the whole solid around so that
+/// it still faces out. Each open boundary of the surface is then
+/// joined to its copy by a rim of quads, split into triangles, which gets
+/// its own vertices so that it is flat shaded. Vertices with no normal are
+/// moved along the average normal of their triangles.
+///
+/// The surface has to share its vertices along its edges, see `weld`.
+/// Returns the number of rims built, which is the number of boundary
+/// loops of the surface.
+pub fn solidify(
+    vertices: &mut Vec<Vertex>,
+    triangles: &mut Vec<Triangle<usize>>,
+    thickness: f32,
+) -> usize {
+    let loops = {
+        let neighbors = Neighbors::new(Vec::<()>::new(), std::mem::take(triangles));
+        let loops = neighbors.boundary_loops();
+        *triangles = neighbors.split().1;
+        loops
+    };
+
+    let count = vertices.len();
+    let mut fallback = vec![Vector3::new(0., 0., 0.); count];
+    for t in triangles.iter() {
+        let p = |i: usize| Vector3::from(vertices[i].pos);
+        let n = (p(t.y) - p(t.x)).cross(p(t.z) - p(t.x));
+        for i in [t.x, t.y, t.z] {
+            fallback[i] += n;
+        }
+    }
+    for i in 0..count {
+        let v = vertices[i];
+        let n = math::try_normalize(Vector3::from(v.normal))
+            .or_else(|| math::try_normalize(fallback[i]))
+            .unwrap_or_else(|| Vector3::new(0., 0., 0.));
+        vertices.push(Vertex {
+            pos: (Vector3::from(v.pos) - n * thickness).into(),
+            normal: (-n).into(),
+        });
+    }
+    let back: Vec<Triangle<usize>> = triangles
+        .iter()
+        .map(|t| Triangle::new(t.x + count, t.z + count, t.y + count))
+        .collect();
+    triangles.extend(back);
+
+    // boundary loops follow the winding of the faces along them, the rim
+    // goes back along each edge
+    for ring in &loops {
+        for k in 0..ring.len() {
+            let (a, b) = (ring[k], ring[(k + 1) % ring.len()]);
+            let p = [b, a, a + count, b + count].map(|i| Vector3::from(vertices[i].pos));
+            let normal = math::try_normalize((p[1] - p[0]).cross(p[3] - p[0]))
+                .or_else(|| math::try_normalize((p[2] - p[1]).cross(p[0] - p[1])))
+                .unwrap_or_else(|| Vector3::new(0., 0., 0.));
+            let base = vertices.len();
+            vertices.extend(p.iter().map(|&p| Vertex {
+                pos: p.into(),
+                normal: normal.into(),
+            }));
+            triangles.push(Triangle::new(base, base + 1, base + 2));
+            triangles.push(Triangle::new(base, base + 2, base + 3));
+        }
+    }
+
+    // built in front, the solid is inside out
+    if thickness < 0. {
+        for t in triangles.iter_mut() {
+            *t = Triangle::new(t.x, t.z, t.y);
+        }
+        for v in vertices.iter_mut() {
+            v.normal = (-Vector3::from(v.normal)).into();
+        }
+    }
+    loops.len()
+}
@@ -0,0 +1,348 @@
+//! Search structures over points, to find those near a position without
+//! going through all of them.
+
+use std::collections::HashMap;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::analytic::Aabb;
+use crate::Position;
+
+/// A uniform grid of cubic cells over points, bucketing each point in
+/// the cell it falls in.
+///
+/// Points are identified by the order they were inserted in, from zero.
+/// Queries are fastest when the cells are about as large as the radius
+/// searched, and only the cells that exist are stored, so the points can
+/// be spread out as far as they like.
+#[derive(Clone, Debug)]
+pub struct SpatialGrid {
+    cell_size: f32,
+    points: Vec<Position>,
+    cells: HashMap<[i64; 3], Vec<usize>>,
+    // the range of cells holding points, to know when the search for the
+    // nearest point has seen them all
+    min: [i64; 3],
+    max: [i64; 3],
+}
+
+impl SpatialGrid {
+    /// Create an empty grid of cells `cell_size` wide.
+    pub fn new(cell_size: f32) -> Self {
+        assert!(cell_size > 0.);
+        SpatialGrid {
+            cell_size,
+            points: Vec::new(),
+            cells: HashMap::new(),
+            min: [i64::MAX; 3],
+            max: [i64::MIN; 3],
+        }
+    }
+
+    /// Create a grid of cells `cell_size` wide holding `points`.
+    pub fn from_points<I>(cell_size: f32, points: I) -> Self
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        let mut grid = Self::new(cell_size);
+        for p in points {
+            grid.insert(p);
+        }
+        grid
+    }
+
+    /// Add a point to the grid, and return its index.
+    pub fn insert(&mut self, point: Position) -> usize {
+        let index = self.points.len();
+        let cell = self.cell(point);
+        for (k, &c) in cell.iter().enumerate() {
+            self.min[k] = self.min[k].min(c);
+            self.max[k] = self.max[k].max(c);
+        }
+        self.cells.entry(cell).or_default().push(index);
+        self.points.push(point);
+        index
+    }
+
+    /// The number of points in the grid.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether there are no points in the grid.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The points of the grid, in the order they were inserted.
+    pub fn points(&self) -> &[Position] {
+        &self.points
+    }
+
+    /// The indices of the points within `radius` of `point`, in the order
+    /// they were inserted.
+    pub fn query_radius(&self, point: Position, radius: f32) -> Vec<usize> {
+        let mut found = Vec::new();
+        if radius < 0. || self.is_empty() {
+            return found;
+        }
+        let (lo, hi) = (
+            self.cell([point.x - radius, point.y - radius, point.z - radius].into()),
+            self.cell([point.x + radius, point.y + radius, point.z + radius].into()),
+        );
+        let p = Vector3::from(point);
+        for x in lo[0].max(self.min[0])..=hi[0].min(self.max[0]) {
+            for y in lo[1].max(self.min[1])..=hi[1].min(self.max[1]) {
+                for z in lo[2].max(self.min[2])..=hi[2].min(self.max[2]) {
+                    for &i in self.cells.get(&[x, y, z]).into_iter().flatten() {
+                        if (Vector3::from(self.points[i]) - p).magnitude2() <= radius * radius {
+                            found.push(i);
+                        }
+                    }
+                }
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+
+    /// The index of the point nearest to `point` and its distance, or
+    /// `None` if the grid is empty. Of points at the same distance, the
+    /// first inserted is returned.
+    pub fn nearest(&self, point: Position) -> Option<(usize, f32)> {
+        if self.is_empty() {
+            return None;
+        }
+        // wide enough for far away queries not to overflow
+        let c = self.cell(point).map(i128::from);
+        let (min, max) = (self.min.map(i128::from), self.max.map(i128::from));
+        let p = Vector3::from(point);
+        let mut best: Option<(usize, f32)> = None;
+        // search shells of cells around the cell of `point`, from the
+        // first reaching the cells holding points, until the next shell is
+        // farther than the best point found, or beyond all those cells
+        let first = (0..3)
+            .map(|k| (min[k] - c[k]).max(c[k] - max[k]).max(0))
+            .max()
+            .unwrap_or(0);
+        let last = (0..3)
+            .map(|k| (c[k] - min[k]).abs().max((max[k] - c[k]).abs()))
+            .max()
+            .unwrap_or(0);
+        for ring in first..=last {
+            if let Some((_, d)) = best {
+                if d < (ring - 1) as f32 * self.cell_size {
+                    break;
+                }
+            }
+            let range = |k: usize| (c[k] - ring).max(min[k])..=(c[k] + ring).min(max[k]);
+            for x in range(0) {
+                for y in range(1) {
+                    for z in range(2) {
+                        let shell = (x - c[0]).abs().max((y - c[1]).abs()).max((z - c[2]).abs());
+                        if shell != ring {
+                            continue;
+                        }
+                        let cell = [x as i64, y as i64, z as i64];
+                        for &i in self.cells.get(&cell).into_iter().flatten() {
+                            let d = (Vector3::from(self.points[i]) - p).magnitude();
+                            let closer = match best {
+                                Some((j, b)) => d < b || (d == b && i < j),
+                                None => true,
+                            };
+                            if closer {
+                                best = Some((i, d));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    fn cell(&self, point: Position) -> [i64; 3] {
+        [point.x, point.y, point.z].map(|c| (c / self.cell_size).floor() as i64)
+    }
+}
+
+/// A k-d tree over points, splitting them in halves along the axis they
+/// spread most along, for nearest neighbor and range queries.
+///
+/// Unlike a `SpatialGrid` it needs no cell size, and stays fast however
+/// unevenly the points are spread, but it cannot grow: it is built once
+/// from all the points. Points are identified by their index in the slice
+/// the tree was built from.
+#[derive(Clone, Debug)]
+pub struct KdTree {
+    points: Vec<Position>,
+    // point indices, the median of each range being the node splitting it
+    order: Vec<usize>,
+    // the axis each node splits along, at the position of the node
+    axes: Vec<u8>,
+}
+
+impl KdTree {
+    /// Build the tree over `points`.
+    pub fn build(points: &[Position]) -> Self {
+        let mut tree = KdTree {
+            points: points.to_vec(),
+            order: (0..points.len()).collect(),
+            axes: vec![0; points.len()],
+        };
+        tree.split(0, points.len());
+        tree
+    }
+
+    /// The number of points in the tree.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether there are no points in the tree.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The points of the tree, in the order they were given.
+    pub fn points(&self) -> &[Position] {
+        &self.points
+    }
+
+    /// The index of the point nearest to `point` and its distance, or
+    /// `None` if the tree is empty. Of points at the same distance, the
+    /// one with the lowest index is returned.
+    pub fn nearest(&self, point: Position) -> Option<(usize, f32)> {
+        self.k_nearest(point, 1).pop()
+    }
+
+    /// The indices of the `k` points nearest to `point` with their
+    /// distances, from the nearest to the farthest, or all the points if
+    /// there are fewer than `k`. Points at the same distance are ordered
+    /// by index.
+    pub fn k_nearest(&self, point: Position, k: usize) -> Vec<(usize, f32)> {
+        let mut found = Vec::with_capacity(k + 1);
+        if k > 0 {
+            self.search_nearest(0, self.len(), Vector3::from(point), k, &mut found);
+        }
+        found
+            .into_iter()
+            .map(|(i, d2): (usize, f32)| (i, d2.sqrt()))
+            .collect()
+    }
+
+    /// The indices of the points within `radius` of `point`, in
+    /// increasing order.
+    pub fn query_radius(&self, point: Position, radius: f32) -> Vec<usize> {
+        let mut found = Vec::new();
+        if radius >= 0. {
+            let p = Vector3::from(point);
+            self.search_range(0, self.len(), &mut found, &|q, axis| {
+                (
+                    p[axis] - radius,
+                    p[axis] + radius,
+                    (q - p).magnitude2() <= radius * radius,
+                )
+            });
+        }
+        found.sort_unstable();
+        found
+    }
+
+    /// The indices of the points inside of `aabb`, in increasing order.
+    pub fn query_aabb(&self, aabb: &Aabb) -> Vec<usize> {
+        let mut found = Vec::new();
+        let (min, max) = (Vector3::from(aabb.min), Vector3::from(aabb.max));
+        self.search_range(0, self.len(), &mut found, &|q, axis| {
+            (min[axis], max[axis], aabb.contains(q.into()))
+        });
+        found.sort_unstable();
+        found
+    }
+
+    fn split(&mut self, lo: usize, hi: usize) {
+        if hi - lo < 2 {
+            return;
+        }
+        let points = &self.points;
+        let bounds = Aabb::from_points(self.order[lo..hi].iter().map(|&i| points[i])).unwrap();
+        let size = Vector3::from(bounds.size());
+        let axis = if size.x >= size.y && size.x >= size.z {
+            0
+        } else if size.y >= size.z {
+            1
+        } else {
+            2
+        };
+        let mid = (lo + hi) / 2;
+        let coordinate = |i: &usize| Vector3::from(points[*i])[axis];
+        self.order[lo..hi]
+            .select_nth_unstable_by(mid - lo, |a, b| coordinate(a).total_cmp(&coordinate(b)));
+        self.axes[mid] = axis as u8;
+        self.split(lo, mid);
+        self.split(mid + 1, hi);
+    }
+
+    // keeps `found` sorted by squared distance, then index, at most `k`
+    // long
+    fn search_nearest(
+        &self,
+        lo: usize,
+        hi: usize,
+        p: Vector3<f32>,
+        k: usize,
+        found: &mut Vec<(usize, f32)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let i = self.order[mid];
+        let q = Vector3::from(self.points[i]);
+        let d2 = (q - p).magnitude2();
+        let at = found.partition_point(|&(j, e)| e < d2 || (e == d2 && j < i));
+        if at < k {
+            found.insert(at, (i, d2));
+            found.truncate(k);
+        }
+
+        let axis = self.axes[mid] as usize;
+        let offset = p[axis] - q[axis];
+        let (near, far) = if offset < 0. {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.search_nearest(near.0, near.1, p, k, found);
+        // the other side is at least as far as the splitting plane
+        let worst = found.last().map(|&(_, e)| e);
+        if found.len() < k || worst.is_none_or(|e| offset * offset <= e) {
+            self.search_nearest(far.0, far.1, p, k, found);
+        }
+    }
+
+    // `test` gives the extent of the range along an axis, and whether a
+    // point is in it
+    fn search_range<F>(&self, lo: usize, hi: usize, found: &mut Vec<usize>, test: &F)
+    where
+        F: Fn(Vector3<f32>, usize) -> (f32, f32, bool),
+    {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let i = self.order[mid];
+        let q = Vector3::from(self.points[i]);
+        let axis = self.axes[mid] as usize;
+        let (min, max, inside) = test(q, axis);
+        if inside {
+            found.push(i);
+        }
+        if min <= q[axis] {
+            self.search_range(lo, mid, found, test);
+        }
+        if max >= q[axis] {
+            self.search_range(mid + 1, hi, found, test);
+        }
+    }
+}
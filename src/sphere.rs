@@ -1,16 +1,27 @@
 use super::generators::{IndexedPolygon, SharedVertex};
 use super::Polygon::{PolyQuad, PolyTri};
-use super::{Polygon, Quad, Triangle};
-use crate::Vertex;
+use super::{MapVertex, Polygon, Quad, Triangle};
+use crate::{InsideOut, Vertex};
 use std::f32::consts::PI;
 
 /// Represents a sphere with radius of 1, centered at (0, 0, 0)
+///
+/// The rings around the poles are fans of triangles, emitted as `PolyTri`,
+/// sharing a single vertex at each pole, rather than quads with two corners
+/// on the pole, so no zero area triangle comes out of triangulation.
+///
+/// Only part of the sphere can be generated, by restricting the angle
+/// `phi` around the z axis, measured from the +x axis, and the angle
+/// `theta` from the +z pole. Domes, orange slices and other patches then
+/// have vertices on their edges, and fans only at the poles they include.
 #[derive(Clone, Copy)]
 pub struct SphereUv {
-    u: usize,
-    v: usize,
+    idx: usize,
+    end: usize,
     sub_u: usize,
     sub_v: usize,
+    phi: [f32; 2],
+    theta: [f32; 2],
 }
 
 impl SphereUv {
@@ -20,16 +31,43 @@ impl SphereUv {
     pub fn new(u: usize, v: usize) -> Self {
         assert!(u > 1 && v > 1);
         SphereUv {
-            u: 0,
-            v: 0,
+            idx: 0,
+            end: u * v,
             sub_u: u,
             sub_v: v,
+            phi: [0., PI * 2.],
+            theta: [0., PI],
         }
     }
 
+    /// Only generate the part of the sphere between the angles `min` and
+    /// `max` around the z axis. The `u` points are spread over this range.
+    pub fn with_phi(mut self, min: f32, max: f32) -> Self {
+        assert!(min < max && max - min <= PI * 2.);
+        self.phi = [min, max];
+        self
+    }
+
+    /// Only generate the part of the sphere between the angles `min` and
+    /// `max` from the +z pole, which are between 0 and pi. The `v` points
+    /// are spread over this range.
+    pub fn with_theta(mut self, min: f32, max: f32) -> Self {
+        assert!(0. <= min && min < max && max <= PI);
+        self.theta = [min, max];
+        self
+    }
+
+    /// Turn the sphere inside out, to be seen from within like an
+    /// environment dome.
+    pub fn inside_out(self) -> InsideOut<Self> {
+        InsideOut::new(self)
+    }
+
     fn vert(&self, u: usize, v: usize) -> Vertex {
-        let u = (u as f32 / self.sub_u as f32) * PI * 2.;
-        let v = (v as f32 / self.sub_v as f32) * PI;
+        let [phi0, phi1] = self.phi;
+        let [theta0, theta1] = self.theta;
+        let u = phi0 + (u as f32 / self.sub_u as f32) * (phi1 - phi0);
+        let v = theta0 + (v as f32 / self.sub_v as f32) * (theta1 - theta0);
 
         let p = [u.cos() * v.sin(), u.sin() * v.sin(), v.cos()];
         Vertex {
@@ -37,87 +75,114 @@ impl SphereUv {
             normal: p.into(),
         }
     }
+
+    fn north_pole(&self) -> bool {
+        self.theta[0] <= 0.
+    }
+
+    fn south_pole(&self) -> bool {
+        self.theta[1] >= PI
+    }
+
+    // whether the last points around each ring wrap around to the first
+    fn wraps(&self) -> bool {
+        self.phi[1] - self.phi[0] >= PI * 2.
+    }
+
+    // the number of shared vertices in each ring, other than at the poles
+    fn ring_len(&self) -> usize {
+        if self.wraps() {
+            self.sub_u
+        } else {
+            self.sub_u + 1
+        }
+    }
+
+    // the shared vertex at `u` around ring `v`
+    fn index(&self, u: usize, v: usize) -> usize {
+        let north = self.north_pole() as usize;
+        if v == 0 && self.north_pole() {
+            0
+        } else if v == self.sub_v && self.south_pole() {
+            self.shared_vertex_count() - 1
+        } else {
+            // mathematically, reaching `u == sub_u` should trivially resolve,
+            // because sin(2pi) == sin(0), but rounding errors go in the way.
+            let u = if self.wraps() { u % self.sub_u } else { u };
+            north + (v - north) * self.ring_len() + u
+        }
+    }
+
+    fn polygon(&self, idx: usize) -> Polygon<Vertex> {
+        self.indexed_polygon(idx)
+            .map_vertex(|i| self.shared_vertex(i))
+    }
 }
 
 impl Iterator for SphereUv {
     type Item = Polygon<Vertex>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.u == self.sub_u {
-            self.u = 0;
-            self.v += 1;
-            if self.v == self.sub_v {
-                return None;
-            }
-        }
-
-        // mathematically, reaching `u + 1 == sub_u` should trivially resolve,
-        // because sin(2pi) == sin(0), but rounding errors go in the way.
-        let u1 = (self.u + 1) % self.sub_u;
-
-        let x = self.vert(self.u, self.v);
-        let y = self.vert(self.u, self.v + 1);
-        let z = self.vert(u1, self.v + 1);
-        let w = self.vert(u1, self.v);
-        let v = self.v;
-        self.u += 1;
-
-        Some(if v == 0 {
-            PolyTri(Triangle::new(x, y, z))
-        } else if v == self.sub_v - 1 {
-            // overriding z to force u == 0 for consistency
-            let z = self.vert(0, self.sub_v);
-            PolyTri(Triangle::new(z, w, x))
+        if self.idx < self.end {
+            self.idx += 1;
+            Some(self.polygon(self.idx - 1))
         } else {
-            PolyQuad(Quad::new(x, y, z, w))
-        })
+            None
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let n = (self.sub_v - self.v) * self.sub_u + (self.sub_u - self.u);
+        let n = self.end - self.idx;
         (n, Some(n))
     }
 }
 
+impl DoubleEndedIterator for SphereUv {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            Some(self.polygon(self.end))
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for SphereUv {}
+
 impl SharedVertex<Vertex> for SphereUv {
     fn shared_vertex(&self, idx: usize) -> Vertex {
-        if idx == 0 {
+        let north = self.north_pole() as usize;
+        if idx == 0 && self.north_pole() {
             self.vert(0, 0)
-        } else if idx == self.shared_vertex_count() - 1 {
+        } else if idx == self.shared_vertex_count() - 1 && self.south_pole() {
             self.vert(0, self.sub_v)
         } else {
-            // since the bottom verts all map to the same
+            // since the pole verts all map to the same
             // we jump over them in index space
-            let idx = idx - 1;
-            let u = idx % self.sub_u;
-            let v = idx / self.sub_u;
-            self.vert(u, v + 1)
+            let idx = idx - north;
+            let u = idx % self.ring_len();
+            let v = idx / self.ring_len();
+            self.vert(u, v + north)
         }
     }
 
     fn shared_vertex_count(&self) -> usize {
-        (self.sub_v - 1) * (self.sub_u) + 2
+        let poles = self.north_pole() as usize + self.south_pole() as usize;
+        (self.sub_v + 1 - poles) * self.ring_len() + poles
     }
 }
 
 impl IndexedPolygon<Polygon<usize>> for SphereUv {
     fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
-        let f = |u: usize, v: usize| {
-            if v == 0 {
-                0
-            } else if self.sub_v == v {
-                (self.sub_v - 1) * (self.sub_u) + 1
-            } else {
-                (v - 1) * self.sub_u + (u % self.sub_u) + 1
-            }
-        };
+        let f = |u: usize, v: usize| self.index(u, v);
 
         let u = idx % self.sub_u;
         let v = idx / self.sub_u;
 
-        if v == 0 {
+        if v == 0 && self.north_pole() {
             PolyTri(Triangle::new(f(u, v), f(u, v + 1), f(u + 1, v + 1)))
-        } else if self.sub_v - 1 == v {
+        } else if self.sub_v - 1 == v && self.south_pole() {
             PolyTri(Triangle::new(f(u + 1, v + 1), f(u + 1, v), f(u, v)))
         } else {
             PolyQuad(Quad::new(
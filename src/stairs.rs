@@ -0,0 +1,141 @@
+//! Stairs and ramps, for blocking out levels
+
+use cgmath::Vector3;
+
+use crate::flat::{flat_faces_generator, FlatFaces};
+
+/// A flight of stairs, climbing along +y and rising along +z from the
+/// origin, `width` wide across x and centered on it.
+///
+/// Each step is a riser facing -y and a tread facing +z, in that order,
+/// from the bottom step to the top one. The sides, and the back and the
+/// bottom, can be added to close the stairs into a solid; the sides come
+/// after the steps, one quad per step on each side, then the back and the
+/// bottom. Every face has its own vertices, so that it is flat shaded.
+#[derive(Clone, Debug)]
+pub struct Stairs {
+    steps: usize,
+    rise: f32,
+    run: f32,
+    width: f32,
+    sides: bool,
+    back: bool,
+    faces: FlatFaces,
+}
+
+impl Stairs {
+    /// Create `steps` steps, each `rise` high and `run` deep, with no
+    /// sides and no back.
+    pub fn new(steps: usize, rise: f32, run: f32, width: f32) -> Self {
+        assert!(steps > 0 && rise > 0. && run > 0. && width > 0.);
+        let mut stairs = Stairs {
+            steps,
+            rise,
+            run,
+            width,
+            sides: false,
+            back: false,
+            faces: FlatFaces::default(),
+        };
+        stairs.build();
+        stairs
+    }
+
+    /// Close the sides of the stairs.
+    pub fn with_sides(mut self) -> Self {
+        self.sides = true;
+        self.build();
+        self
+    }
+
+    /// Close the back and the bottom of the stairs. Along with the sides,
+    /// this makes the stairs a closed solid.
+    pub fn with_back(mut self) -> Self {
+        self.back = true;
+        self.build();
+        self
+    }
+
+    fn build(&mut self) {
+        let (w, rise, run) = (self.width / 2., self.rise, self.run);
+        let (length, height) = (self.steps as f32 * run, self.steps as f32 * rise);
+        let p = |x: f32, y: f32, z: f32| Vector3::new(x, y, z);
+        let mut faces = FlatFaces::default();
+        for i in 0..self.steps {
+            let (y0, y1) = (i as f32 * run, (i + 1) as f32 * run);
+            let (z0, z1) = (i as f32 * rise, (i + 1) as f32 * rise);
+            faces.add(&[p(-w, y0, z0), p(w, y0, z0), p(w, y0, z1), p(-w, y0, z1)]);
+            faces.add(&[p(-w, y0, z1), p(w, y0, z1), p(w, y1, z1), p(-w, y1, z1)]);
+        }
+        if self.sides {
+            // the outline of a side is seen whole from the bottom of the
+            // back, so it is cut into quads around that corner, which join
+            // the edges of the steps, the back and the bottom exactly
+            for x in [w, -w] {
+                for i in 0..self.steps {
+                    let (y0, y1) = (i as f32 * run, (i + 1) as f32 * run);
+                    let (z0, z1) = (i as f32 * rise, (i + 1) as f32 * rise);
+                    let mut quad = [p(x, length, 0.), p(x, y0, z0), p(x, y0, z1), p(x, y1, z1)];
+                    if x > 0. {
+                        quad.reverse();
+                    }
+                    faces.add(&quad);
+                }
+            }
+        }
+        if self.back {
+            faces.add(&[
+                p(w, length, 0.),
+                p(-w, length, 0.),
+                p(-w, length, height),
+                p(w, length, height),
+            ]);
+            faces.add(&[
+                p(-w, 0., 0.),
+                p(-w, length, 0.),
+                p(w, length, 0.),
+                p(w, 0., 0.),
+            ]);
+        }
+        self.faces = faces;
+    }
+}
+
+/// A wedge climbing along +y and rising along +z from the origin, `width`
+/// wide across x and centered on it.
+///
+/// The faces are the slope, the back, the bottom, and the two triangular
+/// sides, each with its own vertices so that it is flat shaded.
+#[derive(Clone, Debug)]
+pub struct Ramp {
+    faces: FlatFaces,
+}
+
+impl Ramp {
+    /// Create a ramp rising by `rise` over a length of `run`.
+    pub fn new(rise: f32, run: f32, width: f32) -> Self {
+        assert!(rise > 0. && run > 0. && width > 0.);
+        let w = width / 2.;
+        let p = |x: f32, y: f32, z: f32| Vector3::new(x, y, z);
+        let mut faces = FlatFaces::default();
+        faces.add(&[
+            p(-w, 0., 0.),
+            p(w, 0., 0.),
+            p(w, run, rise),
+            p(-w, run, rise),
+        ]);
+        faces.add(&[
+            p(w, run, 0.),
+            p(-w, run, 0.),
+            p(-w, run, rise),
+            p(w, run, rise),
+        ]);
+        faces.add(&[p(-w, 0., 0.), p(-w, run, 0.), p(w, run, 0.), p(w, 0., 0.)]);
+        faces.add(&[p(w, 0., 0.), p(w, run, 0.), p(w, run, rise)]);
+        faces.add(&[p(-w, 0., 0.), p(-w, run, rise), p(-w, run, 0.)]);
+        Ramp { faces }
+    }
+}
+
+flat_faces_generator!(Stairs);
+flat_faces_generator!(Ramp);
@@ -0,0 +1,163 @@
+//! Reading and writing of triangle meshes in the STL format.
+//!
+//! STL stores independent triangles, each with a facet normal, either as
+//! text or in a compact binary form. Both are supported for writing, and
+//! recognized automatically when reading.
+
+use std::io::{self, Read, Write};
+
+use cgmath::Vector3;
+
+use crate::bounds::EmitPositions;
+use crate::math;
+use crate::{Position, Triangle, Vertex};
+
+/// Write `triangles` as a binary STL file. The facet normals are computed
+/// from the winding of the triangles.
+pub fn write_stl_binary<W, I, V>(writer: W, triangles: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = Triangle<V>>,
+    V: EmitPositions,
+{
+    let facets: Vec<[Vector3<f32>; 4]> = triangles.into_iter().map(facet).collect();
+    let mut w = io::BufWriter::new(writer);
+    let mut header = [0u8; 80];
+    header[..7].copy_from_slice(b"genmesh");
+    w.write_all(&header)?;
+    w.write_all(&(facets.len() as u32).to_le_bytes())?;
+    for f in &facets {
+        for v in f {
+            for &x in &[v.x, v.y, v.z] {
+                w.write_all(&x.to_le_bytes())?;
+            }
+        }
+        // attribute byte count, unused
+        w.write_all(&[0, 0])?;
+    }
+    w.flush()
+}
+
+/// Write `triangles` as a text STL file, naming the solid `name`. The
+/// facet normals are computed from the winding of the triangles.
+pub fn write_stl_ascii<W, I, V>(writer: W, name: &str, triangles: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = Triangle<V>>,
+    V: EmitPositions,
+{
+    let mut w = io::BufWriter::new(writer);
+    writeln!(w, "solid {}", name)?;
+    for t in triangles {
+        let [n, a, b, c] = facet(t);
+        writeln!(w, "facet normal {:e} {:e} {:e}", n.x, n.y, n.z)?;
+        writeln!(w, "  outer loop")?;
+        for v in &[a, b, c] {
+            writeln!(w, "    vertex {:e} {:e} {:e}", v.x, v.y, v.z)?;
+        }
+        writeln!(w, "  endloop")?;
+        writeln!(w, "endfacet")?;
+    }
+    writeln!(w, "endsolid {}", name)?;
+    w.flush()
+}
+
+/// Read the triangles of an STL file, in either the binary or the text
+/// form. Every vertex gets the normal of its facet; missing facet normals
+/// are computed from the winding of the triangle.
+pub fn read_stl<R: Read>(mut reader: R) -> io::Result<Vec<Triangle<Vertex>>> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+
+    // text files start with "solid" too, but the size of a binary file
+    // is fully determined by its triangle count
+    let binary = data.len() >= 84 && {
+        let count = u32::from_le_bytes([data[80], data[81], data[82], data[83]]) as u64;
+        84 + 50 * count == data.len() as u64
+    };
+    if binary {
+        Ok(read_binary(&data))
+    } else if data.starts_with(b"solid") {
+        read_ascii(&data)
+    } else {
+        Err(invalid("not an STL file"))
+    }
+}
+
+fn read_binary(data: &[u8]) -> Vec<Triangle<Vertex>> {
+    data[84..]
+        .chunks_exact(50)
+        .map(|f| {
+            let float = |at: usize| f32::from_le_bytes([f[at], f[at + 1], f[at + 2], f[at + 3]]);
+            let vector = |at: usize| Vector3::new(float(at), float(at + 4), float(at + 8));
+            triangle(vector(0), [vector(12), vector(24), vector(36)])
+        })
+        .collect()
+}
+
+fn read_ascii(data: &[u8]) -> io::Result<Vec<Triangle<Vertex>>> {
+    let text = std::str::from_utf8(data).map_err(|_| invalid("STL text is not UTF-8"))?;
+    let mut tokens = text.split_whitespace();
+
+    let mut triangles = Vec::new();
+    let mut normal = Vector3::new(0., 0., 0.);
+    let mut corners = Vec::with_capacity(3);
+    while let Some(token) = tokens.next() {
+        match token {
+            "normal" => normal = parse_vector(&mut tokens)?,
+            "vertex" => corners.push(parse_vector(&mut tokens)?),
+            "endfacet" => {
+                if corners.len() != 3 {
+                    return Err(invalid("STL facet without three vertices"));
+                }
+                triangles.push(triangle(normal, [corners[0], corners[1], corners[2]]));
+                corners.clear();
+            }
+            _ => {}
+        }
+    }
+    Ok(triangles)
+}
+
+fn parse_vector<'a, I>(tokens: &mut I) -> io::Result<Vector3<f32>>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut c = [0f32; 3];
+    for x in c.iter_mut() {
+        *x = tokens
+            .next()
+            .and_then(|t| t.parse().ok())
+            .ok_or_else(|| invalid("malformed STL number"))?;
+    }
+    Ok(Vector3::new(c[0], c[1], c[2]))
+}
+
+fn triangle(normal: Vector3<f32>, [a, b, c]: [Vector3<f32>; 3]) -> Triangle<Vertex> {
+    let normal = math::try_normalize(normal)
+        .or_else(|| math::try_normalize((b - a).cross(c - a)))
+        .unwrap_or_else(Vector3::unit_z);
+    let v = |p: Vector3<f32>| Vertex {
+        pos: p.into(),
+        normal: normal.into(),
+    };
+    Triangle::new(v(a), v(b), v(c))
+}
+
+// the facet normal followed by the three corners
+fn facet<V: EmitPositions>(t: Triangle<V>) -> [Vector3<f32>; 4] {
+    let mut p: Vec<Position> = Vec::with_capacity(3);
+    t.emit_positions(|x| p.push(x));
+    let (a, b, c) = (
+        Vector3::from(p[0]),
+        Vector3::from(p[1]),
+        Vector3::from(p[2]),
+    );
+    let n = (b - a).cross(c - a);
+    let n = math::try_normalize(n).unwrap_or(n);
+    [n, a, b, c]
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,66 @@
+//! Index buffers for strip primitives, separated by primitive restarts.
+//!
+//! Pipelines with primitive restart enabled start a new strip whenever the
+//! restart index is found in the index buffer, which draws many strips in
+//! a single call. `IndexType::MAX` is the value graphics APIs use for it.
+
+use crate::{IndexType, Line, Polygon};
+
+/// Build a line strip index buffer from a stream of lines. A strip goes on
+/// as long as each line starts where the previous one ended, so the lines
+/// of polygons, as produced by `Lines`, give one closed strip per polygon.
+///
+/// Returns `None` if an index does not fit in `I`, or is `restart` itself.
+pub fn line_strip_indices<T, I>(lines: T, restart: I) -> Option<Vec<I>>
+where
+    T: IntoIterator<Item = Line<usize>>,
+    I: IndexType,
+{
+    let index = |i: usize| I::try_from_usize(i).filter(|&i| i != restart);
+    let mut out = Vec::new();
+    let mut last = None;
+    for line in lines {
+        if last != Some(line.x) {
+            if last.is_some() {
+                out.push(restart);
+            }
+            out.push(index(line.x)?);
+        }
+        out.push(index(line.y)?);
+        last = Some(line.y);
+    }
+    Some(out)
+}
+
+/// Build a triangle strip index buffer with one strip for each polygon.
+/// The strips cover the polygons with the same winding as `EmitTriangles`,
+/// though they may split them along other diagonals.
+///
+/// Returns `None` if an index does not fit in `I`, or is `restart` itself.
+pub fn triangle_strip_indices<T, I>(polygons: T, restart: I) -> Option<Vec<I>>
+where
+    T: IntoIterator<Item = Polygon<usize>>,
+    I: IndexType,
+{
+    let index = |i: usize| I::try_from_usize(i).filter(|&i| i != restart);
+    let mut out = Vec::new();
+    for (n, polygon) in polygons.into_iter().enumerate() {
+        if n > 0 {
+            out.push(restart);
+        }
+        let verts: Vec<usize> = polygon.as_vertices().collect();
+        // zigzag between both ends, which keeps the winding of a convex
+        // polygon since strips flip every other triangle
+        let (mut lo, mut hi) = (1, verts.len() - 1);
+        out.push(index(verts[0])?);
+        while lo <= hi {
+            out.push(index(verts[lo])?);
+            lo += 1;
+            if lo <= hi {
+                out.push(index(verts[hi])?);
+                hi -= 1;
+            }
+        }
+    }
+    Some(out)
+}
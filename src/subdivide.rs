@@ -0,0 +1,257 @@
+//! Splitting the polygons of a stream into smaller ones.
+
+use std::collections::VecDeque;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::math;
+use crate::Polygon::{PolyNGon, PolyQuad, PolyTri};
+use crate::{EmitTriangles, Polygon, Quad, Triangle, Vertex};
+
+/// Splits the polygons of a stream, for meshes dense enough for vertex
+/// lighting, displacement or physics.
+pub trait Subdivide: Sized {
+    /// Split every polygon `n` times: triangles into four triangles, from
+    /// the middle of their edges, and other polygons into a quad for each
+    /// corner, from the middle of their edges and their center.
+    fn subdivide(self, n: usize) -> SubdivideIterator<Self, fn(Vertex) -> Vertex> {
+        fn keep(v: Vertex) -> Vertex {
+            v
+        }
+        self.subdivide_with(n, keep)
+    }
+
+    /// Like `subdivide`, passing every new vertex through `f`, for example
+    /// to move it back onto a sphere or a distance field. The vertices in
+    /// the middle of an edge are made once for each polygon along it, so
+    /// `f` should only depend on the vertex it is given.
+    fn subdivide_with<F>(self, n: usize, f: F) -> SubdivideIterator<Self, F>
+    where
+        F: FnMut(Vertex) -> Vertex;
+
+    /// Split every polygon with an edge longer than `max_len`, until no
+    /// edge is. Long edges are cut in half, again and again, so an edge
+    /// shared by two polygons is cut at the same points on both sides and
+    /// no cracks open between them.
+    ///
+    /// Triangles are split into triangles. Quads are split into two quads
+    /// across a pair of long opposite edges, or into a triangle and a quad
+    /// if only one of them is long. Other polygons are triangulated first
+    /// if they have a long edge. The new vertices are halfway along the
+    /// edges, with the average normal of their ends.
+    fn subdivide_to_edge_length(self, max_len: f32) -> SubdivideToEdgeLengthIterator<Self>;
+}
+
+impl<T> Subdivide for T
+where
+    T: Iterator<Item = Polygon<Vertex>>,
+{
+    fn subdivide_with<F>(self, n: usize, f: F) -> SubdivideIterator<T, F>
+    where
+        F: FnMut(Vertex) -> Vertex,
+    {
+        SubdivideIterator {
+            source: self,
+            levels: n,
+            f,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    fn subdivide_to_edge_length(self, max_len: f32) -> SubdivideToEdgeLengthIterator<T> {
+        assert!(max_len > 0.);
+        SubdivideToEdgeLengthIterator {
+            source: self,
+            max_len,
+            stack: Vec::new(),
+            buffer: VecDeque::new(),
+        }
+    }
+}
+
+/// An iterator splitting polygons a number of times
+pub struct SubdivideIterator<I, F> {
+    source: I,
+    levels: usize,
+    f: F,
+    buffer: VecDeque<Polygon<Vertex>>,
+}
+
+impl<I, F> SubdivideIterator<I, F>
+where
+    F: FnMut(Vertex) -> Vertex,
+{
+    fn split(&mut self, polygon: Polygon<Vertex>) {
+        let mut level = vec![polygon];
+        for _ in 0..self.levels {
+            let mut next = Vec::with_capacity(level.len() * 4);
+            for polygon in level {
+                let vertices: Vec<Vertex> = polygon.as_vertices().collect();
+                let n = vertices.len();
+                let f = &mut self.f;
+                let mid: Vec<Vertex> = (0..n)
+                    .map(|k| f(midpoint(vertices[k], vertices[(k + 1) % n])))
+                    .collect();
+                if n == 3 {
+                    let mid = [mid[0], mid[1], mid[2]];
+                    let corners = [vertices[0], vertices[1], vertices[2]];
+                    for [x, y, z] in split_triangle(corners, mid) {
+                        next.push(PolyTri(Triangle::new(x, y, z)));
+                    }
+                    next.push(PolyTri(Triangle::new(mid[0], mid[1], mid[2])));
+                    continue;
+                }
+                let center = f(centroid(&vertices));
+                for k in 0..n {
+                    next.push(PolyQuad(Quad::new(
+                        vertices[k],
+                        mid[k],
+                        center,
+                        mid[(k + n - 1) % n],
+                    )));
+                }
+            }
+            level = next;
+        }
+        self.buffer.extend(level);
+    }
+}
+
+/// Cut a triangle into the three triangles at its corners, given the
+/// middles of its edges, the first going from the first corner to the
+/// second; the fourth is the triangle of the middles, `mid` itself.
+pub(crate) fn split_triangle<T: Copy>(corners: [T; 3], mid: [T; 3]) -> [[T; 3]; 3] {
+    [
+        [corners[0], mid[0], mid[2]],
+        [corners[1], mid[1], mid[0]],
+        [corners[2], mid[2], mid[1]],
+    ]
+}
+
+impl<I, F> Iterator for SubdivideIterator<I, F>
+where
+    I: Iterator<Item = Polygon<Vertex>>,
+    F: FnMut(Vertex) -> Vertex,
+{
+    type Item = Polygon<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, _) = self.source.size_hint();
+        let per = 4usize.saturating_pow(self.levels as u32);
+        (
+            lo.saturating_mul(per).saturating_add(self.buffer.len()),
+            None,
+        )
+    }
+
+    fn next(&mut self) -> Option<Polygon<Vertex>> {
+        loop {
+            if let Some(p) = self.buffer.pop_front() {
+                return Some(p);
+            }
+            let polygon = self.source.next()?;
+            self.split(polygon);
+        }
+    }
+}
+
+/// An iterator splitting polygons until their edges are short enough
+pub struct SubdivideToEdgeLengthIterator<I> {
+    source: I,
+    max_len: f32,
+    // polygons still to be checked, the next one last
+    stack: Vec<Polygon<Vertex>>,
+    buffer: VecDeque<Polygon<Vertex>>,
+}
+
+impl<I> SubdivideToEdgeLengthIterator<I> {
+    fn split(&mut self, polygon: Polygon<Vertex>) {
+        self.stack.push(polygon);
+        while let Some(polygon) = self.stack.pop() {
+            let vertices: Vec<Vertex> = polygon.clone().as_vertices().collect();
+            let n = vertices.len();
+            let length = |k: usize| {
+                (Vector3::from(vertices[(k + 1) % n].pos) - Vector3::from(vertices[k].pos))
+                    .magnitude()
+            };
+            let longest = (0..n).max_by(|&a, &b| length(a).total_cmp(&length(b)));
+            let k = match longest {
+                Some(k) if length(k) > self.max_len => k,
+                _ => {
+                    self.buffer.push_back(polygon);
+                    continue;
+                }
+            };
+            // the pieces, pushed last first
+            match polygon {
+                PolyTri(_) => {
+                    let (a, b, c) = (vertices[k], vertices[(k + 1) % 3], vertices[(k + 2) % 3]);
+                    let m = midpoint(a, b);
+                    self.stack.push(PolyTri(Triangle::new(m, b, c)));
+                    self.stack.push(PolyTri(Triangle::new(a, m, c)));
+                }
+                PolyQuad(_) => {
+                    let [a, b, c, d] = [0, 1, 2, 3].map(|i| vertices[(k + i) % 4]);
+                    let m = midpoint(a, b);
+                    if length((k + 2) % 4) > self.max_len {
+                        let o = midpoint(c, d);
+                        self.stack.push(PolyQuad(Quad::new(m, b, c, o)));
+                        self.stack.push(PolyQuad(Quad::new(a, m, o, d)));
+                    } else {
+                        self.stack.push(PolyQuad(Quad::new(m, b, c, d)));
+                        self.stack.push(PolyTri(Triangle::new(a, m, d)));
+                    }
+                }
+                PolyNGon(_) => {
+                    let mut triangles = Vec::new();
+                    polygon.emit_triangles(|t| triangles.push(PolyTri(t)));
+                    self.stack.extend(triangles.into_iter().rev());
+                }
+            }
+        }
+    }
+}
+
+fn centroid(vertices: &[Vertex]) -> Vertex {
+    let zero = Vector3::new(0., 0., 0.);
+    let pos = vertices.iter().fold(zero, |a, v| a + Vector3::from(v.pos));
+    let normal = vertices
+        .iter()
+        .fold(zero, |a, v| a + Vector3::from(v.normal));
+    let normal = math::try_normalize(normal).unwrap_or_else(|| vertices[0].normal.into());
+    Vertex {
+        pos: (pos / vertices.len() as f32).into(),
+        normal: normal.into(),
+    }
+}
+
+fn midpoint(a: Vertex, b: Vertex) -> Vertex {
+    let normal = Vector3::from(a.normal) + Vector3::from(b.normal);
+    let normal = math::try_normalize(normal).unwrap_or_else(|| a.normal.into());
+    Vertex {
+        pos: ((Vector3::from(a.pos) + Vector3::from(b.pos)) * 0.5).into(),
+        normal: normal.into(),
+    }
+}
+
+impl<I> Iterator for SubdivideToEdgeLengthIterator<I>
+where
+    I: Iterator<Item = Polygon<Vertex>>,
+{
+    type Item = Polygon<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, _) = self.source.size_hint();
+        (lo.saturating_add(self.buffer.len()), None)
+    }
+
+    fn next(&mut self) -> Option<Polygon<Vertex>> {
+        loop {
+            if let Some(p) = self.buffer.pop_front() {
+                return Some(p);
+            }
+            let polygon = self.source.next()?;
+            self.split(polygon);
+        }
+    }
+}
@@ -0,0 +1,539 @@
+//! Landscapes: heightfields raised from noise and worn down by erosion,
+//! and endless ones built tile by tile.
+
+use std::ops::Range;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::generators::{IndexedPolygon, SharedVertex};
+use crate::noise::Fbm;
+use crate::{MapVertex, Normal, Polygon, Position, Quad, Vertex, VertexAttributes};
+
+/// A vertex of a `Terrain`, with how much water flows through it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+pub struct TerrainVertex {
+    /// Vertex position
+    pub pos: Position,
+    /// Vertex normal
+    pub normal: Normal,
+    /// How much rain drains through the vertex, from `0` on ridges to `1`
+    /// at the bottom of the largest river; wet valleys and riverbeds can
+    /// be textured by it
+    pub flow: f32,
+}
+
+impl VertexAttributes for TerrainVertex {
+    fn position(&self) -> Position {
+        self.pos
+    }
+
+    fn normal(&self) -> Option<Normal> {
+        Some(self.normal)
+    }
+
+    fn custom(&self) -> &[f32] {
+        std::slice::from_ref(&self.flow)
+    }
+
+    fn set_normal(&mut self, normal: Normal) {
+        self.normal = normal;
+    }
+}
+
+impl From<TerrainVertex> for Vertex {
+    fn from(v: TerrainVertex) -> Self {
+        Vertex {
+            pos: v.pos,
+            normal: v.normal,
+        }
+    }
+}
+
+/// How `Terrain::with_hydraulic_erosion` rains on the terrain.
+///
+/// Every iteration, rain falls on the whole terrain, the water runs
+/// downhill, dissolving the ground where it runs fast and dropping it
+/// where it slows down, and some of it evaporates. The amounts are in the
+/// units of the heights.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HydraulicErosion {
+    /// The number of iterations
+    pub iterations: usize,
+    /// The water falling on each vertex every iteration
+    pub rain: f32,
+    /// How much sediment a unit of running water can carry
+    pub capacity: f32,
+    /// The fraction of the missing sediment water dissolves from the
+    /// ground every iteration
+    pub solubility: f32,
+    /// The fraction of the excess sediment water drops every iteration
+    pub deposition: f32,
+    /// The fraction of the water that evaporates every iteration
+    pub evaporation: f32,
+}
+
+impl Default for HydraulicErosion {
+    fn default() -> Self {
+        HydraulicErosion {
+            iterations: 50,
+            rain: 0.01,
+            capacity: 0.5,
+            solubility: 0.1,
+            deposition: 0.1,
+            evaporation: 0.05,
+        }
+    }
+}
+
+/// A heightfield over a plane `width` by `depth` wide, centered on the
+/// origin and facing +z, with heights from `Fbm` noise that erosion can
+/// then wear down.
+///
+/// The vertices and quads are laid out as those of `Plane::with_size`,
+/// and the vertices carry how much water flows through them. Normals are
+/// the slopes of the heightfield, estimated from the neighbors of each
+/// vertex.
+#[derive(Clone, Debug)]
+pub struct Terrain {
+    range: Range<usize>,
+    subdivide_x: usize,
+    subdivide_y: usize,
+    width: f32,
+    depth: f32,
+    heights: Vec<f32>,
+    vertices: Vec<TerrainVertex>,
+}
+
+impl Terrain {
+    /// Create a terrain of `x` by `y` quads, `width` by `depth` wide,
+    /// whose height at each vertex is `height` times `noise` sampled at
+    /// the x and y of the vertex.
+    pub fn new(width: f32, depth: f32, x: usize, y: usize, noise: &Fbm, height: f32) -> Self {
+        assert!(x > 0 && y > 0);
+        assert!(width > 0. && depth > 0.);
+        let mut terrain = Terrain {
+            range: 0..x * y,
+            subdivide_x: x,
+            subdivide_y: y,
+            width,
+            depth,
+            heights: Vec::with_capacity((x + 1) * (y + 1)),
+            vertices: Vec::new(),
+        };
+        for j in 0..=y {
+            for i in 0..=x {
+                let [px, py] = terrain.ground(i, j);
+                terrain.heights.push(height * noise.sample2([px, py]));
+            }
+        }
+        terrain.build();
+        terrain
+    }
+
+    /// Carve valleys and riverbeds into the terrain with running water.
+    pub fn with_hydraulic_erosion(mut self, erosion: HydraulicErosion) -> Self {
+        let n = self.heights.len();
+        let neighbors = self.neighbors();
+        let (mut water, mut sediment) = (vec![0f32; n], vec![0f32; n]);
+        for _ in 0..erosion.iterations {
+            for w in &mut water {
+                *w += erosion.rain;
+            }
+            // move the water downhill, towards each lower neighbor in
+            // proportion to how much lower its surface is
+            let (mut next_water, mut next_sediment) = (water.clone(), sediment.clone());
+            let mut moved = vec![0f32; n];
+            for i in 0..n {
+                let surface = self.heights[i] + water[i];
+                let drops: Vec<(usize, f32)> = neighbors[i]
+                    .iter()
+                    .map(|&j| (j, surface - self.heights[j] - water[j]))
+                    .filter(|&(_, d)| d > 0.)
+                    .collect();
+                let total: f32 = drops.iter().map(|d| d.1).sum();
+                let steepest = drops.iter().map(|d| d.1).fold(0., f32::max);
+                // half the steepest drop levels the two surfaces
+                let amount = water[i].min(steepest * 0.5);
+                if amount <= 0. {
+                    continue;
+                }
+                let carried = sediment[i] * amount / water[i];
+                next_water[i] -= amount;
+                next_sediment[i] -= carried;
+                for (j, d) in drops {
+                    next_water[j] += amount * d / total;
+                    next_sediment[j] += carried * d / total;
+                }
+                moved[i] = amount;
+            }
+            water = next_water;
+            sediment = next_sediment;
+
+            for i in 0..n {
+                let capacity = erosion.capacity * moved[i];
+                let change = if sediment[i] > capacity {
+                    -erosion.deposition * (sediment[i] - capacity)
+                } else {
+                    erosion.solubility * (capacity - sediment[i])
+                };
+                self.heights[i] -= change;
+                sediment[i] += change;
+                water[i] *= 1. - erosion.evaporation;
+            }
+        }
+        // what is still carried settles where it is
+        for (h, s) in self.heights.iter_mut().zip(sediment) {
+            *h += s;
+        }
+        self.build();
+        self
+    }
+
+    /// Crumble the slopes steeper than `talus`, in height over distance,
+    /// down onto their lower neighbors, over `iterations` iterations.
+    pub fn with_thermal_erosion(mut self, iterations: usize, talus: f32) -> Self {
+        let neighbors = self.neighbors();
+        let (dx, dy) = self.spacing();
+        for _ in 0..iterations {
+            let mut next = self.heights.clone();
+            for (i, around) in neighbors.iter().enumerate() {
+                let h = self.heights[i];
+                let excess: Vec<(usize, f32)> = around
+                    .iter()
+                    .map(|&j| {
+                        let run = if j + 1 == i || i + 1 == j { dx } else { dy };
+                        (j, h - self.heights[j] - talus * run)
+                    })
+                    .filter(|&(_, e)| e > 0.)
+                    .collect();
+                let total: f32 = excess.iter().map(|e| e.1).sum();
+                let largest = excess.iter().map(|e| e.1).fold(0., f32::max);
+                if total <= 0. {
+                    continue;
+                }
+                // half of the largest excess, shared out as the excesses
+                let amount = largest * 0.5;
+                next[i] -= amount;
+                for (j, e) in excess {
+                    next[j] += amount * e / total;
+                }
+            }
+            self.heights = next;
+        }
+        self.build();
+        self
+    }
+
+    /// The height of each vertex, row by row along x.
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+
+    fn ground(&self, i: usize, j: usize) -> [f32; 2] {
+        [
+            (i as f32 / self.subdivide_x as f32 - 0.5) * self.width,
+            (j as f32 / self.subdivide_y as f32 - 0.5) * self.depth,
+        ]
+    }
+
+    fn spacing(&self) -> (f32, f32) {
+        (
+            self.width / self.subdivide_x as f32,
+            self.depth / self.subdivide_y as f32,
+        )
+    }
+
+    // the vertices next to each vertex along x and y
+    fn neighbors(&self) -> Vec<Vec<usize>> {
+        let (nx, ny) = (self.subdivide_x + 1, self.subdivide_y + 1);
+        (0..nx * ny)
+            .map(|k| {
+                let (i, j) = (k % nx, k / nx);
+                let mut around = Vec::with_capacity(4);
+                if i > 0 {
+                    around.push(k - 1);
+                }
+                if i + 1 < nx {
+                    around.push(k + 1);
+                }
+                if j > 0 {
+                    around.push(k - nx);
+                }
+                if j + 1 < ny {
+                    around.push(k + nx);
+                }
+                around
+            })
+            .collect()
+    }
+
+    fn build(&mut self) {
+        let (nx, ny) = (self.subdivide_x + 1, self.subdivide_y + 1);
+        let (dx, dy) = self.spacing();
+        let flow = self.flow();
+        let h = |i: usize, j: usize| self.heights[j * nx + i];
+        self.vertices = (0..nx * ny)
+            .map(|k| {
+                let (i, j) = (k % nx, k / nx);
+                // the slopes from the neighbors on either side, or the one
+                // side there is at the edges
+                let (i0, i1) = (i.saturating_sub(1), (i + 1).min(nx - 1));
+                let (j0, j1) = (j.saturating_sub(1), (j + 1).min(ny - 1));
+                let sx = (h(i1, j) - h(i0, j)) / ((i1 - i0) as f32 * dx);
+                let sy = (h(i, j1) - h(i, j0)) / ((j1 - j0) as f32 * dy);
+                let [x, y] = self.ground(i, j);
+                TerrainVertex {
+                    pos: [x, y, h(i, j)].into(),
+                    normal: Vector3::new(-sx, -sy, 1.).normalize().into(),
+                    flow: flow[k],
+                }
+            })
+            .collect();
+    }
+
+    // Rain falls on every vertex and drains to its lowest neighbor, from
+    // the highest vertex down; the water through each vertex is brought
+    // to [0, 1] on a log scale, since rivers gather orders of magnitude
+    // more than slopes.
+    fn flow(&self) -> Vec<f32> {
+        let neighbors = self.neighbors();
+        let mut order: Vec<usize> = (0..self.heights.len()).collect();
+        order.sort_by(|&a, &b| self.heights[b].total_cmp(&self.heights[a]));
+        let mut water = vec![1f32; self.heights.len()];
+        for &i in &order {
+            let lowest = neighbors[i]
+                .iter()
+                .cloned()
+                .filter(|&j| self.heights[j] < self.heights[i])
+                .min_by(|&a, &b| self.heights[a].total_cmp(&self.heights[b]));
+            if let Some(j) = lowest {
+                water[j] += water[i];
+            }
+        }
+        let most = water.iter().cloned().fold(1., f32::max);
+        water.iter().map(|w| w.ln() / most.ln().max(1e-6)).collect()
+    }
+
+    fn quad(&self, idx: usize) -> Quad<usize> {
+        let (x, y) = (idx % self.subdivide_x, idx / self.subdivide_x);
+        let base = y * (self.subdivide_x + 1) + x;
+        Quad::new(
+            base,
+            base + 1,
+            base + self.subdivide_x + 2,
+            base + self.subdivide_x + 1,
+        )
+    }
+}
+
+impl Iterator for Terrain {
+    type Item = Polygon<TerrainVertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range
+            .next()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vertices[i]))
+    }
+}
+
+impl DoubleEndedIterator for Terrain {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range
+            .next_back()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vertices[i]))
+    }
+}
+
+impl ExactSizeIterator for Terrain {}
+
+impl SharedVertex<TerrainVertex> for Terrain {
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn shared_vertex(&self, idx: usize) -> TerrainVertex {
+        self.vertices[idx]
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for Terrain {
+    fn indexed_polygon_count(&self) -> usize {
+        self.subdivide_x * self.subdivide_y
+    }
+
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        Polygon::PolyQuad(self.quad(idx))
+    }
+}
+
+/// An endless heightfield cut into square tiles, for worlds too large to
+/// build at once: each tile is built on its own when it is needed, and
+/// fits its neighbors exactly.
+///
+/// Tile `(chunk_x, chunk_y)` covers `chunk_x * size` to `(chunk_x + 1) *
+/// size` along x, and likewise along y. The heights are `height` times the
+/// noise at each point, so a vertex on the border of two tiles has the
+/// same position and normal in both, and there are no cracks or seams in
+/// the shading.
+#[derive(Clone, Copy, Debug)]
+pub struct TerrainChunks {
+    size: f32,
+    resolution: usize,
+    noise: Fbm,
+    height: f32,
+}
+
+/// A side of a `TerrainChunk`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChunkEdge {
+    /// The side towards -x
+    Left,
+    /// The side towards +x
+    Right,
+    /// The side towards -y
+    Bottom,
+    /// The side towards +y
+    Top,
+}
+
+impl TerrainChunks {
+    /// Tiles `size` wide of `resolution` by `resolution` quads, with
+    /// heights from `noise`.
+    pub fn new(size: f32, resolution: usize, noise: Fbm, height: f32) -> Self {
+        assert!(size > 0. && resolution > 0);
+        TerrainChunks {
+            size,
+            resolution,
+            noise,
+            height,
+        }
+    }
+
+    /// Flat tiles `size` wide of `resolution` by `resolution` quads, an
+    /// endless `Plane`.
+    pub fn flat(size: f32, resolution: usize) -> Self {
+        Self::new(size, resolution, Fbm::default(), 0.)
+    }
+
+    /// Build the tile at `(chunk_x, chunk_y)`.
+    pub fn chunk(&self, chunk_x: i32, chunk_y: i32) -> TerrainChunk {
+        let n = self.resolution as i64;
+        let spacing = self.size as f64 / n as f64;
+        // every tile places the points of the grid the same way, so those
+        // on shared borders are computed from the same numbers
+        let coordinate = |g: i64| (g as f64 * spacing) as f32;
+        let height = |gx: i64, gy: i64| {
+            if self.height == 0. {
+                0.
+            } else {
+                self.height * self.noise.sample2([coordinate(gx), coordinate(gy)])
+            }
+        };
+        let (x0, y0) = (chunk_x as i64 * n, chunk_y as i64 * n);
+        let mut vertices = Vec::with_capacity((self.resolution + 1) * (self.resolution + 1));
+        for j in 0..=n {
+            for i in 0..=n {
+                let (gx, gy) = (x0 + i, y0 + j);
+                let sx = (height(gx + 1, gy) - height(gx - 1, gy))
+                    / (coordinate(gx + 1) - coordinate(gx - 1));
+                let sy = (height(gx, gy + 1) - height(gx, gy - 1))
+                    / (coordinate(gy + 1) - coordinate(gy - 1));
+                vertices.push(Vertex {
+                    pos: [coordinate(gx), coordinate(gy), height(gx, gy)].into(),
+                    normal: Vector3::new(-sx, -sy, 1.).normalize().into(),
+                });
+            }
+        }
+        TerrainChunk {
+            range: 0..self.resolution * self.resolution,
+            resolution: self.resolution,
+            vertices,
+        }
+    }
+
+    /// Build the tiles `chunks_x` by `chunks_y` one at a time as the
+    /// iterator is advanced, row by row along x, with their addresses.
+    pub fn chunks(
+        &self,
+        chunks_x: Range<i32>,
+        chunks_y: Range<i32>,
+    ) -> impl Iterator<Item = ((i32, i32), TerrainChunk)> + '_ {
+        chunks_y.flat_map(move |y| chunks_x.clone().map(move |x| ((x, y), self.chunk(x, y))))
+    }
+}
+
+/// A tile of `TerrainChunks`, laid out as a `Plane`: vertices row by row
+/// along x from the corner towards -x and -y, and quads likewise.
+#[derive(Clone, Debug)]
+pub struct TerrainChunk {
+    range: Range<usize>,
+    resolution: usize,
+    vertices: Vec<Vertex>,
+}
+
+impl TerrainChunk {
+    /// The indices of the vertices along `edge`, by increasing x or y.
+    /// They are the same points, in the same order, as those along the
+    /// opposite edge of the neighboring tile, to stitch the tiles into a
+    /// single mesh.
+    pub fn edge_indices(&self, edge: ChunkEdge) -> Vec<usize> {
+        let n = self.resolution + 1;
+        match edge {
+            ChunkEdge::Left => (0..n).map(|j| j * n).collect(),
+            ChunkEdge::Right => (0..n).map(|j| j * n + n - 1).collect(),
+            ChunkEdge::Bottom => (0..n).collect(),
+            ChunkEdge::Top => (0..n).map(|i| (n - 1) * n + i).collect(),
+        }
+    }
+}
+
+impl Iterator for TerrainChunk {
+    type Item = Polygon<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range
+            .next()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vertices[i]))
+    }
+}
+
+impl DoubleEndedIterator for TerrainChunk {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range
+            .next_back()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vertices[i]))
+    }
+}
+
+impl ExactSizeIterator for TerrainChunk {}
+
+impl SharedVertex<Vertex> for TerrainChunk {
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        self.vertices[idx]
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for TerrainChunk {
+    fn indexed_polygon_count(&self) -> usize {
+        self.resolution * self.resolution
+    }
+
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        let n = self.resolution;
+        let base = (idx / n) * (n + 1) + idx % n;
+        Polygon::PolyQuad(Quad::new(base, base + 1, base + n + 2, base + n + 1))
+    }
+}
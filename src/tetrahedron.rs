@@ -1,7 +1,9 @@
+use std::ops::Range;
+
 use cgmath::{InnerSpace, Vector3};
 
 use crate::generators::{IndexedPolygon, SharedVertex};
-use crate::{Polygon, Polygon::PolyTri, Triangle, Vertex};
+use crate::{MapVertex, Polygon, Polygon::PolyTri, Triangle, Vertex};
 
 // from Paul Bourke: http://paulbourke.net/geometry/platonic/
 const VERTICES: [[f32; 3]; 4] = [[1., 1., 1.], [1., -1., -1.], [-1., 1., -1.], [-1., -1., 1.]];
@@ -28,13 +30,15 @@ const FACES: [[usize; 3]; 4] = [[0, 2, 1], [2, 3, 1], [0, 1, 3], [0, 3, 2]];
 
 /// a platonic tetrahedron solid
 pub struct Tetrahedron {
-    i: usize,
+    range: Range<usize>,
 }
 
 impl Tetrahedron {
     /// create a simple tetrahedron with normalized vertices
     pub fn new() -> Self {
-        Self { i: 0 }
+        Self {
+            range: 0..FACES.len(),
+        }
     }
 
     fn vert(&self, idx: usize) -> Vertex {
@@ -46,27 +50,36 @@ impl Tetrahedron {
     }
 }
 
+impl Default for Tetrahedron {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Iterator for Tetrahedron {
     type Item = Polygon<Vertex>;
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (FACES.len(), Some(FACES.len()))
+        self.range.size_hint()
     }
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.i == FACES.len() {
-            return None;
-        }
-
-        let face = FACES[self.i];
-        let va = self.vert(face[0]);
-        let vb = self.vert(face[1]);
-        let vc = self.vert(face[2]);
+        self.range
+            .next()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vert(i)))
+    }
+}
 
-        Some(PolyTri(Triangle::new(va, vb, vc)))
+impl DoubleEndedIterator for Tetrahedron {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range
+            .next_back()
+            .map(|idx| self.indexed_polygon(idx).map_vertex(|i| self.vert(i)))
     }
 }
 
+impl ExactSizeIterator for Tetrahedron {}
+
 impl SharedVertex<Vertex> for Tetrahedron {
     fn shared_vertex_count(&self) -> usize {
         VERTICES.len()
@@ -0,0 +1,414 @@
+//! Extruded text, made from the glyph outlines of a TrueType or OpenType
+//! font.
+//!
+//! The outlines are read with `ttf-parser`, which is re-exported so that
+//! the `Face` passed to `TextMesh` comes from the same version.
+
+use std::ops::Range;
+
+use cgmath::{InnerSpace, Vector3};
+use ttf_parser::{GlyphId, OutlineBuilder};
+
+pub use ttf_parser::{self, Face};
+
+use crate::generators::{IndexedPolygon, SharedVertex};
+use crate::math;
+use crate::{triangulate_polygon, MapVertex, Polygon, Quad, Triangle, Vertex};
+
+// how far the flattened curves may stray from the outline, in ems
+const TOLERANCE: f32 = 0.002;
+
+// the sides are smooth shaded across corners sharper than this, which is
+// the cosine of the angle between the faces
+const SMOOTH: f32 = 0.866;
+
+/// A string of text as a solid, for 3D labels and titles.
+///
+/// The text reads along +x with +y up, starting with the baseline of the
+/// first line at the origin, and sizes are in ems, so that the font size
+/// is one. The back of the letters is at z = 0 and the front at z = `depth`.
+/// Lines are broken at `\n`, and the glyphs are laid out one after the
+/// other by their advance, without kerning or shaping.
+///
+/// The front and back faces are triangles, and the sides quads; each part
+/// has its own vertices so that the edges between them are sharp. A bevel
+/// cuts the edges around the front and back at 45 degrees, insetting the
+/// front and back faces by the bevel width.
+#[derive(Clone, Debug)]
+pub struct TextMesh {
+    range: Range<usize>,
+    vertices: Vec<Vertex>,
+    polygons: Vec<Polygon<usize>>,
+}
+
+impl TextMesh {
+    /// Create the mesh of `text` set in the font `face`, extruded by
+    /// `depth` with a `bevel` wide bevel on the edges. A depth of zero
+    /// gives flat text, made of the front faces only.
+    pub fn new(face: &Face, text: &str, depth: f32, bevel: f32) -> Self {
+        let scale = 1. / f32::from(face.units_per_em());
+        let line_height = f32::from(face.ascender() - face.descender() + face.line_gap()) * scale;
+        let mut outline = Outline {
+            contours: Vec::new(),
+            current: Vec::new(),
+            origin: [0., 0.],
+            scale,
+        };
+        for c in text.chars() {
+            if c == '\n' {
+                outline.origin = [0., outline.origin[1] - line_height];
+                continue;
+            }
+            let glyph = face.glyph_index(c).unwrap_or(GlyphId(0));
+            face.outline_glyph(glyph, &mut outline);
+            outline.close();
+            let advance = face.glyph_hor_advance(glyph).unwrap_or(0);
+            outline.origin[0] += f32::from(advance) * scale;
+        }
+        TextMesh::from_contours(&outline.contours, depth, bevel)
+    }
+
+    /// Create the mesh of any outline, made of closed `contours` in the XY
+    /// plane, extruded like the glyphs of a font. The contours are sorted
+    /// into outlines and holes by how they nest, whatever their winding.
+    pub fn from_contours<C>(contours: &[C], depth: f32, bevel: f32) -> Self
+    where
+        C: AsRef<[[f32; 2]]>,
+    {
+        assert!(depth >= 0. && bevel >= 0.);
+        let mut contours: Vec<Vec<[f32; 2]>> = contours
+            .iter()
+            .map(|c| clean(c.as_ref()))
+            .filter(|c| c.len() >= 3 && signed_area(c) != 0.)
+            .collect();
+
+        // a contour inside an odd number of others is a hole, in the
+        // innermost contour around it
+        let nesting: Vec<Vec<usize>> = (0..contours.len())
+            .map(|i| {
+                (0..contours.len())
+                    .filter(|&j| j != i && contains(&contours[j], contours[i][0]))
+                    .collect()
+            })
+            .collect();
+        let mut shapes: Vec<(usize, Vec<usize>)> = Vec::new();
+        for (i, around) in nesting.iter().enumerate() {
+            let outer = around.len() % 2 == 0;
+            // outlines counter clockwise and holes clockwise, so that the
+            // solid is always on the left
+            if (signed_area(&contours[i]) > 0.) != outer {
+                contours[i].reverse();
+            }
+            if outer {
+                shapes.push((i, Vec::new()));
+            }
+        }
+        for (i, around) in nesting.iter().enumerate() {
+            if around.len() % 2 == 1 {
+                let parent = around
+                    .iter()
+                    .find(|&&j| nesting[j].len() + 1 == around.len())
+                    .cloned();
+                if let Some(shape) = shapes.iter_mut().find(|s| Some(s.0) == parent) {
+                    shape.1.push(i);
+                }
+            }
+        }
+
+        let bevel = bevel.min(depth * 0.5);
+        let insets: Vec<Vec<[f32; 2]>> = contours.iter().map(|c| inset(c, bevel)).collect();
+        let mut mesh = TextMesh {
+            range: 0..0,
+            vertices: Vec::new(),
+            polygons: Vec::new(),
+        };
+        for (outer, holes) in &shapes {
+            let holes: Vec<&[[f32; 2]]> = holes.iter().map(|&h| &insets[h][..]).collect();
+            let triangles = triangulate_polygon(&insets[*outer], &holes);
+            let ring = || {
+                insets[*outer]
+                    .iter()
+                    .chain(holes.iter().flat_map(|h| h.iter()))
+                    .cloned()
+            };
+            mesh.cap(ring(), &triangles, depth, false);
+            if depth > 0. {
+                mesh.cap(ring(), &triangles, 0., true);
+            }
+        }
+        if depth > 0. {
+            for (contour, inset) in contours.iter().zip(&insets) {
+                if bevel > 0. {
+                    mesh.side((inset, 0.), (contour, bevel));
+                    mesh.side((contour, bevel), (contour, depth - bevel));
+                    mesh.side((contour, depth - bevel), (inset, depth));
+                } else {
+                    mesh.side((contour, 0.), (contour, depth));
+                }
+            }
+        }
+        mesh.range = 0..mesh.polygons.len();
+        mesh
+    }
+
+    // a flat face at height `z`, facing -z if it is the back
+    fn cap<I>(&mut self, ring: I, triangles: &[Triangle<usize>], z: f32, back: bool)
+    where
+        I: Iterator<Item = [f32; 2]>,
+    {
+        let base = self.vertices.len();
+        let normal = [0., 0., if back { -1. } else { 1. }];
+        self.vertices.extend(ring.map(|p| Vertex {
+            pos: [p[0], p[1], z].into(),
+            normal: normal.into(),
+        }));
+        self.polygons.extend(triangles.iter().map(|t| {
+            let t = Triangle::new(t.x + base, t.y + base, t.z + base);
+            Polygon::PolyTri(if back {
+                Triangle::new(t.x, t.z, t.y)
+            } else {
+                t
+            })
+        }));
+    }
+
+    // the band of quads between the contour `a` at height `za` and the same
+    // contour, possibly inset, `b` at height `zb` above it
+    fn side(&mut self, (a, za): (&[[f32; 2]], f32), (b, zb): (&[[f32; 2]], f32)) {
+        let n = a.len();
+        let at = |ring: &[[f32; 2]], i: usize, z: f32| {
+            let p = ring[i % n];
+            Vector3::new(p[0], p[1], z)
+        };
+        let normals: Vec<Vector3<f32>> = (0..n)
+            .map(|i| {
+                let (a0, a1, b0) = (at(a, i, za), at(a, i + 1, za), at(b, i, zb));
+                let normal = (a1 - a0).cross(b0 - a0);
+                math::try_normalize(normal).unwrap_or(normal)
+            })
+            .collect();
+        // the normal at the start of edge `i` if `end` is false, else at its
+        // end, smoothed with the next edge over when they are nearly flat
+        let corner = |i: usize, end: bool| {
+            let (own, other) = if end {
+                (normals[i], normals[(i + 1) % n])
+            } else {
+                (normals[i], normals[(i + n - 1) % n])
+            };
+            if own.dot(other) > SMOOTH {
+                (own + other).normalize()
+            } else {
+                own
+            }
+        };
+        for i in 0..n {
+            let base = self.vertices.len();
+            let corners = [
+                (at(a, i, za), corner(i, false)),
+                (at(a, i + 1, za), corner(i, true)),
+                (at(b, i + 1, zb), corner(i, true)),
+                (at(b, i, zb), corner(i, false)),
+            ];
+            self.vertices
+                .extend(corners.iter().map(|&(pos, normal)| Vertex {
+                    pos: pos.into(),
+                    normal: normal.into(),
+                }));
+            self.polygons.push(Polygon::PolyQuad(Quad::new(
+                base,
+                base + 1,
+                base + 2,
+                base + 3,
+            )));
+        }
+    }
+}
+
+// collects the contours of the glyphs, flattening the curves
+struct Outline {
+    contours: Vec<Vec<[f32; 2]>>,
+    current: Vec<[f32; 2]>,
+    // the pen position, in ems
+    origin: [f32; 2],
+    scale: f32,
+}
+
+impl Outline {
+    fn point(&self, x: f32, y: f32) -> [f32; 2] {
+        [
+            self.origin[0] + x * self.scale,
+            self.origin[1] + y * self.scale,
+        ]
+    }
+
+    // add the points of a curve, in as many segments as the tolerance
+    // needs given how far `bend` says the curve is from a straight line
+    fn curve<F: Fn(f32) -> [f32; 2]>(&mut self, bend: f32, at: F) {
+        let segments = (bend / TOLERANCE).sqrt().ceil().clamp(1., 32.) as usize;
+        for k in 1..=segments {
+            self.current.push(at(k as f32 / segments as f32));
+        }
+    }
+}
+
+fn distance(a: [f32; 2], b: [f32; 2]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt()
+}
+
+impl OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        let p = self.point(x, y);
+        self.current.push(p);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.current.push(p);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let p0 = *self.current.last().unwrap_or(&self.origin);
+        let (p1, p2) = (self.point(x1, y1), self.point(x, y));
+        // the largest distance between the curve and its chord
+        let bend = distance(p1, [(p0[0] + p2[0]) * 0.5, (p0[1] + p2[1]) * 0.5]) * 0.5;
+        self.curve(bend, |t| {
+            let s = 1. - t;
+            [
+                s * s * p0[0] + 2. * s * t * p1[0] + t * t * p2[0],
+                s * s * p0[1] + 2. * s * t * p1[1] + t * t * p2[1],
+            ]
+        });
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let p0 = *self.current.last().unwrap_or(&self.origin);
+        let (p1, p2, p3) = (self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+        // bounded by how far the control points are from the chord
+        let chord = |t: f32| [p0[0] + (p3[0] - p0[0]) * t, p0[1] + (p3[1] - p0[1]) * t];
+        let bend = distance(p1, chord(1. / 3.)).max(distance(p2, chord(2. / 3.))) * 0.75;
+        self.curve(bend, |t| {
+            let s = 1. - t;
+            let (a, b, c, d) = (s * s * s, 3. * s * s * t, 3. * s * t * t, t * t * t);
+            [
+                a * p0[0] + b * p1[0] + c * p2[0] + d * p3[0],
+                a * p0[1] + b * p1[1] + c * p2[1] + d * p3[1],
+            ]
+        });
+    }
+
+    fn close(&mut self) {
+        if !self.current.is_empty() {
+            let contour = std::mem::take(&mut self.current);
+            self.contours.push(contour);
+        }
+    }
+}
+
+// drop the repeated points, including a last point closing the contour
+fn clean(contour: &[[f32; 2]]) -> Vec<[f32; 2]> {
+    let mut out: Vec<[f32; 2]> = Vec::with_capacity(contour.len());
+    for &p in contour {
+        if out.last().is_none_or(|&q| distance(p, q) > 1e-6) {
+            out.push(p);
+        }
+    }
+    while out.len() > 1 && distance(out[0], out[out.len() - 1]) <= 1e-6 {
+        out.pop();
+    }
+    out
+}
+
+fn signed_area(contour: &[[f32; 2]]) -> f32 {
+    let mut area = 0.;
+    for k in 0..contour.len() {
+        let (a, b) = (contour[k], contour[(k + 1) % contour.len()]);
+        area += a[0] * b[1] - b[0] * a[1];
+    }
+    area * 0.5
+}
+
+// even-odd test of `p` against the contour
+fn contains(contour: &[[f32; 2]], p: [f32; 2]) -> bool {
+    let mut inside = false;
+    for k in 0..contour.len() {
+        let (a, b) = (contour[k], contour[(k + 1) % contour.len()]);
+        if (a[1] > p[1]) != (b[1] > p[1]) {
+            let x = a[0] + (p[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0]);
+            if p[0] < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+// move each point of the contour by `width` to its left, into the solid
+fn inset(contour: &[[f32; 2]], width: f32) -> Vec<[f32; 2]> {
+    if width == 0. {
+        return contour.to_vec();
+    }
+    let n = contour.len();
+    let left = |k: usize| {
+        let (a, b) = (contour[k % n], contour[(k + 1) % n]);
+        let length = distance(a, b);
+        [(a[1] - b[1]) / length, (b[0] - a[0]) / length]
+    };
+    (0..n)
+        .map(|k| {
+            let (l0, l1) = (left(k + n - 1), left(k));
+            // the miter, which is longer at sharp corners; limited so
+            // that spikes don't shoot across the glyph
+            let scale = (1. / (1. + l0[0] * l1[0] + l0[1] * l1[1]).max(0.125)) * width;
+            let p = contour[k];
+            [
+                p[0] + (l0[0] + l1[0]) * scale,
+                p[1] + (l0[1] + l1[1]) * scale,
+            ]
+        })
+        .collect()
+}
+
+impl Iterator for TextMesh {
+    type Item = Polygon<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range
+            .next()
+            .map(|idx| self.polygons[idx].clone().map_vertex(|i| self.vertices[i]))
+    }
+}
+
+impl DoubleEndedIterator for TextMesh {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range
+            .next_back()
+            .map(|idx| self.polygons[idx].clone().map_vertex(|i| self.vertices[i]))
+    }
+}
+
+impl ExactSizeIterator for TextMesh {}
+
+impl SharedVertex<Vertex> for TextMesh {
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        self.vertices[idx]
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
+impl IndexedPolygon<Polygon<usize>> for TextMesh {
+    fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
+        self.polygons[idx].clone()
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.polygons.len()
+    }
+}
@@ -5,14 +5,23 @@ use cgmath::{InnerSpace, Vector3};
 use super::generators::{IndexedPolygon, SharedVertex};
 use super::{MapVertex, Polygon, Polygon::PolyQuad, Quad, Vertex};
 
+/// Represents a torus centered at (0, 0, 0), its ring lying in the XZ plane
 ///
+/// The ring can be cut short, sweeping only part of the way around for
+/// elbows and arches, optionally closing the ends with flat caps. The tube
+/// can also twist along the ring, which moves its vertices and quads around
+/// it without changing its shape.
 #[derive(Clone, Copy)]
 pub struct Torus {
     idx: usize,
+    end: usize,
     radius: f32,
     tubular_radius: f32,
     radial_segments: usize,
     tubular_segments: usize,
+    arc: f32,
+    twist: f32,
+    caps: [bool; 2],
 }
 
 impl Torus {
@@ -31,42 +40,93 @@ impl Torus {
         assert!(tubular_segments > 2 && radial_segments > 2);
         Torus {
             idx: 0,
+            end: radial_segments * tubular_segments,
             radius,
             tubular_radius,
             radial_segments,
             tubular_segments,
+            arc: 2. * PI,
+            twist: 0.,
+            caps: [false; 2],
         }
     }
-}
 
-impl Iterator for Torus {
-    type Item = Polygon<Vertex>;
+    /// Only sweep the tube `arc` radians around the ring, starting from the
+    /// +x axis. The radial segments are spread over the arc.
+    pub fn with_arc(mut self, arc: f32) -> Self {
+        assert!(arc > 0.);
+        self.arc = arc.min(2. * PI);
+        self.end = self.indexed_polygon_count();
+        self
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.idx < self.indexed_polygon_count() {
-            let idx = self.idx;
-            self.idx += 1;
-            Some(
-                self.indexed_polygon(idx)
-                    .map_vertex(|i| self.shared_vertex(i)),
-            )
+    /// Turn the tube `twist` radians around its center line from one end of
+    /// the sweep to the other. A full torus rounds the twist to a whole
+    /// number of tubular segments, so that its ends still meet.
+    pub fn with_twist(mut self, twist: f32) -> Self {
+        self.twist = twist;
+        self
+    }
+
+    /// Close the `start` and `end` of a partial sweep with flat caps. The
+    /// caps are polygons with a side for each tubular segment, and their
+    /// own vertices, so that they are flat shaded. A full torus has no caps.
+    pub fn with_caps(mut self, start: bool, end: bool) -> Self {
+        self.caps = [start, end];
+        self.end = self.indexed_polygon_count();
+        self
+    }
+
+    fn closed(&self) -> bool {
+        self.arc >= 2. * PI
+    }
+
+    fn rings(&self) -> usize {
+        if self.closed() {
+            self.radial_segments
         } else {
-            None
+            self.radial_segments + 1
         }
     }
-}
 
-impl SharedVertex<Vertex> for Torus {
-    fn shared_vertex(&self, idx: usize) -> Vertex {
-        let (h, u) = (
-            (idx / self.tubular_segments) as f32,
-            (idx % self.tubular_segments) as f32,
-        );
-        let alpha = u * 2. * PI / self.tubular_segments as f32;
-        let beta = h * 2. * PI / self.radial_segments as f32;
+    fn segment_twist(&self) -> f32 {
+        if self.closed() {
+            let step = 2. * PI / self.tubular_segments as f32;
+            (self.twist / step).round() * step
+        } else {
+            self.twist
+        }
+    }
+
+    // the number of vertices of the tube itself
+    fn tube_vertex_count(&self) -> usize {
+        // closed tori have always had one spare vertex
+        self.rings() * self.tubular_segments + self.closed() as usize
+    }
+
+    fn has_cap(&self, end: usize) -> bool {
+        self.caps[end] && !self.closed()
+    }
+
+    // the first vertex of the cap at `end`, 0 or 1
+    fn cap_base(&self, end: usize) -> usize {
+        let before = if end == 1 && self.has_cap(0) {
+            self.tubular_segments
+        } else {
+            0
+        };
+        self.tube_vertex_count() + before
+    }
+
+    // the vertex `u` around the tube, on ring `h`, and the direction the
+    // ring sweeps in at that point
+    fn vert(&self, h: usize, u: usize) -> (Vertex, Vector3<f32>) {
+        let beta = h as f32 * self.arc / self.radial_segments as f32;
+        let alpha = u as f32 * 2. * PI / self.tubular_segments as f32
+            + self.segment_twist() * beta / self.arc;
         let gamma = self.radius + self.tubular_radius * alpha.cos();
 
-        Vertex {
+        let vertex = Vertex {
             pos: [
                 gamma * beta.cos(),
                 self.tubular_radius * alpha.sin(),
@@ -80,37 +140,119 @@ impl SharedVertex<Vertex> for Torus {
             )
             .normalize()
             .into(),
+        };
+        (vertex, Vector3::new(-beta.sin(), 0., -beta.cos()))
+    }
+}
+
+impl Iterator for Torus {
+    type Item = Polygon<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.idx;
+        (n, Some(n))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.idx += 1;
+            Some(
+                self.indexed_polygon(self.idx - 1)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl DoubleEndedIterator for Torus {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            Some(
+                self.indexed_polygon(self.end)
+                    .map_vertex(|i| self.shared_vertex(i)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Torus {}
+
+impl SharedVertex<Vertex> for Torus {
+    fn shared_vertex(&self, idx: usize) -> Vertex {
+        if idx < self.tube_vertex_count() {
+            return self
+                .vert(idx / self.tubular_segments, idx % self.tubular_segments)
+                .0;
+        }
+        // the caps face away from the tube
+        let end = if self.has_cap(1) && idx >= self.cap_base(1) {
+            1
+        } else {
+            0
+        };
+        let u = idx - self.cap_base(end);
+        let h = end * self.radial_segments;
+        let (vertex, tangent) = self.vert(h, u);
+        let normal = if end == 0 { -tangent } else { tangent };
+        Vertex {
+            pos: vertex.pos,
+            normal: normal.into(),
         }
     }
 
     fn shared_vertex_count(&self) -> usize {
-        self.tubular_segments * self.radial_segments + 1
+        let caps = self.has_cap(0) as usize + self.has_cap(1) as usize;
+        self.tube_vertex_count() + caps * self.tubular_segments
     }
 }
 
 impl IndexedPolygon<Polygon<usize>> for Torus {
     fn indexed_polygon(&self, idx: usize) -> Polygon<usize> {
-        // check for wrap around the end end
-        let ncol = if self.indexed_polygon_count() - idx > self.tubular_segments {
-            self.tubular_segments as isize
-        } else {
-            -((self.indexed_polygon_count() - self.tubular_segments) as isize)
-        };
+        let ts = self.tubular_segments;
+        let tube = self.radial_segments * ts;
+        if idx >= tube {
+            let end = if idx == tube && self.has_cap(0) { 0 } else { 1 };
+            let base = self.cap_base(end);
+            // counter-clockwise seen from outside, which is backwards
+            // around the tube at the end of the sweep
+            let ngon = (0..ts)
+                .map(|u| {
+                    if end == 0 {
+                        base + u
+                    } else {
+                        base + ts - 1 - u
+                    }
+                })
+                .collect();
+            return Polygon::PolyNGon(ngon);
+        }
 
-        // check for wrap around the end end
-        let nrow = if idx % self.tubular_segments != self.tubular_segments - 1 {
-            1isize
-        } else {
-            1isize - (self.tubular_segments as isize)
+        let (h, u) = (idx / ts, idx % ts);
+        let index = |h: usize, u: usize| {
+            if h == self.rings() {
+                // back to the first ring, turned by the twist
+                let step = 2. * PI / ts as f32;
+                let shift = (self.segment_twist() / step).round() as isize;
+                (u as isize + shift).rem_euclid(ts as isize) as usize
+            } else {
+                h * ts + u % ts
+            }
         };
-
-        let idx = idx as isize;
-        PolyQuad(
-            Quad::new(idx, idx + ncol, idx + nrow + ncol, idx + nrow).map_vertex(|x| x as usize),
-        )
+        PolyQuad(Quad::new(
+            index(h, u),
+            index(h + 1, u),
+            index(h + 1, u + 1),
+            index(h, u + 1),
+        ))
     }
 
     fn indexed_polygon_count(&self) -> usize {
-        self.tubular_segments * self.radial_segments
+        let caps = self.has_cap(0) as usize + self.has_cap(1) as usize;
+        self.tubular_segments * self.radial_segments + caps
     }
 }
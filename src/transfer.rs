@@ -0,0 +1,140 @@
+//! Carrying vertex attributes over from one mesh to another close to it.
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::attributes::VertexAttributes;
+use crate::bvh::Bvh;
+use crate::math;
+use crate::{Normal, Position, Triangle};
+
+/// The attributes of a source mesh at the point closest to a target
+/// vertex, found by `transfer_attributes`.
+///
+/// Each attribute is blended between the corners of the source triangle
+/// the point lies on, and is `None` unless all three corners have it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transferred {
+    /// Index of the source triangle the point lies on
+    pub triangle: usize,
+    /// The weights of the corners of the triangle at the point, summing
+    /// to one
+    pub barycentric: [f32; 3],
+    /// The closest point on the source mesh
+    pub point: Position,
+    /// Distance between the target vertex and `point`
+    pub distance: f32,
+    /// The source normal, normalized again after blending
+    pub normal: Option<Normal>,
+    /// The source texture coordinates
+    pub uv: Option<[f32; 2]>,
+    /// The source color
+    pub color: Option<[f32; 4]>,
+    /// The other source values, blended one by one. Empty unless all
+    /// three corners have as many.
+    pub custom: Vec<f32>,
+}
+
+/// For each of the `target` vertices, find the closest point on the
+/// `source` triangle mesh and blend the source attributes there, for
+/// baking normals, texture coordinates or colors from a detailed mesh
+/// onto a simplified or remeshed one.
+///
+/// The attributes are returned in the order of the target vertices, to be
+/// written into them as suits their type; all of them are `None` if the
+/// source has no triangles. The search goes through a `Bvh` of the source
+/// triangles, so it takes about logarithmic time per target vertex.
+pub fn transfer_attributes<S, T>(
+    source: &[S],
+    source_triangles: &[Triangle<usize>],
+    target: &[T],
+) -> Vec<Option<Transferred>>
+where
+    S: VertexAttributes,
+    T: VertexAttributes,
+{
+    let corners: Vec<Triangle<Position>> = source_triangles
+        .iter()
+        .map(|t| {
+            Triangle::new(
+                source[t.x].position(),
+                source[t.y].position(),
+                source[t.z].position(),
+            )
+        })
+        .collect();
+    let bvh = Bvh::build(&corners);
+
+    target
+        .iter()
+        .map(|v| {
+            let nearest = bvh.nearest(v.position())?;
+            let t = &source_triangles[nearest.triangle];
+            let c = &corners[nearest.triangle];
+            let w = barycentric(nearest.point, [c.x, c.y, c.z]);
+            let [a, b, c] = [&source[t.x], &source[t.y], &source[t.z]];
+
+            let blend = |x: &[f32], y: &[f32], z: &[f32]| -> Vec<f32> {
+                x.iter()
+                    .zip(y)
+                    .zip(z)
+                    .map(|((x, y), z)| x * w[0] + y * w[1] + z * w[2])
+                    .collect()
+            };
+            let normal = match (a.normal(), b.normal(), c.normal()) {
+                (Some(x), Some(y), Some(z)) => {
+                    let n = blend(&[x.x, x.y, x.z], &[y.x, y.y, y.z], &[z.x, z.y, z.z]);
+                    math::try_normalize(Vector3::new(n[0], n[1], n[2])).map(Into::into)
+                }
+                _ => None,
+            };
+            let uv = match (a.uv(), b.uv(), c.uv()) {
+                (Some(x), Some(y), Some(z)) => {
+                    let uv = blend(&x, &y, &z);
+                    Some([uv[0], uv[1]])
+                }
+                _ => None,
+            };
+            let color = match (a.color(), b.color(), c.color()) {
+                (Some(x), Some(y), Some(z)) => {
+                    let c = blend(&x, &y, &z);
+                    Some([c[0], c[1], c[2], c[3]])
+                }
+                _ => None,
+            };
+            let (x, y, z) = (a.custom(), b.custom(), c.custom());
+            let custom = if x.len() == y.len() && y.len() == z.len() {
+                blend(x, y, z)
+            } else {
+                Vec::new()
+            };
+
+            Some(Transferred {
+                triangle: nearest.triangle,
+                barycentric: w,
+                point: nearest.point,
+                distance: nearest.distance,
+                normal,
+                uv,
+                color,
+                custom,
+            })
+        })
+        .collect()
+}
+
+// The weights of the corners of a triangle at a point lying on it.
+// Degenerate triangles give all the weight to their first corner.
+fn barycentric(point: Position, corners: [Position; 3]) -> [f32; 3] {
+    let [a, b, c] = corners.map(Vector3::from);
+    let p = Vector3::from(point);
+    let (e1, e2, d) = (b - a, c - a, p - a);
+    let (d11, d12, d22) = (e1.dot(e1), e1.dot(e2), e2.dot(e2));
+    let denom = d11 * d22 - d12 * d12;
+    if denom <= f32::EPSILON * d11 * d22 {
+        return [1., 0., 0.];
+    }
+    let (d1, d2) = (d.dot(e1), d.dot(e2));
+    let v = ((d22 * d1 - d12 * d2) / denom).clamp(0., 1.);
+    let w = ((d11 * d2 - d12 * d1) / denom).clamp(0., 1. - v);
+    [1. - v - w, v, w]
+}
@@ -1,7 +1,6 @@
-use std::collections::VecDeque;
-
+use crate::queue::SmallQueue;
 use crate::Polygon::{PolyNGon, PolyQuad, PolyTri};
-use crate::{NGon, Polygon, Quad, Triangle};
+use crate::{ConstNGon, NGon, Polygon, Quad, Triangle};
 
 /// provides a way to convert a polygon down to triangles
 pub trait EmitTriangles {
@@ -23,12 +22,7 @@ impl<T: Clone> EmitTriangles for Quad<T> {
     where
         F: FnMut(Triangle<T>),
     {
-        let &Quad {
-            ref x,
-            ref y,
-            ref z,
-            ref w,
-        } = self;
+        let Quad { x, y, z, w } = self;
         emit(Triangle::new(x.clone(), y.clone(), z.clone()));
         emit(Triangle::new(z.clone(), w.clone(), x.clone()));
     }
@@ -59,7 +53,7 @@ impl<T: Clone> EmitTriangles for NGon<T> {
         let mut v_iter = self.verts.iter();
         if let Some(start) = v_iter.next() {
             if let Some(mut recent) = v_iter.next() {
-                while let Some(vert) = v_iter.next() {
+                for vert in v_iter {
                     emit(Triangle::new(start.clone(), recent.clone(), vert.clone()));
                     recent = vert;
                 }
@@ -68,6 +62,26 @@ impl<T: Clone> EmitTriangles for NGon<T> {
     }
 }
 
+impl<T: Clone, const N: usize> EmitTriangles for ConstNGon<T, N> {
+    type Vertex = T;
+
+    /// triangulates a convex n-sided polygon
+    fn emit_triangles<F>(&self, mut emit: F)
+    where
+        F: FnMut(Triangle<T>),
+    {
+        debug_assert!(N >= 3);
+
+        for i in 2..N {
+            emit(Triangle::new(
+                self.verts[0].clone(),
+                self.verts[i - 1].clone(),
+                self.verts[i].clone(),
+            ));
+        }
+    }
+}
+
 impl<T: Clone> EmitTriangles for Polygon<T> {
     type Vertex = T;
 
@@ -77,9 +91,9 @@ impl<T: Clone> EmitTriangles for Polygon<T> {
         F: FnMut(Triangle<T>),
     {
         match self {
-            &PolyTri(ref t) => t.emit_triangles(emit),
-            &PolyQuad(ref q) => q.emit_triangles(emit),
-            &PolyNGon(ref n) => n.emit_triangles(emit),
+            PolyTri(t) => t.emit_triangles(emit),
+            PolyQuad(q) => q.emit_triangles(emit),
+            PolyNGon(n) => n.emit_triangles(emit),
         }
     }
 }
@@ -101,14 +115,14 @@ impl<V, P: EmitTriangles<Vertex = V>, T: Iterator<Item = P>> Triangulate<T, V> f
 /// Used to iterator of polygons into a iterator of triangles
 pub struct TriangulateIterator<SRC, V> {
     source: SRC,
-    buffer: VecDeque<Triangle<V>>,
+    buffer: SmallQueue<Triangle<V>>,
 }
 
 impl<V, U: EmitTriangles<Vertex = V>, SRC: Iterator<Item = U>> TriangulateIterator<SRC, V> {
     fn new(src: SRC) -> TriangulateIterator<SRC, V> {
         TriangulateIterator {
             source: src,
-            buffer: VecDeque::new(),
+            buffer: SmallQueue::new(),
         }
     }
 }
@@ -120,14 +134,13 @@ impl<V, U: EmitTriangles<Vertex = V>, SRC: Iterator<Item = U>> Iterator
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (n, _) = self.source.size_hint();
-        (n, None)
+        (n + self.buffer.len(), None)
     }
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            match self.buffer.pop_front() {
-                Some(v) => return Some(v),
-                None => (),
+            if let Some(v) = self.buffer.pop_front() {
+                return Some(v);
             }
 
             match self.source.next() {
@@ -137,3 +150,53 @@ impl<V, U: EmitTriangles<Vertex = V>, SRC: Iterator<Item = U>> Iterator
         }
     }
 }
+
+/// Triangulate a simple polygon given by its 2D `points`, in counter
+/// clockwise order, by ear clipping. Unlike `EmitTriangles` this also
+/// handles concave polygons. Returns triangles of indices into `points`,
+/// wound the same way as the polygon.
+pub(crate) fn ear_clip(points: &[[f32; 2]]) -> Vec<[usize; 3]> {
+    let cross = |o: [f32; 2], a: [f32; 2], b: [f32; 2]| {
+        (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+    };
+    let inside = |p: [f32; 2], a: [f32; 2], b: [f32; 2], c: [f32; 2]| {
+        cross(a, b, p) >= 0. && cross(b, c, p) >= 0. && cross(c, a, p) >= 0.
+    };
+
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut triangles = Vec::with_capacity(points.len().saturating_sub(2));
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let is_ear = |i: usize| {
+            let (a, b, c) = (
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            );
+            let (pa, pb, pc) = (points[a], points[b], points[c]);
+            cross(pa, pb, pc) > 0.
+                && remaining
+                    .iter()
+                    .filter(|&&j| j != a && j != b && j != c)
+                    .all(|&j| {
+                        // copies of the corners, as left by the bridges to
+                        // holes, do not block the ear
+                        let q = points[j];
+                        q == pa || q == pb || q == pc || !inside(q, pa, pb, pc)
+                    })
+        };
+        // a polygon that is degenerate or not simple may have no ear left,
+        // clip an arbitrary corner so that we still make progress
+        let i = (0..n).find(|&i| is_ear(i)).unwrap_or(0);
+        triangles.push([
+            remaining[(i + n - 1) % n],
+            remaining[i],
+            remaining[(i + 1) % n],
+        ]);
+        remaining.remove(i);
+    }
+    if remaining.len() == 3 {
+        triangles.push([remaining[0], remaining[1], remaining[2]]);
+    }
+    triangles
+}
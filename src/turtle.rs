@@ -0,0 +1,331 @@
+//! Turtle graphics, drawing branching skeletons for trees, plants and
+//! lightning bolts, wrapped in tubes.
+
+use std::f32::consts::PI;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::math;
+use crate::Polygon::{PolyNGon, PolyQuad};
+use crate::{Line, Polygon, Position, Quad, Vertex};
+
+// Where the turtle is, the way it faces, and how thick it draws.
+#[derive(Clone, Copy, Debug)]
+struct State {
+    node: usize,
+    heading: Vector3<f32>,
+    left: Vector3<f32>,
+    up: Vector3<f32>,
+    radius: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    pos: Vector3<f32>,
+    radius: f32,
+}
+
+// A drawn segment, with the frame of the turtle as it drew it.
+#[derive(Clone, Copy, Debug)]
+struct Edge {
+    from: usize,
+    to: usize,
+    heading: Vector3<f32>,
+    left: Vector3<f32>,
+    up: Vector3<f32>,
+    radius: f32,
+}
+
+/// Draws a branching skeleton of segments by moving, turning, saving and
+/// restoring a turtle, then wraps it in tubes.
+///
+/// The turtle starts at the origin facing along z, with its left along x
+/// and its up along y. Turns are in radians, and follow the right hand
+/// rule: `yaw` turns the heading towards the left, `pitch` turns it
+/// towards the up direction, and `roll` turns the left towards the up.
+///
+/// ```
+/// use genmesh::Turtle;
+///
+/// let tree = Turtle::new(0.1)
+///     .forward(1.)
+///     .push()
+///     .yaw(0.5)
+///     .forward(0.5)
+///     .pop()
+///     .yaw(-0.5)
+///     .forward(0.5);
+/// assert_eq!(tree.skeleton().len(), 3);
+/// let polygons: Vec<_> = tree.tubes(8).collect();
+/// # let _ = polygons;
+/// ```
+#[derive(Clone, Debug)]
+pub struct Turtle {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    state: State,
+    stack: Vec<State>,
+}
+
+impl Turtle {
+    /// A turtle drawing tubes of the given `radius`.
+    pub fn new(radius: f32) -> Self {
+        Turtle {
+            nodes: vec![Node {
+                pos: Vector3::new(0., 0., 0.),
+                radius,
+            }],
+            edges: Vec::new(),
+            state: State {
+                node: 0,
+                heading: Vector3::unit_z(),
+                left: Vector3::unit_x(),
+                up: Vector3::unit_y(),
+                radius,
+            },
+            stack: Vec::new(),
+        }
+    }
+
+    /// Move `distance` along the heading, drawing a segment.
+    pub fn forward(mut self, distance: f32) -> Self {
+        let from = self.state.node;
+        self.step(distance);
+        let s = self.state;
+        self.edges.push(Edge {
+            from,
+            to: s.node,
+            heading: s.heading,
+            left: s.left,
+            up: s.up,
+            radius: self.nodes[from].radius,
+        });
+        self
+    }
+
+    /// Move `distance` along the heading without drawing.
+    pub fn jump(mut self, distance: f32) -> Self {
+        self.step(distance);
+        self
+    }
+
+    /// Turn the heading towards the left by `angle`.
+    pub fn yaw(mut self, angle: f32) -> Self {
+        let s = &mut self.state;
+        let (h, l) = rotate(s.heading, s.left, angle);
+        s.heading = h;
+        s.left = l;
+        self
+    }
+
+    /// Turn the heading towards the up direction by `angle`.
+    pub fn pitch(mut self, angle: f32) -> Self {
+        let s = &mut self.state;
+        let (h, u) = rotate(s.heading, s.up, angle);
+        s.heading = h;
+        s.up = u;
+        self
+    }
+
+    /// Turn the left direction towards the up direction by `angle`, about
+    /// the heading.
+    pub fn roll(mut self, angle: f32) -> Self {
+        let s = &mut self.state;
+        let (l, u) = rotate(s.left, s.up, angle);
+        s.left = l;
+        s.up = u;
+        self
+    }
+
+    /// Set the radius of the tubes drawn from now on.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.state.radius = radius;
+        self
+    }
+
+    /// Save where the turtle is, its heading and its radius, to come back
+    /// with `pop` and start another branch.
+    pub fn push(mut self) -> Self {
+        self.stack.push(self.state);
+        self
+    }
+
+    /// Go back to the state of the last `push`. Does nothing if there is
+    /// none left.
+    pub fn pop(mut self) -> Self {
+        if let Some(state) = self.stack.pop() {
+            self.state = state;
+        }
+        self
+    }
+
+    /// Follow a string of commands, as produced by `expand_lsystem`:
+    ///
+    /// - `F` moves `step` forward, drawing, and `f` without drawing
+    /// - `+` and `-` yaw by `angle` and `-angle`
+    /// - `^` and `&` pitch by `angle` and `-angle`
+    /// - `\` and `/` roll by `angle` and `-angle`
+    /// - `|` turns around
+    /// - `[` and `]` push and pop
+    /// - `!` multiplies the radius by `taper`
+    ///
+    /// Any other character is skipped.
+    pub fn interpret(mut self, commands: &str, step: f32, angle: f32, taper: f32) -> Self {
+        for c in commands.chars() {
+            self = match c {
+                'F' => self.forward(step),
+                'f' => self.jump(step),
+                '+' => self.yaw(angle),
+                '-' => self.yaw(-angle),
+                '^' => self.pitch(angle),
+                '&' => self.pitch(-angle),
+                '\\' => self.roll(angle),
+                '/' => self.roll(-angle),
+                '|' => self.yaw(PI),
+                '[' => self.push(),
+                ']' => self.pop(),
+                '!' => {
+                    let radius = self.state.radius * taper;
+                    self.radius(radius)
+                }
+                _ => self,
+            };
+        }
+        self
+    }
+
+    /// The segments drawn so far.
+    pub fn skeleton(&self) -> Vec<Line<Position>> {
+        self.edges
+            .iter()
+            .map(|e| Line::new(self.nodes[e.from].pos.into(), self.nodes[e.to].pos.into()))
+            .collect()
+    }
+
+    /// Wrap every segment in a tube of `sides` quads around, of the radius
+    /// the turtle had where the segment starts and ends.
+    ///
+    /// Where a segment carries on from the one before, the one closest to
+    /// its direction if the skeleton branches there, the two tubes are cut
+    /// on the plane halfway between them and share their vertices, so
+    /// turning the turtle bends the tube without cracks. Other branches
+    /// start inside the tube they branch from. The ends of the skeleton
+    /// are closed with flat caps.
+    pub fn tubes(&self, sides: usize) -> std::vec::IntoIter<Polygon<Vertex>> {
+        assert!(sides > 2);
+        let mut incoming: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); self.nodes.len()];
+        for (i, e) in self.edges.iter().enumerate() {
+            incoming[e.to] = Some(i);
+            outgoing[e.from].push(i);
+        }
+        // the segment carrying on from the one into each node
+        let carry_on = |node: usize| {
+            let into = self.edges[incoming[node]?].heading;
+            outgoing[node].iter().cloned().max_by(|&a, &b| {
+                let (a, b) = (self.edges[a].heading, self.edges[b].heading);
+                into.dot(a).total_cmp(&into.dot(b))
+            })
+        };
+
+        let ring = |e: &Edge, at: usize, radius: f32, next: Option<&Edge>| -> Vec<Vertex> {
+            let center = self.nodes[at].pos;
+            let miter = next
+                .and_then(|n| math::try_normalize(e.heading + n.heading))
+                .filter(|m| e.heading.dot(*m) > 1e-3);
+            (0..sides)
+                .map(|k| {
+                    let t = k as f32 / sides as f32 * PI * 2.;
+                    let o = (e.left * t.cos() + e.up * t.sin()) * radius;
+                    let o = match miter {
+                        Some(m) => o - e.heading * (o.dot(m) / e.heading.dot(m)),
+                        None => o,
+                    };
+                    Vertex {
+                        pos: (center + o).into(),
+                        normal: math::try_normalize(o).unwrap_or(e.left).into(),
+                    }
+                })
+                .collect()
+        };
+        let cap = |ring: &[Vertex], normal: Vector3<f32>, reverse: bool| {
+            let mut vertices: Vec<Vertex> = ring
+                .iter()
+                .map(|v| Vertex {
+                    pos: v.pos,
+                    normal: normal.into(),
+                })
+                .collect();
+            if reverse {
+                vertices.reverse();
+            }
+            PolyNGon(vertices.into_iter().collect())
+        };
+
+        // the ring at the end of each segment
+        let ends: Vec<Vec<Vertex>> = self
+            .edges
+            .iter()
+            .map(|e| {
+                let next = carry_on(e.to).map(|i| &self.edges[i]);
+                ring(e, e.to, self.nodes[e.to].radius, next)
+            })
+            .collect();
+
+        let mut polygons = Vec::new();
+        for (i, e) in self.edges.iter().enumerate() {
+            let start = match incoming[e.from] {
+                Some(before) if carry_on(e.from) == Some(i) => ends[before].clone(),
+                before => {
+                    let start = ring(e, e.from, e.radius, None);
+                    if before.is_none() {
+                        polygons.push(cap(&start, -e.heading, true));
+                    }
+                    start
+                }
+            };
+            let end = &ends[i];
+            for k in 0..sides {
+                let l = (k + 1) % sides;
+                polygons.push(PolyQuad(Quad::new(start[k], start[l], end[l], end[k])));
+            }
+            if outgoing[e.to].is_empty() {
+                polygons.push(cap(end, e.heading, false));
+            }
+        }
+        polygons.into_iter()
+    }
+
+    fn step(&mut self, distance: f32) {
+        let pos = self.nodes[self.state.node].pos + self.state.heading * distance;
+        self.state.node = self.nodes.len();
+        self.nodes.push(Node {
+            pos,
+            radius: self.state.radius,
+        });
+    }
+}
+
+// Turn `a` towards `b` by `angle`, about the axis perpendicular to both.
+fn rotate(a: Vector3<f32>, b: Vector3<f32>, angle: f32) -> (Vector3<f32>, Vector3<f32>) {
+    let (s, c) = angle.sin_cos();
+    ((a * c + b * s).normalize(), (b * c - a * s).normalize())
+}
+
+/// Rewrite `axiom` with the rules of an L-system `iterations` times, each
+/// time replacing every character that has a rule with its replacement,
+/// all at once. The result can be drawn with `Turtle::interpret`.
+pub fn expand_lsystem(axiom: &str, rules: &[(char, &str)], iterations: usize) -> String {
+    let mut current = axiom.to_string();
+    for _ in 0..iterations {
+        let mut next = String::with_capacity(current.len() * 2);
+        for c in current.chars() {
+            match rules.iter().find(|r| r.0 == c) {
+                Some((_, replacement)) => next.push_str(replacement),
+                None => next.push(c),
+            }
+        }
+        current = next;
+    }
+    current
+}
@@ -0,0 +1,36 @@
+//! Splitting an indexed mesh into faces with their own vertices.
+
+use cgmath::Vector3;
+
+use crate::math;
+use crate::{Triangle, VertexAttributes};
+
+/// Give every triangle of an indexed mesh its own three vertices, with
+/// the normal of the face, for the flat shaded look of low poly styles.
+/// This is the inverse of indexing, and of `weld`.
+///
+/// The vertices are copied with all their attributes, and laid out three
+/// by three in the order of the triangles, so that triangle `i` uses the
+/// vertices `3 * i` to `3 * i + 2`. Degenerate triangles keep the normals
+/// their vertices had.
+pub fn unshare<V>(vertices: &[V], triangles: &[Triangle<usize>]) -> (Vec<V>, Vec<Triangle<usize>>)
+where
+    V: VertexAttributes + Clone,
+{
+    let mut unshared = Vec::with_capacity(triangles.len() * 3);
+    for t in triangles {
+        let corners = [t.x, t.y, t.z].map(|i| vertices[i].clone());
+        let p = |i: usize| Vector3::from(corners[i].position());
+        let normal = math::try_normalize((p(1) - p(0)).cross(p(2) - p(0)));
+        for mut v in corners {
+            if let Some(n) = normal {
+                v.set_normal(n.into());
+            }
+            unshared.push(v);
+        }
+    }
+    let triangles = (0..triangles.len())
+        .map(|i| Triangle::new(3 * i, 3 * i + 1, 3 * i + 2))
+        .collect();
+    (unshared, triangles)
+}
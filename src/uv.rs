@@ -0,0 +1,508 @@
+//! Texture coordinates projected onto a mesh from simple shapes, to
+//! texture imported meshes or the results of CSG without unwrapping them
+//! in a modeling package.
+//!
+//! ```
+//! use genmesh::generators::Torus;
+//! use genmesh::uv::{ProjectUv, Projection};
+//! use genmesh::Axis;
+//!
+//! let textured: Vec<_> = Torus::new(1., 0.25, 16, 8)
+//!     .project_uv(Projection::Cylindrical { axis: Axis::Z })
+//!     .collect();
+//! assert_eq!(textured.len(), 16 * 8);
+//! ```
+
+use std::f32::consts::PI;
+
+use cgmath::{InnerSpace, Vector2, Vector3};
+
+use crate::analytic::orthonormal_basis;
+use crate::math;
+use crate::{
+    Axis, Line, MapVertex, Neighbors, Polygon, Position, TexturedVertex, Triangle, VertexAttributes,
+};
+
+/// The ways to project texture coordinates onto a mesh.
+///
+/// The planar projections use the coordinates as they are, one unit of
+/// texture per unit of length, scale the result to repeat the texture
+/// more or less often. The axes of a projection go through the origin.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Projection {
+    /// Project along `axis`, onto the plane of the other two axes, in
+    /// the order that makes a right handed frame with it: y and z for x,
+    /// z and x for y, and x and y for z.
+    Planar {
+        /// The axis to project along
+        axis: Axis,
+    },
+    /// Project each polygon along the axis closest to its normal, so that
+    /// each face of a box gets its own undistorted projection. Faces
+    /// looking down an axis have their `u` reversed, so that the texture
+    /// is not mirrored when seen from outside.
+    Box,
+    /// Like `Box`, picking the axis for each vertex from its own normal
+    /// rather than for the whole polygon, the per vertex version of
+    /// triplanar mapping in shaders. Polygons whose vertices pick
+    /// different axes get stretched, with no seams cut between them.
+    Triplanar,
+    /// Wrap the texture around `axis`: `u` goes once around it, counter
+    /// clockwise, and `v` is the distance along it.
+    Cylindrical {
+        /// The axis to wrap around
+        axis: Axis,
+    },
+    /// Wrap the texture around a sphere centered on the origin: `u` goes
+    /// once around `axis`, like `Cylindrical`, and `v` from `0` at the
+    /// bottom pole to `1` at the top.
+    Spherical {
+        /// The axis through the poles
+        axis: Axis,
+    },
+}
+
+impl Projection {
+    /// The texture coordinates of a vertex, with `normal` the normal used
+    /// by the box projections.
+    pub fn project<P, N>(&self, pos: P, normal: N) -> [f32; 2]
+    where
+        P: Into<mint::Vector3<f32>>,
+        N: Into<mint::Vector3<f32>>,
+    {
+        let p = Vector3::from(pos.into());
+        match *self {
+            Projection::Planar { axis } => planar(axis, p, false),
+            Projection::Box | Projection::Triplanar => {
+                let n = Vector3::from(normal.into());
+                let axis = dominant(n);
+                planar(axis, p, n[axis.frame()[2]] < 0.)
+            }
+            Projection::Cylindrical { axis } => {
+                let [a, b, c] = axis.frame();
+                [around(p[a], p[b]), p[c]]
+            }
+            Projection::Spherical { axis } => {
+                let [a, b, c] = axis.frame();
+                let r = p.magnitude();
+                let height = if r > 0. { p[c] / r } else { 0. };
+                [around(p[a], p[b]), 0.5 + height.clamp(-1., 1.).asin() / PI]
+            }
+        }
+    }
+}
+
+fn planar(axis: Axis, p: Vector3<f32>, mirrored: bool) -> [f32; 2] {
+    let [a, b, _] = axis.frame();
+    [if mirrored { -p[a] } else { p[a] }, p[b]]
+}
+
+fn around(x: f32, y: f32) -> f32 {
+    let u = y.atan2(x) / (2. * PI);
+    if u < 0. {
+        u + 1.
+    } else {
+        u
+    }
+}
+
+fn dominant(n: Vector3<f32>) -> Axis {
+    let (x, y, z) = (n.x.abs(), n.y.abs(), n.z.abs());
+    if x >= y && x >= z {
+        Axis::X
+    } else if y >= z {
+        Axis::Y
+    } else {
+        Axis::Z
+    }
+}
+
+/// Projects texture coordinates onto a stream of polygons.
+pub trait ProjectUv: Sized {
+    /// Give every vertex texture coordinates from `projection`, keeping
+    /// its position and normal.
+    ///
+    /// Vertices with no normal get the normal of their polygon. With the
+    /// wrapping projections, the polygons crossing the seam where `u`
+    /// goes from `1` back to `0` get coordinates past `1` on that side,
+    /// so the texture repeats rather than squeezing back across the whole
+    /// of it, and the vertices on the axis get the `u` of the rest of
+    /// their polygon.
+    fn project_uv(self, projection: Projection) -> ProjectUvIterator<Self>;
+}
+
+impl<I, V> ProjectUv for I
+where
+    I: Iterator<Item = Polygon<V>>,
+    V: VertexAttributes + Clone,
+{
+    fn project_uv(self, projection: Projection) -> ProjectUvIterator<I> {
+        ProjectUvIterator {
+            source: self,
+            projection,
+        }
+    }
+}
+
+/// An iterator projecting texture coordinates onto the polygons of its
+/// source
+#[derive(Clone)]
+pub struct ProjectUvIterator<I> {
+    source: I,
+    projection: Projection,
+}
+
+impl<I, V> Iterator for ProjectUvIterator<I>
+where
+    I: Iterator<Item = Polygon<V>>,
+    V: VertexAttributes + Clone,
+{
+    type Item = Polygon<TexturedVertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.source.size_hint()
+    }
+
+    fn next(&mut self) -> Option<Polygon<TexturedVertex>> {
+        let polygon = self.source.next()?;
+        let points: Vec<Vector3<f32>> = polygon
+            .clone()
+            .as_vertices()
+            .map(|v| Vector3::from(v.position()))
+            .collect();
+        let face = math::try_normalize(math::newell_normal(&points))
+            .unwrap_or_else(|| Vector3::new(0., 0., 1.));
+        let projection = self.projection;
+        let mut textured = Vec::with_capacity(points.len());
+        let polygon = polygon.map_vertex(|v| {
+            let pos = v.position();
+            let normal = v.normal().unwrap_or_else(|| face.into());
+            let along = match projection {
+                Projection::Box => face.into(),
+                _ => normal,
+            };
+            textured.push(projection.project(pos, along));
+            TexturedVertex {
+                pos,
+                normal,
+                uv: [0., 0.],
+            }
+        });
+        fix_seam(projection, &points, &mut textured);
+        let mut uvs = textured.into_iter();
+        Some(polygon.map_vertex(|v| TexturedVertex {
+            uv: uvs.next().unwrap(),
+            ..v
+        }))
+    }
+}
+
+impl<I, V> ExactSizeIterator for ProjectUvIterator<I>
+where
+    I: ExactSizeIterator<Item = Polygon<V>>,
+    V: VertexAttributes + Clone,
+{
+}
+
+// moves the `u` of the vertices on one side of the seam past 1, and
+// gives the vertices on the axis, where `u` means nothing, the average of
+// the others
+fn fix_seam(projection: Projection, points: &[Vector3<f32>], uvs: &mut [[f32; 2]]) {
+    let axis = match projection {
+        Projection::Cylindrical { axis } | Projection::Spherical { axis } => axis,
+        _ => return,
+    };
+    let [a, b, _] = axis.frame();
+    let off: Vec<bool> = points
+        .iter()
+        .map(|p| p[a].hypot(p[b]) > 1e-6 * p.magnitude().max(1e-6))
+        .collect();
+    let us = || {
+        uvs.iter()
+            .zip(&off)
+            .filter(|(_, &off)| off)
+            .map(|(uv, _)| uv[0])
+    };
+    let count = us().count();
+    if count == 0 {
+        return;
+    }
+    let min = us().fold(f32::INFINITY, f32::min);
+    let max = us().fold(f32::NEG_INFINITY, f32::max);
+    let crosses = max - min > 0.5;
+    let mut sum = 0.;
+    for (uv, &off) in uvs.iter_mut().zip(&off) {
+        if off {
+            if crosses && uv[0] < 0.5 {
+                uv[0] += 1.;
+            }
+            sum += uv[0];
+        }
+    }
+    let mean = sum / count as f32;
+    for (uv, &off) in uvs.iter_mut().zip(&off) {
+        if !off {
+            uv[0] = mean;
+        }
+    }
+}
+
+/// Texture coordinates for an indexed mesh that give every part of its
+/// surface its own place in the unit square, as lightmaps need, built by
+/// `lightmap_atlas`.
+///
+/// Vertices on the edge between two charts have a different place in
+/// each, so they are split: the atlas has its own vertices, each coming
+/// from a vertex of the original mesh.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Atlas {
+    /// For every vertex of the atlas, the index of the vertex of the
+    /// original mesh it comes from, to copy its other attributes
+    pub source: Vec<usize>,
+    /// The texture coordinates of every vertex of the atlas
+    pub uvs: Vec<[f32; 2]>,
+    /// The triangles of the original mesh, in the same order, indexing
+    /// into the vertices of the atlas
+    pub triangles: Vec<Triangle<usize>>,
+    /// For every triangle, the chart it belongs to
+    pub charts: Vec<usize>,
+}
+
+/// Cut an indexed triangle mesh into charts and pack them into the unit
+/// square, for a second set of texture coordinates to bake lighting
+/// into.
+///
+/// Charts are grown from a triangle across its edges, taking in the
+/// neighbors whose normal is no more than `max_angle` radians from that
+/// of the first triangle, which should be less than a right angle so that
+/// no triangle folds over another. Each chart is projected flat along
+/// that normal, turned to fit the smallest rectangle, and packed into
+/// rows with the others. All the charts keep the same scale, so that the
+/// lightmap has the same resolution everywhere.
+///
+/// The charts are at least `padding` apart, and at least half of that
+/// from the edges of the square, to keep the texels of one from bleeding
+/// into another when filtered. A padding of a texel or two, as a fraction
+/// of the size of the lightmap, is usual.
+pub fn lightmap_atlas(
+    positions: &[Position],
+    triangles: &[Triangle<usize>],
+    max_angle: f32,
+    padding: f32,
+) -> Atlas {
+    assert!((0. ..0.5).contains(&padding));
+    let p = |i: usize| Vector3::from(positions[i]);
+    let normals: Vec<Option<Vector3<f32>>> = triangles
+        .iter()
+        .map(|t| math::try_normalize((p(t.y) - p(t.x)).cross(p(t.z) - p(t.x))))
+        .collect();
+
+    // grow the charts
+    let neighbors = Neighbors::new(Vec::<()>::new(), triangles.to_vec());
+    let min_cos = max_angle.cos();
+    let mut chart_of = vec![usize::MAX; triangles.len()];
+    let mut charts: Vec<(Vector3<f32>, Vec<usize>)> = Vec::new();
+    for seed in 0..triangles.len() {
+        if chart_of[seed] != usize::MAX {
+            continue;
+        }
+        let id = charts.len();
+        let normal = normals[seed].unwrap_or_else(Vector3::unit_z);
+        chart_of[seed] = id;
+        let mut members = vec![seed];
+        let mut k = 0;
+        while k < members.len() {
+            let t = &triangles[members[k]];
+            k += 1;
+            for (a, b) in [(t.x, t.y), (t.y, t.z), (t.z, t.x)] {
+                for f in neighbors.faces_sharing_edge(&Line::new(a, b)) {
+                    let fits = normals[f].is_none_or(|n| n.dot(normal) >= min_cos);
+                    if chart_of[f] == usize::MAX && fits {
+                        chart_of[f] = id;
+                        members.push(f);
+                    }
+                }
+            }
+        }
+        charts.push((normal, members));
+    }
+
+    // project them, giving each its own vertices
+    let mut atlas = Atlas {
+        source: Vec::new(),
+        uvs: Vec::new(),
+        triangles: vec![Triangle::new(0, 0, 0); triangles.len()],
+        charts: chart_of,
+    };
+    let mut local = vec![usize::MAX; positions.len()];
+    // the first vertex of each chart, and its size once turned
+    let mut extents: Vec<(usize, Vector2<f32>)> = Vec::with_capacity(charts.len());
+    for (normal, members) in &charts {
+        let first = atlas.source.len();
+        let (u, v) = orthonormal_basis(*normal);
+        for &f in members {
+            let t = &triangles[f];
+            let [x, y, z] = [t.x, t.y, t.z].map(|i| {
+                if local[i] == usize::MAX || local[i] < first {
+                    local[i] = atlas.source.len();
+                    atlas.source.push(i);
+                    atlas.uvs.push([p(i).dot(u), p(i).dot(v)]);
+                }
+                local[i]
+            });
+            atlas.triangles[f] = Triangle::new(x, y, z);
+        }
+        let size = fit_rectangle(&mut atlas.uvs[first..]);
+        extents.push((first, size));
+    }
+
+    // pack them, with a margin in the units of the mesh that is as large
+    // as the padding once the whole atlas is scaled down to the square
+    let sizes: Vec<Vector2<f32>> = extents.iter().map(|e| e.1).collect();
+    let mut margin = 0.;
+    let mut packed = pack(&sizes, margin);
+    for _ in 0..64 {
+        if margin >= padding * packed.1 {
+            break;
+        }
+        margin = padding * packed.1 * 1.05;
+        packed = pack(&sizes, margin);
+    }
+    let (offsets, side) = packed;
+    let scale = if side > 0. { 1. / side } else { 1. };
+    for (k, &(first, _)) in extents.iter().enumerate() {
+        let last = extents.get(k + 1).map_or(atlas.uvs.len(), |e| e.0);
+        for uv in &mut atlas.uvs[first..last] {
+            let q = Vector2::new(uv[0], uv[1]);
+            let q = (q + offsets[k] + Vector2::new(margin, margin) * 0.5) * scale;
+            *uv = [q.x, q.y];
+        }
+    }
+    atlas
+}
+
+// turns and moves the points so that they fit the smallest rectangle
+// from the origin, and returns its size, largest first
+fn fit_rectangle(uvs: &mut [[f32; 2]]) -> Vector2<f32> {
+    let points: Vec<Vector2<f32>> = uvs.iter().map(|&[x, y]| Vector2::new(x, y)).collect();
+    let hull = hull_2d(&points);
+    // the smallest rectangle has a side along an edge of the hull
+    let mut best = (f32::INFINITY, Vector2::new(1., 0.));
+    for k in 0..hull.len() {
+        let edge = hull[(k + 1) % hull.len()] - hull[k];
+        if edge.magnitude2() == 0. {
+            continue;
+        }
+        let dir = edge.normalize();
+        let (lo, hi) = bounds(hull.iter().map(|&q| rotate(q, dir)));
+        let size = hi - lo;
+        if size.x * size.y < best.0 {
+            best = (size.x * size.y, dir);
+        }
+    }
+    let dir = best.1;
+    let (lo, hi) = bounds(points.iter().map(|&q| rotate(q, dir)));
+    let size = hi - lo;
+    for (uv, &q) in uvs.iter_mut().zip(&points) {
+        let mut q = rotate(q, dir) - lo;
+        if size.y > size.x {
+            q = Vector2::new(size.y - q.y, q.x);
+        }
+        *uv = [q.x, q.y];
+    }
+    if size.y > size.x {
+        Vector2::new(size.y, size.x)
+    } else {
+        size
+    }
+}
+
+// `q` in the frame whose first axis is `dir`
+fn rotate(q: Vector2<f32>, dir: Vector2<f32>) -> Vector2<f32> {
+    Vector2::new(q.dot(dir), dir.x * q.y - dir.y * q.x)
+}
+
+fn bounds<I: Iterator<Item = Vector2<f32>>>(points: I) -> (Vector2<f32>, Vector2<f32>) {
+    let inf = f32::INFINITY;
+    points.fold(
+        (Vector2::new(inf, inf), Vector2::new(-inf, -inf)),
+        |(lo, hi), q| {
+            (
+                Vector2::new(lo.x.min(q.x), lo.y.min(q.y)),
+                Vector2::new(hi.x.max(q.x), hi.y.max(q.y)),
+            )
+        },
+    )
+}
+
+// the convex hull, counter clockwise, by Andrew's monotone chain
+fn hull_2d(points: &[Vector2<f32>]) -> Vec<Vector2<f32>> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+    let cross = |o: Vector2<f32>, a: Vector2<f32>, b: Vector2<f32>| {
+        (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+    };
+    let mut hull: Vec<Vector2<f32>> = Vec::with_capacity(2 * sorted.len());
+    for pass in 0..2 {
+        let start = hull.len();
+        for &q in sorted.iter() {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], q) <= 0.
+            {
+                hull.pop();
+            }
+            hull.push(q);
+        }
+        hull.pop();
+        if pass == 0 {
+            sorted.reverse();
+        }
+    }
+    hull
+}
+
+// places the rectangles, each grown by `margin`, in rows; returns where
+// each goes, and the size of the square around them all
+fn pack(sizes: &[Vector2<f32>], margin: f32) -> (Vec<Vector2<f32>>, f32) {
+    let grown: Vec<Vector2<f32>> = sizes
+        .iter()
+        .map(|s| s + Vector2::new(margin, margin))
+        .collect();
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|&a, &b| grown[b].y.total_cmp(&grown[a].y));
+    let area: f32 = grown.iter().map(|s| s.x * s.y).sum();
+    let widest = grown.iter().map(|s| s.x).fold(0., f32::max);
+
+    let mut best: (Vec<Vector2<f32>>, f32) = (Vec::new(), f32::INFINITY);
+    // rows as wide as the square would be with no wasted space, and
+    // a little wider
+    for step in 0..8 {
+        let width = (area.sqrt() * (1. + step as f32 * 0.1)).max(widest);
+        let mut placed = vec![Vector2::new(0., 0.); sizes.len()];
+        let (mut x, mut y, mut row, mut right) = (0f32, 0f32, 0f32, 0f32);
+        for &k in &order {
+            let s = grown[k];
+            if x > 0. && x + s.x > width {
+                y += row;
+                x = 0.;
+                row = 0.;
+            }
+            placed[k] = Vector2::new(x, y);
+            x += s.x;
+            right = right.max(x);
+            row = row.max(s.y);
+        }
+        let side = right.max(y + row);
+        if side < best.1 {
+            best = (placed, side);
+        }
+    }
+    if sizes.is_empty() {
+        best.1 = 0.;
+    }
+    best
+}
@@ -0,0 +1,108 @@
+//! Checks for the mistakes that make an indexed mesh unusable, or break
+//! the passes that expect a well formed surface.
+//!
+//! ```
+//! use genmesh::validate::validate;
+//! use genmesh::Triangle;
+//!
+//! let positions = [[0., 0., 0.].into(), [1., 0., 0.].into(), [0., 1., 0.].into()];
+//! let report = validate(&positions, &[Triangle::new(0, 1, 2), Triangle::new(0, 1, 3)]);
+//! assert_eq!(vec![1], report.out_of_range);
+//! assert!(!report.is_valid());
+//! ```
+
+use std::collections::HashMap;
+
+use cgmath::Vector3;
+
+use crate::math;
+use crate::{Line, Position, Triangle};
+
+/// The problems found in a mesh by `validate`.
+///
+/// Triangles are referred to by their index in the input, edges by their
+/// two vertex indices with the smallest first.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// Triangles using a vertex index past the end of the vertices.
+    /// They are left out of all the other checks.
+    pub out_of_range: Vec<usize>,
+    /// Triangles with no area, either because a vertex is repeated or
+    /// because the corners are collinear.
+    pub degenerate: Vec<usize>,
+    /// Pairs of triangles using the same three vertices, whatever their
+    /// winding; the first of the pair is the earlier triangle.
+    pub duplicate_faces: Vec<(usize, usize)>,
+    /// Edges shared by more than two triangles.
+    pub non_manifold_edges: Vec<Line<usize>>,
+    /// Edges shared by two triangles that both go along it in the same
+    /// direction, meaning one of them is facing the wrong way.
+    pub inconsistent_winding: Vec<Line<usize>>,
+}
+
+impl Report {
+    /// Whether no problem at all was found.
+    pub fn is_valid(&self) -> bool {
+        self.out_of_range.is_empty()
+            && self.degenerate.is_empty()
+            && self.duplicate_faces.is_empty()
+            && self.non_manifold_edges.is_empty()
+            && self.inconsistent_winding.is_empty()
+    }
+}
+
+/// Check the triangles indexing into `positions` for out of range
+/// indices, degenerate and duplicate faces, non manifold edges, and
+/// inconsistent winding.
+pub fn validate(positions: &[Position], triangles: &[Triangle<usize>]) -> Report {
+    let mut report = Report::default();
+    let mut faces: HashMap<[usize; 3], usize> = HashMap::new();
+    // for every undirected edge, the number of times it is used going
+    // from the smaller index to the larger one, and the other way round
+    let mut edges: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+
+    for (i, t) in triangles.iter().enumerate() {
+        let corners = [t.x, t.y, t.z];
+        if corners.iter().any(|&v| v >= positions.len()) {
+            report.out_of_range.push(i);
+            continue;
+        }
+
+        if t.x == t.y || t.y == t.z || t.z == t.x {
+            report.degenerate.push(i);
+            continue;
+        }
+        let p = |i: usize| Vector3::from(positions[i]);
+        if math::is_degenerate(p(t.x), p(t.y), p(t.z)) {
+            report.degenerate.push(i);
+        }
+
+        let mut key = corners;
+        key.sort_unstable();
+        if let Some(&first) = faces.get(&key) {
+            report.duplicate_faces.push((first, i));
+        } else {
+            faces.insert(key, i);
+        }
+
+        for &(a, b) in &[(t.x, t.y), (t.y, t.z), (t.z, t.x)] {
+            let uses = edges.entry((a.min(b), a.max(b))).or_default();
+            if a < b {
+                uses.0 += 1;
+            } else {
+                uses.1 += 1;
+            }
+        }
+    }
+
+    let mut edges: Vec<_> = edges.into_iter().collect();
+    edges.sort_unstable();
+    for ((a, b), (forward, backward)) in edges {
+        if forward + backward > 2 {
+            report.non_manifold_edges.push(Line::new(a, b));
+        } else if forward == 2 || backward == 2 {
+            report.inconsistent_winding.push(Line::new(a, b));
+        }
+    }
+    report
+}
@@ -0,0 +1,79 @@
+//! Merging of vertices that are close enough to be considered the same.
+
+use std::collections::HashMap;
+
+use crate::math::position_key;
+use crate::spatial::SpatialGrid;
+use crate::{Triangle, Vertex};
+
+/// Merge the vertices of an indexed mesh that lie within `epsilon` of
+/// each other, closing the seams left between meshes that were built
+/// separately.
+///
+/// Vertices are visited in order, and each one is merged into the first
+/// kept vertex within `epsilon`, whose normal is kept as is. Triangles
+/// that collapse because two of their corners merged are removed, and
+/// so are the vertices that end up unused.
+///
+/// Returns, for every original vertex, its index in the welded mesh, or
+/// `None` if it was removed along with the triangles using it.
+pub fn weld(
+    vertices: &mut Vec<Vertex>,
+    triangles: &mut Vec<Triangle<usize>>,
+    epsilon: f32,
+) -> Vec<Option<usize>> {
+    let merged = cluster(vertices, epsilon);
+
+    let mut kept: Vec<Triangle<usize>> = Vec::with_capacity(triangles.len());
+    for t in triangles.drain(..) {
+        let t = Triangle::new(merged[t.x], merged[t.y], merged[t.z]);
+        if t.x != t.y && t.y != t.z && t.z != t.x {
+            kept.push(t);
+        }
+    }
+
+    // compact the vertices that are still referenced
+    let mut used = vec![None; vertices.len()];
+    let mut compacted = Vec::new();
+    for t in kept.iter_mut() {
+        for i in [&mut t.x, &mut t.y, &mut t.z] {
+            *i = *used[*i].get_or_insert_with(|| {
+                compacted.push(vertices[*i]);
+                compacted.len() - 1
+            });
+        }
+    }
+
+    *vertices = compacted;
+    *triangles = kept;
+    merged.into_iter().map(|m| used[m]).collect()
+}
+
+// for each vertex, the index of the vertex it merges into
+fn cluster(vertices: &[Vertex], epsilon: f32) -> Vec<usize> {
+    if epsilon <= 0. {
+        let mut exact: HashMap<[u32; 3], usize> = HashMap::new();
+        return vertices
+            .iter()
+            .enumerate()
+            .map(|(i, v)| *exact.entry(position_key(v.pos)).or_insert(i))
+            .collect();
+    }
+
+    // the kept vertices, in order, so that the first one found is the
+    // first one kept
+    let mut grid = SpatialGrid::new(epsilon);
+    let mut kept = Vec::new();
+    let mut merged = Vec::with_capacity(vertices.len());
+    for (i, v) in vertices.iter().enumerate() {
+        match grid.query_radius(v.pos, epsilon).first() {
+            Some(&k) => merged.push(kept[k]),
+            None => {
+                grid.insert(v.pos);
+                kept.push(i);
+                merged.push(i);
+            }
+        }
+    }
+    merged
+}
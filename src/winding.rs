@@ -0,0 +1,259 @@
+//! Turning faces around, one at a time or to agree with their neighbors.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::generators::{IndexedPolygon, SharedVertex};
+use crate::Polygon::{PolyNGon, PolyQuad, PolyTri};
+use crate::{ConstNGon, MapVertex, NGon, Polygon, Quad, Triangle, Vertex, VertexAttributes};
+
+/// Reverses the order of the vertices of a polygon, which makes it face
+/// the other way. The first vertex stays first.
+pub trait FlipWinding {
+    /// Return the polygon with its winding reversed
+    fn flip_winding(self) -> Self;
+}
+
+impl<T> FlipWinding for Triangle<T> {
+    fn flip_winding(self) -> Self {
+        let Triangle { x, y, z } = self;
+        Triangle::new(x, z, y)
+    }
+}
+
+impl<T> FlipWinding for Quad<T> {
+    fn flip_winding(self) -> Self {
+        let Quad { x, y, z, w } = self;
+        Quad::new(x, w, z, y)
+    }
+}
+
+impl<T> FlipWinding for NGon<T> {
+    fn flip_winding(self) -> Self {
+        let mut ngon: NGon<T> = self.verts.into_iter().rev().collect();
+        let len = ngon.verts.len();
+        ngon.verts.rotate_right(1.min(len));
+        ngon
+    }
+}
+
+impl<T, const N: usize> FlipWinding for ConstNGon<T, N> {
+    fn flip_winding(mut self) -> Self {
+        self.verts.reverse();
+        self.verts.rotate_right(1.min(N));
+        self
+    }
+}
+
+impl<T> FlipWinding for Polygon<T> {
+    fn flip_winding(self) -> Self {
+        match self {
+            PolyTri(t) => PolyTri(t.flip_winding()),
+            PolyQuad(q) => PolyQuad(q.flip_winding()),
+            PolyNGon(n) => PolyNGon(n.flip_winding()),
+        }
+    }
+}
+
+/// Turns a stream of polygons inside out.
+pub trait ReverseWinding: Sized {
+    /// Reverse the winding of every polygon and negate the normals of
+    /// their vertices, so that each face points the other way.
+    fn reverse_winding(self) -> ReverseWindingIterator<Self>;
+}
+
+impl<T, P> ReverseWinding for T
+where
+    T: Iterator<Item = P>,
+    P: FlipWinding + MapVertex<Vertex, Vertex, Output = P>,
+{
+    fn reverse_winding(self) -> ReverseWindingIterator<T> {
+        ReverseWindingIterator { source: self }
+    }
+}
+
+/// An iterator of polygons facing the other way than those of its source
+pub struct ReverseWindingIterator<I> {
+    source: I,
+}
+
+impl<I, P> Iterator for ReverseWindingIterator<I>
+where
+    I: Iterator<Item = P>,
+    P: FlipWinding + MapVertex<Vertex, Vertex, Output = P>,
+{
+    type Item = P;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.source.size_hint()
+    }
+
+    fn next(&mut self) -> Option<P> {
+        self.source.next().map(|p| {
+            p.flip_winding().map_vertex(|v| Vertex {
+                pos: v.pos,
+                normal: [-v.normal.x, -v.normal.y, -v.normal.z].into(),
+            })
+        })
+    }
+}
+
+/// A generator turned inside out, facing its inside, for skyboxes and
+/// environment domes seen from within.
+///
+/// Both the polygons streamed by the generator and its indexed polygons
+/// have their winding reversed, and every normal is negated. Texture
+/// coordinates are left as they are, so textures read mirrored from the
+/// inside.
+#[derive(Clone, Copy, Debug)]
+pub struct InsideOut<G> {
+    generator: G,
+}
+
+impl<G> InsideOut<G> {
+    /// Turn `generator` inside out.
+    pub fn new(generator: G) -> Self {
+        InsideOut { generator }
+    }
+}
+
+fn negate_normal<V: VertexAttributes>(mut v: V) -> V {
+    if let Some(n) = v.normal() {
+        v.set_normal([-n.x, -n.y, -n.z].into());
+    }
+    v
+}
+
+impl<G, V> Iterator for InsideOut<G>
+where
+    G: Iterator<Item = Polygon<V>>,
+    V: VertexAttributes + Clone,
+{
+    type Item = Polygon<V>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.generator.size_hint()
+    }
+
+    fn next(&mut self) -> Option<Polygon<V>> {
+        self.generator
+            .next()
+            .map(|p| p.flip_winding().map_vertex(negate_normal))
+    }
+}
+
+impl<G, V> DoubleEndedIterator for InsideOut<G>
+where
+    G: DoubleEndedIterator<Item = Polygon<V>>,
+    V: VertexAttributes + Clone,
+{
+    fn next_back(&mut self) -> Option<Polygon<V>> {
+        self.generator
+            .next_back()
+            .map(|p| p.flip_winding().map_vertex(negate_normal))
+    }
+}
+
+impl<G, V> ExactSizeIterator for InsideOut<G>
+where
+    G: ExactSizeIterator<Item = Polygon<V>>,
+    V: VertexAttributes + Clone,
+{
+}
+
+impl<G, V> SharedVertex<V> for InsideOut<G>
+where
+    G: SharedVertex<V>,
+    V: VertexAttributes,
+{
+    fn shared_vertex(&self, idx: usize) -> V {
+        negate_normal(self.generator.shared_vertex(idx))
+    }
+
+    fn shared_vertex_count(&self) -> usize {
+        self.generator.shared_vertex_count()
+    }
+}
+
+impl<G, P> IndexedPolygon<P> for InsideOut<G>
+where
+    G: IndexedPolygon<P>,
+    P: FlipWinding,
+{
+    fn indexed_polygon(&self, idx: usize) -> P {
+        self.generator.indexed_polygon(idx).flip_winding()
+    }
+
+    fn indexed_polygon_count(&self) -> usize {
+        self.generator.indexed_polygon_count()
+    }
+}
+
+/// Flip triangles of an indexed mesh so that neighbors agree on which
+/// side is the front, using only the connectivity of the mesh.
+///
+/// Two triangles agree when they go along their shared edge in opposite
+/// directions. The orientation spreads across edges shared by exactly
+/// two triangles; in each connected part, the orientation chosen is the
+/// one that flips the fewest triangles. Parts that cannot be oriented,
+/// like a Möbius strip, are made consistent wherever possible.
+///
+/// Returns the number of triangles that were flipped.
+pub fn make_winding_consistent(triangles: &mut [Triangle<usize>]) -> usize {
+    // faces on each undirected edge, with whether they go from the
+    // smaller vertex to the larger one
+    let mut edges: HashMap<(usize, usize), Vec<(usize, bool)>> = HashMap::new();
+    for (i, t) in triangles.iter().enumerate() {
+        for &(a, b) in &[(t.x, t.y), (t.y, t.z), (t.z, t.x)] {
+            if a != b {
+                edges
+                    .entry((a.min(b), a.max(b)))
+                    .or_default()
+                    .push((i, a < b));
+            }
+        }
+    }
+
+    let mut flip: Vec<Option<bool>> = vec![None; triangles.len()];
+    let mut flipped = 0;
+    let mut queue = VecDeque::new();
+    for seed in 0..triangles.len() {
+        if flip[seed].is_some() {
+            continue;
+        }
+        flip[seed] = Some(false);
+        queue.push_back(seed);
+        let mut part = vec![seed];
+
+        while let Some(i) = queue.pop_front() {
+            let t = &triangles[i];
+            let flip_i = flip[i] == Some(true);
+            for &(a, b) in &[(t.x, t.y), (t.y, t.z), (t.z, t.x)] {
+                let faces = match edges.get(&(a.min(b), a.max(b))) {
+                    Some(faces) if faces.len() == 2 => faces,
+                    _ => continue,
+                };
+                let forward = (a < b) != flip_i;
+                for &(j, dir) in faces {
+                    if j == i || flip[j].is_some() {
+                        continue;
+                    }
+                    // the neighbor must go the other way along the edge
+                    flip[j] = Some(dir == forward);
+                    queue.push_back(j);
+                    part.push(j);
+                }
+            }
+        }
+
+        let count = part.iter().filter(|&&i| flip[i] == Some(true)).count();
+        let invert = count * 2 > part.len();
+        for &i in &part {
+            if flip[i] == Some(!invert) {
+                let t = triangles[i].clone();
+                triangles[i] = t.flip_winding();
+                flipped += 1;
+            }
+        }
+    }
+    flipped
+}
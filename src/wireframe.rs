@@ -0,0 +1,125 @@
+use std::collections::{HashSet, VecDeque};
+use std::f32::consts::PI;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::analytic::{orthonormal_basis, Sphere};
+use crate::math;
+use crate::{Line, Polygon, Quad, Vertex};
+
+/// Turns a stream of lines into solid geometry, by wrapping each line in
+/// a thin cylinder. Useful to draw wireframes without relying on the
+/// line rasterization of the graphics API.
+pub trait Tubes: Sized {
+    /// Wrap every line into an open tube of the given `radius`, made of
+    /// `segments` quads around its circumference.
+    fn tubes(self, radius: f32, segments: usize) -> TubesIterator<Self>;
+}
+
+impl<T> Tubes for T
+where
+    T: Iterator<Item = Line<Vertex>>,
+{
+    fn tubes(self, radius: f32, segments: usize) -> TubesIterator<T> {
+        assert!(radius > 0. && segments > 2);
+        TubesIterator {
+            source: self,
+            radius,
+            segments,
+            joints: None,
+            seen: HashSet::new(),
+            buffer: VecDeque::new(),
+        }
+    }
+}
+
+/// An iterator that turns lines into the polygons of tubes around them
+pub struct TubesIterator<I> {
+    source: I,
+    radius: f32,
+    segments: usize,
+    joints: Option<(usize, usize)>,
+    seen: HashSet<[u32; 3]>,
+    buffer: VecDeque<Polygon<Vertex>>,
+}
+
+impl<I> TubesIterator<I> {
+    /// Also emit a sphere of the tube radius at each distinct end point,
+    /// which closes the tubes and rounds off the joints between them.
+    /// `u` and `v` are the resolution of the `SphereUv` used for the joints.
+    pub fn with_joints(mut self, u: usize, v: usize) -> Self {
+        self.joints = Some((u, v));
+        self
+    }
+
+    fn emit_tube(&mut self, line: &Line<Vertex>) {
+        let a = Vector3::from(line.x.pos);
+        let b = Vector3::from(line.y.pos);
+        let dir = b - a;
+        if dir.magnitude2() == 0. {
+            return;
+        }
+        let (x, y) = orthonormal_basis(dir);
+        let (segments, radius) = (self.segments, self.radius);
+
+        let ring = |i: usize| {
+            let t = (i % segments) as f32 / segments as f32 * PI * 2.;
+            x * t.cos() + y * t.sin()
+        };
+        let v = |base: Vector3<f32>, n: Vector3<f32>| Vertex {
+            pos: (base + n * radius).into(),
+            normal: n.into(),
+        };
+        for i in 0..segments {
+            let n0 = ring(i);
+            let n1 = ring(i + 1);
+            self.buffer.push_back(Polygon::PolyQuad(Quad::new(
+                v(a, n0),
+                v(a, n1),
+                v(b, n1),
+                v(b, n0),
+            )));
+        }
+    }
+
+    fn emit_joint(&mut self, vertex: &Vertex) {
+        let (u, v) = match self.joints {
+            Some(res) => res,
+            None => return,
+        };
+        if self.seen.insert(math::position_key(vertex.pos)) {
+            let sphere = Sphere::new(vertex.pos, self.radius);
+            self.buffer.extend(sphere.mesh(u, v));
+        }
+    }
+}
+
+impl<I> Iterator for TubesIterator<I>
+where
+    I: Iterator<Item = Line<Vertex>>,
+{
+    type Item = Polygon<Vertex>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // zero length lines are skipped, so only the buffered
+        // polygons are certain
+        (self.buffer.len(), None)
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(p) = self.buffer.pop_front() {
+                return Some(p);
+            }
+
+            match self.source.next() {
+                Some(line) => {
+                    self.emit_tube(&line);
+                    self.emit_joint(&line.x);
+                    self.emit_joint(&line.y);
+                }
+                None => return None,
+            }
+        }
+    }
+}
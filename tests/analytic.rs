@@ -0,0 +1,157 @@
+extern crate cgmath;
+extern crate genmesh;
+
+use cgmath::{InnerSpace, Vector3};
+use genmesh::analytic::{Capsule, Cylinder, Obb, Plane, Sphere};
+use genmesh::Line;
+
+const EPSILON: f32 = 1e-5;
+
+#[test]
+fn sphere_plane() {
+    let sphere = Sphere::new([0., 0., 1.].into(), 2.);
+    let plane = Plane::new([0., 0., 2.].into(), 2.);
+    let circle = sphere.intersect_plane(&plane).unwrap();
+    assert_eq!(circle.center, [0., 0., 1.].into());
+
+    let lines: Vec<_> = circle.lines(16).collect();
+    assert_eq!(16, lines.len());
+    for Line { x, y } in &lines {
+        assert!((x.z - 1.).abs() < EPSILON);
+        assert!((Vector3::new(x.x, x.y, 0.).magnitude() - 2.).abs() < EPSILON);
+        assert!((Vector3::new(y.x, y.y, 0.).magnitude() - 2.).abs() < EPSILON);
+    }
+    // the strip is closed
+    assert_eq!(lines[0].x, lines[15].y);
+
+    let far = Plane::new([0., 0., 1.].into(), 3.5);
+    assert!(sphere.intersect_plane(&far).is_none());
+}
+
+#[test]
+fn sphere_sphere() {
+    let a = Sphere::new([0., 0., 0.].into(), 1.);
+    let b = Sphere::new([1., 0., 0.].into(), 1.);
+    let circle = a.intersect_sphere(&b).unwrap();
+    assert!((circle.center.x - 0.5).abs() < EPSILON);
+    for Line { x, .. } in circle.lines(8) {
+        let p = Vector3::from(x);
+        assert!((p.magnitude() - 1.).abs() < EPSILON);
+        assert!(((p - Vector3::unit_x()).magnitude() - 1.).abs() < EPSILON);
+    }
+
+    let c = Sphere::new([3., 0., 0.].into(), 1.);
+    assert!(a.intersect_sphere(&c).is_none());
+    let inner = Sphere::new([0.1, 0., 0.].into(), 0.2);
+    assert!(a.intersect_sphere(&inner).is_none());
+}
+
+#[test]
+fn cylinder_plane() {
+    let cylinder = Cylinder::new([0., 0., 0.].into(), [0., 0., 1.].into(), 1.);
+    let slanted = Plane::from_point_normal([0., 0., 1.].into(), [1., 0., 1.].into());
+    let ellipse = cylinder.intersect_plane(&slanted).unwrap();
+    assert!((Vector3::from(ellipse.major).magnitude() - 2f32.sqrt()).abs() < EPSILON);
+    assert!((Vector3::from(ellipse.minor).magnitude() - 1.).abs() < EPSILON);
+    for Line { x, .. } in ellipse.lines(12) {
+        assert!((Vector3::new(x.x, x.y, 0.).magnitude() - 1.).abs() < EPSILON);
+        assert!(slanted.signed_distance(x).abs() < EPSILON);
+    }
+
+    let parallel = Plane::new([1., 0., 0.].into(), 0.5);
+    assert!(cylinder.intersect_plane(&parallel).is_none());
+}
+
+#[test]
+fn oriented_box() {
+    use genmesh::generators::{Cube, SharedVertex};
+
+    // a cube stretched along X and then rotated by 45 degrees around Z
+    let (s, c) = std::f32::consts::FRAC_PI_4.sin_cos();
+    let points: Vec<_> = Cube::new()
+        .shared_vertex_iter()
+        .map(|v| {
+            let p = Vector3::from(v.pos) * 3f32.sqrt();
+            let p = Vector3::new(p.x * 4., p.y, p.z * 2.);
+            [p.x * c - p.y * s, p.x * s + p.y * c, p.z].into()
+        })
+        .collect();
+
+    let obb = Obb::from_points(points.iter().cloned()).unwrap();
+    assert!(Vector3::from(obb.center).magnitude() < EPSILON);
+    assert!((obb.half_extents[0] - 4.).abs() < 1e-4);
+    assert!((obb.half_extents[1] - 2.).abs() < 1e-4);
+    assert!((obb.half_extents[2] - 1.).abs() < 1e-4);
+    assert!((Vector3::from(obb.axes[0]).dot(Vector3::new(c, s, 0.)).abs() - 1.).abs() < 1e-4);
+    assert!((obb.volume() - 64.).abs() < 1e-2);
+    for p in points {
+        assert!(obb.contains(p));
+    }
+    for p in obb.corners().iter() {
+        assert!(obb.contains(*p));
+    }
+
+    assert!(Obb::from_points(Vec::new()).is_none());
+}
+
+#[test]
+fn enclosing_sphere() {
+    use genmesh::generators::{IcoSphere, SharedVertex};
+
+    let ico = IcoSphere::subdivide(2);
+    let points: Vec<_> = ico
+        .shared_vertex_iter()
+        .map(|v| [v.pos.x * 2. + 1., v.pos.y * 2., v.pos.z * 2. - 3.].into())
+        .collect();
+    let sphere = Sphere::enclosing(points.iter().cloned()).unwrap();
+    assert!((sphere.radius - 2.).abs() < 1e-4);
+    assert!((Vector3::from(sphere.center) - Vector3::new(1., 0., -3.)).magnitude() < 1e-4);
+    assert!(points.iter().all(|&p| sphere.contains(p)));
+
+    // two points define the diameter
+    let pair = Sphere::enclosing(vec![[0., 0., 0.].into(), [0., 4., 0.].into()]).unwrap();
+    assert_eq!(pair.center, [0., 2., 0.].into());
+    assert_eq!(pair.radius, 2.);
+}
+
+#[test]
+fn capsule_fit() {
+    use genmesh::generators::{Cylinder as CylinderGen, SharedVertex};
+    use genmesh::{capsule_proxy, sphere_proxy};
+
+    // a tall cylinder along Z, radius 1, from -4 to 4
+    let points: Vec<_> = CylinderGen::subdivide(16, 4)
+        .shared_vertex_iter()
+        .map(|v| [v.pos.x, v.pos.y, v.pos.z * 4.].into())
+        .collect();
+    let capsule = Capsule::fit(points.iter().cloned()).unwrap();
+    assert!((capsule.radius - 1.).abs() < 1e-4);
+    assert!(points.iter().all(|&p| capsule.contains(p)));
+    let length = (Vector3::from(capsule.a) - Vector3::from(capsule.b)).magnitude();
+    // the rims of the cylinder sit exactly on the equator of the caps
+    assert!((length - 8.).abs() < 1e-3);
+
+    let (fitted, mesh) = capsule_proxy(points.iter().cloned(), 8, 6).unwrap();
+    assert_eq!(fitted, capsule);
+    for poly in mesh {
+        for v in poly.as_vertices() {
+            let p = Vector3::from(v.pos);
+            // every vertex of the proxy lies on the surface of the capsule
+            let t =
+                p.z.max(capsule.a.z.min(capsule.b.z))
+                    .min(capsule.a.z.max(capsule.b.z));
+            let axis_point = Vector3::new(capsule.a.x, capsule.a.y, t);
+            assert!(((p - axis_point).magnitude() - capsule.radius).abs() < 1e-3);
+        }
+    }
+
+    let (sphere, mesh) = sphere_proxy(points.iter().cloned(), 8, 6).unwrap();
+    assert!(points.iter().all(|&p| sphere.contains(p)));
+    assert_eq!(8 * 6, mesh.count());
+}
+
+#[test]
+#[should_panic(expected = "the axis of a cylinder must not be zero")]
+fn cylinder_zero_axis() {
+    Cylinder::new([0., 0., 0.].into(), [0., 0., 0.].into(), 1.);
+}
@@ -0,0 +1,133 @@
+extern crate genmesh;
+
+use genmesh::generators::{IndexedPolygon, Plane, SharedVertex};
+use genmesh::{
+    recompute_normals, MapVertex, Mesh, Normal, NormalFallback, Position, Triangulate,
+    VertexAttributes,
+};
+
+// an engine vertex without normals, carrying texture coordinates and a color
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Textured {
+    pos: [f32; 3],
+    uv: [f32; 2],
+    color: [f32; 4],
+}
+
+impl VertexAttributes for Textured {
+    fn position(&self) -> Position {
+        self.pos.into()
+    }
+
+    fn uv(&self) -> Option<[f32; 2]> {
+        Some(self.uv)
+    }
+
+    fn color(&self) -> Option<[f32; 4]> {
+        Some(self.color)
+    }
+}
+
+// the same with a normal
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Lit {
+    pos: [f32; 3],
+    normal: [f32; 3],
+}
+
+impl VertexAttributes for Lit {
+    fn position(&self) -> Position {
+        self.pos.into()
+    }
+
+    fn normal(&self) -> Option<Normal> {
+        Some(self.normal.into())
+    }
+
+    fn set_normal(&mut self, normal: Normal) {
+        self.normal = normal.into();
+    }
+}
+
+fn textured(v: genmesh::Vertex) -> Textured {
+    Textured {
+        pos: v.pos.into(),
+        uv: [v.pos.x * 0.5 + 0.5, v.pos.y * 0.5 + 0.5],
+        color: [1., 0., 0., 1.],
+    }
+}
+
+#[test]
+fn mesh_of_custom_vertices() {
+    let plane = Plane::subdivide(2, 2);
+    let polygons = plane
+        .indexed_polygon_iter()
+        .triangulate()
+        .map(|t| t.map_vertex(|i| textured(plane.shared_vertex(i))));
+    let mesh: Mesh = Mesh::from_polygons_with_indexer(polygons, 16);
+
+    assert_eq!(mesh.vertex_count(), 9);
+    assert_eq!(mesh.triangle_count(), 8);
+    assert!(mesh.normals.is_empty());
+    assert_eq!(mesh.uvs.len(), 9);
+    assert_eq!(mesh.colors.len(), 9);
+    assert_eq!(mesh.color_buffer().len(), 9 * 4);
+    // position, uv and color for each vertex
+    assert_eq!(mesh.interleaved().len(), 9 * 9);
+    assert!(mesh.colors.iter().all(|c| *c == [1., 0., 0., 1.]));
+    for (p, uv) in mesh.positions.iter().zip(&mesh.uvs) {
+        assert_eq!(*uv, [p.x * 0.5 + 0.5, p.y * 0.5 + 0.5]);
+    }
+}
+
+#[test]
+fn normals_of_custom_vertices() {
+    let plane = Plane::new();
+    let mut vertices: Vec<Lit> = plane
+        .shared_vertex_iter()
+        .map(|v| Lit {
+            pos: v.pos.into(),
+            normal: [0., 0., 0.],
+        })
+        .collect();
+    let triangles: Vec<_> = plane.indexed_polygon_iter().triangulate().collect();
+    recompute_normals(&mut vertices, &triangles, NormalFallback::Skip);
+    for v in &vertices {
+        assert_eq!(v.normal, [0., 0., 1.]);
+    }
+
+    // position only vertices go through unchanged
+    let mut positions: Vec<Position> = vertices.iter().map(|v| v.position()).collect();
+    let before = positions.clone();
+    recompute_normals(&mut positions, &triangles, NormalFallback::Skip);
+    assert_eq!(positions, before);
+}
+
+// a vertex whose normal is optional
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct MaybeLit {
+    pos: [f32; 3],
+    normal: Option<[f32; 3]>,
+}
+
+impl VertexAttributes for MaybeLit {
+    fn position(&self) -> Position {
+        self.pos.into()
+    }
+
+    fn normal(&self) -> Option<Normal> {
+        self.normal.map(Into::into)
+    }
+}
+
+#[test]
+#[should_panic(expected = "only some of the vertices have normals")]
+fn mesh_of_mixed_vertices() {
+    let lit = |pos: [f32; 3], normal| MaybeLit { pos, normal };
+    let triangle = genmesh::Triangle::new(
+        lit([0., 0., 0.], Some([0., 0., 1.])),
+        lit([1., 0., 0.], Some([0., 0., 1.])),
+        lit([0., 1., 0.], None),
+    );
+    let _: Mesh = Mesh::from_polygons_with_indexer(vec![triangle], 8);
+}
@@ -0,0 +1,98 @@
+extern crate genmesh;
+
+use std::f32::consts::PI;
+
+use cgmath::{InnerSpace, Vector3};
+use genmesh::generators::{Cube, IndexedPolygon, SharedVertex};
+use genmesh::validate::validate;
+use genmesh::{bevel, indexed_volume, weld, EmitTriangles, Polygon, Position, Triangle, Vertex};
+
+fn bevelled_cube(width: f32, segments: usize) -> (Vec<Vertex>, Vec<Polygon<usize>>) {
+    let cube = Cube::new();
+    let positions: Vec<Position> = cube.shared_vertex_iter().map(|v| v.pos).collect();
+    let polygons: Vec<Polygon<usize>> = cube.indexed_polygon_iter().collect();
+    bevel(&positions, &polygons, width, segments)
+}
+
+// welds the pieces back together, checks the surface is closed and
+// returns its volume
+fn closed_volume(mut vertices: Vec<Vertex>, polygons: &[Polygon<usize>]) -> f32 {
+    let mut triangles: Vec<Triangle<usize>> = Vec::new();
+    for p in polygons {
+        p.clone().emit_triangles(|t| triangles.push(t));
+    }
+    weld(&mut vertices, &mut triangles, 1e-5);
+    let positions: Vec<Position> = vertices.iter().map(|v| v.pos).collect();
+    let report = validate(&positions, &triangles);
+    assert!(report.is_valid(), "{:?}", report);
+    let mut edges = std::collections::HashSet::new();
+    for t in &triangles {
+        for (a, b) in [(t.x, t.y), (t.y, t.z), (t.z, t.x)] {
+            edges.insert((a, b));
+        }
+    }
+    assert!(edges.iter().all(|&(a, b)| edges.contains(&(b, a))));
+    indexed_volume(&positions, &triangles)
+}
+
+// every vertex normal leans the same way as the polygon it is on
+fn assert_normals_agree(vertices: &[Vertex], polygons: &[Polygon<usize>]) {
+    for p in polygons {
+        p.clone().emit_triangles(|t| {
+            let pos = |i: usize| Vector3::from(vertices[i].pos);
+            let face = (pos(t.y) - pos(t.x)).cross(pos(t.z) - pos(t.x));
+            for i in [t.x, t.y, t.z] {
+                assert!(Vector3::from(vertices[i].normal).dot(face) > 0.);
+            }
+        });
+    }
+}
+
+#[test]
+fn chamfered_cube() {
+    let (vertices, polygons) = bevelled_cube(0.1, 1);
+    // the faces, a quad along each edge and a triangle at each corner
+    assert_eq!(polygons.len(), 6 + 12 + 8);
+    assert_normals_agree(&vertices, &polygons);
+
+    // each edge loses a prism, and each corner 5/6 of a small cube
+    let (l, w) = (2. / 3f32.sqrt(), 0.1);
+    let expected = l * l * l - 12. * w * w / 2. * (l - 2. * w) - 8. * 5. / 6. * w * w * w;
+    assert!((closed_volume(vertices, &polygons) - expected).abs() < 1e-4);
+}
+
+#[test]
+fn rounded_cube() {
+    let (vertices, polygons) = bevelled_cube(0.1, 8);
+    // the corners are fans around a new vertex, three profiles long
+    assert_eq!(polygons.len(), 6 + 12 * 8 + 8 * 3 * 8);
+    assert_normals_agree(&vertices, &polygons);
+    // the rounded edges and corners are smooth
+    for v in &vertices {
+        assert!((Vector3::from(v.normal).magnitude() - 1.).abs() < 1e-5);
+    }
+
+    let (l, w) = (2. / 3f32.sqrt(), 0.1);
+    let expected =
+        l * l * l - 12. * (l - 2. * w) * w * w * (1. - PI / 4.) - 8. * w * w * w * (1. - PI / 6.);
+    // the segments cut a little into the round
+    let v = closed_volume(vertices, &polygons);
+    assert!(v < expected && v > expected - 2e-3);
+}
+
+#[test]
+fn flat_edges_are_kept() {
+    // a subdivided cube only bevels the edges of the cube
+    let cube = Cube::subdivided(2, 2, 2);
+    let positions: Vec<Position> = cube.shared_vertex_iter().map(|v| v.pos).collect();
+    let polygons: Vec<Polygon<usize>> = cube.indexed_polygon_iter().collect();
+    let (vertices, bevelled) = bevel(&positions, &polygons, 0.05, 1);
+    assert_eq!(bevelled.len(), 24 + 12 * 2 + 8);
+    assert_normals_agree(&vertices, &bevelled);
+    closed_volume(vertices, &bevelled);
+
+    // an open surface keeps its boundary
+    let (vertices, bevelled) = bevel(&positions, &polygons[..4], 0.05, 1);
+    assert_eq!(bevelled.len(), 4);
+    assert_eq!(vertices.len(), 16);
+}
@@ -0,0 +1,49 @@
+extern crate genmesh;
+
+use genmesh::generators::{Cube, IndexedPolygon, SharedVertex, SphereUv};
+use genmesh::{
+    indexed_bounding_box, indexed_bounding_sphere, Bounds, MapToVertices, Position, Triangle,
+    Triangulate, Vertex, Vertices,
+};
+
+#[test]
+fn bounding_box_of_streams() {
+    let s = 1. / 3f32.sqrt();
+    let from_polygons = Cube::new().bounding_box().unwrap();
+    let from_vertices = Cube::new().vertices().bounding_box().unwrap();
+    let from_positions = Cube::new()
+        .vertex(|v: Vertex| v.pos)
+        .triangulate()
+        .bounding_box()
+        .unwrap();
+    assert_eq!(from_polygons, from_vertices);
+    assert_eq!(from_polygons, from_positions);
+    assert_eq!(from_polygons.min, [-s, -s, -s].into());
+    assert_eq!(from_polygons.max, [s, s, s].into());
+
+    assert!(std::iter::empty::<Vertex>().bounding_box().is_none());
+}
+
+#[test]
+fn bounding_sphere_of_streams() {
+    let sphere = SphereUv::new(16, 8).bounding_sphere().unwrap();
+    assert!((sphere.radius - 1.).abs() < 1e-4);
+    assert!(Cube::new().vertices().all(|v| sphere.contains(v.pos)));
+    assert!(std::iter::empty::<Vertex>().bounding_sphere().is_none());
+}
+
+#[test]
+fn bounding_volumes_indexed() {
+    let cube = Cube::new();
+    let mut positions: Vec<Position> = cube.shared_vertex_iter().map(|v| v.pos).collect();
+    // an unused vertex far away does not count
+    positions.push([10., 10., 10.].into());
+    let triangles: Vec<Triangle<usize>> = cube.indexed_polygon_iter().triangulate().collect();
+
+    let aabb = indexed_bounding_box(&positions, &triangles).unwrap();
+    assert_eq!(aabb, Cube::new().bounding_box().unwrap());
+    let sphere = indexed_bounding_sphere(&positions, &triangles).unwrap();
+    assert!((sphere.radius - 1.).abs() < 1e-4);
+
+    assert!(indexed_bounding_box(&positions, &[]).is_none());
+}
@@ -0,0 +1,116 @@
+extern crate genmesh;
+
+use genmesh::buffer::{
+    decode_octahedral, encode_octahedral, f16_to_f32, f32_to_f16, Attribute, Format,
+    InterleavedBuilder, NormalFormat, PositionFormat, UvFormat,
+};
+use genmesh::generators::SphereUv;
+use genmesh::{Vertex, Vertices};
+
+#[test]
+fn half_floats() {
+    assert_eq!(0x0000, f32_to_f16(0.));
+    assert_eq!(0x8000, f32_to_f16(-0.));
+    assert_eq!(0x3c00, f32_to_f16(1.));
+    assert_eq!(0xc000, f32_to_f16(-2.));
+    assert_eq!(0x7bff, f32_to_f16(65504.));
+    assert_eq!(0x7c00, f32_to_f16(1e6));
+    assert_eq!(0xfc00, f32_to_f16(f32::NEG_INFINITY));
+    assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+    // smallest subnormal
+    assert_eq!(0x0001, f32_to_f16(5.960_464_5e-8));
+    // ties round to even
+    assert_eq!(0x3c00, f32_to_f16(1. + 1. / 2048.));
+    assert_eq!(0x3c02, f32_to_f16(1. + 3. / 2048.));
+
+    for bits in 0..0x7c00u16 {
+        assert_eq!(bits, f32_to_f16(f16_to_f32(bits)));
+        assert_eq!(bits | 0x8000, f32_to_f16(f16_to_f32(bits | 0x8000)));
+    }
+}
+
+#[test]
+fn octahedral_normals() {
+    for v in SphereUv::new(16, 16).vertices() {
+        let decoded = decode_octahedral(encode_octahedral(v.normal));
+        let dot = v.normal.x * decoded.x + v.normal.y * decoded.y + v.normal.z * decoded.z;
+        assert!(dot > 0.999_999, "{:?} decoded to {:?}", v.normal, decoded);
+    }
+    assert_eq!([0, 0], encode_octahedral([0., 0., 1.].into()));
+}
+
+#[test]
+fn interleaved_layout() {
+    let vertex = Vertex {
+        pos: [1., -2., 0.5].into(),
+        normal: [0., 0., -1.].into(),
+    };
+
+    let mut full = InterleavedBuilder::new(PositionFormat::Float32, NormalFormat::Float32);
+    full.push(vertex);
+    let (layout, bytes) = full.finish();
+    assert_eq!(24, layout.stride);
+    assert_eq!(24, bytes.len());
+    assert_eq!(&1f32.to_le_bytes()[..], &bytes[0..4]);
+    assert_eq!(&(-1f32).to_le_bytes()[..], &bytes[20..24]);
+
+    let mut packed = InterleavedBuilder::new(PositionFormat::Float16, NormalFormat::Float16)
+        .with_uvs(UvFormat::Unorm16);
+    packed.push_with_uv(vertex, [0.5, 2.]);
+    packed.push_with_uv(vertex, [0., 1.]);
+    assert_eq!(2, packed.len());
+    let (layout, bytes) = packed.finish();
+    assert_eq!(20, layout.stride);
+    assert_eq!(
+        vec![
+            (Attribute::Position, Format::Float16x4, 0),
+            (Attribute::Normal, Format::Float16x4, 8),
+            (Attribute::TexCoord, Format::Unorm16x2, 16),
+        ],
+        layout
+            .attributes
+            .iter()
+            .map(|a| (a.attribute, a.format, a.offset))
+            .collect::<Vec<_>>()
+    );
+    let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+    assert_eq!(-2., f16_to_f32(u16_at(2)));
+    assert_eq!(1., f16_to_f32(u16_at(6)));
+    assert_eq!(-1., f16_to_f32(u16_at(12)));
+    assert_eq!(32768, u16_at(16));
+    // clamped to the representable range
+    assert_eq!(65535, u16_at(18));
+    assert_eq!(0, u16_at(36));
+}
+
+#[test]
+#[should_panic]
+fn missing_uvs() {
+    let mut builder = InterleavedBuilder::new(PositionFormat::Float32, NormalFormat::Octahedral)
+        .with_uvs(UvFormat::Float32);
+    builder.extend(SphereUv::new(4, 4).vertices());
+}
+
+#[cfg(feature = "wgpu")]
+#[test]
+fn wgpu_layouts() {
+    let builder = InterleavedBuilder::new(PositionFormat::Float16, NormalFormat::Octahedral)
+        .with_uvs(UvFormat::Unorm16);
+    let layout = builder.layout();
+    let attributes = layout.wgpu_attributes();
+    let desc = layout.wgpu_layout(&attributes);
+    assert_eq!(layout.stride as u64, desc.array_stride);
+    assert_eq!(3, desc.attributes.len());
+    assert_eq!(wgpu::VertexFormat::Float16x4, desc.attributes[0].format);
+    assert_eq!(wgpu::VertexFormat::Snorm16x2, desc.attributes[1].format);
+    assert_eq!(1, desc.attributes[1].shader_location);
+    assert_eq!(
+        layout.attributes[2].offset as u64,
+        desc.attributes[2].offset
+    );
+    assert_eq!(2, desc.attributes[2].shader_location);
+
+    let plain = Vertex::wgpu_layout();
+    assert_eq!(24, plain.array_stride);
+    assert_eq!(12, plain.attributes[1].offset);
+}
@@ -0,0 +1,90 @@
+extern crate genmesh;
+
+use genmesh::analytic::Aabb;
+use genmesh::bvh::Bvh;
+use genmesh::generators::{IcoSphere, SphereUv};
+use genmesh::{MapToVertices, Position, Triangle, Triangulate};
+
+fn triangles<I: Iterator<Item = genmesh::Polygon<genmesh::Vertex>>>(
+    iter: I,
+) -> Vec<Triangle<Position>> {
+    iter.vertex(|v| v.pos).triangulate().collect()
+}
+
+fn distance(a: Position, b: Position) -> f32 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
+}
+
+#[test]
+fn bvh_raycast() {
+    let bvh = Bvh::build(&triangles(SphereUv::new(32, 16)));
+    let hit = bvh
+        .raycast([-3., 0.01, 0.02].into(), [2., 0., 0.].into())
+        .unwrap();
+    // the facets sit slightly inside the unit sphere
+    assert!(hit.point.x < -0.98 && hit.point.x > -1.001);
+    assert!((hit.distance - (3. + hit.point.x) / 2.).abs() < 1e-5);
+
+    assert!(bvh
+        .raycast([-3., 0., 0.].into(), [-1., 0., 0.].into())
+        .is_none());
+    assert!(bvh
+        .raycast([-3., 2., 0.].into(), [1., 0., 0.].into())
+        .is_none());
+}
+
+#[test]
+fn bvh_queries_match_brute_force() {
+    let tris = triangles(IcoSphere::subdivide(2));
+    let bvh = Bvh::build(&tris);
+
+    let query = Aabb::new([0.2, -0.3, 0.].into(), [1., 0.3, 0.5].into());
+    let expected: Vec<usize> = tris
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| Aabb::new(t.x, t.x).grow(t.y).grow(t.z).intersects(&query))
+        .map(|(i, _)| i)
+        .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(expected, bvh.query_aabb(&query));
+
+    for &p in &[[0.1, 0.2, 0.3], [2., -1., 0.5], [0., 0., -5.]] {
+        let p: Position = p.into();
+        let nearest = bvh.nearest(p).unwrap();
+        let d = (p.x * p.x + p.y * p.y + p.z * p.z).sqrt();
+        // the facets are within a few percent of the unit sphere
+        assert!((nearest.distance - (d - 1.).abs()).abs() < 0.05);
+        assert!((distance(p, nearest.point) - nearest.distance).abs() < 1e-5);
+        let t = &tris[nearest.triangle];
+        let plane = [t.x, t.y, t.z];
+        assert!(plane.iter().all(|&v| distance(v, nearest.point) < 0.5));
+    }
+}
+
+#[test]
+fn bvh_refit() {
+    let mut tris = triangles(IcoSphere::subdivide(1));
+    let mut bvh = Bvh::build(&tris);
+    for t in &mut tris {
+        for v in [&mut t.x, &mut t.y, &mut t.z] {
+            v.x += 10.;
+        }
+    }
+    bvh.refit(&tris);
+    let bounds = bvh.bounds().unwrap();
+    assert!((bounds.min.x - 9.).abs() < 1e-5 && (bounds.max.x - 11.).abs() < 1e-5);
+    let hit = bvh
+        .raycast([10., 0.05, -5.].into(), [0., 0., 1.].into())
+        .unwrap();
+    assert!(hit.point.z < -0.8, "{:?}", hit);
+}
+
+#[test]
+fn bvh_empty() {
+    let bvh = Bvh::build(&[]);
+    assert!(bvh.bounds().is_none());
+    assert!(bvh.nearest([0., 0., 0.].into()).is_none());
+    assert!(bvh
+        .raycast([0., 0., 0.].into(), [1., 0., 0.].into())
+        .is_none());
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "bytemuck")]
+
+extern crate genmesh;
+
+use genmesh::generators::{Cube, SharedVertex};
+use genmesh::{AsBytes, Vertex};
+
+#[test]
+fn vertex_bytes() {
+    let vertices: Vec<Vertex> = Cube::new().shared_vertex_iter().collect();
+    let bytes = vertices.as_bytes();
+    assert_eq!(24 * 24, bytes.len());
+    assert_eq!(&vertices[0].pos.x.to_ne_bytes(), &bytes[0..4]);
+    assert_eq!(&vertices[0].normal.z.to_ne_bytes(), &bytes[20..24]);
+    assert_eq!(&vertices[1].pos.x.to_ne_bytes(), &bytes[24..28]);
+
+    let back: &[Vertex] = bytemuck::cast_slice(bytes);
+    assert_eq!(&vertices[..], back);
+
+    let indices: Vec<u32> = vec![0, 1, 2];
+    assert_eq!(12, indices.as_bytes().len());
+}
@@ -0,0 +1,97 @@
+extern crate genmesh;
+
+use genmesh::generators::{IndexedPolygon, SharedVertex, SphereUv};
+use genmesh::validate::validate;
+use genmesh::{compact, remove_degenerate_faces, Position, Triangle, Triangulate, Vertex};
+
+fn quad() -> Vec<Position> {
+    vec![
+        [0., 0., 0.].into(),
+        [1., 0., 0.].into(),
+        [1., 1., 0.].into(),
+        [0., 1., 0.].into(),
+        // on the line from 0 to 1
+        [0.5, 0., 0.].into(),
+        // not used
+        [5., 5., 5.].into(),
+    ]
+}
+
+#[test]
+fn remove() {
+    let mut vertices = quad();
+    let mut triangles = vec![
+        Triangle::new(0, 1, 2),
+        Triangle::new(0, 0, 3),
+        Triangle::new(0, 4, 1),
+        Triangle::new(1, 2, 0),
+        Triangle::new(0, 2, 3),
+        Triangle::new(0, 3, 2),
+    ];
+    assert_eq!(
+        remove_degenerate_faces(&mut vertices, &mut triangles, false),
+        4
+    );
+    assert_eq!(vertices, quad());
+    assert_eq!(
+        triangles,
+        vec![Triangle::new(0, 1, 2), Triangle::new(0, 2, 3)]
+    );
+    assert!(validate(&vertices, &triangles).is_valid());
+}
+
+#[test]
+fn remove_and_compact() {
+    let mut vertices = quad();
+    let mut triangles = vec![
+        Triangle::new(0, 4, 1),
+        Triangle::new(3, 1, 2),
+        Triangle::new(1, 2, 2),
+    ];
+    assert_eq!(
+        remove_degenerate_faces(&mut vertices, &mut triangles, true),
+        2
+    );
+    assert_eq!(vertices, quad()[1..4].to_vec());
+    assert_eq!(triangles, vec![Triangle::new(2, 0, 1)]);
+}
+
+#[test]
+fn clean_mesh_untouched() {
+    let sphere = SphereUv::new(12, 8);
+    let mut vertices: Vec<Vertex> = sphere.shared_vertex_iter().collect();
+    let mut triangles: Vec<Triangle<usize>> = sphere.indexed_polygon_iter().triangulate().collect();
+    let expected = (vertices.clone(), triangles.clone());
+    assert_eq!(
+        remove_degenerate_faces(&mut vertices, &mut triangles, true),
+        0
+    );
+    assert_eq!((vertices, triangles), expected);
+}
+
+#[test]
+fn compact_remap() {
+    let mut vertices = quad();
+    let mut triangles = vec![Triangle::new(3, 1, 2), Triangle::new(4, 1, 3)];
+    let remap = compact(&mut vertices, &mut triangles);
+    assert_eq!(remap, vec![None, Some(0), Some(1), Some(2), Some(3), None]);
+    assert_eq!(vertices, quad()[1..5].to_vec());
+    assert_eq!(
+        triangles,
+        vec![Triangle::new(2, 0, 1), Triangle::new(3, 0, 2)]
+    );
+
+    // other attributes follow with the mapping
+    let colors = ["a", "b", "c", "d", "e", "f"];
+    let mut kept = vec![""; vertices.len()];
+    for (i, r) in remap.iter().enumerate() {
+        if let Some(r) = *r {
+            kept[r] = colors[i];
+        }
+    }
+    assert_eq!(kept, vec!["b", "c", "d", "e"]);
+
+    let mut none: Vec<Position> = quad();
+    assert_eq!(compact(&mut none, &mut []), vec![None; 6]);
+    assert!(none.is_empty());
+}
@@ -0,0 +1,97 @@
+extern crate genmesh;
+
+use genmesh::analytic::Plane;
+use genmesh::generators::{Cube, SphereUv};
+use genmesh::{Clip, Polygon, Triangulate, Vertex, Vertices};
+
+fn area(polygons: &[Polygon<Vertex>]) -> f32 {
+    polygons
+        .iter()
+        .cloned()
+        .triangulate()
+        .map(|t| {
+            let (a, b, c) = (t.x.pos, t.y.pos, t.z.pos);
+            let u = [b.x - a.x, b.y - a.y, b.z - a.z];
+            let v = [c.x - a.x, c.y - a.y, c.z - a.z];
+            let n = [
+                u[1] * v[2] - u[2] * v[1],
+                u[2] * v[0] - u[0] * v[2],
+                u[0] * v[1] - u[1] * v[0],
+            ];
+            (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt() * 0.5
+        })
+        .sum()
+}
+
+#[test]
+fn clip_keeps_front() {
+    let plane = Plane::new([1., 0., 0.].into(), 0.5);
+    let clipped: Vec<_> = Cube::new().clip(plane).collect();
+    // the +X face whole, and a slice of the four faces around it
+    assert_eq!(clipped.len(), 5);
+    for v in clipped.iter().cloned().vertices() {
+        assert!(v.pos.x >= 0.5);
+    }
+    let s = 1. / 3f32.sqrt();
+    let expected = 4. * s * s + 4. * 2. * s * (s - 0.5);
+    assert!((area(&clipped) - expected).abs() < 1e-4);
+}
+
+#[test]
+fn clip_everything() {
+    let behind = Plane::new([0., 0., 1.].into(), 2.);
+    assert_eq!(Cube::new().clip(behind).count(), 0);
+    let front = Plane::new([0., 0., 1.].into(), -2.);
+    assert_eq!(Cube::new().clip(front).count(), 6);
+}
+
+#[test]
+fn clip_cap() {
+    let plane = Plane::new([0., 1., 1.].into(), 0.);
+    let clipped: Vec<_> = SphereUv::new(16, 12).clip(plane).with_cap().collect();
+    let normal = [0., -(0.5f32).sqrt(), -(0.5f32).sqrt()];
+    let cap: Vec<_> = clipped
+        .iter()
+        .filter(|p| {
+            (*p).clone().as_vertices().all(|v| {
+                (v.normal.x - normal[0]).abs() < 1e-5
+                    && (v.normal.y - normal[1]).abs() < 1e-5
+                    && (v.normal.z - normal[2]).abs() < 1e-5
+            })
+        })
+        .cloned()
+        .collect();
+    assert!(!cap.is_empty());
+    // the cap is a polygon inscribed in the unit circle
+    let a = area(&cap);
+    assert!(a > 2.5 && a < std::f32::consts::PI, "{}", a);
+
+    // every cap triangle faces away from the kept half
+    for t in cap.into_iter().triangulate() {
+        let (a, b, c) = (t.x.pos, t.y.pos, t.z.pos);
+        let u = [b.x - a.x, b.y - a.y, b.z - a.z];
+        let v = [c.x - a.x, c.y - a.y, c.z - a.z];
+        let n = [
+            u[1] * v[2] - u[2] * v[1],
+            u[2] * v[0] - u[0] * v[2],
+            u[0] * v[1] - u[1] * v[0],
+        ];
+        assert!(n[0] * normal[0] + n[1] * normal[1] + n[2] * normal[2] >= 0.);
+    }
+}
+
+#[test]
+fn clip_cap_with_hole() {
+    use genmesh::generators::Torus;
+    let plane = Plane::new([0., 1., 0.].into(), 0.);
+    let clipped: Vec<_> = Torus::new(1., 0.25, 16, 8).clip(plane).with_cap().collect();
+    let cap: Vec<_> = clipped
+        .iter()
+        .filter(|p| (*p).clone().as_vertices().all(|v| v.normal.y == -1.))
+        .cloned()
+        .collect();
+    // an annulus between radius 0.75 and 1.25, give or take the facets
+    let a = area(&cap);
+    let exact = std::f32::consts::PI * (1.25 * 1.25 - 0.75 * 0.75);
+    assert!((a - exact).abs() < 0.2 * exact, "{} {}", a, exact);
+}
@@ -0,0 +1,34 @@
+//! Fixtures shared by the integration tests.
+
+#![allow(dead_code)]
+
+use genmesh::generators::{IndexedPolygon, SharedVertex};
+use genmesh::{weld, Polygon, Position, Triangle, Triangulate, Vertex};
+
+/// The vertices of `generator` and its triangles indexing into them. With
+/// a tolerance, the vertices closer than it are welded together, which
+/// joins the faces of generators with split normals.
+pub fn indexed<G>(generator: &G, tolerance: Option<f32>) -> (Vec<Vertex>, Vec<Triangle<usize>>)
+where
+    G: SharedVertex<Vertex> + IndexedPolygon<Polygon<usize>>,
+{
+    let mut vertices: Vec<Vertex> = generator.shared_vertex_iter().collect();
+    let mut triangles: Vec<Triangle<usize>> =
+        generator.indexed_polygon_iter().triangulate().collect();
+    if let Some(tolerance) = tolerance {
+        weld(&mut vertices, &mut triangles, tolerance);
+    }
+    (vertices, triangles)
+}
+
+/// Same as `indexed`, keeping only the positions of the vertices.
+pub fn indexed_positions<G>(
+    generator: &G,
+    tolerance: Option<f32>,
+) -> (Vec<Position>, Vec<Triangle<usize>>)
+where
+    G: SharedVertex<Vertex> + IndexedPolygon<Polygon<usize>>,
+{
+    let (vertices, triangles) = indexed(generator, tolerance);
+    (vertices.iter().map(|v| v.pos).collect(), triangles)
+}
@@ -0,0 +1,92 @@
+extern crate genmesh;
+
+mod common;
+
+use genmesh::compare::{compare, Difference, Tolerance};
+use genmesh::generators::{Cube, IcoSphere};
+use genmesh::{Position, Triangle};
+
+use common::indexed;
+
+#[test]
+fn same_up_to_order_and_rounding() {
+    let (vertices, triangles) = indexed(&IcoSphere::subdivide(2), None);
+    // shuffle the vertices, nudge them, and start each triangle elsewhere
+    let n = vertices.len();
+    let new_index = |i: usize| (i * 7 + 3) % n;
+    let mut moved = vertices.clone();
+    for (i, v) in vertices.iter().enumerate() {
+        let mut v = *v;
+        v.pos.x += 1e-6;
+        v.normal.y += 1e-5;
+        moved[new_index(i)] = v;
+    }
+    let renumbered: Vec<Triangle<usize>> = triangles
+        .iter()
+        .rev()
+        .map(|t| Triangle::new(new_index(t.z), new_index(t.x), new_index(t.y)))
+        .collect();
+    let tolerance = Tolerance::default();
+    assert_eq!(
+        compare(&vertices, &triangles, &moved, &renumbered, tolerance),
+        Ok(())
+    );
+
+    // but not flipped
+    let flipped: Vec<Triangle<usize>> = renumbered
+        .iter()
+        .map(|t| Triangle::new(t.x, t.z, t.y))
+        .collect();
+    assert_eq!(
+        compare(&vertices, &triangles, &moved, &flipped, tolerance),
+        Err(Difference::Triangle(0))
+    );
+}
+
+#[test]
+fn differences() {
+    let (vertices, triangles) = indexed(&Cube::new(), None);
+    let tolerance = Tolerance::default();
+    assert_eq!(
+        compare(&vertices, &triangles, &vertices[1..], &triangles, tolerance),
+        Err(Difference::VertexCount {
+            expected: 24,
+            actual: 23
+        })
+    );
+    assert_eq!(
+        compare(&vertices, &triangles, &vertices, &triangles[1..], tolerance),
+        Err(Difference::TriangleCount {
+            expected: 12,
+            actual: 11
+        })
+    );
+
+    let mut moved = vertices.clone();
+    moved[5].pos.z += 1e-3;
+    assert_eq!(
+        compare(&vertices, &triangles, &moved, &triangles, tolerance),
+        Err(Difference::Vertex(5))
+    );
+    let loose = Tolerance {
+        position: 1e-2,
+        ..tolerance
+    };
+    assert_eq!(
+        compare(&vertices, &triangles, &moved, &triangles, loose),
+        Ok(())
+    );
+
+    // the corners of the cube have a vertex per face, told apart by their
+    // normals
+    let mut turned = vertices.clone();
+    turned[2].normal = turned[20].normal;
+    assert!(compare(&vertices, &triangles, &turned, &triangles, tolerance).is_err());
+
+    // positions only, without normals
+    let positions: Vec<Position> = vertices.iter().map(|v| v.pos).collect();
+    assert_eq!(
+        compare(&positions, &triangles, &vertices, &triangles, tolerance),
+        Err(Difference::Vertex(0))
+    );
+}
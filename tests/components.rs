@@ -0,0 +1,78 @@
+extern crate genmesh;
+
+use genmesh::generators::{Cube, IcoSphere, IndexedPolygon, SharedVertex};
+use genmesh::{
+    filter_components, label_components, split_components, weld, Triangle, Triangulate, Vertex,
+    Vertices,
+};
+
+// two icospheres and a lone triangle, as one indexed mesh
+fn fragments() -> (Vec<Vertex>, Vec<Triangle<usize>>) {
+    let sphere = IcoSphere::subdivide(1);
+    let mut vertices: Vec<Vertex> = sphere.shared_vertex_iter().collect();
+    let faces: Vec<Triangle<usize>> = sphere.indexed_polygon_iter().triangulate().collect();
+    let n = vertices.len();
+    let mut triangles = faces.clone();
+    vertices.extend(sphere.shared_vertex_iter().map(|mut v| {
+        v.pos.x += 3.;
+        v
+    }));
+    triangles.extend(
+        faces
+            .iter()
+            .map(|t| Triangle::new(t.x + n, t.y + n, t.z + n)),
+    );
+    vertices.extend(vertices[..3].to_vec());
+    triangles.push(Triangle::new(2 * n, 2 * n + 1, 2 * n + 2));
+    (vertices, triangles)
+}
+
+#[test]
+fn label() {
+    let (_, triangles) = fragments();
+    let (labels, count) = label_components(&triangles);
+    assert_eq!(count, 3);
+    assert_eq!(labels.len(), triangles.len());
+    assert!(labels[..80].iter().all(|&c| c == 0));
+    assert!(labels[80..160].iter().all(|&c| c == 1));
+    assert_eq!(labels[160], 2);
+
+    assert_eq!(label_components(&[]), (vec![], 0));
+}
+
+#[test]
+fn split() {
+    let (vertices, triangles) = fragments();
+    let parts = split_components(&vertices, &triangles);
+    assert_eq!(parts.len(), 3);
+    let sphere = IcoSphere::subdivide(1);
+    for (v, t) in &parts[..2] {
+        assert_eq!(v.len(), sphere.shared_vertex_count());
+        assert_eq!(t.len(), 80);
+    }
+    // the second sphere keeps its offset, and its own indexing
+    assert!(parts[1].0.iter().all(|v| v.pos.x > 1.));
+    assert_eq!(parts[1].1, parts[0].1);
+    assert_eq!(parts[2].0, vertices[..3].to_vec());
+    assert_eq!(parts[2].1, vec![Triangle::new(0, 1, 2)]);
+}
+
+#[test]
+fn filter() {
+    let (_, triangles) = fragments();
+    assert_eq!(filter_components(&triangles, 2), triangles[..160].to_vec());
+    assert_eq!(filter_components(&triangles, 1), triangles);
+    assert!(filter_components(&triangles, 81).is_empty());
+}
+
+#[test]
+fn welded_cube() {
+    // the faces of the cube only join once their corners are welded
+    let mut vertices: Vec<Vertex> = Cube::new().triangulate().vertices().collect();
+    let mut triangles: Vec<Triangle<usize>> = (0..vertices.len() / 3)
+        .map(|i| Triangle::new(3 * i, 3 * i + 1, 3 * i + 2))
+        .collect();
+    assert_eq!(label_components(&triangles).1, 12);
+    weld(&mut vertices, &mut triangles, 1e-5);
+    assert_eq!(label_components(&triangles).1, 1);
+}
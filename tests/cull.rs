@@ -0,0 +1,50 @@
+extern crate genmesh;
+
+use genmesh::analytic::Plane;
+use genmesh::generators::{Cube, IcoSphere};
+use genmesh::{Cull, Triangulate, View};
+
+#[test]
+fn cull_backfaces() {
+    let top: Vec<_> = Cube::new()
+        .cull_backfaces(View::Direction([0., 0., -1.].into()))
+        .collect();
+    assert_eq!(top.len(), 1);
+
+    assert_eq!(
+        Cube::new()
+            .triangulate()
+            .cull_backfaces(View::Point([5., 5., 5.].into()))
+            .count(),
+        6
+    );
+    // from inside, every face looks away
+    assert_eq!(
+        Cube::new()
+            .cull_backfaces(View::Point([0., 0., 0.].into()))
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn cull_outside() {
+    // the -x face is the only one entirely behind x = 0.5
+    let kept = Cube::new()
+        .cull_outside(vec![Plane::new([1., 0., 0.].into(), 0.5)])
+        .count();
+    assert_eq!(kept, 5);
+
+    // a box around the +z cap of the sphere
+    let planes = vec![
+        Plane::new([0., 0., 1.].into(), 0.5),
+        Plane::new([1., 0., 0.].into(), -0.5),
+        Plane::new([-1., 0., 0.].into(), -0.5),
+        Plane::new([0., 1., 0.].into(), -0.5),
+        Plane::new([0., -1., 0.].into(), -0.5),
+    ];
+    let all = IcoSphere::subdivide(2).count();
+    let kept: Vec<_> = IcoSphere::subdivide(2).cull_outside(planes).collect();
+    assert!(!kept.is_empty() && kept.len() < all / 4);
+    assert!(Cube::new().cull_outside(Vec::new()).count() == 6);
+}
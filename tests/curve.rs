@@ -0,0 +1,215 @@
+extern crate genmesh;
+
+use std::f32::consts::PI;
+
+use cgmath::{InnerSpace, Matrix4, Vector3, Vector4};
+use genmesh::curve::{ArcLength, CatmullRom, CubicBezier, Curve, Frame, Polyline};
+use genmesh::Position;
+
+fn p(x: f32, y: f32, z: f32) -> Position {
+    [x, y, z].into()
+}
+
+fn distance(a: Position, b: Position) -> f32 {
+    (Vector3::from(a) - Vector3::from(b)).magnitude()
+}
+
+// the derivative agrees with the change of the points around `t`
+fn assert_derivative<C: Curve>(curve: &C, t: f32) {
+    let h = 1e-3;
+    let d = (Vector3::from(curve.point(t + h)) - Vector3::from(curve.point(t - h))) / (2. * h);
+    let e = Vector3::from(curve.derivative(t));
+    assert!(
+        (d - e).magnitude() < 1e-2 * e.magnitude().max(1.),
+        "{:?} {:?}",
+        d,
+        e
+    );
+}
+
+#[test]
+fn polyline() {
+    let line = Polyline::new(vec![p(0., 0., 0.), p(3., 0., 0.), p(3., 1., 0.)]);
+    assert!((line.length() - 4.).abs() < 1e-6);
+    // each segment takes half of the parameter, whatever its length
+    assert_eq!(p(3., 0., 0.), line.point(0.5));
+    assert_eq!(p(3., 0.5, 0.), line.point(0.75));
+    assert!(distance(p(2., 0., 0.), line.point_at_length(2.)) < 1e-5);
+    assert!(distance(p(3., 0.5, 0.), line.point_at_length(3.5)) < 1e-5);
+    assert_eq!(p(3., 1., 0.), line.point_at_length(10.));
+    assert_derivative(&line, 0.3);
+
+    let lines: Vec<_> = line.lines().collect();
+    assert_eq!(2, lines.len());
+    assert_eq!(p(3., 0., 0.), lines[0].y.pos);
+    assert_eq!([0., 1., 0.], <[f32; 3]>::from(lines[1].x.normal));
+}
+
+#[test]
+fn catmull_rom() {
+    let points = vec![p(0., 0., 0.), p(1., 2., 0.), p(3., 2., 1.), p(4., 0., 1.)];
+    let spline = CatmullRom::new(points.clone());
+    // through every point
+    for (i, &q) in points.iter().enumerate() {
+        assert!(distance(q, spline.point(i as f32 / 3.)) < 1e-5);
+    }
+    // with the tangent of the neighbors at the inner points, and the
+    // chord at the ends
+    let tangent = |t: f32| Vector3::from(spline.derivative(t)).normalize();
+    assert!((tangent(1. / 3.) - Vector3::new(3., 2., 1.).normalize()).magnitude() < 1e-5);
+    assert!((tangent(0.) - Vector3::new(1., 2., 0.).normalize()).magnitude() < 1e-5);
+    for t in [0.1, 0.4, 0.5, 0.9] {
+        assert_derivative(&spline, t);
+    }
+    // longer than the chords
+    let chords = Polyline::new(points).length();
+    assert!(spline.length() > chords);
+    assert!(spline.length() < chords * 1.2);
+}
+
+#[test]
+fn bezier() {
+    // the usual approximation of a quarter of the unit circle
+    let k = 0.552_284_8;
+    let arc = CubicBezier::new(p(1., 0., 0.), p(1., k, 0.), p(k, 1., 0.), p(0., 1., 0.));
+    assert_eq!(p(1., 0., 0.), arc.point(0.));
+    assert_eq!(p(0., 1., 0.), arc.point(1.));
+    assert!((arc.length() - PI / 2.).abs() < 1e-3);
+    for t in [0.2, 0.5, 0.8] {
+        assert!((Vector3::from(arc.point(t)).magnitude() - 1.).abs() < 1e-3);
+        assert_derivative(&arc, t);
+    }
+    let table = ArcLength::new(&arc, 1000);
+    assert!((table.length() - arc.length()).abs() < 1e-4);
+    // halfway along is at 45 degrees, by symmetry
+    let half = arc.point(table.parameter(table.length() / 2.));
+    assert!((half.x - half.y).abs() < 1e-4);
+}
+
+#[test]
+fn resample_evenly() {
+    let spline = CatmullRom::new(vec![
+        p(0., 0., 0.),
+        p(1., 2., 0.),
+        p(2., 2., 1.),
+        p(4., 0., 0.),
+    ]);
+    let polyline = spline.resample(41);
+    let points = polyline.points();
+    assert_eq!(41, points.len());
+    assert_eq!(spline.point(0.), points[0]);
+    assert_eq!(spline.point(1.), points[40]);
+    let step = spline.length() / 40.;
+    for w in points.windows(2) {
+        assert!((distance(w[0], w[1]) - step).abs() < step * 0.02);
+    }
+
+    let by_length = spline.resample_by_length(0.5);
+    let n = by_length.points().len();
+    assert_eq!((spline.length() / 0.5).ceil() as usize + 1, n);
+    for w in by_length.points().windows(2) {
+        assert!(distance(w[0], w[1]) <= 0.5);
+    }
+}
+
+fn assert_orthonormal(f: &Frame) {
+    let (t, n, b) = (
+        Vector3::from(f.tangent),
+        Vector3::from(f.normal),
+        Vector3::from(f.binormal),
+    );
+    for v in [t, n, b] {
+        assert!((v.magnitude() - 1.).abs() < 1e-4);
+    }
+    assert!(t.dot(n).abs() < 1e-4 && t.dot(b).abs() < 1e-4 && n.dot(b).abs() < 1e-4);
+    assert!((n.cross(b) - t).magnitude() < 1e-4);
+}
+
+// a helix around z, turning once every 2 pi of height
+struct Helix;
+
+impl Curve for Helix {
+    fn point(&self, t: f32) -> Position {
+        let a = t * 4. * PI;
+        p(a.cos(), a.sin(), a)
+    }
+
+    fn derivative(&self, t: f32) -> mint::Vector3<f32> {
+        let a = t * 4. * PI;
+        (Vector3::new(-a.sin(), a.cos(), 1.) * 4. * PI).into()
+    }
+}
+
+#[test]
+fn frenet_frames() {
+    let frames = Frame::frenet(&Helix, 50);
+    assert_eq!(50, frames.len());
+    for f in &frames {
+        assert_orthonormal(f);
+        // the normal of a helix points straight at its axis
+        let towards = -Vector3::new(f.position.x, f.position.y, 0.);
+        assert!((Vector3::from(f.normal) - towards).magnitude() < 1e-2);
+    }
+
+    // straight lines keep whatever normal they start with
+    let line = Polyline::new(vec![p(0., 0., 0.), p(1., 1., 0.)]);
+    let frames = Frame::frenet(&line, 5);
+    for f in &frames {
+        assert_orthonormal(f);
+        assert_eq!(frames[0].normal, f.normal);
+    }
+}
+
+#[test]
+fn parallel_transport_frames() {
+    // around a circle in the xy plane
+    let points: Vec<Position> = (0..=64)
+        .map(|i| {
+            let a = i as f32 / 64. * 2. * PI;
+            p(a.cos(), a.sin(), 0.)
+        })
+        .collect();
+    let frames = Frame::parallel_transport(&points);
+    assert_eq!(65, frames.len());
+    let first = Vector3::from(frames[0].normal);
+    for f in &frames {
+        assert_orthonormal(f);
+        // the frames turn with the circle and never spin about it, so the
+        // normal keeps its angle to the plane of the circle
+        let z = Vector3::from(f.normal).z;
+        assert!((z - first.z).abs() < 1e-3);
+    }
+
+    // turning a corner only turns the normal about the bend
+    let corner = [
+        p(0., 0., 0.),
+        p(0., 0., 1.),
+        p(0., 0., 2.),
+        p(1., 0., 2.),
+        p(2., 0., 2.),
+    ];
+    let frames = Frame::parallel_transport(&corner);
+    assert!((Vector3::from(frames[4].tangent) - Vector3::unit_x()).magnitude() < 1e-5);
+    let y = |f: &Frame| Vector3::from(f.normal).y;
+    assert!((y(&frames[0]) - y(&frames[4])).abs() < 1e-5);
+
+    // repeated points take the tangent of their neighbors
+    let repeated = [p(0., 0., 0.), p(0., 0., 0.), p(0., 1., 0.)];
+    for f in Frame::parallel_transport(&repeated) {
+        assert_orthonormal(&f);
+    }
+    assert!(Frame::parallel_transport(&[]).is_empty());
+}
+
+#[test]
+fn frame_matrix() {
+    let frames = Frame::frenet(&Helix, 7);
+    let f = frames[3];
+    let m = Matrix4::from(f.matrix());
+    let origin = m * Vector4::new(0., 0., 0., 1.);
+    assert!((origin.truncate() - Vector3::from(f.position)).magnitude() < 1e-5);
+    let z = (m * Vector4::new(0., 0., 1., 0.)).truncate();
+    assert!((z - Vector3::from(f.tangent)).magnitude() < 1e-5);
+    let x = (m * Vector4::new(1., 0., 0., 0.)).truncate();
+    assert!((x - Vector3::from(f.normal)).magnitude() < 1e-5);
+}
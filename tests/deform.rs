@@ -0,0 +1,124 @@
+extern crate genmesh;
+
+use std::f32::consts::PI;
+
+use cgmath::{InnerSpace, Vector3};
+use genmesh::generators::{Cylinder, SharedVertex};
+use genmesh::{Axis, Deform, Deformer, Polygon, Triangulate, Vertex};
+
+// the normal of a deformed vertex is square to the deformed surface
+// around it, checked by deforming points a little way along the surface
+fn assert_normals(deformer: Deformer, vertices: &[Vertex]) {
+    let position = |p: Vector3<f32>| {
+        let v = deformer.apply(Vertex {
+            pos: p.into(),
+            normal: [0., 0., 1.].into(),
+        });
+        Vector3::from(v.pos)
+    };
+    for v in vertices {
+        let (p, n) = (Vector3::from(v.pos), Vector3::from(v.normal));
+        let deformed = deformer.apply(*v);
+        let normal = Vector3::from(deformed.normal);
+        assert!((normal.magnitude() - 1.).abs() < 1e-4);
+        assert!((Vector3::from(deformed.pos) - position(p)).magnitude() < 1e-6);
+        let tangent = if n.x.abs() < 0.9 {
+            n.cross(Vector3::unit_x())
+        } else {
+            n.cross(Vector3::unit_y())
+        };
+        for t in [tangent, n.cross(tangent)] {
+            let d = (position(p + t * 1e-2) - position(p - t * 1e-2)).normalize();
+            assert!(d.dot(normal).abs() < 1e-3, "{:?} {:?}", deformer, v);
+        }
+    }
+}
+
+#[test]
+fn twist() {
+    let twist = Deformer::Twist {
+        axis: Axis::Z,
+        angle: PI / 2.,
+    };
+    let v = twist.apply(Vertex {
+        pos: [1., 0., 1.].into(),
+        normal: [1., 0., 0.].into(),
+    });
+    assert!((Vector3::from(v.pos) - Vector3::new(0., 1., 1.)).magnitude() < 1e-6);
+
+    let cylinder = Cylinder::subdivide(16, 4);
+    let vertices: Vec<Vertex> = cylinder.shared_vertex_iter().collect();
+    assert_normals(twist, &vertices);
+    assert_normals(
+        Deformer::Twist {
+            axis: Axis::X,
+            angle: 1.,
+        },
+        &vertices,
+    );
+}
+
+#[test]
+fn bend() {
+    // a quarter turn of a bend of radius 2 along z, curling towards x;
+    // the side facing away from the bend ends up facing up
+    let bend = Deformer::Bend {
+        axis: Axis::Z,
+        curvature: 0.5,
+    };
+    let v = bend.apply(Vertex {
+        pos: [0., 0., PI].into(),
+        normal: [-1., 0., 0.].into(),
+    });
+    assert!((Vector3::from(v.pos) - Vector3::new(2., 0., 2.)).magnitude() < 1e-5);
+    assert!((Vector3::from(v.normal) - Vector3::new(0., 0., 1.)).magnitude() < 1e-5);
+
+    let vertices: Vec<Vertex> = Cylinder::subdivide(16, 4).shared_vertex_iter().collect();
+    assert_normals(bend, &vertices);
+    for axis in [Axis::X, Axis::Y] {
+        assert_normals(
+            Deformer::Bend {
+                axis,
+                curvature: 0.3,
+            },
+            &vertices,
+        );
+    }
+
+    // no curvature leaves the vertices alone
+    let straight = Deformer::Bend {
+        axis: Axis::Z,
+        curvature: 0.,
+    };
+    for v in &vertices {
+        let s = straight.apply(*v);
+        assert_eq!(s.pos, v.pos);
+        assert!((Vector3::from(s.normal) - Vector3::from(v.normal)).magnitude() < 1e-6);
+    }
+}
+
+#[test]
+fn taper() {
+    let taper = Deformer::Taper {
+        axis: Axis::Z,
+        factor: 0.5,
+    };
+    let vertices: Vec<Vertex> = Cylinder::subdivide(16, 4).shared_vertex_iter().collect();
+    for v in &vertices {
+        let t = taper.apply(*v);
+        let scale = 1. + 0.5 * v.pos.z;
+        assert!((t.pos.x - v.pos.x * scale).abs() < 1e-6);
+        assert!((t.pos.y - v.pos.y * scale).abs() < 1e-6);
+        assert_eq!(t.pos.z, v.pos.z);
+    }
+    assert_normals(taper, &vertices);
+
+    // the adapters deform whole polygons
+    let polygons: Vec<Polygon<Vertex>> = Cylinder::subdivide(16, 4)
+        .taper(Axis::Z, 0.5)
+        .twist(Axis::Z, 1.)
+        .bend(Axis::Y, 0.2)
+        .collect();
+    assert_eq!(polygons.len(), Cylinder::subdivide(16, 4).count());
+    assert!(polygons.into_iter().triangulate().count() > 0);
+}
@@ -0,0 +1,61 @@
+extern crate genmesh;
+
+use genmesh::generators::{Cube, IndexedPolygon, Plane, SharedVertex};
+use genmesh::{
+    sort_indices_by_depth, DepthSort, MapToVertices, Position, Triangle, Triangulate, View,
+};
+
+// the planes z = 0, 1, 2 in that order
+fn layers() -> Vec<Triangle<Position>> {
+    (0..3)
+        .flat_map(|z| {
+            Plane::new()
+                .triangulate()
+                .vertex(move |v| Position::from([v.pos.x, v.pos.y, z as f32]))
+        })
+        .collect()
+}
+
+#[test]
+fn sort_by_depth() {
+    let from_above: Vec<_> = layers()
+        .into_iter()
+        .sort_by_depth(View::Point([0., 0., 10.].into()))
+        .collect();
+    assert_eq!(from_above.len(), 6);
+    let z: Vec<f32> = from_above.iter().map(|t| t.x.z).collect();
+    assert_eq!(z, [0., 0., 1., 1., 2., 2.]);
+
+    let looking_down: Vec<f32> = layers()
+        .into_iter()
+        .sort_by_depth(View::Direction([0., 0., 1.].into()))
+        .map(|t| t.x.z)
+        .collect();
+    assert_eq!(looking_down, [2., 2., 1., 1., 0., 0.]);
+}
+
+#[test]
+fn sort_indices() {
+    let cube = Cube::new();
+    let positions: Vec<Position> = cube.shared_vertex_iter().map(|v| v.pos).collect();
+    let mut triangles: Vec<Triangle<usize>> = cube.indexed_polygon_iter().triangulate().collect();
+    let eye = [0., 0., -5.];
+    sort_indices_by_depth(&positions, &mut triangles, View::Point(eye.into()));
+    assert_eq!(triangles.len(), 12);
+    let distance = |t: &Triangle<usize>| {
+        let c: Vec<f32> = (0..3)
+            .map(|k| {
+                let p = [positions[t.x], positions[t.y], positions[t.z]];
+                let c = p.iter().map(|p| [p.x, p.y, p.z][k]).sum::<f32>() / 3.;
+                c - eye[k]
+            })
+            .collect();
+        c.iter().map(|c| c * c).sum::<f32>()
+    };
+    for w in triangles.windows(2) {
+        assert!(distance(&w[0]) >= distance(&w[1]));
+    }
+    // the +z face is the farthest, the -z face the nearest
+    assert!(triangles[..2].iter().all(|t| positions[t.x].z > 0.5));
+    assert!(triangles[10..].iter().all(|t| positions[t.x].z < -0.5));
+}
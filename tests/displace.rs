@@ -0,0 +1,67 @@
+extern crate genmesh;
+
+use genmesh::generators::{IcoSphere, IndexedPolygon, SharedVertex, SphereUv};
+use genmesh::{
+    recompute_normals, Displace, NormalFallback, Triangle, Triangulate, Vertex, Vertices,
+};
+
+fn length(v: [f32; 3]) -> f32 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+#[test]
+fn displace_polygons() {
+    for v in SphereUv::new(8, 6).displace(|_, _| 0.5).vertices() {
+        assert!((length(v.pos.into()) - 1.5).abs() < 1e-5);
+    }
+    // the function sees the original position and normal
+    let lifted: Vec<Vertex> = SphereUv::new(8, 6)
+        .displace(|pos, normal| {
+            assert_eq!(pos, normal);
+            if pos.z > 0. {
+                1.
+            } else {
+                0.
+            }
+        })
+        .vertices()
+        .collect();
+    for v in lifted {
+        let expected = if v.normal.z > 0. { 2. } else { 1. };
+        assert!((length(v.pos.into()) - expected).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn rock() {
+    let ico = IcoSphere::subdivide(2);
+    let triangles: Vec<Triangle<usize>> = ico.indexed_polygon_iter().triangulate().collect();
+    let mut vertices: Vec<Vertex> = ico
+        .shared_vertex_iter()
+        .displace(|pos, _| 0.2 * (pos.x * 7.).sin() * (pos.y * 5.).cos())
+        .collect();
+
+    let before: Vec<Vertex> = vertices.clone();
+    recompute_normals(&mut vertices, &triangles, NormalFallback::default());
+
+    let mut changed = 0;
+    for (old, new) in before.iter().zip(&vertices) {
+        assert_eq!(old.pos, new.pos);
+        assert!((length(new.normal.into()) - 1.).abs() < 1e-5);
+        // still facing outward, but tilted by the bumps
+        let facing = old.pos.x * new.normal.x + old.pos.y * new.normal.y + old.pos.z * new.normal.z;
+        assert!(facing > 0.);
+        if (old.normal.x - new.normal.x).abs() > 1e-3 {
+            changed += 1;
+        }
+    }
+    assert!(changed > vertices.len() / 2);
+
+    // a sphere that was not displaced keeps roughly the same normals
+    let mut smooth: Vec<Vertex> = ico.shared_vertex_iter().collect();
+    recompute_normals(&mut smooth, &triangles, NormalFallback::Skip);
+    for v in &smooth {
+        let dot = v.pos.x * v.normal.x + v.pos.y * v.normal.y + v.pos.z * v.normal.z;
+        assert!(dot > 0.99);
+    }
+}
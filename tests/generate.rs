@@ -8,21 +8,42 @@ use std::fmt::Debug;
 /// 1) by using the `Iterator` implementation of the given generator
 /// 2) by producing shared vertices and sampling them with the
 ///    produced indexed polygons.
-fn test<F, P, G>(generator: G)
+///
+/// The `Iterator` implementation is walked from both ends at once, checking
+/// its length along the way.
+fn test<F, P, G>(mut generator: G)
 where
     F: EmitTriangles,
     F::Vertex: Clone + Copy + Debug + PartialEq,
     P: EmitTriangles<Vertex = usize>,
-    G: generators::SharedVertex<F::Vertex> + generators::IndexedPolygon<P> + Iterator<Item = F>,
+    G: generators::SharedVertex<F::Vertex>
+        + generators::IndexedPolygon<P>
+        + DoubleEndedIterator<Item = F>
+        + ExactSizeIterator,
 {
     let vertices: Vec<_> = generator.shared_vertex_iter().collect();
+    let reversed: Vec<_> = generator.shared_vertex_iter().rev().collect();
+    assert_eq!(generator.shared_vertex_iter().len(), vertices.len());
+    assert!(vertices.iter().eq(reversed.iter().rev()));
 
     let f1: Vec<_> = generator
         .indexed_polygon_iter()
         .triangulate()
         .map(|f| f.map_vertex(|u| vertices[u]))
         .collect();
-    let f0: Vec<_> = generator.triangulate().collect();
+    let n = generator.indexed_polygon_count();
+    assert_eq!(generator.indexed_polygon_iter().len(), n);
+    assert_eq!(generator.len(), n);
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    while let Some(p) = generator.next() {
+        front.push(p);
+        back.extend(generator.next_back());
+        assert_eq!(generator.len(), n - front.len() - back.len());
+    }
+    assert!(generator.next_back().is_none());
+    front.extend(back.into_iter().rev());
+    let f0: Vec<_> = front.into_iter().triangulate().collect();
 
     assert_eq!(f0.len(), f1.len());
     for (i, (p0, p1)) in f0.iter().zip(f1.iter()).enumerate() {
@@ -34,11 +55,15 @@ where
 fn gen_plane() {
     test(generators::Plane::new());
     test(generators::Plane::subdivide(3, 4));
+    test(generators::Plane::with_size(3., 1., 6, 2));
 }
 
 #[test]
 fn gen_cube() {
     test(generators::Cube::new());
+    test(generators::Cube::textured());
+    test(generators::Cube::subdivided(1, 2, 3));
+    test(generators::Cube::subdivided(4, 1, 2).with_uvs());
 }
 
 #[test]
@@ -56,6 +81,7 @@ fn gen_sphere_uv() {
 fn gen_ico_sphere() {
     test(generators::IcoSphere::new());
     test(generators::IcoSphere::subdivide(3));
+    test(generators::IcoSphere::subdivide(2).with_uvs());
 }
 
 #[test]
@@ -68,7 +94,614 @@ fn gen_torus() {
     test(generators::Torus::new(1., 0.5, 8, 8));
 }
 
+#[test]
+fn gen_annulus() {
+    test(generators::Annulus::new(0.5, 1., 8, 3));
+    test(generators::Annulus::new(0., 1., 6, 2));
+}
+
 #[test]
 fn gen_circle() {
-    test(generators::Circle::new(4))
+    test(generators::Circle::new(4));
+    test(generators::Circle::subdivide(5, 3));
+}
+
+#[test]
+fn gen_platonic() {
+    test(generators::Tetrahedron::new());
+    test(generators::Octahedron::new());
+    test(generators::Dodecahedron::new());
+}
+
+/// Check that every face of a group has all its vertex normals in the
+/// direction given for the group, if any.
+fn test_groups<G>(generator: G, normals: &[Option<[f32; 3]>])
+where
+    G: generators::SharedVertex<genmesh::Vertex>
+        + generators::IndexedPolygon<genmesh::Polygon<usize>>
+        + generators::PolygonGroup,
+{
+    assert_eq!(generator.polygon_group_count() as usize, normals.len());
+    let grouped: Vec<_> = generator.grouped_polygon_iter().collect();
+    assert_eq!(grouped.len(), generator.indexed_polygon_count());
+    let mut seen = vec![false; normals.len()];
+    for (i, (group, poly)) in grouped.into_iter().enumerate() {
+        assert_eq!(group, generator.polygon_group(i));
+        assert_eq!(poly, generator.indexed_polygon(i));
+        seen[group as usize] = true;
+        if let Some(n) = normals[group as usize] {
+            poly.emit_triangles(|t| {
+                for v in [t.x, t.y, t.z] {
+                    let normal: [f32; 3] = generator.shared_vertex(v).normal.into();
+                    assert_eq!(normal, n);
+                }
+            });
+        }
+    }
+    assert!(seen.into_iter().all(|s| s));
+}
+
+#[test]
+fn polygon_groups() {
+    test_groups(
+        generators::Cylinder::subdivide(6, 2),
+        &[None, Some([0., 0., -1.]), Some([0., 0., 1.])],
+    );
+    test_groups(generators::Cone::new(6), &[None, Some([0., 0., -1.])]);
+    test_groups(
+        generators::Cube::new(),
+        &[
+            Some([1., 0., 0.]),
+            Some([-1., 0., 0.]),
+            Some([0., 1., 0.]),
+            Some([0., -1., 0.]),
+            Some([0., 0., 1.]),
+            Some([0., 0., -1.]),
+        ],
+    );
+}
+
+#[test]
+fn cube_faces() {
+    use generators::{CubeFace, SharedVertex};
+
+    let cube = generators::Cube::textured();
+    let mut uvs = Vec::new();
+    for (i, &face) in CubeFace::ALL.iter().enumerate() {
+        assert_eq!(CubeFace::from_index(i), Some(face));
+        assert_eq!(face.index(), i);
+        let indices = cube.face_indices(face);
+        let quad = cube.face(face);
+        assert_eq!(quad, indices.map_vertex(|i| cube.shared_vertex(i)));
+
+        let normal: [f32; 3] = face.normal().into();
+        let corners = [quad.x, quad.y, quad.z, quad.w];
+        for v in corners.iter() {
+            assert_eq!(v.normal, face.normal());
+            let pos: [f32; 3] = v.pos.into();
+            let d: f32 = pos.iter().zip(normal.iter()).map(|(p, n)| p * n).sum();
+            assert!(d > 0.);
+        }
+        // the whole texture goes around the face counter-clockwise
+        let turn = |a: [f32; 2], b: [f32; 2], c: [f32; 2]| {
+            (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+        };
+        assert!(turn(quad.x.uv, quad.y.uv, quad.z.uv) > 0.);
+        let mut face_uvs: Vec<_> = corners.iter().map(|v| v.uv).collect();
+        face_uvs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(face_uvs, vec![[0., 0.], [0., 1.], [1., 0.], [1., 1.]]);
+        uvs.extend(face_uvs);
+    }
+    assert_eq!(CubeFace::from_index(6), None);
+
+    // untextured cubes have the same geometry
+    let plain: Vec<genmesh::Vertex> = generators::Cube::new().shared_vertex_iter().collect();
+    let textured: Vec<genmesh::Vertex> = cube.shared_vertex_iter().map(|v| v.into()).collect();
+    assert_eq!(plain, textured);
+}
+
+#[test]
+fn subdivided_cube() {
+    use generators::{Cube, IndexedPolygon, SharedVertex};
+
+    let cube = Cube::subdivided(2, 3, 4);
+    assert_eq!(cube.indexed_polygon_count(), 2 * (6 + 12 + 8));
+    assert_eq!(cube.shared_vertex_count(), 2 * (12 + 20 + 15));
+    assert_eq!(cube.len(), cube.indexed_polygon_count());
+
+    // every vertex is on the face it belongs to, which is as large as the
+    // faces of the plain cube
+    let half = 1. / 3f32.sqrt();
+    for v in cube.shared_vertex_iter() {
+        let pos: [f32; 3] = v.pos.into();
+        let normal: [f32; 3] = v.normal.into();
+        for k in 0..3 {
+            if normal[k] != 0. {
+                assert!((pos[k] - normal[k] * half).abs() < 1e-6);
+            } else {
+                assert!(pos[k].abs() <= half + 1e-6);
+            }
+        }
+    }
+    test_groups(
+        cube,
+        &[
+            Some([1., 0., 0.]),
+            Some([-1., 0., 0.]),
+            Some([0., 1., 0.]),
+            Some([0., -1., 0.]),
+            Some([0., 0., 1.]),
+            Some([0., 0., -1.]),
+        ],
+    );
+
+    // the faces are cut according to their own axes
+    let cube = Cube::subdivided(2, 3, 4).with_uvs();
+    let mut us: Vec<f32> = cube
+        .shared_vertex_iter()
+        .filter(|v| v.normal.z == 1.)
+        .map(|v| v.uv[0])
+        .collect();
+    us.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    us.dedup();
+    assert_eq!(us, vec![0., 0.5, 1.]);
+
+    // cutting each face once leaves the plain cube as it was, with the
+    // corners of each face numbered around it
+    let plain = Cube::new();
+    for (i, p) in plain.indexed_polygon_iter().enumerate() {
+        let i = 4 * i;
+        assert_eq!(
+            p,
+            genmesh::Polygon::PolyQuad(genmesh::Quad::new(i, i + 1, i + 2, i + 3))
+        );
+    }
+    let first: [f32; 3] = plain.shared_vertex(0).pos.into();
+    assert_eq!(first, [1., 1., -1.].map(|c: f32| c / 3f32.sqrt()));
+}
+
+#[test]
+fn gen_quad_sphere() {
+    use generators::{IndexedPolygon, QuadSphere, SharedVertex};
+
+    test(QuadSphere::new(1));
+    test(QuadSphere::new(4));
+    test(QuadSphere::new(3).with_uvs());
+
+    let sphere = QuadSphere::new(8);
+    assert_eq!(sphere.indexed_polygon_count(), 6 * 64);
+    for v in sphere.shared_vertex_iter() {
+        let pos: [f32; 3] = v.pos.into();
+        let len = (pos[0] * pos[0] + pos[1] * pos[1] + pos[2] * pos[2]).sqrt();
+        assert!((len - 1.).abs() < 1e-5);
+        assert_eq!(v.pos, v.normal);
+    }
+
+    // the quads are about the same size all over
+    let area = |p: genmesh::Polygon<usize>| {
+        let mut area = 0.;
+        p.emit_triangles(|t| {
+            let [a, b, c] = [t.x, t.y, t.z].map(|i| {
+                let p: [f32; 3] = sphere.shared_vertex(i).pos.into();
+                cgmath::Vector3::from(p)
+            });
+            area += cgmath::InnerSpace::magnitude((b - a).cross(c - a)) / 2.;
+        });
+        area
+    };
+    let areas: Vec<f32> = sphere.indexed_polygon_iter().map(area).collect();
+    let min = areas.iter().cloned().fold(f32::MAX, f32::min);
+    let max = areas.iter().cloned().fold(0., f32::max);
+    assert!(max / min < 1.6, "{}", max / min);
+}
+
+#[test]
+fn ico_sphere_uvs() {
+    use generators::{IcoSphere, IndexedPolygon, SharedVertex};
+
+    for n in 0..4 {
+        let plain = IcoSphere::subdivide(n);
+        let sphere = IcoSphere::subdivide(n).with_uvs();
+        assert_eq!(
+            plain.indexed_polygon_count(),
+            sphere.indexed_polygon_count()
+        );
+        assert!(sphere.shared_vertex_count() > plain.shared_vertex_count());
+        let vertices: Vec<_> = sphere.shared_vertex_iter().collect();
+
+        let mut used = vec![false; vertices.len()];
+        for poly in sphere.indexed_polygon_iter() {
+            poly.emit_triangles(|t| {
+                for i in [t.x, t.y, t.z] {
+                    used[i] = true;
+                    // the geometry is left as it is
+                    let p: [f32; 3] = vertices[i].pos.into();
+                    assert!(plain
+                        .shared_vertex_iter()
+                        .any(|v| <[f32; 3]>::from(v.pos) == p));
+                }
+                // no face is stretched across the texture or flipped
+                let [a, b, c] = [t.x, t.y, t.z].map(|i| vertices[i].uv);
+                let area = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+                assert!(area > 0.);
+                for uv in [a, b, c] {
+                    assert!((uv[0] - a[0]).abs() <= 0.5);
+                }
+            });
+        }
+        assert!(used.into_iter().all(|u| u));
+    }
+}
+
+#[test]
+fn sphere_uv_poles() {
+    use generators::{IndexedPolygon, SharedVertex, SphereUv};
+    use genmesh::Polygon::{PolyQuad, PolyTri};
+
+    let sphere = SphereUv::new(7, 5);
+    let last = sphere.shared_vertex_count() - 1;
+    for (i, poly) in sphere.indexed_polygon_iter().enumerate() {
+        match poly {
+            PolyTri(t) => {
+                assert!(!(7..4 * 7).contains(&i));
+                let pole = if i < 7 { 0 } else { last };
+                assert_eq!([t.x, t.y, t.z].iter().filter(|&&v| v == pole).count(), 1);
+            }
+            PolyQuad(q) => {
+                for v in [q.x, q.y, q.z, q.w] {
+                    assert!(v != 0 && v != last);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    // neither the indexed nor the streamed triangles are degenerate
+    let area = |a: [f32; 3], b: [f32; 3], c: [f32; 3]| {
+        let (a, b, c) = (
+            cgmath::Vector3::from(a),
+            cgmath::Vector3::from(b),
+            cgmath::Vector3::from(c),
+        );
+        cgmath::InnerSpace::magnitude((b - a).cross(c - a))
+    };
+    for t in sphere.indexed_polygon_iter().triangulate() {
+        let [a, b, c] = [t.x, t.y, t.z].map(|i| sphere.shared_vertex(i).pos.into());
+        assert!(area(a, b, c) > 1e-3);
+    }
+    for t in sphere.triangulate() {
+        assert!(area(t.x.pos.into(), t.y.pos.into(), t.z.pos.into()) > 1e-3);
+    }
+}
+
+#[test]
+fn partial_sphere_uv() {
+    use generators::{IndexedPolygon, SharedVertex, SphereUv};
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    let dome = SphereUv::new(8, 4).with_theta(0., FRAC_PI_2);
+    let slice = SphereUv::new(4, 6).with_phi(0., FRAC_PI_2);
+    let patch = SphereUv::new(3, 2).with_phi(-0.5, 0.5).with_theta(1., 2.);
+    test(dome);
+    test(slice);
+    test(patch);
+
+    // the dome has a pole on top, and a full ring of vertices at the bottom
+    assert_eq!(dome.shared_vertex_count(), 1 + 4 * 8);
+    let rim: Vec<_> = dome
+        .shared_vertex_iter()
+        .filter(|v| v.pos.z.abs() < 1e-6)
+        .collect();
+    assert_eq!(rim.len(), 8);
+    assert!(dome.shared_vertex_iter().all(|v| v.pos.z > -1e-6));
+    let fans = dome
+        .indexed_polygon_iter()
+        .filter(|p| matches!(p, genmesh::Polygon::PolyTri(_)))
+        .count();
+    assert_eq!(fans, 8);
+
+    // the slice has both poles, and vertices on both of its edges
+    assert_eq!(slice.shared_vertex_count(), 2 + 5 * 5);
+    for angle in [0., FRAC_PI_2] {
+        let edge = slice
+            .shared_vertex_iter()
+            .filter(|v| v.pos.x.hypot(v.pos.y) > 1e-3)
+            .filter(|v| (v.pos.y.atan2(v.pos.x) - angle).abs() < 1e-5)
+            .count();
+        assert_eq!(edge, 5);
+    }
+
+    // the patch has no pole at all, only quads
+    assert_eq!(patch.shared_vertex_count(), 3 * 4);
+    for v in patch.shared_vertex_iter() {
+        let theta = v.pos.z.acos();
+        let phi = v.pos.y.atan2(v.pos.x);
+        assert!((1. - 1e-5..=2. + 1e-5).contains(&theta));
+        assert!((-0.5 - 1e-5..=0.5 + 1e-5).contains(&phi));
+    }
+    assert!(patch
+        .indexed_polygon_iter()
+        .all(|p| matches!(p, genmesh::Polygon::PolyQuad(_))));
+
+    // the full ranges are the whole sphere
+    let full = SphereUv::new(6, 5).with_phi(-PI, PI).with_theta(0., PI);
+    assert_eq!(
+        full.shared_vertex_count(),
+        SphereUv::new(6, 5).shared_vertex_count()
+    );
+}
+
+#[test]
+fn partial_torus() {
+    use generators::{IndexedPolygon, SharedVertex, Torus};
+    use genmesh::{indexed_volume, Position, Triangle};
+    use std::f32::consts::PI;
+
+    let volume = |torus: &Torus| {
+        let positions: Vec<Position> = torus.shared_vertex_iter().map(|v| v.pos).collect();
+        let triangles: Vec<Triangle<usize>> = torus.indexed_polygon_iter().triangulate().collect();
+        indexed_volume(&positions, &triangles)
+    };
+    let full = Torus::new(1., 0.25, 64, 32);
+    let tube = PI * 0.25 * 0.25;
+    assert!((volume(&full) - tube * 2. * PI).abs() < 0.01);
+
+    // an elbow, closed at both ends, is a quarter of the torus
+    let elbow = Torus::new(1., 0.25, 16, 32)
+        .with_arc(PI / 2.)
+        .with_caps(true, true);
+    test(elbow);
+    assert_eq!(elbow.indexed_polygon_count(), 16 * 32 + 2);
+    assert_eq!(elbow.shared_vertex_count(), 17 * 32 + 2 * 32);
+    assert!((volume(&elbow) - tube * PI / 2.).abs() < 0.01);
+    let ends: Vec<_> = elbow.indexed_polygon_iter().skip(16 * 32).collect();
+    for (end, normal) in ends.into_iter().zip([[0., 0., 1.], [-1., 0., 0.]]) {
+        end.emit_triangles(|t| {
+            for v in [t.x, t.y, t.z] {
+                let n: [f32; 3] = elbow.shared_vertex(v).normal.into();
+                for k in 0..3 {
+                    assert!((n[k] - normal[k]).abs() < 1e-5);
+                }
+            }
+        });
+    }
+
+    // without caps the ends are left open
+    let arch = Torus::new(1., 0.25, 8, 6)
+        .with_arc(PI)
+        .with_caps(false, true);
+    test(arch);
+    assert_eq!(arch.indexed_polygon_count(), 8 * 6 + 1);
+    assert_eq!(
+        Torus::new(1., 0.25, 8, 6).with_caps(true, true).len(),
+        8 * 6
+    );
+
+    // twisting moves the vertices around the tube, but keeps its shape
+    for twisted in [
+        Torus::new(1., 0.25, 64, 32).with_twist(PI / 8.),
+        Torus::new(1., 0.25, 64, 32).with_twist(-3. * PI),
+        Torus::new(1., 0.25, 16, 32)
+            .with_arc(PI / 2.)
+            .with_twist(1.),
+    ] {
+        test(twisted);
+        for v in twisted.shared_vertex_iter() {
+            let p: [f32; 3] = v.pos.into();
+            let d = (p[0].hypot(p[2]) - 1.).hypot(p[1]);
+            assert!((d - 0.25).abs() < 1e-5);
+        }
+    }
+    let twisted = Torus::new(1., 0.25, 64, 32).with_twist(PI / 8.);
+    assert!((volume(&twisted) - volume(&full)).abs() < 1e-3);
+}
+
+/// Like `test`, for generators of lines.
+fn test_lines<G>(mut generator: G)
+where
+    G: generators::SharedVertex<genmesh::Vertex>
+        + generators::IndexedPolygon<genmesh::Line<usize>>
+        + DoubleEndedIterator<Item = genmesh::Line<genmesh::Vertex>>
+        + ExactSizeIterator,
+{
+    let vertices: Vec<_> = generator.shared_vertex_iter().collect();
+    let indexed: Vec<_> = generator
+        .indexed_polygon_iter()
+        .map(|l| l.map_vertex(|i| vertices[i]))
+        .collect();
+    assert_eq!(generator.len(), indexed.len());
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    while let Some(l) = generator.next() {
+        front.push(l);
+        back.extend(generator.next_back());
+    }
+    front.extend(back.into_iter().rev());
+    assert_eq!(front, indexed);
+}
+
+#[test]
+fn gen_lines() {
+    use generators::{CircleLines, GridLines, IndexedPolygon, SharedVertex};
+
+    test_lines(GridLines::new(2., 0.5));
+    test_lines(CircleLines::new(12));
+
+    // 9 lines in each direction, the outer ones on the edges
+    let grid = GridLines::new(2., 0.5);
+    assert_eq!(grid.indexed_polygon_count(), 18);
+    for l in grid {
+        assert_eq!(l.x.pos.y, 0.);
+        let d = [l.y.pos.x - l.x.pos.x, l.y.pos.z - l.x.pos.z];
+        assert!(d == [4., 0.] || d == [0., 4.]);
+        let across = if d[0] == 0. { l.x.pos.x } else { l.x.pos.z };
+        assert_eq!(across % 0.5, 0.);
+        assert!(across.abs() <= 2.);
+    }
+    // lines stop at the extent even if it is not a multiple of the spacing
+    let grid = GridLines::new(1.2, 0.5);
+    assert_eq!(grid.len(), 10);
+    assert!(grid.shared_vertex_iter().all(|v| v.pos.x.abs() <= 1.2));
+
+    // the outline goes all the way around
+    let circle = CircleLines::new(12);
+    assert_eq!(circle.shared_vertex_count(), 12);
+    let lines: Vec<_> = circle.indexed_polygon_iter().collect();
+    for (a, b) in lines.iter().zip(lines.iter().cycle().skip(1)) {
+        assert_eq!(a.y, b.x);
+    }
+    for v in circle.shared_vertex_iter() {
+        assert!((v.pos.x.hypot(v.pos.y) - 1.).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn gen_frustum() {
+    use cgmath::{InnerSpace, SquareMatrix, Vector3};
+    use generators::{Frustum, IndexedPolygon, SharedVertex};
+    use genmesh::{indexed_volume, Position};
+
+    let frustum = Frustum::new(1., 1.5, 0.5, 4.);
+    test(frustum);
+
+    // the faces point out of the frustum, and enclose its volume
+    let positions: Vec<Position> = frustum.shared_vertex_iter().map(|v| v.pos).collect();
+    let triangles: Vec<_> = frustum.indexed_polygon_iter().triangulate().collect();
+    let area = |d: f32| (2. * d * 0.5f32.tan()).powi(2) * 1.5;
+    let (a0, a1) = (area(0.5), area(4.));
+    let expected = 3.5 / 3. * (a0 + a1 + (a0 * a1).sqrt());
+    assert!((indexed_volume(&positions, &triangles) - expected).abs() < 1e-3);
+    let center = Vector3::new(0., 0., -2.25);
+    for poly in frustum.indexed_polygon_iter() {
+        poly.emit_triangles(|t| {
+            for v in [t.x, t.y, t.z] {
+                let v = frustum.shared_vertex(v);
+                let out = Vector3::from(v.pos) - center;
+                assert!(out.dot(v.normal.into()) > 0.);
+            }
+        });
+    }
+
+    // a projection matrix gives the same frustum
+    let projection = cgmath::perspective(cgmath::Rad(1.), 1.5, 0.5, 4.);
+    let from_matrix = Frustum::from_inverse_projection(projection.invert().unwrap());
+    for (a, b) in frustum.corners().iter().zip(from_matrix.corners().iter()) {
+        assert!((Vector3::from(*a) - Vector3::from(*b)).magnitude() < 1e-4);
+    }
+    test(from_matrix);
+    for (a, b) in frustum
+        .shared_vertex_iter()
+        .zip(from_matrix.shared_vertex_iter())
+    {
+        assert!((Vector3::from(a.normal) - Vector3::from(b.normal)).magnitude() < 1e-4);
+    }
+
+    // each edge joins two corners of a face
+    let edges = frustum.edges();
+    let corners = frustum.corners();
+    for e in edges.iter() {
+        let a = corners.iter().position(|c| *c == e.x).unwrap();
+        let b = corners.iter().position(|c| *c == e.y).unwrap();
+        assert_eq!((a ^ b).count_ones(), 1);
+    }
+    for (i, a) in edges.iter().enumerate() {
+        for b in &edges[i + 1..] {
+            assert!(!(a.x == b.x && a.y == b.y));
+        }
+    }
+}
+
+// Weld the flat shaded faces of a generator, and check that they close a
+// solid of the given volume, with every normal facing out of it.
+fn assert_closed_solid<G>(generator: G, volume: f32)
+where
+    G: generators::SharedVertex<genmesh::Vertex>
+        + generators::IndexedPolygon<genmesh::Polygon<usize>>,
+{
+    use genmesh::{indexed_volume, weld, Neighbors, Position};
+
+    let mut vertices: Vec<_> = generator.shared_vertex_iter().collect();
+    let mut triangles: Vec<_> = generator.indexed_polygon_iter().triangulate().collect();
+    for t in &triangles {
+        let p = |i: usize| cgmath::Vector3::from(vertices[i].pos);
+        let n = (p(t.y) - p(t.x)).cross(p(t.z) - p(t.x));
+        for i in [t.x, t.y, t.z] {
+            assert!(cgmath::InnerSpace::dot(n, vertices[i].normal.into()) > 0.);
+        }
+    }
+    weld(&mut vertices, &mut triangles, 1e-5);
+    let positions: Vec<Position> = vertices.iter().map(|v| v.pos).collect();
+    assert!(genmesh::validate::validate(&positions, &triangles).is_valid());
+    let neighbors = Neighbors::new(Vec::<()>::new(), triangles.clone());
+    assert!(neighbors.boundary_loops().is_empty());
+    assert!((indexed_volume(&positions, &triangles) - volume).abs() < 1e-4);
+}
+
+#[test]
+fn gen_stairs() {
+    use generators::Stairs;
+
+    let stairs = Stairs::new(4, 0.2, 0.3, 1.);
+    test(stairs.clone());
+    assert_eq!(8, stairs.len());
+    test(stairs.clone().with_sides());
+    assert_eq!(16, stairs.clone().with_sides().len());
+    let closed = stairs.with_sides().with_back();
+    test(closed.clone());
+    assert_eq!(18, closed.len());
+    // a step's worth of the box for each step, and one more for each step
+    // above it
+    let volume = 0.2 * 0.3 * (1 + 2 + 3 + 4) as f32;
+    assert_closed_solid(closed.clone(), volume);
+
+    // the first faces are the first step
+    let mut faces = closed.map(|p| p.map_vertex(|v| v.normal));
+    assert!(faces
+        .next()
+        .unwrap()
+        .as_vertices()
+        .all(|n| n == [0., -1., 0.].into()));
+    assert!(faces
+        .next()
+        .unwrap()
+        .as_vertices()
+        .all(|n| n == [0., 0., 1.].into()));
+}
+
+#[test]
+fn gen_ramp() {
+    let ramp = generators::Ramp::new(1., 2., 3.);
+    test(ramp.clone());
+    assert_eq!(5, ramp.len());
+    assert_closed_solid(ramp, 3.);
+}
+
+#[test]
+fn gen_box_frame() {
+    let frame = generators::BoxFrame::new(0.25);
+    test(frame.clone());
+    assert_eq!(48, frame.len());
+    // the cube, less the three square holes through it
+    let s = 0.75f32;
+    assert_closed_solid(frame, 8. - 24. * s * s + 16. * s * s * s);
+}
+
+#[test]
+fn gen_terrain() {
+    use genmesh::noise::Fbm;
+
+    let terrain = generators::Terrain::new(4., 2., 8, 5, &Fbm::default(), 0.5);
+    test(terrain.clone());
+    assert_eq!(40, terrain.len());
+    test(terrain.with_thermal_erosion(3, 0.3));
+}
+
+#[test]
+fn gen_terrain_chunk() {
+    use genmesh::noise::Fbm;
+
+    let chunks = generators::TerrainChunks::new(2., 4, Fbm::default(), 0.5);
+    test(chunks.chunk(0, 0));
+    test(chunks.chunk(-3, 2));
+    assert_eq!(16, chunks.chunk(1, 1).len());
 }
@@ -0,0 +1,94 @@
+extern crate genmesh;
+
+mod common;
+
+use std::f32::consts::PI;
+
+use genmesh::generators::{IcoSphere, Plane};
+use genmesh::{geodesic_distances, GeodesicMethod, Position, Triangle};
+
+use common::indexed_positions;
+
+// the largest relative error against the straight line distance
+fn plane_error(method: GeodesicMethod) -> f32 {
+    let (positions, triangles) = indexed_positions(&Plane::subdivide(16, 16), Some(1e-5));
+    let seed = positions
+        .iter()
+        .position(|p| p.x == -1. && p.y == -1.)
+        .unwrap();
+    let distances = geodesic_distances(&positions, &triangles, &[seed], method);
+    assert_eq!(distances[seed], 0.);
+    positions
+        .iter()
+        .zip(&distances)
+        .filter(|(_, &d)| d > 0.)
+        .map(|(p, &d)| {
+            let exact = ((p.x + 1.).powi(2) + (p.y + 1.).powi(2)).sqrt();
+            assert!(d >= exact - 1e-4);
+            (d - exact) / exact
+        })
+        .fold(0., f32::max)
+}
+
+#[test]
+fn plane() {
+    let dijkstra = plane_error(GeodesicMethod::Dijkstra);
+    let marching = plane_error(GeodesicMethod::FastMarching);
+    assert!(dijkstra > 0.05);
+    assert!(marching < 0.02, "{}", marching);
+}
+
+#[test]
+fn sphere() {
+    let (positions, triangles) = indexed_positions(&IcoSphere::subdivide(3), Some(1e-5));
+    let seed = positions.iter().position(|p| p.z > 0.99).unwrap();
+    let distances = geodesic_distances(
+        &positions,
+        &triangles,
+        &[seed],
+        GeodesicMethod::FastMarching,
+    );
+    for (p, d) in positions.iter().zip(&distances) {
+        // the great circle distance, on a sphere of radius one
+        let exact = p.z.clamp(-1., 1.).acos();
+        assert!((d - exact).abs() < 0.05 * PI, "{} {}", d, exact);
+    }
+}
+
+#[test]
+fn seeds_and_islands() {
+    let positions: Vec<Position> = vec![
+        [0., 0., 0.].into(),
+        [1., 0., 0.].into(),
+        [0., 1., 0.].into(),
+        [1., 1., 0.].into(),
+        [5., 5., 5.].into(),
+        [6., 5., 5.].into(),
+        [5., 6., 5.].into(),
+    ];
+    let triangles = vec![
+        Triangle::new(0, 1, 2),
+        Triangle::new(1, 3, 2),
+        Triangle::new(4, 5, 6),
+    ];
+    let distances = geodesic_distances(&positions, &triangles, &[0, 3], GeodesicMethod::Dijkstra);
+    assert_eq!(&distances[..4], &[0., 1., 1., 0.]);
+    assert!(distances[4..].iter().all(|d| d.is_infinite()));
+    assert!(
+        geodesic_distances(&positions, &triangles, &[], GeodesicMethod::FastMarching)
+            .iter()
+            .all(|d| d.is_infinite())
+    );
+}
+
+#[test]
+#[should_panic]
+fn seed_out_of_range() {
+    let (positions, triangles) = indexed_positions(&Plane::new(), Some(1e-5));
+    geodesic_distances(
+        &positions,
+        &triangles,
+        &[positions.len()],
+        GeodesicMethod::Dijkstra,
+    );
+}
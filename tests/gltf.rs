@@ -0,0 +1,120 @@
+#![cfg(feature = "gltf")]
+
+extern crate genmesh;
+
+use genmesh::generators::{Cube, IndexedPolygon, SharedVertex, Terrain};
+use genmesh::gltf::GltfMesh;
+use genmesh::noise::Fbm;
+use std::io;
+
+use genmesh::{TerrainVertex, Triangle, Triangulate, Vertex};
+
+fn cube() -> (Vec<Vertex>, Vec<Triangle<usize>>) {
+    let cube = Cube::new();
+    (
+        cube.shared_vertex_iter().collect(),
+        cube.indexed_polygon_iter().triangulate().collect(),
+    )
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+#[test]
+fn gltf_glb_layout() {
+    let (vertices, triangles) = cube();
+    let uvs = vec![[0.5, 0.5]; vertices.len()];
+    let glb = GltfMesh::new(&vertices, &triangles)
+        .with_uvs(&uvs)
+        .to_glb()
+        .unwrap();
+
+    assert_eq!(b"glTF", &glb[0..4]);
+    assert_eq!(2, u32_at(&glb, 4));
+    assert_eq!(glb.len() as u32, u32_at(&glb, 8));
+
+    let json_len = u32_at(&glb, 12) as usize;
+    assert_eq!(b"JSON", &glb[16..20]);
+    assert_eq!(0, json_len % 4);
+    let json = std::str::from_utf8(&glb[20..20 + json_len]).unwrap();
+    assert!(json.contains(r#""POSITION":0,"NORMAL":1,"TEXCOORD_0":2"#));
+    assert!(json.contains(r#""count":36,"type":"SCALAR""#));
+    assert!(!json.contains("uri"));
+
+    let bin_at = 20 + json_len;
+    let bin_len = u32_at(&glb, bin_at) as usize;
+    assert_eq!(b"BIN\0", &glb[bin_at + 4..bin_at + 8]);
+    // 24 vertices of 8 floats, then 36 indices
+    assert_eq!(24 * 8 * 4 + 36 * 4, bin_len);
+    assert_eq!(glb.len(), bin_at + 8 + bin_len);
+
+    let bin = &glb[bin_at + 8..];
+    let first = f32::from_bits(u32_at(bin, 0));
+    assert_eq!(vertices[0].pos.x, first);
+    let last_index = u32_at(bin, bin_len - 4) as usize;
+    assert_eq!(triangles[11].z, last_index);
+}
+
+#[test]
+fn gltf_embedded_buffer() {
+    let (vertices, triangles) = cube();
+    let mesh = GltfMesh::new(&vertices, &triangles);
+    let text = mesh.to_gltf().unwrap();
+    assert!(text.starts_with('{') && text.ends_with('}'));
+    assert!(text.contains(r#""uri":"data:application/octet-stream;base64,"#));
+    assert!(!text.contains("TEXCOORD_0"));
+    // 24 vertices of 6 floats and 36 indices, in base64
+    let bytes: usize = 24 * 6 * 4 + 36 * 4;
+    assert!(text.contains(&format!(r#""byteLength":{},"uri""#, bytes)));
+    let start = text.find("base64,").unwrap() + 7;
+    let end = start + text[start..].find('"').unwrap();
+    assert_eq!(bytes.div_ceil(3) * 4, end - start);
+
+    let mut written = Vec::new();
+    mesh.write_gltf(&mut written).unwrap();
+    assert_eq!(text.as_bytes(), &written[..]);
+}
+
+#[test]
+fn gltf_custom_attributes() {
+    let terrain = Terrain::new(1., 1., 2, 2, &Fbm::default(), 1.);
+    let vertices: Vec<TerrainVertex> = terrain.shared_vertex_iter().collect();
+    let triangles: Vec<Triangle<usize>> = terrain.indexed_polygon_iter().triangulate().collect();
+    let text = GltfMesh::new(&vertices, &triangles).to_gltf().unwrap();
+    assert!(text.contains(r#""POSITION":0,"NORMAL":1,"_CUSTOM_0":2"#));
+    assert!(text.contains(r#""count":9,"type":"SCALAR""#));
+}
+
+#[test]
+fn gltf_invalid_meshes() {
+    let (vertices, mut triangles) = cube();
+    triangles.push(Triangle::new(0, 1, vertices.len()));
+    let mesh = GltfMesh::new(&vertices, &triangles);
+    let error = mesh.to_glb().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert!(mesh.write_gltf(Vec::new()).is_err());
+
+    // empty buffer views are not allowed
+    let empty = GltfMesh::new(&vertices, &[]);
+    assert_eq!(
+        empty.to_gltf().unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+
+    let (mut vertices, triangles) = cube();
+    let uvs = vec![[0., 0.]; vertices.len() - 1];
+    let short = GltfMesh::new(&vertices, &triangles).with_uvs(&uvs);
+    assert_eq!(
+        short.to_glb().unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+
+    // the bounds of the positions cannot be written as JSON
+    vertices[0].pos.x = f32::NAN;
+    let nan = GltfMesh::new(&vertices, &triangles);
+    assert_eq!(
+        nan.to_gltf().unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
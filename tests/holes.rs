@@ -0,0 +1,118 @@
+extern crate genmesh;
+
+use std::collections::HashSet;
+
+use genmesh::generators::{Cube, IndexedPolygon, SharedVertex};
+use genmesh::{fill_holes, HoleFill, Neighbors, Triangle, Triangulate, Vertex};
+
+/// A cube with welded corners, missing its last `missing` faces.
+fn open_cube(missing: usize) -> (Vec<Vertex>, Vec<Triangle<usize>>) {
+    let cube = Cube::new();
+    let all: Vec<Vertex> = cube.shared_vertex_iter().collect();
+    let mut vertices: Vec<Vertex> = Vec::new();
+    let mut weld = |i: usize| match vertices.iter().position(|v| v.pos == all[i].pos) {
+        Some(j) => j,
+        None => {
+            vertices.push(all[i]);
+            vertices.len() - 1
+        }
+    };
+    let triangles = cube
+        .indexed_polygon_iter()
+        .take(6 - missing)
+        .triangulate()
+        .map(|t| Triangle::new(weld(t.x), weld(t.y), weld(t.z)))
+        .collect();
+    (vertices, triangles)
+}
+
+/// Every directed edge of a closed, consistently wound mesh is used once.
+fn assert_watertight(triangles: &[Triangle<usize>]) {
+    let mut edges = HashSet::new();
+    for t in triangles {
+        for &(a, b) in &[(t.x, t.y), (t.y, t.z), (t.z, t.x)] {
+            assert!(edges.insert((a, b)), "edge {:?} used twice", (a, b));
+        }
+    }
+    for &(a, b) in &edges {
+        assert!(edges.contains(&(b, a)), "edge {:?} is open", (a, b));
+    }
+}
+
+#[test]
+fn fill_cube() {
+    for &mode in &[HoleFill::Fan, HoleFill::EarClip, HoleFill::Centroid] {
+        let (mut vertices, mut triangles) = open_cube(1);
+        assert_eq!(8, vertices.len());
+        assert_eq!(1, fill_holes(&mut vertices, &mut triangles, mode));
+        assert_watertight(&triangles);
+
+        let (count, added) = match mode {
+            HoleFill::Centroid => (9, 14),
+            _ => (8, 12),
+        };
+        assert_eq!(count, vertices.len());
+        assert_eq!(added, triangles.len());
+
+        let mesh = Neighbors::new(vertices, triangles);
+        assert!(mesh.boundary_loops().is_empty());
+    }
+
+    // removing two opposite faces leaves two holes
+    let cube = Cube::new();
+    let (mut vertices, _) = open_cube(0);
+    let all: Vec<Vertex> = cube.shared_vertex_iter().collect();
+    let weld = |i: usize| vertices.iter().position(|v| v.pos == all[i].pos).unwrap();
+    let mut triangles: Vec<Triangle<usize>> = cube
+        .indexed_polygon_iter()
+        .skip(2)
+        .triangulate()
+        .map(|t| Triangle::new(weld(t.x), weld(t.y), weld(t.z)))
+        .collect();
+    assert_eq!(
+        2,
+        fill_holes(&mut vertices, &mut triangles, HoleFill::EarClip)
+    );
+    assert_watertight(&triangles);
+}
+
+#[test]
+fn fill_concave() {
+    // an L-shaped patch facing +Z, its outline has a reflex corner at 3
+    //
+    //  5---4
+    //  |   |
+    //  |   3---2
+    //  |       |
+    //  0-------1
+    let corners = [[0., 0.], [2., 0.], [2., 1.], [1., 1.], [1., 2.], [0., 2.]];
+    let mut vertices: Vec<Vertex> = corners
+        .iter()
+        .map(|&[x, y]| Vertex {
+            pos: [x, y, 0.].into(),
+            normal: [0., 0., 1.].into(),
+        })
+        .collect();
+    let mut triangles = vec![
+        Triangle::new(0, 1, 2),
+        Triangle::new(0, 2, 3),
+        Triangle::new(0, 3, 5),
+        Triangle::new(3, 4, 5),
+    ];
+    assert_eq!(
+        1,
+        fill_holes(&mut vertices, &mut triangles, HoleFill::EarClip)
+    );
+    assert_eq!(8, triangles.len());
+
+    // the back side covers the same area, and faces -Z
+    let mut area = 0.;
+    for t in &triangles[4..] {
+        let p = |i: usize| vertices[i].pos;
+        let (a, b, c) = (p(t.x), p(t.y), p(t.z));
+        let z = (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+        assert!(z < 0.);
+        area -= z / 2.;
+    }
+    assert_eq!(3., area);
+}
@@ -0,0 +1,75 @@
+extern crate genmesh;
+
+use genmesh::generators::{Cube, IcoSphere, SharedVertex};
+use genmesh::validate::validate;
+use genmesh::{convex_hull, Neighbors, Position, Triangle, Vertex};
+
+fn check_hull(vertices: &[Vertex], triangles: &[Triangle<usize>], cloud: &[Position]) {
+    let positions: Vec<Position> = vertices.iter().map(|v| v.pos).collect();
+    let report = validate(&positions, triangles);
+    assert!(report.non_manifold_edges.is_empty());
+    assert!(report.inconsistent_winding.is_empty());
+    assert!(report.degenerate.is_empty());
+    assert!(Neighbors::new(positions.clone(), triangles.to_vec())
+        .boundary_edges()
+        .is_empty());
+
+    // every point of the cloud is behind every face
+    for t in triangles {
+        let p = |i: usize| [positions[i].x, positions[i].y, positions[i].z];
+        let (a, b, c) = (p(t.x), p(t.y), p(t.z));
+        let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+        let n = [
+            u[1] * v[2] - u[2] * v[1],
+            u[2] * v[0] - u[0] * v[2],
+            u[0] * v[1] - u[1] * v[0],
+        ];
+        for q in cloud {
+            let d = n[0] * (q.x - a[0]) + n[1] * (q.y - a[1]) + n[2] * (q.z - a[2]);
+            assert!(d <= 1e-5);
+        }
+    }
+}
+
+#[test]
+fn hull_of_cube() {
+    // the corners of a cube, repeated, plus points inside it
+    let mut cloud: Vec<Position> = Cube::new().shared_vertex_iter().map(|v| v.pos).collect();
+    for i in 0..50 {
+        let t = i as f32 / 50.;
+        cloud.push([t - 0.5, (t * 7.).sin() * 0.5, (t * 3.).cos() * 0.5].into());
+    }
+    let (vertices, triangles) = convex_hull(&cloud);
+    assert_eq!(8, vertices.len());
+    assert_eq!(12, triangles.len());
+    check_hull(&vertices, &triangles, &cloud);
+
+    // corner normals point away from the center
+    for v in &vertices {
+        let n = v.normal;
+        assert!(n.x * v.pos.x > 0. && n.y * v.pos.y > 0. && n.z * v.pos.z > 0.);
+    }
+}
+
+#[test]
+fn hull_of_sphere() {
+    let cloud: Vec<Position> = IcoSphere::subdivide(2)
+        .shared_vertex_iter()
+        .map(|v| v.pos)
+        .collect();
+    let (vertices, triangles) = convex_hull(&cloud);
+    assert_eq!(cloud.len(), vertices.len());
+    assert_eq!(2 * cloud.len() - 4, triangles.len());
+    check_hull(&vertices, &triangles, &cloud);
+}
+
+#[test]
+fn flat_hull() {
+    let flat: Vec<Position> = (0..10)
+        .map(|i| [i as f32, (i * i) as f32, 0.].into())
+        .collect();
+    let (vertices, triangles) = convex_hull(&flat);
+    assert!(vertices.is_empty() && triangles.is_empty());
+    assert!(convex_hull(&flat[..3]).0.is_empty());
+}
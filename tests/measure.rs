@@ -0,0 +1,93 @@
+extern crate genmesh;
+
+use genmesh::generators::{Cube, IcoSphere, IndexedPolygon, SharedVertex};
+use genmesh::{
+    indexed_centroid, indexed_surface_area, indexed_volume, MapToVertices, Measure, Position,
+    Triangle, Triangulate, Vertex, Vertices,
+};
+
+#[test]
+fn measure_cube() {
+    let s = 2. / 3f32.sqrt();
+    assert!((Cube::new().surface_area() - 6. * s * s).abs() < 1e-5);
+    assert!((Cube::new().volume() - s * s * s).abs() < 1e-5);
+    let c = Cube::new().triangulate().centroid().unwrap();
+    assert!(c.x.abs() < 1e-6 && c.y.abs() < 1e-6 && c.z.abs() < 1e-6);
+}
+
+#[test]
+fn measure_shifted_sphere() {
+    let shifted = || {
+        IcoSphere::subdivide(3)
+            .vertex(|v: Vertex| Position::from([v.pos.x + 3., v.pos.y, v.pos.z - 1.]))
+    };
+    let area = shifted().surface_area();
+    let volume = shifted().volume();
+    // slightly under the unit sphere, the facets are inside of it
+    assert!(area < 4. * std::f32::consts::PI && area > 0.98 * 4. * std::f32::consts::PI);
+    assert!(
+        volume < 4. / 3. * std::f32::consts::PI && volume > 0.95 * 4. / 3. * std::f32::consts::PI
+    );
+    let c = shifted().centroid().unwrap();
+    assert!((c.x - 3.).abs() < 1e-4 && c.y.abs() < 1e-4 && (c.z + 1.).abs() < 1e-4);
+}
+
+#[test]
+fn measure_indexed() {
+    let cube = Cube::new();
+    let positions: Vec<Position> = cube.shared_vertex_iter().map(|v| v.pos).collect();
+    let mut triangles: Vec<Triangle<usize>> = cube.indexed_polygon_iter().triangulate().collect();
+    assert!(
+        (indexed_surface_area(&positions, &triangles) - Cube::new().surface_area()).abs() < 1e-5
+    );
+    assert!((indexed_volume(&positions, &triangles) - Cube::new().volume()).abs() < 1e-5);
+    assert!(indexed_centroid(&positions, &triangles).is_some());
+
+    // turned inside out, the volume is negative
+    for t in &mut triangles {
+        std::mem::swap(&mut t.y, &mut t.z);
+    }
+    assert!(indexed_volume(&positions, &triangles) < 0.);
+
+    // a double sided triangle encloses nothing
+    let t = triangles[0].clone();
+    let flat = [t.clone(), Triangle::new(t.x, t.z, t.y)];
+    assert!(indexed_volume(&positions, &flat).abs() < 1e-6);
+    assert!(indexed_centroid(&positions, &flat).is_none());
+}
+
+#[test]
+fn face_normals_and_centroids() {
+    // the cube's faces are squares, so their centers are their vertex
+    // normals scaled to the distance of the faces
+    let s = 1. / 3f32.sqrt();
+    let normals = Cube::new().face_normals();
+    let centroids = Cube::new().face_centroids();
+    let expected: Vec<Vertex> = Cube::new().vertices().step_by(4).collect();
+    assert_eq!(normals.len(), 6);
+    for ((n, c), v) in normals.iter().zip(&centroids).zip(&expected) {
+        assert_eq!(*n, v.normal);
+        assert!((c.x - s * n.x).abs() < 1e-6);
+        assert!((c.y - s * n.y).abs() < 1e-6);
+        assert!((c.z - s * n.z).abs() < 1e-6);
+    }
+
+    // the normals of the triangles follow their winding
+    let triangles = vec![
+        Triangle::new([0., 0., 0.], [2., 0., 0.], [0., 2., 0.]),
+        Triangle::new([0., 0., 0.], [0., 2., 0.], [2., 0., 0.]),
+        Triangle::new([0., 0., 0.], [1., 0., 0.], [2., 0., 0.]),
+    ];
+    let positions = || {
+        triangles
+            .clone()
+            .into_iter()
+            .vertex(|p: [f32; 3]| Position::from(p))
+    };
+    let normals = positions().face_normals();
+    assert_eq!(normals[0], [0., 0., 1.].into());
+    assert_eq!(normals[1], [0., 0., -1.].into());
+    assert_eq!(normals[2], [0., 0., 0.].into());
+    let c = positions().face_centroids()[0];
+    assert!((c.x - 2. / 3.).abs() < 1e-6 && (c.y - 2. / 3.).abs() < 1e-6 && c.z == 0.);
+}
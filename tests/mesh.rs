@@ -0,0 +1,124 @@
+extern crate genmesh;
+
+use genmesh::generators::{Cube, IndexedPolygon, Plane, SharedVertex};
+use genmesh::{Mesh, MeshBuilder, Triangulate};
+
+#[test]
+fn from_generator() {
+    let plane = Plane::subdivide(2, 3);
+    let mesh = Mesh::from_generator(&plane);
+    assert_eq!(mesh.vertex_count(), plane.shared_vertex_count());
+    assert_eq!(mesh.triangle_count(), plane.indexed_polygon_count() * 2);
+    assert_eq!(mesh.normals.len(), mesh.vertex_count());
+    assert!(mesh.uvs.is_empty());
+    assert!(mesh
+        .indices
+        .iter()
+        .all(|&i| (i as usize) < mesh.vertex_count()));
+
+    // the triangles are the ones of the generator
+    let expected: Vec<_> = Plane::subdivide(2, 3).triangulate().collect();
+    let vertices = mesh.vertices();
+    for (t, e) in mesh.triangles().zip(expected) {
+        assert_eq!(vertices[t.x], e.x);
+        assert_eq!(vertices[t.y], e.y);
+        assert_eq!(vertices[t.z], e.z);
+    }
+}
+
+#[test]
+fn from_polygons_with_indexer() {
+    // the cube has 8 corners, each with 3 normals
+    let mesh = Mesh::from_polygons_with_indexer(Cube::new(), 8);
+    assert_eq!(mesh.vertex_count(), 24);
+    assert_eq!(mesh.triangle_count(), 12);
+    let direct = Mesh::from_generator(&Cube::new());
+    assert_eq!(mesh.vertex_count(), direct.vertex_count());
+}
+
+#[test]
+fn buffers() {
+    let mesh = Mesh::from_generator(&Plane::new());
+    let uvs: Vec<[f32; 2]> = mesh
+        .positions
+        .iter()
+        .map(|p| [p.x * 0.5 + 0.5, p.y * 0.5 + 0.5])
+        .collect();
+    let mesh = mesh.with_uvs(uvs);
+
+    assert_eq!(mesh.position_buffer().len(), 4 * 3);
+    assert_eq!(mesh.normal_buffer().len(), 4 * 3);
+    assert_eq!(mesh.uv_buffer().len(), 4 * 2);
+
+    let interleaved = mesh.interleaved();
+    assert_eq!(interleaved.len(), 4 * 8);
+    for i in 0..4 {
+        let v = &interleaved[i * 8..i * 8 + 8];
+        assert_eq!(v[..3], mesh.position_buffer()[i * 3..i * 3 + 3]);
+        assert_eq!(v[3..6], [0., 0., 1.]);
+        assert_eq!(v[6..], mesh.uvs[i]);
+    }
+}
+
+#[test]
+fn builder() {
+    let identity = [
+        [1., 0., 0., 0.],
+        [0., 1., 0., 0.],
+        [0., 0., 1., 0.],
+        [0., 0., 0., 1.],
+    ];
+    let mirrored = [
+        [-1., 0., 0., 0.],
+        [0., 1., 0., 0.],
+        [0., 0., 1., 0.],
+        [3., 0., 0., 1.],
+    ];
+    let cube = Mesh::from_generator(&Cube::new());
+    let mesh = MeshBuilder::new()
+        .add(&Cube::new(), identity)
+        .add(&Cube::new(), mirrored)
+        .build();
+
+    assert_eq!(mesh.vertex_count(), 2 * cube.vertex_count());
+    assert_eq!(mesh.triangle_count(), 2 * cube.triangle_count());
+    assert_eq!(mesh.indices[..cube.indices.len()], cube.indices[..]);
+
+    // the mirrored copy is offset, and its faces still point outward
+    let vertices = mesh.vertices();
+    for t in mesh.triangles().skip(cube.triangle_count()) {
+        assert!(t.x >= cube.vertex_count());
+        let (a, b, c) = (vertices[t.x].pos, vertices[t.y].pos, vertices[t.z].pos);
+        let e1 = [b.x - a.x, b.y - a.y, b.z - a.z];
+        let e2 = [c.x - a.x, c.y - a.y, c.z - a.z];
+        let n = [
+            e1[1] * e2[2] - e1[2] * e2[1],
+            e1[2] * e2[0] - e1[0] * e2[2],
+            e1[0] * e2[1] - e1[1] * e2[0],
+        ];
+        let out = [a.x - 3., a.y, a.z];
+        assert!(n[0] * out[0] + n[1] * out[1] + n[2] * out[2] > 0.);
+    }
+}
+
+#[test]
+fn index_types() {
+    use genmesh::{Indexer, LruIndexer};
+
+    let plane = Plane::subdivide(10, 10);
+    let wide = Mesh::from_generator(&plane);
+    let narrow: Mesh<u16> = Mesh::try_from_generator(&plane).unwrap();
+    let native: Mesh<usize> = Mesh::try_from_polygons_with_indexer(plane, 32).unwrap();
+    assert!(wide.triangles().eq(narrow.triangles()));
+    assert_eq!(native.vertex_count(), wide.vertex_count());
+
+    // 257 * 256 vertices do not fit in 16 bits
+    let plane = Plane::subdivide(256, 255);
+    assert!(Mesh::<u16>::try_from_generator(&plane).is_none());
+    assert!(Mesh::<u32>::try_from_generator(&plane).is_some());
+
+    let mut indexer = LruIndexer::new(4, |_, _: usize| ());
+    let indices: Vec<Option<u16>> = (0..65540).map(|i| indexer.index_as(i)).collect();
+    assert_eq!(indices[65535], Some(65535));
+    assert_eq!(indices[65536], None);
+}
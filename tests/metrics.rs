@@ -0,0 +1,82 @@
+extern crate genmesh;
+
+mod common;
+
+use std::f32::consts::PI;
+
+use genmesh::generators::{Cube, IcoSphere, Plane};
+use genmesh::{curvature, triangle_quality, Position, Triangle};
+
+use common::indexed_positions;
+
+#[test]
+fn sphere() {
+    let (positions, triangles) = indexed_positions(&IcoSphere::subdivide(3), Some(1e-5));
+    for c in curvature(&positions, &triangles) {
+        assert!((c.gaussian - 1.).abs() < 0.05, "{:?}", c);
+        assert!((c.mean - 1.).abs() < 0.05, "{:?}", c);
+    }
+
+    // seen from inside, the sphere curves away from its normals
+    let (positions, triangles) =
+        indexed_positions(&IcoSphere::subdivide(2).inside_out(), Some(1e-5));
+    for c in curvature(&positions, &triangles) {
+        assert!(c.gaussian > 0.9 && c.mean < -0.9, "{:?}", c);
+    }
+}
+
+#[test]
+fn cube() {
+    let (positions, triangles) = indexed_positions(&Cube::subdivided(2, 2, 2), Some(1e-5));
+    let curvatures = curvature(&positions, &triangles);
+    for (p, c) in positions.iter().zip(&curvatures) {
+        let sharp = [p.x, p.y, p.z].iter().filter(|c| c.abs() > 0.5).count();
+        match sharp {
+            // the middle of the faces is flat
+            1 => assert!(c.gaussian.abs() < 1e-4 && c.mean.abs() < 1e-4),
+            // the edges bend in one direction only
+            2 => assert!(c.gaussian.abs() < 1e-4 && c.mean > 0.),
+            _ => assert!(c.gaussian > 0. && c.mean > 0.),
+        }
+    }
+}
+
+#[test]
+fn open_plane() {
+    let (positions, triangles) = indexed_positions(&Plane::subdivide(3, 3), Some(1e-5));
+    let curvatures = curvature(&positions, &triangles);
+    assert_eq!(curvatures.len(), 16);
+    for c in curvatures {
+        assert!(c.gaussian.abs() < 1e-4 && c.mean.abs() < 1e-4);
+    }
+}
+
+#[test]
+fn quality() {
+    let h = 3f32.sqrt() / 2.;
+    let positions: Vec<Position> = vec![
+        [0., 0., 0.].into(),
+        [1., 0., 0.].into(),
+        [0.5, h, 0.].into(),
+        [0., 1., 0.].into(),
+        [10., 0.1, 0.].into(),
+        [2., 0., 0.].into(),
+    ];
+    let quality = triangle_quality(
+        &positions,
+        &[
+            Triangle::new(0, 1, 2),
+            Triangle::new(0, 1, 3),
+            Triangle::new(0, 4, 3),
+            Triangle::new(0, 1, 5),
+        ],
+    );
+    assert!((quality[0].aspect_ratio - 1.).abs() < 1e-5);
+    assert!((quality[0].min_angle - PI / 3.).abs() < 1e-5);
+    assert!((quality[1].min_angle - PI / 4.).abs() < 1e-5);
+    assert!((quality[1].aspect_ratio - (1. + 2f32.sqrt()) / 2.).abs() < 1e-5);
+    assert!(quality[2].aspect_ratio > 5.);
+    assert!(quality[2].min_angle < quality[1].min_angle);
+    assert_eq!(quality[3].aspect_ratio, f32::INFINITY);
+    assert_eq!(quality[3].min_angle, 0.);
+}
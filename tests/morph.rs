@@ -0,0 +1,53 @@
+extern crate genmesh;
+
+use genmesh::generators::{Cube, Plane, QuadSphere, SharedVertex};
+use genmesh::{MorphTargets, Vertex};
+
+#[test]
+fn sphere_to_cube() {
+    let sphere = QuadSphere::new(3);
+    let cube = Cube::subdivided(3, 3, 3);
+    let mut morph = MorphTargets::new(&sphere);
+    assert_eq!(morph.target_count(), 0);
+    assert_eq!(morph.add_target(&cube), Some(0));
+    assert_eq!(morph.target_count(), 1);
+    assert_eq!(
+        morph.position_delta_buffer(0).len(),
+        3 * morph.base.vertex_count()
+    );
+    assert_eq!(
+        morph.normal_delta_buffer(0).len(),
+        3 * morph.base.vertex_count()
+    );
+
+    // no weight gives the base, a full weight the target
+    assert!(morph.blend(&[]) == morph.base);
+    assert!(morph.blend(&[0.]) == morph.base);
+    let full = morph.blend(&[1.]);
+    let expected: Vec<Vertex> = cube.shared_vertex_iter().collect();
+    for (v, e) in full.vertices().iter().zip(&expected) {
+        for (a, b) in [(v.pos, e.pos), (v.normal, e.normal)] {
+            assert!(
+                (a.x - b.x).abs() < 1e-5 && (a.y - b.y).abs() < 1e-5 && (a.z - b.z).abs() < 1e-5
+            );
+        }
+    }
+    assert!(full.indices == morph.base.indices);
+
+    // halfway, the normals are unit length again
+    for n in morph.blend(&[0.5]).normals {
+        let len = (n.x * n.x + n.y * n.y + n.z * n.z).sqrt();
+        assert!((len - 1.).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn mismatched_targets() {
+    let mut morph = MorphTargets::new(&QuadSphere::new(2));
+    assert_eq!(morph.add_target(&Cube::subdivided(3, 3, 3)), None);
+    // as many vertices, but other polygons
+    let plane = Plane::subdivide(5, 8);
+    assert_eq!(plane.shared_vertex_count(), morph.base.vertex_count());
+    assert_eq!(morph.add_target(&plane), None);
+    assert_eq!(morph.target_count(), 0);
+}
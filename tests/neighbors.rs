@@ -0,0 +1,167 @@
+extern crate genmesh;
+
+use genmesh::generators::{IndexedPolygon, Plane, SharedVertex};
+use genmesh::{Line, Neighbors, Triangle, Triangulate, Vertex};
+
+/// A 2x2 grid of quads, split into 8 triangles. Vertex 4 is in the center.
+fn grid() -> Neighbors<Vertex> {
+    let plane = Plane::subdivide(2, 2);
+    Neighbors::new(
+        plane.shared_vertex_iter().collect(),
+        plane.indexed_polygon_iter().triangulate().collect(),
+    )
+}
+
+#[test]
+fn vertex_adjacency() {
+    let mesh = grid();
+    // the quads are split along the diagonal going through the center
+    assert_eq!(vec![0, 1, 3, 5, 7, 8], mesh.vertices_adjacent_to_vertex(4));
+    assert_eq!(vec![1, 3, 4], mesh.vertices_adjacent_to_vertex(0));
+    assert_eq!(
+        vec![Line::new(2, 1), Line::new(2, 5)],
+        mesh.edges_of_vertex(2)
+    );
+    // each face is only listed once per vertex
+    assert_eq!(Some(&[0, 1][..]), mesh.vertex_neighbors(&0));
+}
+
+#[test]
+fn edge_queries() {
+    let mesh = grid();
+    let mut shared = mesh.faces_sharing_edge(&Line::new(4, 1));
+    shared.sort_unstable();
+    assert_eq!(2, shared.len());
+    assert_eq!(shared, {
+        let mut other = mesh.faces_sharing_edge(&Line::new(1, 4));
+        other.sort_unstable();
+        other
+    });
+    assert_eq!(1, mesh.faces_sharing_edge(&Line::new(0, 1)).len());
+    assert!(mesh.faces_sharing_edge(&Line::new(0, 8)).is_empty());
+
+    // neighbors are found across edges whatever the winding
+    assert_eq!(2, mesh.polygon_neighbors(0).unwrap().len());
+}
+
+#[test]
+fn boundary() {
+    let mesh = grid();
+    let edges = mesh.boundary_edges();
+    assert_eq!(8, edges.len());
+    for e in &edges {
+        assert!(e.x != 4 && e.y != 4);
+    }
+
+    let closed = Neighbors::new(
+        vec![(); 4],
+        vec![
+            Triangle::new(0, 2, 1),
+            Triangle::new(2, 3, 1),
+            Triangle::new(0, 1, 3),
+            Triangle::new(0, 3, 2),
+        ],
+    );
+    assert!(closed.boundary_edges().is_empty());
+}
+
+#[test]
+fn boundary_loops() {
+    let mesh = grid();
+    let loops = mesh.boundary_loops();
+    assert_eq!(1, loops.len());
+    assert_eq!(8, loops[0].len());
+    assert!(!loops[0].contains(&4));
+    // consecutive vertices of the loop are joined by boundary edges
+    let edges = mesh.boundary_edges();
+    let n = loops[0].len();
+    for i in 0..n {
+        let e = Line::new(loops[0][i], loops[0][(i + 1) % n]);
+        assert!(edges.contains(&e));
+    }
+
+    // two triangles touching at vertex 0 give two loops
+    let bowtie = Neighbors::new(
+        vec![(); 5],
+        vec![Triangle::new(0, 1, 2), Triangle::new(0, 3, 4)],
+    );
+    let mut loops = bowtie.boundary_loops();
+    for l in loops.iter_mut() {
+        let min = (0..l.len()).min_by_key(|&i| l[i]).unwrap();
+        l.rotate_left(min);
+    }
+    loops.sort();
+    assert_eq!(vec![vec![0, 1, 2], vec![0, 3, 4]], loops);
+
+    // a cube without its top face has a single square hole
+    let cube = genmesh::generators::Cube::new();
+    let positions: Vec<_> = cube.shared_vertex_iter().map(|v| v.pos).collect();
+    let weld = |i: usize| positions.iter().position(|p| *p == positions[i]).unwrap();
+    let triangles: Vec<Triangle<usize>> = cube
+        .indexed_polygon_iter()
+        .take(5)
+        .triangulate()
+        .map(|t| Triangle::new(weld(t.x), weld(t.y), weld(t.z)))
+        .collect();
+    let open = Neighbors::new(positions, triangles);
+    let loops = open.boundary_loops();
+    assert_eq!(1, loops.len());
+    assert_eq!(4, loops[0].len());
+}
+
+#[test]
+fn degenerate_normals() {
+    use genmesh::NormalFallback;
+
+    // a fan around vertex 0 where the last triangle has collapsed onto a line
+    let positions: Vec<[f32; 3]> = vec![
+        [0., 0., 0.],
+        [1., 0., 0.],
+        [0., 1., 0.],
+        [-1., 0., 0.],
+        [-2., 0., 0.],
+    ];
+    let mesh = Neighbors::new(
+        positions,
+        vec![
+            Triangle::new(0, 1, 2),
+            Triangle::new(0, 2, 3),
+            Triangle::new(0, 3, 4),
+        ],
+    );
+    let pos = |p: &[f32; 3]| (*p).into();
+
+    let up = [0., 0., 1.].into();
+    assert_eq!(up, mesh.normal_for_face(0, pos));
+    assert_eq!(up, mesh.normal_for_face(2, pos));
+    assert_eq!(
+        None,
+        mesh.normal_for_face_with(2, pos, NormalFallback::Skip)
+    );
+    let side = [1., 0., 0.].into();
+    assert_eq!(
+        Some(side),
+        mesh.normal_for_face_with(2, pos, NormalFallback::Up(side))
+    );
+
+    // the degenerate face does not disturb the smooth normal
+    assert_eq!(up, mesh.normal_for_vertex(0, pos));
+    assert_eq!(
+        Some(up),
+        mesh.normal_for_vertex_with(0, pos, NormalFallback::Skip)
+    );
+    // vertex 4 only belongs to the degenerate face
+    assert_eq!(
+        None,
+        mesh.normal_for_vertex_with(4, pos, NormalFallback::Skip)
+    );
+
+    // nothing around a lone degenerate face: no NaN, just the fallback
+    let line = Neighbors::new(
+        vec![[0f32, 0., 0.], [1., 0., 0.], [2., 0., 0.]],
+        vec![Triangle::new(0, 1, 2)],
+    );
+    let n = line.normal_for_vertex(1, pos);
+    assert_eq!(up, n);
+    assert!(!n.x.is_nan());
+}
@@ -0,0 +1,90 @@
+extern crate genmesh;
+
+use genmesh::noise::{perlin2, perlin3, value2, value3, Basis, Fbm};
+use genmesh::Position;
+
+// points spread over a few cells, off the grid
+fn points() -> impl Iterator<Item = [f32; 3]> {
+    (0..1000).map(|i| {
+        let i = i as f32;
+        [
+            (i * 0.618_034).fract() * 7. - 3.,
+            (i * 0.414_214).fract() * 7. - 3.,
+            (i * 0.732_051).fract() * 7. - 3.,
+        ]
+    })
+}
+
+#[test]
+fn within_range_and_seeded() {
+    let mut differ = 0;
+    for [x, y, z] in points() {
+        let p = Position::from([x, y, z]);
+        for v in [
+            value2([x, y], 3),
+            value3(p, 3),
+            perlin2([x, y], 3),
+            perlin3(p, 3),
+        ] {
+            assert!((-1. ..=1.).contains(&v));
+        }
+        assert_eq!(perlin3(p, 3), perlin3(p, 3));
+        if perlin3(p, 3) != perlin3(p, 4) && value2([x, y], 3) != value2([x, y], 4) {
+            differ += 1;
+        }
+    }
+    assert!(differ > 900);
+}
+
+#[test]
+fn smooth() {
+    for [x, y, z] in points() {
+        let (p, q) = (
+            Position::from([x, y, z]),
+            Position::from([x + 1e-3, y, z - 1e-3]),
+        );
+        assert!((value3(p, 1) - value3(q, 1)).abs() < 1e-2);
+        assert!((perlin3(p, 1) - perlin3(q, 1)).abs() < 1e-2);
+        assert!((perlin2([x, y], 1) - perlin2([x + 1e-3, y], 1)).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn perlin_is_zero_on_the_grid() {
+    for x in -3..3 {
+        for y in -3..3 {
+            assert_eq!(perlin2([x as f32, y as f32], 9), 0.);
+            assert_eq!(perlin3([x as f32, y as f32, 2.].into(), 9), 0.);
+        }
+    }
+}
+
+#[test]
+fn fbm() {
+    let noise = Fbm {
+        basis: Basis::Value,
+        octaves: 1,
+        frequency: 2.,
+        ..Fbm::default()
+    };
+    // a single layer is the basis, scaled by the frequency
+    let p = Position::from([0.3, -1.2, 0.7]);
+    assert_eq!(noise.sample3(p), value3([0.6, -2.4, 1.4].into(), 0));
+    assert_eq!(noise.sample2([0.3, -1.2]), value2([0.6, -2.4], 0));
+
+    let noise = Fbm {
+        octaves: 6,
+        ..Fbm::default()
+    };
+    let samples: Vec<f32> = points().map(|p| noise.sample3(p.into())).collect();
+    assert!(samples.iter().all(|s| (-1. ..=1.).contains(s)));
+    let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+    assert!(mean.abs() < 0.1);
+    assert!(samples.iter().any(|&s| s > 0.2) && samples.iter().any(|&s| s < -0.2));
+
+    let none = Fbm {
+        octaves: 0,
+        ..Fbm::default()
+    };
+    assert_eq!(none.sample2([0.5, 0.5]), 0.);
+}
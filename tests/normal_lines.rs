@@ -0,0 +1,45 @@
+extern crate genmesh;
+
+use cgmath::{InnerSpace, Vector3};
+use genmesh::generators::{Cube, SharedVertex};
+use genmesh::{NormalLines, Position, Vertex, Vertices};
+
+#[test]
+fn lines_along_normals() {
+    let lines: Vec<_> = Cube::new().vertices().normal_lines(0.5).collect();
+    assert_eq!(lines.len(), 24);
+    for l in &lines {
+        let d = Vector3::from(l.y.pos) - Vector3::from(l.x.pos);
+        assert!((d - Vector3::from(l.x.normal) * 0.5).magnitude() < 1e-6);
+        assert_eq!(l.x.normal, l.y.normal);
+    }
+}
+
+#[test]
+fn tangents_follow_normals() {
+    let vertices: Vec<Vertex> = Cube::new().shared_vertex_iter().collect();
+    let lines: Vec<_> = vertices
+        .iter()
+        .cloned()
+        .normal_and_tangent_lines(2., |v: &Vertex| {
+            if v.normal.z == 0. {
+                Some([0., 0., 1.].into())
+            } else {
+                None
+            }
+        })
+        .collect();
+    // the four side faces have tangents, the top and bottom do not
+    assert_eq!(lines.len(), 24 + 16);
+    let up: Vec<_> = lines.iter().filter(|l| l.x.normal.z == 1.).collect();
+    assert_eq!(up.len(), 16 + 4);
+    for l in up {
+        assert!((l.y.pos.z - l.x.pos.z - 2.).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn positions_have_no_normals() {
+    let positions: Vec<Position> = vec![[0., 0., 0.].into(), [1., 0., 0.].into()];
+    assert_eq!(positions.into_iter().normal_lines(1.).count(), 0);
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "obj")]
+
+extern crate genmesh;
+
+use genmesh::generators::{Circle, Cube, IndexedPolygon, SharedVertex};
+use genmesh::obj::{write_obj, write_obj_with_uvs};
+use genmesh::{NGon, Polygon, Vertex};
+
+#[test]
+fn obj_quads() {
+    let cube = Cube::new();
+    let vertices: Vec<Vertex> = cube.shared_vertex_iter().collect();
+    let polygons: Vec<Polygon<usize>> = cube.indexed_polygon_iter().collect();
+    let mut out = Vec::new();
+    write_obj(&mut out, &vertices, &polygons).unwrap();
+    let text = String::from_utf8(out).unwrap();
+
+    assert_eq!(24, text.lines().filter(|l| l.starts_with("v ")).count());
+    assert_eq!(24, text.lines().filter(|l| l.starts_with("vn ")).count());
+    assert_eq!(0, text.lines().filter(|l| l.starts_with("vt ")).count());
+    let faces: Vec<&str> = text.lines().filter(|l| l.starts_with("f ")).collect();
+    assert_eq!(6, faces.len());
+    assert_eq!("f 1//1 2//2 3//3 4//4", faces[0]);
+}
+
+#[test]
+fn obj_ngon_with_uvs() {
+    let circle = Circle::new(8);
+    let vertices: Vec<Vertex> = circle.shared_vertex_iter().collect();
+    let uvs: Vec<[f32; 2]> = vertices
+        .iter()
+        .map(|v| [v.pos.x * 0.5 + 0.5, v.pos.y * 0.5 + 0.5])
+        .collect();
+    let mut ngon = NGon::new();
+    for i in 1..vertices.len() {
+        ngon.add_vertex(i);
+    }
+    let mut out = Vec::new();
+    write_obj_with_uvs(&mut out, &vertices, &uvs, &[Polygon::PolyNGon(ngon)]).unwrap();
+    let text = String::from_utf8(out).unwrap();
+
+    assert_eq!(9, text.lines().filter(|l| l.starts_with("vt ")).count());
+    let face = text.lines().find(|l| l.starts_with("f ")).unwrap();
+    assert_eq!(8, face.split_whitespace().count() - 1);
+    assert!(face.starts_with("f 2/2/2 3/3/3"));
+}
+
+#[test]
+fn obj_bad_index() {
+    let vertices = vec![
+        Vertex {
+            pos: [0., 0., 0.].into(),
+            normal: [0., 0., 1.].into(),
+        };
+        3
+    ];
+    let polygons = [Polygon::PolyTri(genmesh::Triangle::new(0, 1, 3))];
+    assert!(write_obj(Vec::new(), &vertices, &polygons).is_err());
+}
+
+#[test]
+fn obj_positions_only() {
+    let positions: Vec<genmesh::Position> = vec![
+        [0., 0., 0.].into(),
+        [1., 0., 0.].into(),
+        [0., 1., 0.].into(),
+    ];
+    let polygons = [Polygon::PolyTri(genmesh::Triangle::new(0, 1, 2))];
+    let mut out = Vec::new();
+    write_obj(&mut out, &positions, &polygons).unwrap();
+    let text = String::from_utf8(out).unwrap();
+
+    assert_eq!(0, text.lines().filter(|l| l.starts_with("vn ")).count());
+    assert_eq!(Some("f 1 2 3"), text.lines().find(|l| l.starts_with("f ")));
+}
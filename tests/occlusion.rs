@@ -0,0 +1,75 @@
+extern crate genmesh;
+
+mod common;
+
+use genmesh::generators::{Cube, Plane};
+use genmesh::{bake_ao, Position, Triangle};
+
+use common::indexed;
+
+#[test]
+fn open_and_convex() {
+    let (vertices, triangles) = indexed(&Plane::subdivide(4, 4), None);
+    let ao = bake_ao(&vertices, &triangles, 64, 10.);
+    assert_eq!(ao.len(), vertices.len());
+    assert!(ao.iter().all(|&a| a == 0.));
+
+    let (vertices, triangles) = indexed(&Cube::subdivided(2, 2, 2), None);
+    assert!(bake_ao(&vertices, &triangles, 64, 10.)
+        .iter()
+        .all(|&a| a == 0.));
+}
+
+#[test]
+fn enclosed() {
+    let (vertices, triangles) = indexed(&Cube::subdivided(2, 2, 2).inside_out(), None);
+    let ao = bake_ao(&vertices, &triangles, 64, 10.);
+    assert!(ao.iter().all(|&a| a == 1.));
+
+    // too short to reach the far walls, only the neighboring ones
+    let ao = bake_ao(&vertices, &triangles, 256, 0.5);
+    let corner = vertices
+        .iter()
+        .position(|v| v.pos.x.abs() > 0.5 && v.pos.y.abs() > 0.5 && v.pos.z.abs() > 0.5)
+        .unwrap();
+    let center = vertices
+        .iter()
+        .position(|v| v.pos.x.abs() < 1e-6 && v.pos.y.abs() < 1e-6)
+        .unwrap();
+    assert!(ao[center] == 0.);
+    assert!(ao[corner] > 0.5 && ao[corner] < 1.);
+}
+
+#[test]
+fn crease() {
+    // a floor and a wall standing on it along x = 0
+    let positions: Vec<Position> = vec![
+        [-1., -1., 0.].into(),
+        [1., -1., 0.].into(),
+        [1., 1., 0.].into(),
+        [-1., 1., 0.].into(),
+        [0., -1., 0.].into(),
+        [0., 1., 0.].into(),
+        [0., -1., 2.].into(),
+        [0., 1., 2.].into(),
+        [-0.5, 0., 0.].into(),
+        [-1., 0., 0.].into(),
+    ];
+    let triangles = vec![
+        Triangle::new(4, 1, 2),
+        Triangle::new(4, 2, 5),
+        Triangle::new(0, 4, 8),
+        Triangle::new(8, 4, 5),
+        Triangle::new(8, 5, 3),
+        Triangle::new(0, 8, 9),
+        Triangle::new(9, 8, 3),
+        Triangle::new(4, 6, 7),
+        Triangle::new(4, 7, 5),
+    ];
+    let ao = bake_ao(&positions, &triangles, 256, 100.);
+    // the wall hides less of the sky further away from it, and the top
+    // of the wall only sees the floor near the horizon
+    assert!(ao[8] > 0.1 && ao[8] < 0.5);
+    assert!(ao[9] > 0. && ao[9] < ao[8]);
+    assert!(ao[6] < ao[9] && ao[7] < ao[9]);
+}
@@ -0,0 +1,87 @@
+extern crate genmesh;
+
+mod common;
+
+use cgmath::{InnerSpace, Vector3};
+use genmesh::generators::{Cube, IcoSphere, Plane};
+use genmesh::{indexed_volume, offset, Position, Triangle, Vertex};
+
+use common::indexed;
+
+fn volume(vertices: &[Vertex], triangles: &[Triangle<usize>]) -> f32 {
+    let positions: Vec<Position> = vertices.iter().map(|v| v.pos).collect();
+    indexed_volume(&positions, triangles)
+}
+
+#[test]
+fn cube_keeps_flat_faces() {
+    // the corners of the cube are 1 / sqrt(3) from the center on each axis
+    let s = 1. / 3f32.sqrt();
+    let (mut vertices, triangles) = indexed(&Cube::new(), None);
+    let normals: Vec<_> = vertices.iter().map(|v| v.normal).collect();
+    offset(&mut vertices, &triangles, 0.5);
+    // the separate vertices of each face still meet at the corners, which
+    // moved diagonally so that each face moved straight out
+    for v in &vertices {
+        for c in [v.pos.x, v.pos.y, v.pos.z] {
+            assert!((c.abs() - (s + 0.5)).abs() < 1e-3, "{:?}", v.pos);
+        }
+    }
+    let side = 2. * (s + 0.5);
+    assert!((volume(&vertices, &triangles) - side * side * side).abs() < 1e-2 * side);
+    assert!(normals == vertices.iter().map(|v| v.normal).collect::<Vec<_>>());
+
+    offset(&mut vertices, &triangles, -0.9);
+    for v in &vertices {
+        for c in [v.pos.x, v.pos.y, v.pos.z] {
+            assert!((c.abs() - (s - 0.4)).abs() < 1e-3, "{:?}", v.pos);
+        }
+    }
+}
+
+#[test]
+fn sphere_grows_evenly() {
+    let (mut vertices, triangles) = indexed(&IcoSphere::subdivide(3), None);
+    offset(&mut vertices, &triangles, 0.25);
+    for v in &vertices {
+        let r = Vector3::from(v.pos).magnitude();
+        assert!((r - 1.25).abs() < 1e-2, "{}", r);
+    }
+    offset(&mut vertices, &triangles, -0.5);
+    for v in &vertices {
+        let r = Vector3::from(v.pos).magnitude();
+        assert!((r - 0.75).abs() < 1e-2, "{}", r);
+    }
+}
+
+#[test]
+fn open_surface() {
+    let (mut vertices, triangles) = indexed(&Plane::subdivide(3, 3), None);
+    offset(&mut vertices, &triangles, 0.1);
+    for v in &vertices {
+        assert!((v.pos.z - 0.1).abs() < 1e-4);
+    }
+
+    // a needle sharp tip only moves so far
+    let mut vertices: Vec<Vertex> = [
+        [0., 0., 10.],
+        [0.1, 0., 0.],
+        [-0.05, 0.087, 0.],
+        [-0.05, -0.087, 0.],
+    ]
+    .iter()
+    .map(|&p| Vertex {
+        pos: p.into(),
+        normal: [0., 0., 1.].into(),
+    })
+    .collect();
+    let triangles = vec![
+        Triangle::new(0, 1, 2),
+        Triangle::new(0, 2, 3),
+        Triangle::new(0, 3, 1),
+        Triangle::new(1, 3, 2),
+    ];
+    offset(&mut vertices, &triangles, 0.1);
+    let tip = Vector3::from(vertices[0].pos) - Vector3::new(0., 0., 10.);
+    assert!(tip.z > 0.1 && tip.magnitude() <= 0.3 + 1e-5);
+}
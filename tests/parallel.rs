@@ -0,0 +1,39 @@
+#![cfg(feature = "parallel")]
+
+extern crate genmesh;
+
+use genmesh::generators::{
+    IcoSphere, IndexedPolygon, ParIndexedPolygon, ParSharedVertex, Plane, SharedVertex,
+};
+use genmesh::{Polygon, Vertex};
+use rayon::prelude::*;
+
+#[test]
+fn parallel_matches_serial() {
+    let sphere = IcoSphere::subdivide(4);
+    let vertices: Vec<Vertex> = sphere.par_shared_vertices().collect();
+    let serial: Vec<Vertex> = sphere.shared_vertex_iter().collect();
+    assert_eq!(serial, vertices);
+
+    let faces: Vec<Polygon<usize>> = sphere.par_indexed_polygons().collect();
+    let serial: Vec<Polygon<usize>> = sphere.indexed_polygon_iter().collect();
+    assert_eq!(serial, faces);
+}
+
+#[test]
+fn parallel_is_indexed() {
+    let plane = Plane::subdivide(64, 64);
+    let polygons = plane.par_indexed_polygons();
+    assert_eq!(64 * 64, polygons.len());
+    let mut out: Vec<Polygon<usize>> = Vec::new();
+    polygons.collect_into_vec(&mut out);
+    assert_eq!(plane.indexed_polygon(100), out[100]);
+    assert_eq!(
+        plane.shared_vertex(7),
+        plane
+            .par_shared_vertices()
+            .skip(7)
+            .find_first(|_| true)
+            .unwrap()
+    );
+}
@@ -0,0 +1,133 @@
+extern crate genmesh;
+
+use genmesh::{triangulate_2d, triangulate_polygon, Triangle};
+
+fn area(p: &[[f32; 2]], t: &Triangle<usize>) -> f32 {
+    let (a, b, c) = (p[t.x], p[t.y], p[t.z]);
+    ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])) * 0.5
+}
+
+fn assert_delaunay(p: &[[f32; 2]], triangles: &[Triangle<usize>]) {
+    for t in triangles {
+        assert!(area(p, t) > 0.);
+        let (a, b, c) = (p[t.x], p[t.y], p[t.z]);
+        // circumcenter
+        let d = 2. * (a[0] * (b[1] - c[1]) + b[0] * (c[1] - a[1]) + c[0] * (a[1] - b[1]));
+        let sq = |q: [f32; 2]| q[0] * q[0] + q[1] * q[1];
+        let ux = (sq(a) * (b[1] - c[1]) + sq(b) * (c[1] - a[1]) + sq(c) * (a[1] - b[1])) / d;
+        let uy = (sq(a) * (c[0] - b[0]) + sq(b) * (a[0] - c[0]) + sq(c) * (b[0] - a[0])) / d;
+        let r = ((a[0] - ux).powi(2) + (a[1] - uy).powi(2)).sqrt();
+        for q in p {
+            let dist = ((q[0] - ux).powi(2) + (q[1] - uy).powi(2)).sqrt();
+            assert!(dist >= r - 1e-4, "{:?} is inside the circle of {:?}", q, t);
+        }
+    }
+}
+
+#[test]
+fn delaunay_grid() {
+    let mut points = Vec::new();
+    for y in 0..4 {
+        for x in 0..4 {
+            points.push([x as f32, y as f32]);
+        }
+    }
+    let triangles = triangulate_2d(&points);
+    assert_eq!(18, triangles.len());
+    let total: f32 = triangles.iter().map(|t| area(&points, t)).sum();
+    assert!((total - 9.).abs() < 1e-4);
+    assert_delaunay(&points, &triangles);
+}
+
+#[test]
+fn delaunay_scattered() {
+    // a spiral of points, with a repeated one
+    let mut points: Vec<[f32; 2]> = (0..60)
+        .map(|i| {
+            let a = i as f32 * 2.4;
+            let r = (i as f32).sqrt();
+            [r * a.cos(), r * a.sin()]
+        })
+        .collect();
+    points.push(points[10]);
+    let triangles = triangulate_2d(&points);
+    assert_delaunay(&points, &triangles);
+    assert!(triangles
+        .iter()
+        .all(|t| t.x != 60 && t.y != 60 && t.z != 60));
+
+    // every point is used, and the count matches Euler's formula
+    let mut used: Vec<usize> = triangles.iter().flat_map(|t| vec![t.x, t.y, t.z]).collect();
+    used.sort_unstable();
+    used.dedup();
+    assert_eq!(60, used.len());
+    let boundary = {
+        let mut edges = std::collections::HashSet::new();
+        for t in &triangles {
+            for &(a, b) in &[(t.x, t.y), (t.y, t.z), (t.z, t.x)] {
+                if !edges.remove(&(b, a)) {
+                    edges.insert((a, b));
+                }
+            }
+        }
+        edges.len()
+    };
+    assert_eq!(2 * 60 - 2 - boundary, triangles.len());
+
+    assert!(triangulate_2d(&points[..2]).is_empty());
+}
+
+#[test]
+fn polygon_with_holes() {
+    let square = |x: f32, y: f32, size: f32| {
+        vec![[x, y], [x + size, y], [x + size, y + size], [x, y + size]]
+    };
+
+    let outer = square(0., 0., 10.);
+    let holes = vec![square(2., 2., 2.), square(6., 5., 3.), square(1., 6., 1.)];
+    let triangles = triangulate_polygon(&outer, &holes);
+    let points: Vec<[f32; 2]> = outer
+        .iter()
+        .chain(holes.iter().flatten())
+        .cloned()
+        .collect();
+
+    // n + 2h - 2 triangles for n vertices and h holes
+    assert_eq!(16 + 2 * 3 - 2, triangles.len());
+    let total: f32 = triangles.iter().map(|t| area(&points, t)).sum();
+    assert!((total - (100. - 4. - 9. - 1.)).abs() < 1e-3);
+    for t in &triangles {
+        assert!(area(&points, t) > 0.);
+        // no triangle covers a hole
+        let (a, b, c) = (points[t.x], points[t.y], points[t.z]);
+        let center = [(a[0] + b[0] + c[0]) / 3., (a[1] + b[1] + c[1]) / 3.];
+        for h in &holes {
+            let inside = center[0] > h[0][0]
+                && center[0] < h[1][0]
+                && center[1] > h[0][1]
+                && center[1] < h[2][1];
+            assert!(!inside);
+        }
+    }
+}
+
+#[test]
+fn concave_polygon() {
+    // a "C" given clockwise
+    let mut outer = vec![
+        [0., 0.],
+        [3., 0.],
+        [3., 1.],
+        [1., 1.],
+        [1., 2.],
+        [3., 2.],
+        [3., 3.],
+        [0., 3.],
+    ];
+    outer.reverse();
+    let triangles = triangulate_polygon::<Vec<[f32; 2]>>(&outer, &[]);
+    assert_eq!(6, triangles.len());
+    let total: f32 = triangles.iter().map(|t| area(&outer, t)).sum();
+    assert!((total - 7.).abs() < 1e-4);
+    assert!(triangles.iter().all(|t| area(&outer, t) > 0.));
+}
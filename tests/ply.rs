@@ -0,0 +1,112 @@
+#![cfg(feature = "ply")]
+
+extern crate genmesh;
+
+use std::io;
+
+use genmesh::generators::{Cube, IndexedPolygon, SharedVertex, Terrain};
+use genmesh::noise::Fbm;
+use genmesh::ply::{PlyValues, PlyWriter};
+use genmesh::{Polygon, Position, TerrainVertex, Vertex, VertexAttributes};
+
+fn cube() -> (Vec<Vertex>, Vec<Polygon<usize>>) {
+    let cube = Cube::new();
+    (
+        cube.shared_vertex_iter().collect(),
+        cube.indexed_polygon_iter().collect(),
+    )
+}
+
+#[test]
+fn ply_ascii_custom_properties() {
+    let (vertices, faces) = cube();
+    let colors: Vec<u8> = (0..vertices.len() * 3).map(|i| i as u8).collect();
+    let weights: Vec<f64> = (0..vertices.len()).map(|i| i as f64 * 0.5).collect();
+    let mut out = Vec::new();
+    PlyWriter::from_vertices(&vertices)
+        .properties(&["red", "green", "blue"], PlyValues::U8(colors))
+        .property("weight", PlyValues::F64(weights))
+        .faces(&faces)
+        .write_ascii(&mut out)
+        .unwrap();
+    let text = String::from_utf8(out).unwrap();
+
+    let header: Vec<&str> = text.lines().take_while(|&l| l != "end_header").collect();
+    assert_eq!("format ascii 1.0", header[1]);
+    assert!(header.contains(&"element vertex 24"));
+    assert!(header.contains(&"property uchar green"));
+    assert!(header.contains(&"property double weight"));
+    assert!(header.contains(&"element face 6"));
+
+    let body: Vec<&str> = text.lines().skip(header.len() + 1).collect();
+    assert_eq!(30, body.len());
+    let second: Vec<&str> = body[1].split(' ').collect();
+    assert_eq!(10, second.len());
+    assert_eq!(["3", "4", "5", "0.5"], second[6..]);
+    assert_eq!("4 0 1 2 3", body[24]);
+}
+
+#[test]
+fn ply_vertex_custom_values() {
+    let terrain = Terrain::new(1., 1., 2, 2, &Fbm::default(), 1.);
+    let vertices: Vec<TerrainVertex> = terrain.shared_vertex_iter().collect();
+    let mut out = Vec::new();
+    PlyWriter::from_vertices(&vertices)
+        .write_ascii(&mut out)
+        .unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.contains("property float nz\nproperty float custom_0\nelement face"));
+    let first = text.lines().skip_while(|&l| l != "end_header").nth(1);
+    let flow = first.unwrap().split(' ').nth(6).unwrap();
+    assert_eq!(flow.parse::<f32>().unwrap(), vertices[0].flow);
+}
+
+#[test]
+fn ply_uneven_custom_values() {
+    struct Sample(Vec<f32>);
+
+    impl VertexAttributes for Sample {
+        fn position(&self) -> Position {
+            [0., 0., 0.].into()
+        }
+
+        fn custom(&self) -> &[f32] {
+            &self.0
+        }
+    }
+
+    let vertices = [Sample(vec![1.]), Sample(vec![1., 2.])];
+    let error = PlyWriter::from_vertices(&vertices)
+        .write_ascii(Vec::new())
+        .unwrap_err();
+    assert_eq!(io::ErrorKind::InvalidInput, error.kind());
+}
+
+#[test]
+fn ply_binary_layout() {
+    let (vertices, faces) = cube();
+    let mut out = Vec::new();
+    PlyWriter::from_vertices(&vertices)
+        .faces(&faces)
+        .write_binary(&mut out)
+        .unwrap();
+    let end = b"end_header\n";
+    let at = out.windows(end.len()).position(|w| w == end).unwrap() + end.len();
+    // six floats per vertex, then a count and four indices per face
+    assert_eq!(24 * 6 * 4 + 6 * (1 + 4 * 4), out.len() - at);
+    let x = f32::from_le_bytes([out[at], out[at + 1], out[at + 2], out[at + 3]]);
+    assert_eq!(vertices[0].pos.x, x);
+    assert_eq!(4, out[at + 24 * 6 * 4]);
+}
+
+#[test]
+fn ply_without_faces() {
+    let mut out = Vec::new();
+    PlyWriter::new(2)
+        .property("value", PlyValues::I32(vec![-1, 7]))
+        .write_ascii(&mut out)
+        .unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.contains("element face 0"));
+    assert!(text.ends_with("end_header\n-1\n7\n"));
+}
@@ -0,0 +1,102 @@
+extern crate genmesh;
+
+mod common;
+
+use std::io;
+
+use genmesh::generators::{IcoSphere, IndexedPolygon, Plane, SharedVertex};
+use genmesh::{Neighbors, ProgressiveMesh};
+
+use common::indexed_positions;
+
+fn build<G>(generator: G) -> ProgressiveMesh
+where
+    G: SharedVertex<genmesh::Vertex> + IndexedPolygon<genmesh::Polygon<usize>>,
+{
+    let (positions, triangles) = indexed_positions(&generator, None);
+    ProgressiveMesh::build(&positions, triangles)
+}
+
+#[test]
+fn levels_of_detail() {
+    let mesh = build(IcoSphere::subdivide(2));
+    assert!(!mesh.collapses().is_empty());
+    assert_eq!(mesh.triangles(), &mesh.triangles_at(0)[..]);
+
+    let mut previous = mesh.triangles().len();
+    for level in 1..=mesh.collapses().len() {
+        let triangles = mesh.triangles_at(level);
+        assert!(triangles.len() < previous);
+        previous = triangles.len();
+    }
+    let coarsest = mesh.triangles_at(mesh.collapses().len());
+    assert!(coarsest.len() < 320);
+
+    // the coarsest level is still a closed surface
+    let neighbors = Neighbors::new(vec![(); 162], coarsest);
+    assert!(neighbors.boundary_edges().is_empty());
+
+    let lod = mesh.triangles_for_count(100);
+    assert!(lod.len() <= 100);
+}
+
+#[test]
+fn flat_plane_keeps_outline() {
+    let plane = Plane::subdivide(4, 4);
+    let positions: Vec<_> = plane.shared_vertex_iter().map(|v| v.pos).collect();
+    let mesh = build(plane);
+    let coarsest = mesh.triangles_at(mesh.collapses().len());
+    assert_eq!(2, coarsest.len());
+
+    // only the four corners are left
+    let mut used: Vec<usize> = coarsest.iter().flat_map(|t| vec![t.x, t.y, t.z]).collect();
+    used.sort_unstable();
+    used.dedup();
+    assert_eq!(4, used.len());
+    for v in used {
+        assert_eq!(1., positions[v].x.abs());
+        assert_eq!(1., positions[v].y.abs());
+    }
+}
+
+#[test]
+fn serialize_collapses() {
+    let mesh = build(IcoSphere::subdivide(1));
+    let mut bytes = Vec::new();
+    mesh.write_collapses(&mut bytes).unwrap();
+    let collapses = ProgressiveMesh::read_collapses(&bytes[..], 42).unwrap();
+    assert_eq!(mesh.collapses(), &collapses[..]);
+
+    let copy = ProgressiveMesh::new(mesh.triangles().to_vec(), collapses);
+    assert_eq!(mesh, copy);
+
+    assert!(ProgressiveMesh::read_collapses(&b"nope"[..], 42).is_err());
+    // the record is for a larger mesh
+    let error = ProgressiveMesh::read_collapses(&bytes[..], 12).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn reject_invalid_collapses() {
+    let record = |collapses: &[(u32, u32)]| {
+        let mut bytes = b"GMPM".to_vec();
+        bytes.extend_from_slice(&(collapses.len() as u32).to_le_bytes());
+        for &(from, to) in collapses {
+            bytes.extend_from_slice(&from.to_le_bytes());
+            bytes.extend_from_slice(&to.to_le_bytes());
+        }
+        ProgressiveMesh::read_collapses(&bytes[..], 4)
+    };
+    assert_eq!(record(&[(0, 1), (1, 2)]).unwrap().len(), 2);
+    for invalid in [
+        &[(0, 1), (1, 0)][..],
+        &[(0, 1), (2, 0)],
+        &[(0, 1), (0, 2)],
+        &[(3, 3)],
+        &[(4, 0)],
+        &[(0, 4)],
+    ] {
+        let error = record(invalid).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,58 @@
+extern crate genmesh;
+
+mod common;
+
+use genmesh::generators::{Cube, IcoSphere, Plane};
+use genmesh::{indexed_surface_area, quadrangulate, EmitTriangles, Polygon};
+
+use common::indexed_positions;
+
+fn quads(polygons: &[Polygon<usize>]) -> usize {
+    polygons
+        .iter()
+        .filter(|p| matches!(p, Polygon::PolyQuad(_)))
+        .count()
+}
+
+#[test]
+fn quadrangulate_grid() {
+    let (positions, triangles) = indexed_positions(&Plane::subdivide(3, 3), None);
+    let polygons = quadrangulate(&positions, &triangles, 0.01);
+    assert_eq!(polygons.len(), 9);
+    assert_eq!(quads(&polygons), 9);
+
+    // the quads cover the same surface, facing the same way
+    let mut again = Vec::new();
+    for p in &polygons {
+        p.clone().emit_triangles(|t| again.push(t));
+    }
+    let area = indexed_surface_area(&positions, &again);
+    assert!((area - indexed_surface_area(&positions, &triangles)).abs() < 1e-5);
+    for t in &again {
+        let (a, b, c) = (positions[t.x], positions[t.y], positions[t.z]);
+        let z = (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+        assert!(z > 0.);
+    }
+}
+
+#[test]
+fn quadrangulate_cube() {
+    let (positions, triangles) = indexed_positions(&Cube::new(), None);
+    let polygons = quadrangulate(&positions, &triangles, 0.01);
+    assert_eq!(quads(&polygons), 6);
+    assert_eq!(polygons.len(), 6);
+}
+
+#[test]
+fn quadrangulate_curved() {
+    // the faces of an icosahedron are far from coplanar
+    let (positions, triangles) = indexed_positions(&IcoSphere::new(), None);
+    let polygons = quadrangulate(&positions, &triangles, 0.1);
+    assert_eq!(quads(&polygons), 0);
+    assert_eq!(polygons.len(), 20);
+
+    // unless the angle allows it
+    let polygons = quadrangulate(&positions, &triangles, 1.);
+    assert!(quads(&polygons) > 0);
+    assert_eq!(polygons.len(), 20 - quads(&polygons));
+}
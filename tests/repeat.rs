@@ -0,0 +1,83 @@
+extern crate genmesh;
+
+use cgmath::{InnerSpace, Matrix4, Vector3};
+use genmesh::generators::{Cube, Plane};
+use genmesh::{EmitTriangles, MapVertex, Polygon, Quad, Repeat, Vertex};
+
+fn matrix(m: Matrix4<f32>) -> [[f32; 4]; 4] {
+    m.into()
+}
+
+// the vertex normals lean the same way as the faces
+fn assert_facing_out(polygons: &[Polygon<Vertex>]) {
+    for p in polygons {
+        p.clone().emit_triangles(|t| {
+            let (a, b, c) = (
+                Vector3::from(t.x.pos),
+                Vector3::from(t.y.pos),
+                Vector3::from(t.z.pos),
+            );
+            let face = (b - a).cross(c - a);
+            assert!(face.dot(Vector3::from(t.x.normal)) > 0.);
+        });
+    }
+}
+
+#[test]
+fn linear_array() {
+    let step = matrix(Matrix4::from_translation(Vector3::new(3., 0., 0.)));
+    let fence = Cube::new().array(4, step);
+    assert_eq!(fence.len(), 4 * 6);
+    let polygons: Vec<Polygon<Vertex>> = fence.collect();
+    for (i, p) in polygons.iter().enumerate() {
+        let offset = (i / 6) as f32 * 3.;
+        let original = Cube::new().nth(i % 6).unwrap();
+        let moved = original.map_vertex(|v| Vertex {
+            pos: [v.pos.x + offset, v.pos.y, v.pos.z].into(),
+            normal: v.normal,
+        });
+        assert_eq!(*p, moved);
+    }
+
+    // copies that are mirrored are turned back around
+    let mirror = matrix(Matrix4::from_nonuniform_scale(-1., 1., 1.));
+    let polygons: Vec<Polygon<Vertex>> = Cube::new().array(3, mirror).collect();
+    assert_facing_out(&polygons);
+    assert_eq!(polygons[..6], polygons[12..]);
+
+    assert_eq!(Cube::new().array(0, step).count(), 0);
+}
+
+#[test]
+fn radial_array() {
+    // a blade sticking out along +x, facing +z
+    let blade = Plane::new().map(|p| {
+        p.map_vertex(|v| Vertex {
+            pos: [v.pos.x + 2., v.pos.y * 0.2, 0.].into(),
+            normal: v.normal,
+        })
+    });
+    let blades: Vec<Polygon<Vertex>> = blade.radial_array(4, [0., 0., 1.]).collect();
+    assert_eq!(blades.len(), 4);
+    assert_facing_out(&blades);
+    for (i, p) in blades.iter().enumerate() {
+        let Quad { x, y, z, w } = match p {
+            Polygon::PolyQuad(q) => q.clone(),
+            _ => panic!("not a quad"),
+        };
+        let center = (Vector3::from(x.pos)
+            + Vector3::from(y.pos)
+            + Vector3::from(z.pos)
+            + Vector3::from(w.pos))
+            / 4.;
+        let angle = i as f32 * std::f32::consts::FRAC_PI_2;
+        assert!((center - Vector3::new(2. * angle.cos(), 2. * angle.sin(), 0.)).magnitude() < 1e-5);
+        assert!((Vector3::from(x.normal) - Vector3::unit_z()).magnitude() < 1e-5);
+    }
+}
+
+#[test]
+#[should_panic(expected = "the axis of a radial array must not be zero")]
+fn radial_array_zero_axis() {
+    let _ = Plane::new().radial_array(4, [0., 0., 0.]);
+}
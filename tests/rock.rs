@@ -0,0 +1,79 @@
+extern crate genmesh;
+
+use cgmath::{InnerSpace, Vector3};
+use genmesh::generators::{IndexedPolygon, Rock, Roughness, SharedVertex};
+use genmesh::validate::validate;
+use genmesh::{indexed_volume, Neighbors, Position, Triangle, Triangulate, Vertex};
+
+fn indexed(rock: &Rock) -> (Vec<Position>, Vec<Triangle<usize>>) {
+    let positions = rock.shared_vertex_iter().map(|v| v.pos).collect();
+    let triangles = rock.indexed_polygon_iter().triangulate().collect();
+    (positions, triangles)
+}
+
+#[test]
+fn seeded() {
+    let a: Vec<Vertex> = Rock::new(2, 7).shared_vertex_iter().collect();
+    let b: Vec<Vertex> = Rock::new(2, 7).shared_vertex_iter().collect();
+    let c: Vec<Vertex> = Rock::new(2, 8).shared_vertex_iter().collect();
+    assert!(a == b);
+    assert!(a != c);
+}
+
+#[test]
+fn within_amplitude() {
+    let roughness = Roughness {
+        amplitude: 0.2,
+        ..Roughness::default()
+    };
+    let rock = Rock::with_roughness(3, 1, roughness);
+    let radii: Vec<f32> = rock
+        .shared_vertex_iter()
+        .map(|v| Vector3::from(v.pos).magnitude())
+        .collect();
+    assert!(radii.iter().all(|&r| (0.8..=1.2).contains(&r)));
+    // and actually lumpy
+    let min = radii.iter().cloned().fold(f32::MAX, f32::min);
+    let max = radii.iter().cloned().fold(0., f32::max);
+    assert!(max - min > 0.05);
+
+    let smooth = Rock::with_roughness(
+        3,
+        1,
+        Roughness {
+            amplitude: 0.,
+            ..Roughness::default()
+        },
+    );
+    for v in smooth.shared_vertex_iter() {
+        assert!((Vector3::from(v.pos).magnitude() - 1.).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn closed_with_outward_normals() {
+    let mut rock = Rock::new(3, 42);
+    assert_eq!(20 * 64, rock.len());
+    let forward: Vec<_> = rock.clone().collect();
+    assert_eq!(forward.last().cloned(), rock.next_back());
+    let rock = Rock::new(3, 42);
+    let (positions, triangles) = indexed(&rock);
+    assert!(validate(&positions, &triangles).is_valid());
+    let neighbors = Neighbors::new(Vec::<()>::new(), triangles.clone());
+    assert!(neighbors.boundary_loops().is_empty());
+    assert!(indexed_volume(&positions, &triangles) > 0.);
+
+    for v in rock.shared_vertex_iter() {
+        let n = Vector3::from(v.normal);
+        assert!((n.magnitude() - 1.).abs() < 1e-4);
+        assert!(n.dot(Vector3::from(v.pos)) > 0.);
+    }
+
+    // the polygon stream agrees with the indexed form
+    for (p, t) in rock.clone().zip(triangles) {
+        let vertices: Vec<Vertex> = p.as_vertices().collect();
+        assert_eq!(positions[t.x], vertices[0].pos);
+        assert_eq!(positions[t.y], vertices[1].pos);
+        assert_eq!(positions[t.z], vertices[2].pos);
+    }
+}
@@ -0,0 +1,81 @@
+extern crate genmesh;
+
+use genmesh::generators::{Cube, Plane};
+use genmesh::{SampleSurface, Triangle, Vertex};
+
+/// A small deterministic generator of numbers in [0, 1).
+fn lcg(seed: u64) -> impl FnMut() -> f32 {
+    let mut state = seed;
+    move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[test]
+fn sample_on_surface() {
+    let s = 1. / 3f32.sqrt();
+    let points = Cube::new().sample_surface(500, lcg(1));
+    assert_eq!(500, points.len());
+    for p in &points {
+        let c = [p.pos.x, p.pos.y, p.pos.z];
+        // on one of the faces, with that face's normal
+        let axis = (0..3)
+            .find(|&i| (c[i].abs() - s).abs() < 1e-5)
+            .expect("point off the surface");
+        let n = [p.normal.x, p.normal.y, p.normal.z];
+        assert!((n[axis] - c[axis].signum()).abs() < 1e-5);
+        assert!(c.iter().all(|x| x.abs() <= s + 1e-5));
+    }
+}
+
+#[test]
+fn sample_by_area() {
+    let v = |x: f32, y: f32| Vertex {
+        pos: [x, y, 0.].into(),
+        normal: [0., 0., 1.].into(),
+    };
+    // the second triangle is three times as big as the first
+    let triangles = vec![
+        Triangle::new(v(0., 0.), v(1., 0.), v(0., 1.)),
+        Triangle::new(v(2., 0.), v(5., 0.), v(2., 1.)),
+    ];
+    let points = triangles.into_iter().sample_surface(4000, lcg(7));
+    let big = points.iter().filter(|p| p.pos.x >= 2.).count() as f32;
+    assert!((big / 4000. - 0.75).abs() < 0.03, "{}", big);
+    assert!(points
+        .iter()
+        .all(|p| p.pos.x + p.pos.y <= 1. + 1e-5 || (p.pos.x - 2.) + 3. * p.pos.y <= 3. + 1e-4));
+}
+
+#[test]
+fn sample_empty() {
+    assert!(Plane::new().take(0).sample_surface(10, lcg(3)).is_empty());
+}
+
+#[test]
+fn poisson_disk_spacing() {
+    let r = 0.1;
+    let points = Plane::subdivide(4, 4).poisson_disk(r, lcg(11));
+    // a 2x2 square fits at most about 460 disks of that radius
+    assert!(points.len() > 150 && points.len() < 470, "{}", points.len());
+    for (i, a) in points.iter().enumerate() {
+        assert!(a.pos.z == 0. && a.pos.x.abs() <= 1. && a.pos.y.abs() <= 1.);
+        for b in &points[i + 1..] {
+            let d = ((a.pos.x - b.pos.x).powi(2) + (a.pos.y - b.pos.y).powi(2)).sqrt();
+            assert!(d >= r);
+        }
+    }
+
+    // no point is left without a neighbor nearby, there are no big gaps
+    for a in &points {
+        let nearest = points
+            .iter()
+            .filter(|b| b.pos != a.pos)
+            .map(|b| ((a.pos.x - b.pos.x).powi(2) + (a.pos.y - b.pos.y).powi(2)).sqrt())
+            .fold(f32::MAX, f32::min);
+        assert!(nearest < 2. * r);
+    }
+}
@@ -0,0 +1,79 @@
+extern crate genmesh;
+
+use cgmath::{InnerSpace, Matrix4, Vector3, Vector4};
+use genmesh::generators::{Cube, Plane};
+use genmesh::scatter::{Placement, Scatter};
+
+/// A small deterministic generator of numbers in [0, 1).
+fn lcg(seed: u64) -> impl FnMut() -> f32 {
+    let mut state = seed;
+    move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[test]
+fn count_and_density() {
+    let instances = Plane::new().scatter(Placement::Count(25), (1., 1.), lcg(1));
+    assert_eq!(25, instances.len());
+
+    // the plane is two units on each side
+    let instances = Plane::new().scatter(Placement::Density(10.), (1., 1.), lcg(2));
+    assert_eq!(40, instances.len());
+}
+
+#[test]
+fn aligned_to_normal() {
+    let instances = Cube::new().scatter(Placement::Count(200), (0.5, 2.), lcg(3));
+    let mut spins = Vec::new();
+    for i in &instances {
+        assert!(i.scale >= 0.5 && i.scale <= 2.);
+        let m = Matrix4::from(i.matrix());
+        let p = Vector3::from(i.position);
+
+        // the origin of the model is moved onto the surface
+        let origin = m * Vector4::new(0., 0., 0., 1.);
+        assert!((origin.truncate() - p).magnitude() < 1e-5);
+
+        // its z axis is scaled and turned along the face normal, which is
+        // the axis the point sticks out the most along
+        let z = (m * Vector4::new(0., 0., 1., 0.)).truncate();
+        assert!((z.magnitude() - i.scale).abs() < 1e-4);
+        let c = [p.x, p.y, p.z];
+        let axis = (0..3)
+            .max_by(|&a, &b| c[a].abs().total_cmp(&c[b].abs()))
+            .unwrap();
+        assert!((z[axis] / i.scale - c[axis].signum()).abs() < 1e-4);
+
+        let x = (m * Vector4::new(1., 0., 0., 0.)).truncate();
+        spins.push(x / i.scale);
+    }
+    // and spun about it, so the x axes point every which way
+    let mean = spins.iter().fold(Vector3::new(0., 0., 0.), |a, &b| a + b) / spins.len() as f32;
+    assert!(mean.magnitude() < 0.2);
+}
+
+#[test]
+fn spacing() {
+    let instances = Plane::new().scatter(Placement::Spacing(0.3), (1., 1.), lcg(4));
+    assert!(instances.len() > 20);
+    for (i, a) in instances.iter().enumerate() {
+        for b in &instances[i + 1..] {
+            let d = Vector3::from(a.position) - Vector3::from(b.position);
+            assert!(d.magnitude() >= 0.3);
+        }
+    }
+}
+
+#[test]
+fn no_area() {
+    let instances = std::iter::empty::<genmesh::Triangle<genmesh::Vertex>>().scatter(
+        Placement::Count(10),
+        (1., 1.),
+        lcg(5),
+    );
+    assert!(instances.is_empty());
+}
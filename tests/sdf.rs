@@ -0,0 +1,114 @@
+extern crate genmesh;
+
+use genmesh::generators::{IndexedPolygon, SharedVertex};
+use genmesh::sdf::{MarchingCubes, SurfaceNets};
+use genmesh::validate::validate;
+use genmesh::{Neighbors, Polygon, Position, Triangle};
+
+fn sphere(p: Position) -> f32 {
+    (p.x * p.x + p.y * p.y + p.z * p.z).sqrt() - 1.
+}
+
+fn triangles<G: IndexedPolygon<Polygon<usize>>>(mesh: &G) -> Vec<Triangle<usize>> {
+    mesh.indexed_polygon_iter()
+        .map(|p| match p {
+            Polygon::PolyTri(t) => t,
+            _ => panic!("marching cubes only emits triangles"),
+        })
+        .collect()
+}
+
+fn assert_outward(positions: &[Position], triangles: &[Triangle<usize>]) {
+    for t in triangles {
+        let p = |i: usize| [positions[i].x, positions[i].y, positions[i].z];
+        let (a, b, c) = (p(t.x), p(t.y), p(t.z));
+        let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+        let n = [
+            u[1] * v[2] - u[2] * v[1],
+            u[2] * v[0] - u[0] * v[2],
+            u[0] * v[1] - u[1] * v[0],
+        ];
+        assert!(n[0] * a[0] + n[1] * a[1] + n[2] * a[2] > 0.);
+    }
+}
+
+#[test]
+fn marching_sphere() {
+    let mesh = MarchingCubes::new(sphere, [-1.3; 3].into(), [1.2; 3].into(), [12, 14, 10]);
+    let positions: Vec<Position> = mesh.shared_vertex_iter().map(|v| v.pos).collect();
+    let triangles = triangles(&mesh);
+    assert!(triangles.len() > 100);
+
+    // closed and consistently wound
+    let report = validate(&positions, &triangles);
+    assert!(report.non_manifold_edges.is_empty());
+    assert!(report.inconsistent_winding.is_empty());
+    assert!(report.duplicate_faces.is_empty());
+    let neighbors = Neighbors::new(positions.clone(), triangles.clone());
+    assert!(neighbors.boundary_edges().is_empty());
+
+    for v in mesh.shared_vertex_iter() {
+        assert!(sphere(v.pos).abs() < 0.05);
+        let radial = v.pos.x * v.normal.x + v.pos.y * v.normal.y + v.pos.z * v.normal.z;
+        assert!(radial > 0.95);
+    }
+
+    assert_outward(&positions, &triangles);
+
+    assert_eq!(triangles.len(), mesh.count());
+}
+
+#[test]
+fn marching_saddle() {
+    // two spheres touching at a point stress the ambiguous faces
+    let field = |p: Position| {
+        let a = ((p.x - 0.5).powi(2) + p.y * p.y + p.z * p.z).sqrt() - 0.5;
+        let b = ((p.x + 0.5).powi(2) + p.y * p.y + p.z * p.z).sqrt() - 0.5;
+        let c = (p.x * p.x + (p.y - 0.5).powi(2) + (p.z - 0.5).powi(2)).sqrt() - 0.45;
+        a.min(b).min(c)
+    };
+    for &res in &[7, 8, 13] {
+        let mesh = MarchingCubes::new(field, [-1.1; 3].into(), [1.1; 3].into(), [res; 3]);
+        let positions: Vec<Position> = mesh.shared_vertex_iter().map(|v| v.pos).collect();
+        let triangles = triangles(&mesh);
+        let neighbors = Neighbors::new(positions.clone(), triangles.clone());
+        assert!(neighbors.boundary_edges().is_empty());
+        assert!(validate(&positions, &triangles)
+            .inconsistent_winding
+            .is_empty());
+    }
+
+    // nothing to extract
+    let empty = MarchingCubes::new(|_| 1., [0.; 3].into(), [1.; 3].into(), [4; 3]);
+    assert_eq!(0, empty.shared_vertex_count());
+}
+
+#[test]
+fn surface_nets_sphere() {
+    use genmesh::Triangulate;
+
+    let mesh = SurfaceNets::new(sphere, [-1.3; 3].into(), [1.2; 3].into(), [12, 14, 10]);
+    let positions: Vec<Position> = mesh.shared_vertex_iter().map(|v| v.pos).collect();
+    let quads: Vec<Polygon<usize>> = mesh.indexed_polygon_iter().collect();
+    assert!(quads.len() > 100);
+    assert!(quads.iter().all(|q| matches!(q, Polygon::PolyQuad(_))));
+
+    let triangles: Vec<Triangle<usize>> = quads.into_iter().triangulate().collect();
+    let report = validate(&positions, &triangles);
+    assert!(report.non_manifold_edges.is_empty());
+    assert!(report.inconsistent_winding.is_empty());
+    let neighbors = Neighbors::new(positions.clone(), triangles.clone());
+    assert!(neighbors.boundary_edges().is_empty());
+    assert_outward(&positions, &triangles);
+
+    for v in mesh.shared_vertex_iter() {
+        assert!(sphere(v.pos).abs() < 0.05);
+        let radial = v.pos.x * v.normal.x + v.pos.y * v.normal.y + v.pos.z * v.normal.z;
+        assert!(radial > 0.95);
+    }
+    // marching cubes puts a vertex on each crossed grid edge, where surface
+    // nets put a quad
+    let cubes = MarchingCubes::new(sphere, [-1.3; 3].into(), [1.2; 3].into(), [12, 14, 10]);
+    assert_eq!(cubes.shared_vertex_count(), mesh.indexed_polygon_count());
+}
@@ -0,0 +1,67 @@
+extern crate genmesh;
+
+use genmesh::generators::{Cube, IndexedPolygon, Plane, SharedVertex};
+use genmesh::{silhouette, weld, Line, Neighbors, Triangle, Triangulate, Vertex, View};
+
+fn welded_cube() -> Neighbors<Vertex> {
+    let cube = Cube::new();
+    let mut vertices: Vec<Vertex> = cube.shared_vertex_iter().collect();
+    let mut triangles: Vec<Triangle<usize>> = cube.indexed_polygon_iter().triangulate().collect();
+    weld(&mut vertices, &mut triangles, 1e-5);
+    Neighbors::new(vertices, triangles)
+}
+
+#[test]
+fn cube_from_above() {
+    let cube = welded_cube();
+    let edges: Vec<Line<usize>> =
+        silhouette(&cube, View::Direction([0., 0., -2.].into())).collect();
+    // the outline of the top face, without the diagonal splitting it
+    assert_eq!(edges.len(), 4);
+    for e in &edges {
+        let (a, b) = (cube.vertices[e.x].pos, cube.vertices[e.y].pos);
+        assert!(a.z > 0. && b.z > 0.);
+        assert!(a.x == b.x || a.y == b.y);
+        // counter clockwise around the top, seen from above
+        assert!(a.x * b.y - a.y * b.x > 0.);
+    }
+}
+
+#[test]
+fn cube_from_a_corner() {
+    let cube = welded_cube();
+    let edges: Vec<Line<usize>> = silhouette(&cube, View::Point([5., 5., 5.].into())).collect();
+    // a hexagon around the nearest corner, which no edge touches, and
+    // around the farthest one
+    assert_eq!(edges.len(), 6);
+    for e in &edges {
+        for i in [e.x, e.y] {
+            let p = cube.vertices[i].pos;
+            let signs = [p.x, p.y, p.z].iter().filter(|&&c| c > 0.).count();
+            assert!(signs == 1 || signs == 2);
+        }
+    }
+    // the edges chain into a single loop
+    for e in &edges {
+        assert_eq!(edges.iter().filter(|f| f.x == e.y).count(), 1);
+    }
+}
+
+#[test]
+fn open_boundaries() {
+    let plane = Plane::subdivide(3, 3);
+    let vertices: Vec<Vertex> = plane.shared_vertex_iter().collect();
+    let triangles: Vec<Triangle<usize>> = plane.indexed_polygon_iter().triangulate().collect();
+    let plane = Neighbors::new(vertices, triangles);
+
+    // the border of the plane outlines it from above, and nothing is
+    // facing a viewer below it
+    assert_eq!(
+        silhouette(&plane, View::Point([0., 0., 3.].into())).count(),
+        12
+    );
+    assert_eq!(
+        silhouette(&plane, View::Point([0., 0., -3.].into())).count(),
+        0
+    );
+}
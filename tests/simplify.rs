@@ -0,0 +1,67 @@
+extern crate genmesh;
+
+mod common;
+
+use genmesh::generators::{IcoSphere, Plane};
+use genmesh::simplify::{progressive_mesh, simplify};
+use genmesh::validate::validate;
+use genmesh::Neighbors;
+
+use common::indexed_positions;
+
+#[test]
+fn target_count() {
+    let (positions, triangles) = indexed_positions(&IcoSphere::subdivide(3), None);
+    assert_eq!(1280, triangles.len());
+
+    let simple = simplify(&positions, &triangles, 200, f32::INFINITY);
+    assert!(simple.len() <= 200 && simple.len() > 150);
+    assert!(validate(&positions, &simple).is_valid());
+    let neighbors = Neighbors::new(vec![(); positions.len()], simple.clone());
+    assert!(neighbors.boundary_edges().is_empty());
+
+    // the remaining vertices are still spread over the whole sphere
+    let mut used: Vec<usize> = simple.iter().flat_map(|t| vec![t.x, t.y, t.z]).collect();
+    used.sort_unstable();
+    used.dedup();
+    for axis in 0..3 {
+        let coord = |i: &usize| match axis {
+            0 => positions[*i].x,
+            1 => positions[*i].y,
+            _ => positions[*i].z,
+        };
+        assert!(used.iter().map(coord).fold(f32::MIN, f32::max) > 0.9);
+        assert!(used.iter().map(coord).fold(f32::MAX, f32::min) < -0.9);
+    }
+}
+
+#[test]
+fn error_threshold() {
+    // a flat plane costs nothing to simplify down to its corners
+    let (positions, triangles) = indexed_positions(&Plane::subdivide(8, 8), None);
+    let simple = simplify(&positions, &triangles, 0, 1e-6);
+    assert_eq!(2, simple.len());
+
+    // while nothing on a sphere is free
+    let (positions, triangles) = indexed_positions(&IcoSphere::subdivide(2), None);
+    assert_eq!(triangles, simplify(&positions, &triangles, 0, 0.));
+
+    // a loose threshold stops earlier than a generous one
+    let loose = simplify(&positions, &triangles, 0, 0.25);
+    let generous = simplify(&positions, &triangles, 0, 1.);
+    assert!(loose.len() < triangles.len());
+    assert!(generous.len() < loose.len());
+}
+
+#[test]
+fn lod_chain() {
+    let (positions, triangles) = indexed_positions(&IcoSphere::subdivide(3), None);
+    let mesh = progressive_mesh(&positions, triangles);
+    let mut previous = usize::MAX;
+    for &count in &[1000, 500, 100, 20] {
+        let level = mesh.triangles_for_count(count);
+        assert!(level.len() <= count);
+        assert!(level.len() < previous);
+        previous = level.len();
+    }
+}
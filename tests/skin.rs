@@ -0,0 +1,68 @@
+extern crate genmesh;
+
+use genmesh::generators::Cylinder;
+use genmesh::{skin_weights, Bone, Skin, SkinnedVertex, Vertices, MAX_INFLUENCES};
+
+#[test]
+fn bone() {
+    let bone = Bone::new([0., 0., 0.], [0., 0., 2.], 1.);
+    assert_eq!(bone.distance([0., 0.5, 1.]), 0.5);
+    assert_eq!(bone.distance([0., 0., -3.]), 3.);
+    assert_eq!(bone.influence([0., 0., 1.]), 1.);
+    assert_eq!(bone.influence([0., 0.5, 1.]), 0.5);
+    assert_eq!(bone.influence([0., 2., 1.]), 0.);
+}
+
+#[test]
+fn weights() {
+    let bones = [
+        Bone::new([0., 0., -1.], [0., 0., 0.], 0.5),
+        Bone::new([0., 0., 0.], [0., 0., 1.], 0.5),
+    ];
+    // on the joint, both bones pull as much
+    assert_eq!(
+        skin_weights([0., 0.25, 0.], &bones),
+        ([0, 1, 0, 0], [0.5, 0.5, 0., 0.])
+    );
+    assert_eq!(
+        skin_weights([0., 0.25, -0.5], &bones),
+        ([0, 0, 0, 0], [1., 0., 0., 0.])
+    );
+    // out of reach, the closest bone takes it all
+    assert_eq!(
+        skin_weights([0., 5., 0.8], &bones),
+        ([1, 0, 0, 0], [1., 0., 0., 0.])
+    );
+    assert_eq!(skin_weights([0., 0., 0.], &[]), ([0; 4], [0.; 4]));
+
+    // no more than four bones, the strongest ones
+    let fan: Vec<Bone> = (0..6)
+        .map(|k| Bone::new([0., 0., 0.], [1., k as f32 * 0.1, 0.], 1.))
+        .collect();
+    let (joints, weights) = skin_weights([0.5, 0.5, 0.], &fan);
+    assert_eq!(joints, [5, 4, 3, 2]);
+    assert!((weights.iter().sum::<f32>() - 1.).abs() < 1e-6);
+    assert!(weights.windows(2).all(|w| w[0] >= w[1]));
+    assert_eq!(MAX_INFLUENCES, weights.len());
+}
+
+#[test]
+fn cylinder() {
+    let bones = [
+        Bone::new([0., 0., -1.], [0., 0., 0.], 1.5),
+        Bone::new([0., 0., 0.], [0., 0., 1.], 1.5),
+    ];
+    let skinned: Vec<SkinnedVertex> = Cylinder::new(8).skin(&bones).vertices().collect();
+    assert!(!skinned.is_empty());
+    for v in skinned {
+        let total: f32 = v.weights.iter().sum();
+        assert!((total - 1.).abs() < 1e-6);
+        let top = v.weights[v.joints.iter().position(|&j| j == 1).unwrap()];
+        // the upper bone pulls harder further up
+        match v.pos.z {
+            z if z > 0.5 => assert!(top > 0.5),
+            z if z < -0.5 => assert!(top < 0.5),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,68 @@
+extern crate genmesh;
+
+mod common;
+
+use genmesh::generators::{Cube, Torus};
+use genmesh::{slice, Position};
+
+use common::indexed_positions;
+
+/// Signed area of a polyline, projected along one of the axes.
+fn area(ring: &[Position], x: impl Fn(Position) -> f32, y: impl Fn(Position) -> f32) -> f32 {
+    let mut area = 0.;
+    for i in 0..ring.len() {
+        let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+        area += x(a) * y(b) - x(b) * y(a);
+    }
+    area * 0.5
+}
+
+#[test]
+fn slice_cube() {
+    let (positions, triangles) = indexed_positions(&Cube::new(), Some(1e-5));
+    let s = 1. / 3f32.sqrt();
+    let slices = slice(
+        &positions,
+        &triangles,
+        [0., 0., 1.].into(),
+        &[-0.5, 0., 0.5, 2.],
+    );
+    assert_eq!(4, slices.len());
+    for loops in &slices[..3] {
+        assert_eq!(1, loops.len());
+        let a = area(&loops[0], |p| p.x, |p| p.y);
+        assert!((a - 4. * s * s).abs() < 1e-4, "{}", a);
+    }
+    assert!(slices[0][0].iter().all(|p| (p.z + 0.5).abs() < 1e-6));
+    assert!(slices[3].is_empty());
+}
+
+#[test]
+fn slice_through_vertices() {
+    // the planes go through the corners of the cube
+    let (positions, triangles) = indexed_positions(&Cube::new(), Some(1e-5));
+    let s = 1. / 3f32.sqrt();
+    let slices = slice(&positions, &triangles, [0., 0., 1.].into(), &[-s, s]);
+    for loops in &slices {
+        for ring in loops {
+            for i in 0..ring.len() {
+                assert_ne!(ring[i], ring[(i + 1) % ring.len()]);
+            }
+        }
+    }
+}
+
+#[test]
+fn slice_torus_holes() {
+    let (positions, triangles) = indexed_positions(&Torus::new(1., 0.25, 16, 8), Some(1e-5));
+    let slices = slice(&positions, &triangles, [0., 1., 0.].into(), &[0.]);
+    assert_eq!(2, slices[0].len());
+    let mut areas: Vec<f32> = slices[0]
+        .iter()
+        .map(|ring| area(ring, |p| p.z, |p| p.x))
+        .collect();
+    areas.sort_by(|a, b| a.total_cmp(b));
+    // the hole winds the other way around
+    assert!(areas[0] < 0. && areas[1] > 0.);
+    assert!(areas[1] > -areas[0]);
+}
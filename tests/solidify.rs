@@ -0,0 +1,63 @@
+extern crate genmesh;
+
+use std::collections::HashSet;
+
+use genmesh::generators::{IndexedPolygon, Plane, SharedVertex, SphereUv};
+use genmesh::validate::validate;
+use genmesh::{indexed_volume, solidify, weld, Position, Triangle, Triangulate, Vertex};
+
+fn assert_closed(vertices: &[Vertex], triangles: &[Triangle<usize>]) -> f32 {
+    let mut vertices = vertices.to_vec();
+    let mut triangles = triangles.to_vec();
+    weld(&mut vertices, &mut triangles, 1e-6);
+    let positions: Vec<Position> = vertices.iter().map(|v| v.pos).collect();
+    assert!(validate(&positions, &triangles).is_valid());
+    let edges: HashSet<(usize, usize)> = triangles
+        .iter()
+        .flat_map(|t| [(t.x, t.y), (t.y, t.z), (t.z, t.x)])
+        .collect();
+    assert!(edges.iter().all(|&(a, b)| edges.contains(&(b, a))));
+    indexed_volume(&positions, &triangles)
+}
+
+#[test]
+fn solid_plane() {
+    let plane = Plane::subdivide(3, 2);
+    let mut vertices: Vec<Vertex> = plane.shared_vertex_iter().collect();
+    let mut triangles: Vec<Triangle<usize>> = plane.indexed_polygon_iter().triangulate().collect();
+    assert_eq!(solidify(&mut vertices, &mut triangles, 0.25), 1);
+    // the back, and a quad for each of the ten edges around
+    assert_eq!(triangles.len(), 2 * 12 + 2 * 10);
+    assert!((assert_closed(&vertices, &triangles) - 4. * 0.25).abs() < 1e-5);
+    for v in &vertices[12..24] {
+        assert_eq!(v.pos.z, -0.25);
+        assert_eq!(v.normal.z, -1.);
+    }
+
+    // a negative thickness grows the solid in front
+    let mut vertices: Vec<Vertex> = plane.shared_vertex_iter().collect();
+    let mut triangles: Vec<Triangle<usize>> = plane.indexed_polygon_iter().triangulate().collect();
+    solidify(&mut vertices, &mut triangles, -0.25);
+    assert!(vertices.iter().all(|v| v.pos.z >= 0.));
+    // and the surface now faces back
+    assert!((assert_closed(&vertices, &triangles) - 4. * 0.25).abs() < 1e-5);
+    assert!(vertices[..12].iter().all(|v| v.normal.z == -1.));
+}
+
+#[test]
+fn solid_shell() {
+    // the bottom half of a sphere, as a bowl
+    let sphere = SphereUv::new(32, 16);
+    let mut vertices: Vec<Vertex> = sphere.shared_vertex_iter().collect();
+    let all: Vec<Triangle<usize>> = sphere.indexed_polygon_iter().triangulate().collect();
+    let mut triangles: Vec<Triangle<usize>> = all
+        .into_iter()
+        .filter(|t| [t.x, t.y, t.z].iter().all(|&i| vertices[i].pos.z <= 1e-6))
+        .collect();
+    weld(&mut vertices, &mut triangles, 1e-6);
+    assert_eq!(solidify(&mut vertices, &mut triangles, 0.1), 1);
+    let volume = assert_closed(&vertices, &triangles);
+    // about the difference between two half spheres
+    let shell = 2. / 3. * std::f32::consts::PI * (1. - 0.9f32.powi(3));
+    assert!((volume - shell).abs() < 0.05 * shell);
+}
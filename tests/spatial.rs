@@ -0,0 +1,147 @@
+extern crate genmesh;
+
+use cgmath::{InnerSpace, Vector3};
+use genmesh::analytic::Aabb;
+use genmesh::spatial::{KdTree, SpatialGrid};
+use genmesh::Position;
+
+fn lcg(seed: u64) -> impl FnMut() -> f32 {
+    let mut state = seed;
+    move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+fn random_points(n: usize, seed: u64) -> Vec<Position> {
+    let mut rng = lcg(seed);
+    (0..n)
+        .map(|_| [rng() * 10. - 5., rng() * 10. - 5., rng() * 2.].into())
+        .collect()
+}
+
+fn distance(a: Position, b: Position) -> f32 {
+    (Vector3::from(a) - Vector3::from(b)).magnitude()
+}
+
+#[test]
+fn grid_matches_brute_force() {
+    let points = random_points(500, 1);
+    let grid = SpatialGrid::from_points(0.7, points.iter().cloned());
+    assert_eq!(grid.len(), 500);
+
+    for (i, q) in random_points(50, 2).into_iter().enumerate() {
+        // radii smaller and larger than the cells
+        let radius = 0.2 + i as f32 * 0.05;
+        let expected: Vec<usize> = (0..points.len())
+            .filter(|&j| distance(points[j], q) <= radius)
+            .collect();
+        assert_eq!(grid.query_radius(q, radius), expected);
+
+        let (nearest, d) = grid.nearest(q).unwrap();
+        let best = points
+            .iter()
+            .map(|&p| distance(p, q))
+            .fold(f32::INFINITY, f32::min);
+        assert_eq!(d, best);
+        assert_eq!(distance(points[nearest], q), best);
+    }
+
+    // far away from all of the points
+    let far = [100., -40., 3.].into();
+    let (nearest, _) = grid.nearest(far).unwrap();
+    let best = (0..points.len())
+        .min_by(|&a, &b| distance(points[a], far).total_cmp(&distance(points[b], far)))
+        .unwrap();
+    assert_eq!(nearest, best);
+}
+
+#[test]
+fn grid_far_query_with_small_cells() {
+    // the query is ten billion cells away from the points
+    let points: Vec<Position> = random_points(20, 3)
+        .into_iter()
+        .map(|p| [p.x * 1e-3, p.y * 1e-3, p.z * 1e-3].into())
+        .collect();
+    let grid = SpatialGrid::from_points(1e-3, points.iter().cloned());
+    let far = [1e7, 0., 0.].into();
+    let (nearest, _) = grid.nearest(far).unwrap();
+    let best = (0..points.len())
+        .min_by(|&a, &b| distance(points[a], far).total_cmp(&distance(points[b], far)))
+        .unwrap();
+    assert_eq!(distance(points[nearest], far), distance(points[best], far));
+    assert!(grid.nearest([f32::MAX, -f32::MAX, 0.].into()).is_some());
+}
+
+#[test]
+fn empty_and_inserted() {
+    let mut grid = SpatialGrid::new(1.);
+    assert!(grid.is_empty());
+    assert_eq!(grid.nearest([0., 0., 0.].into()), None);
+    assert!(grid.query_radius([0., 0., 0.].into(), 5.).is_empty());
+
+    assert_eq!(grid.insert([0.5, 0.5, 0.5].into()), 0);
+    assert_eq!(grid.insert([0.5, 0.5, 0.5].into()), 1);
+    assert_eq!(grid.insert([-3., 0., 0.].into()), 2);
+    // ties go to the first point inserted
+    assert_eq!(grid.nearest([0., 0., 0.].into()).unwrap().0, 0);
+    assert_eq!(grid.query_radius([0., 0., 0.].into(), 1.), vec![0, 1]);
+    assert_eq!(grid.query_radius([0., 0., 0.].into(), 3.), vec![0, 1, 2]);
+    assert_eq!(grid.points()[2], [-3., 0., 0.].into());
+}
+
+#[test]
+fn kd_tree_matches_brute_force() {
+    let mut points = random_points(400, 3);
+    // some duplicates, and points all on one plane
+    points.extend(points[..20].to_vec());
+    points.extend((0..50).map(|i| Position::from([i as f32 * 0.1, 1., 7.])));
+    let tree = KdTree::build(&points);
+    assert_eq!(tree.len(), points.len());
+
+    for (i, q) in random_points(40, 4).into_iter().enumerate() {
+        let mut expected: Vec<(usize, f32)> = (0..points.len())
+            .map(|j| (j, distance(points[j], q)))
+            .collect();
+        expected.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        let k = 1 + i % 7;
+        let found = tree.k_nearest(q, k);
+        assert_eq!(found.len(), k);
+        for (f, e) in found.iter().zip(&expected) {
+            assert_eq!(f.0, e.0);
+            assert!((f.1 - e.1).abs() < 1e-5);
+        }
+        assert_eq!(tree.nearest(q).unwrap().0, expected[0].0);
+
+        let radius = 0.3 + i as f32 * 0.1;
+        let mut within: Vec<usize> = expected
+            .iter()
+            .filter(|e| {
+                (Vector3::from(points[e.0]) - Vector3::from(q)).magnitude2() <= radius * radius
+            })
+            .map(|e| e.0)
+            .collect();
+        within.sort_unstable();
+        assert_eq!(tree.query_radius(q, radius), within);
+
+        let aabb = Aabb::new(
+            [q.x - 1., q.y - 0.5, q.z - radius].into(),
+            [q.x + 0.5, q.y + 1., q.z + radius].into(),
+        );
+        let inside: Vec<usize> = (0..points.len())
+            .filter(|&j| aabb.contains(points[j]))
+            .collect();
+        assert_eq!(tree.query_aabb(&aabb), inside);
+    }
+
+    assert_eq!(
+        tree.k_nearest([0., 0., 0.].into(), 1000).len(),
+        points.len()
+    );
+    let empty = KdTree::build(&[]);
+    assert!(empty.is_empty());
+    assert_eq!(empty.nearest([0., 0., 0.].into()), None);
+    assert!(empty.query_radius([0., 0., 0.].into(), 1.).is_empty());
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "stl")]
+
+extern crate genmesh;
+
+use genmesh::generators::Cube;
+use genmesh::stl::{read_stl, write_stl_ascii, write_stl_binary};
+use genmesh::{MapToVertices, Triangle, Triangulate, Vertex};
+
+fn assert_same(expected: &[Triangle<Vertex>], read: &[Triangle<Vertex>]) {
+    assert_eq!(expected.len(), read.len());
+    for (a, b) in expected.iter().zip(read) {
+        for (u, v) in [(a.x, b.x), (a.y, b.y), (a.z, b.z)].iter() {
+            assert!((u.pos.x - v.pos.x).abs() < 1e-6);
+            assert!((u.pos.y - v.pos.y).abs() < 1e-6);
+            assert!((u.pos.z - v.pos.z).abs() < 1e-6);
+            // the cube faces are flat, so facet and vertex normals agree
+            assert!((u.normal.x - v.normal.x).abs() < 1e-6);
+            assert!((u.normal.y - v.normal.y).abs() < 1e-6);
+            assert!((u.normal.z - v.normal.z).abs() < 1e-6);
+        }
+    }
+}
+
+#[test]
+fn stl_binary_round_trip() {
+    let triangles: Vec<Triangle<Vertex>> = Cube::new().triangulate().collect();
+    let mut data = Vec::new();
+    write_stl_binary(&mut data, triangles.clone()).unwrap();
+    assert_eq!(84 + 50 * 12, data.len());
+    assert_same(&triangles, &read_stl(&data[..]).unwrap());
+}
+
+#[test]
+fn stl_ascii_round_trip() {
+    let triangles: Vec<Triangle<Vertex>> = Cube::new().triangulate().collect();
+    let mut data = Vec::new();
+    // positions alone are enough to write a file
+    let positions = triangles.iter().cloned().vertex(|v| v.pos);
+    write_stl_ascii(&mut data, "cube", positions).unwrap();
+    let text = String::from_utf8(data.clone()).unwrap();
+    assert!(text.starts_with("solid cube\n"));
+    assert!(text.trim_end().ends_with("endsolid cube"));
+    assert_eq!(12, text.matches("endfacet").count());
+    assert_same(&triangles, &read_stl(&data[..]).unwrap());
+}
+
+#[test]
+fn stl_missing_normals() {
+    let text = "solid t\nfacet normal 0 0 0\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nvertex 0 1 0\nendloop\nendfacet\nendsolid t\n";
+    let triangles = read_stl(text.as_bytes()).unwrap();
+    assert_eq!(1, triangles.len());
+    assert_eq!(1., triangles[0].x.normal.z);
+
+    assert!(read_stl(&b"solid t\nfacet normal 0 0 1\nouter loop\nvertex 0 0\n"[..]).is_err());
+    assert!(read_stl(&b"hello"[..]).is_err());
+}
@@ -0,0 +1,74 @@
+extern crate genmesh;
+
+use genmesh::generators::{Cube, IndexedPolygon, Plane};
+use genmesh::{
+    line_strip_indices, triangle_strip_indices, IndexType, Lines, NGon, Polygon, Quad, Triangle,
+};
+
+// expand a strip buffer back into triangles, flipping the odd ones, with
+// one list of triangles per strip
+fn unstrip(indices: &[u16]) -> Vec<Vec<[usize; 3]>> {
+    let mut strips = Vec::new();
+    for strip in indices.split(|&i| i == u16::MAX) {
+        let strip: Vec<usize> = strip.iter().map(|&i| i as usize).collect();
+        let mut triangles = Vec::new();
+        for k in 2..strip.len() {
+            let (a, b, c) = (strip[k - 2], strip[k - 1], strip[k]);
+            triangles.push(if k % 2 == 0 { [a, b, c] } else { [b, a, c] });
+        }
+        strips.push(triangles);
+    }
+    strips
+}
+
+// whether the triangles of a strip cover `polygon`, wound the same way
+fn covers(triangles: &[[usize; 3]], polygon: &[usize]) -> bool {
+    let n = polygon.len();
+    let at = |v: usize| polygon.iter().position(|&p| p == v).unwrap();
+    triangles.len() == n - 2
+        && triangles.iter().all(|t| {
+            let (a, b, c) = (at(t[0]), at(t[1]), at(t[2]));
+            // going once around the polygon
+            (b + n - a) % n + (c + n - b) % n + (a + n - c) % n == n
+        })
+}
+
+#[test]
+fn triangle_strips() {
+    let cube = Cube::new();
+    let strips = triangle_strip_indices(cube.indexed_polygon_iter(), u16::MAX).unwrap();
+    // four indices per face, and five restarts
+    assert_eq!(strips.len(), 6 * 4 + 5);
+    for (triangles, p) in unstrip(&strips).iter().zip(cube.indexed_polygon_iter()) {
+        let polygon: Vec<usize> = p.as_vertices().collect();
+        assert!(covers(triangles, &polygon));
+    }
+
+    let hexagon: NGon<usize> = (0..6).collect();
+    let strips = triangle_strip_indices(vec![Polygon::PolyNGon(hexagon)], u16::MAX).unwrap();
+    assert_eq!(strips, [0, 1, 5, 2, 4, 3]);
+    assert!(covers(&unstrip(&strips)[0], &[0, 1, 2, 3, 4, 5]));
+}
+
+#[test]
+fn line_strips() {
+    let quads = vec![Quad::new(0usize, 1, 2, 3), Quad::new(3, 2, 4, 5)];
+    let strips = line_strip_indices(quads.into_iter().lines(), u32::MAX).unwrap();
+    assert_eq!(strips, [0, 1, 2, 3, 0, u32::MAX, 3, 2, 4, 5, 3]);
+
+    let plane = Plane::subdivide(2, 2);
+    let strips: Vec<usize> =
+        line_strip_indices(plane.indexed_polygon_iter().lines(), IndexType::MAX).unwrap();
+    assert_eq!(strips.iter().filter(|&&i| i == usize::MAX).count(), 3);
+}
+
+#[test]
+fn overflow() {
+    let big = vec![Polygon::PolyTri(Triangle::new(0usize, 1, 70_000))];
+    assert!(triangle_strip_indices(big.clone(), u16::MAX).is_none());
+    assert!(triangle_strip_indices(big, u32::MAX).is_some());
+
+    // the restart value can not be used as an index
+    let edge = vec![Polygon::PolyTri(Triangle::new(0usize, 1, 65_535))];
+    assert!(triangle_strip_indices(edge, u16::MAX).is_none());
+}
@@ -0,0 +1,163 @@
+extern crate genmesh;
+
+use std::collections::{HashMap, HashSet};
+
+use cgmath::{InnerSpace, Vector3};
+use genmesh::generators::{Cube, IcoSphere, Plane};
+use genmesh::Polygon::{PolyNGon, PolyQuad, PolyTri};
+use genmesh::{EmitTriangles, Polygon, Subdivide, Vertex, Vertices};
+
+fn edges(p: &Polygon<Vertex>) -> Vec<f32> {
+    let v: Vec<Vertex> = p.clone().as_vertices().collect();
+    (0..v.len())
+        .map(|k| (Vector3::from(v[(k + 1) % v.len()].pos) - Vector3::from(v[k].pos)).magnitude())
+        .collect()
+}
+
+// welds the corners by position, and checks that every edge is used
+// once in each direction, which fails on any crack or T-junction
+fn assert_closed(polygons: &[Polygon<Vertex>]) {
+    let mut ids: HashMap<[u32; 3], usize> = HashMap::new();
+    let mut directed = HashSet::new();
+    for p in polygons {
+        let v: Vec<usize> = p
+            .clone()
+            .as_vertices()
+            .map(|v| {
+                let key = [v.pos.x, v.pos.y, v.pos.z].map(|c| (c + 0.).to_bits());
+                let next = ids.len();
+                *ids.entry(key).or_insert(next)
+            })
+            .collect();
+        for k in 0..v.len() {
+            assert!(directed.insert((v[k], v[(k + 1) % v.len()])));
+        }
+    }
+    for &(a, b) in &directed {
+        assert!(directed.contains(&(b, a)));
+    }
+}
+
+#[test]
+fn split_plane() {
+    let polygons: Vec<Polygon<Vertex>> = Plane::new().subdivide_to_edge_length(0.5).collect();
+    assert_eq!(polygons.len(), 16);
+    for p in &polygons {
+        assert!(matches!(p, Polygon::PolyQuad(_)));
+        for e in edges(p) {
+            assert!((e - 0.5).abs() < 1e-6);
+        }
+    }
+
+    // a long and thin quad is only cut across
+    let polygons: Vec<Polygon<Vertex>> = Plane::new().subdivide_to_edge_length(1.5).collect();
+    assert_eq!(polygons.len(), 4);
+    let polygons: Vec<Polygon<Vertex>> = Plane::subdivide(1, 4)
+        .subdivide_to_edge_length(1.5)
+        .collect();
+    assert_eq!(polygons.len(), 8);
+}
+
+#[test]
+fn split_without_cracks() {
+    let polygons: Vec<Polygon<Vertex>> = Cube::new().subdivide_to_edge_length(0.3).collect();
+    assert!(polygons.iter().all(|p| edges(p).iter().all(|&e| e <= 0.3)));
+    assert_closed(&polygons);
+
+    let polygons: Vec<Polygon<Vertex>> = IcoSphere::new().subdivide_to_edge_length(0.2).collect();
+    assert!(polygons.iter().all(|p| edges(p).iter().all(|&e| e <= 0.2)));
+    assert_closed(&polygons);
+    // the new vertices have unit normals
+    let mut count = 0;
+    for p in &polygons {
+        p.clone().emit_triangles(|t| {
+            count += 1;
+            for v in [t.x, t.y, t.z] {
+                assert!((Vector3::from(v.normal).magnitude() - 1.).abs() < 1e-5);
+            }
+        });
+    }
+    assert!(count > 20 * 16);
+}
+
+#[test]
+fn subdivide_counts() {
+    let cube: Vec<_> = Cube::new().collect();
+    assert_eq!(cube.clone().into_iter().subdivide(0).count(), 6);
+    let twice: Vec<_> = cube.into_iter().subdivide(2).collect();
+    assert_eq!(twice.len(), 6 * 16);
+    assert!(twice.iter().all(|p| matches!(p, PolyQuad(_))));
+    assert_closed(&twice);
+
+    let sphere: Vec<_> = IcoSphere::new().collect();
+    let split: Vec<_> = sphere.into_iter().subdivide(3).collect();
+    assert_eq!(split.len(), 20 * 64);
+    assert!(split.iter().all(|p| matches!(p, PolyTri(_))));
+    assert_closed(&split);
+}
+
+#[test]
+fn subdivide_ngon() {
+    let hexagon: Vec<Vertex> = (0..6)
+        .map(|k| {
+            let (s, c) = (k as f32 * std::f32::consts::PI / 3.).sin_cos();
+            Vertex {
+                pos: [c, s, 0.].into(),
+                normal: [0., 0., 1.].into(),
+            }
+        })
+        .collect();
+    let split: Vec<_> = std::iter::once(PolyNGon(hexagon.into_iter().collect()))
+        .subdivide(1)
+        .collect();
+    // a quad at each corner, meeting at the center
+    assert_eq!(split.len(), 6);
+    for p in &split {
+        match p {
+            PolyQuad(q) => assert!(Vector3::from(q.z.pos).magnitude() < 1e-6),
+            _ => panic!("expected quads"),
+        }
+    }
+    assert_eq!(split.into_iter().subdivide(1).count(), 24);
+}
+
+#[test]
+fn subdivide_onto_sphere() {
+    let project = |v: Vertex| {
+        let p = Vector3::from(v.pos).normalize();
+        Vertex {
+            pos: p.into(),
+            normal: p.into(),
+        }
+    };
+    let sphere: Vec<_> = IcoSphere::new()
+        .subdivide_with(2, project)
+        .vertices()
+        .collect();
+    assert_eq!(sphere.len(), 20 * 16 * 3);
+    for v in sphere {
+        let r = Vector3::from(v.pos).magnitude();
+        assert!((r - 1.).abs() < 1e-5);
+    }
+
+    // the same points as the icosphere subdivided as often
+    let mut expected: Vec<_> = IcoSphere::subdivide(2).vertices().map(|v| v.pos).collect();
+    let mut split: Vec<_> = IcoSphere::new()
+        .subdivide_with(2, project)
+        .vertices()
+        .map(|v| v.pos)
+        .collect();
+    let key = |p: &mint::Vector3<f32>| {
+        (
+            (p.x * 1e4).round() as i32,
+            (p.y * 1e4).round() as i32,
+            (p.z * 1e4).round() as i32,
+        )
+    };
+    expected.sort_by_key(key);
+    split.sort_by_key(key);
+    assert_eq!(
+        expected.iter().map(key).collect::<Vec<_>>(),
+        split.iter().map(key).collect::<Vec<_>>()
+    );
+}
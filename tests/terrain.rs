@@ -0,0 +1,155 @@
+extern crate genmesh;
+
+use genmesh::generators::{
+    ChunkEdge, HydraulicErosion, Plane, SharedVertex, Terrain, TerrainChunks,
+};
+use genmesh::noise::Fbm;
+use genmesh::{Mesh, TerrainVertex};
+
+fn terrain() -> Terrain {
+    let noise = Fbm {
+        frequency: 0.5,
+        seed: 11,
+        ..Fbm::default()
+    };
+    Terrain::new(8., 6., 24, 18, &noise, 2.)
+}
+
+fn steepest(terrain: &Terrain) -> f32 {
+    let h = terrain.heights();
+    let spacing = 8. / 24.;
+    (0..h.len())
+        .filter(|k| k % 25 != 24)
+        .map(|k| (h[k + 1] - h[k]).abs() / spacing)
+        .fold(0., f32::max)
+}
+
+#[test]
+fn heights_from_noise() {
+    let noise = Fbm::default();
+    let terrain = Terrain::new(4., 1., 8, 2, &noise, 3.);
+    let plane = Plane::with_size(4., 1., 8, 2);
+    assert_eq!(terrain.shared_vertex_count(), plane.shared_vertex_count());
+    for (i, v) in terrain.shared_vertex_iter().enumerate() {
+        let p = plane.shared_vertex(i).pos;
+        assert_eq!([v.pos.x, v.pos.y], [p.x, p.y]);
+        assert_eq!(v.pos.z, 3. * noise.sample2([p.x, p.y]));
+        assert!((-1. ..=1.).contains(&v.normal.x));
+        assert!(v.normal.z > 0.);
+    }
+
+    // without noise it is a plane
+    let flat = Terrain::new(4., 1., 8, 2, &noise, 0.);
+    for v in flat.shared_vertex_iter() {
+        assert_eq!(v.pos.z, 0.);
+        assert_eq!(v.normal, [0., 0., 1.].into());
+    }
+}
+
+#[test]
+fn thermal_erosion_eases_slopes() {
+    let before = terrain();
+    let total: f32 = before.heights().iter().sum();
+    let after = before.clone().with_thermal_erosion(100, 0.5);
+    assert!(steepest(&after) < steepest(&before));
+    assert!(steepest(&after) < 0.6);
+    let eroded: f32 = after.heights().iter().sum();
+    assert!((eroded - total).abs() < 1e-2);
+}
+
+#[test]
+fn hydraulic_erosion_moves_ground() {
+    let before = terrain();
+    let total: f32 = before.heights().iter().sum();
+    let after = before
+        .clone()
+        .with_hydraulic_erosion(HydraulicErosion::default());
+    let changed = before
+        .heights()
+        .iter()
+        .zip(after.heights())
+        .filter(|(a, b)| (*a - *b).abs() > 1e-4)
+        .count();
+    assert!(changed > before.heights().len() / 2);
+    // the ground is only moved around
+    let eroded: f32 = after.heights().iter().sum();
+    assert!((eroded - total).abs() < 1e-2);
+}
+
+#[test]
+fn flow_gathers_in_valleys() {
+    let terrain = terrain();
+    let vertices: Vec<TerrainVertex> = terrain.shared_vertex_iter().collect();
+    assert!(vertices.iter().all(|v| (0. ..=1.).contains(&v.flow)));
+    assert!(vertices.iter().any(|v| v.flow == 1.));
+    // nothing drains into the highest point
+    let top = vertices
+        .iter()
+        .max_by(|a, b| a.pos.z.total_cmp(&b.pos.z))
+        .unwrap();
+    assert_eq!(top.flow, 0.);
+    // the wettest vertex is lower than most
+    let wettest = vertices
+        .iter()
+        .max_by(|a, b| a.flow.total_cmp(&b.flow))
+        .unwrap();
+    let below = vertices.iter().filter(|v| v.pos.z < wettest.pos.z).count();
+    assert!(below < vertices.len() / 4);
+}
+
+#[test]
+fn flow_reaches_the_mesh() {
+    let terrain = terrain();
+    let mesh = Mesh::from_generator(&terrain);
+    assert_eq!(mesh.custom_count(), 1);
+    let flow: Vec<f32> = terrain.shared_vertex_iter().map(|v| v.flow).collect();
+    assert_eq!(mesh.custom, flow);
+    // position, normal and flow for each vertex
+    assert_eq!(mesh.interleaved().len(), 7 * mesh.vertex_count());
+}
+
+#[test]
+fn chunks_meet_at_seams() {
+    let noise = Fbm {
+        frequency: 0.3,
+        ..Fbm::default()
+    };
+    let chunks = TerrainChunks::new(4., 6, noise, 1.5);
+    for &(x, y) in &[(0, 0), (-1, 3), (5, -2)] {
+        let here = chunks.chunk(x, y);
+        let right = chunks.chunk(x + 1, y);
+        let top = chunks.chunk(x, y + 1);
+        let pairs = [
+            (here.edge_indices(ChunkEdge::Right), &right, ChunkEdge::Left),
+            (here.edge_indices(ChunkEdge::Top), &top, ChunkEdge::Bottom),
+        ];
+        for (edge, other, side) in pairs.iter() {
+            let matching = other.edge_indices(*side);
+            assert_eq!(edge.len(), 7);
+            for (&a, &b) in edge.iter().zip(&matching) {
+                // the very same vertex, normal included
+                assert_eq!(here.shared_vertex(a), other.shared_vertex(b));
+            }
+        }
+        let corner = here.shared_vertex(0).pos;
+        assert_eq!([corner.x, corner.y], [x as f32 * 4., y as f32 * 4.]);
+    }
+
+    // built lazily, in rows
+    let addresses: Vec<(i32, i32)> = chunks.chunks(-1..1, 0..2).map(|(a, _)| a).collect();
+    assert_eq!(addresses, [(-1, 0), (0, 0), (-1, 1), (0, 1)]);
+}
+
+#[test]
+fn flat_chunks_are_planes() {
+    let chunk = TerrainChunks::flat(2., 3).chunk(-1, -1);
+    let plane = Plane::with_size(2., 2., 3, 3);
+    assert_eq!(chunk.shared_vertex_count(), plane.shared_vertex_count());
+    for (i, v) in chunk.shared_vertex_iter().enumerate() {
+        let p = plane.shared_vertex(i);
+        assert!((v.pos.x - (p.pos.x - 1.)).abs() < 1e-6);
+        assert!((v.pos.y - (p.pos.y - 1.)).abs() < 1e-6);
+        assert_eq!(v.pos.z, 0.);
+        assert_eq!(v.normal, p.normal);
+    }
+}
@@ -1,8 +1,8 @@
 extern crate genmesh;
 
 use genmesh::{
-    EmitTriangles, Indexer, LruIndexer, MapToVertices, Quad, Triangle, Triangulate, Vertex,
-    Vertices,
+    EmitTriangles, Indexer, LruIndexer, MapToVertices, Polygon, Quad, Triangle, Triangulate,
+    Vertex, Vertices,
 };
 
 use genmesh::generators::Plane;
@@ -16,9 +16,9 @@ fn quad_vertex() {
         Quad::new(true, false, true, false),
     ];
 
-    let transformed = input.iter().map(|x| x.clone()).vertex(|v| v % 2 != 0);
+    let transformed = input.iter().cloned().vertex(|v| v % 2 != 0);
 
-    for (x, y) in transformed.zip(output.iter().map(|x| x.clone())) {
+    for (x, y) in transformed.zip(output.iter().cloned()) {
         assert_eq!(x, y);
     }
 }
@@ -34,11 +34,11 @@ fn quad_vertex_two_stages() {
 
     let transformed = input
         .iter()
-        .map(|x| x.clone())
+        .cloned()
         .vertex(|v| v as u8)
         .vertex(|v| v % 2 != 0);
 
-    for (x, y) in transformed.zip(output.iter().map(|x| x.clone())) {
+    for (x, y) in transformed.zip(output.iter().cloned()) {
         assert_eq!(x, y);
     }
 }
@@ -51,10 +51,9 @@ fn quad_poly_simple() {
 
     let transformed = input
         .iter()
-        .map(|x| x.clone())
         .map(|v| Quad::new(0isize, v.y as isize, v.z as isize, 0));
 
-    for (x, y) in transformed.zip(output.iter().map(|x| x.clone())) {
+    for (x, y) in transformed.zip(output.iter().cloned()) {
         assert_eq!(x, y);
     }
 }
@@ -68,9 +67,9 @@ fn triangle_vertex() {
         Triangle::new(true, false, true),
     ];
 
-    let transformed = input.iter().map(|x| x.clone()).vertex(|v| v % 2 != 0);
+    let transformed = input.iter().cloned().vertex(|v| v % 2 != 0);
 
-    for (x, y) in transformed.zip(output.iter().map(|x| x.clone())) {
+    for (x, y) in transformed.zip(output.iter().cloned()) {
         assert_eq!(x, y);
     }
 }
@@ -86,11 +85,11 @@ fn triangle_vertex_two_stages() {
 
     let transformed = input
         .iter()
-        .map(|x| x.clone())
+        .cloned()
         .vertex(|v| v as u8)
         .vertex(|v| v % 2 != 0);
 
-    for (x, y) in transformed.zip(output.iter().map(|x| x.clone())) {
+    for (x, y) in transformed.zip(output.iter().cloned()) {
         assert_eq!(x, y);
     }
 }
@@ -103,10 +102,9 @@ fn triangle_poly_simple() {
 
     let transformed = input
         .iter()
-        .map(|x| x.clone())
         .map(|v| Triangle::new(0isize, v.y as isize, v.z as isize));
 
-    for (x, y) in transformed.zip(output.iter().map(|x| x.clone())) {
+    for (x, y) in transformed.zip(output.iter().cloned()) {
         assert_eq!(x, y);
     }
 }
@@ -132,7 +130,10 @@ fn to_triangles() {
 #[test]
 fn plane() {
     let mut plane = Plane::new();
-    let a = plane.next().unwrap();
+    let a = match plane.next().unwrap() {
+        Polygon::PolyQuad(a) => a,
+        p => panic!("expected a quad, got {:?}", p),
+    };
 
     assert_eq!(a.x.pos, [-1f32, -1., 0.].into());
     assert_eq!(a.y.pos, [1f32, -1., 0.].into());
@@ -196,7 +197,7 @@ fn emit_lines() {
     assert_eq!(Line::new(3, 0), lines[3]);
 
     let quads = [Quad::new(0i8, 1, 2, 3), Quad::new(4i8, 5, 6, 7)];
-    let lines: Vec<Line<i8>> = quads.iter().map(|&x| x).lines().collect();
+    let lines: Vec<Line<i8>> = quads.iter().cloned().lines().collect();
 
     assert_eq!(8, lines.len());
     assert_eq!(Line::new(0, 1), lines[0]);
@@ -204,3 +205,248 @@ fn emit_lines() {
     assert_eq!(Line::new(2, 3), lines[2]);
     assert_eq!(Line::new(3, 0), lines[3]);
 }
+
+#[test]
+fn exploded_view() {
+    use genmesh::generators::{Cube, IndexedPolygon, SharedVertex};
+    use genmesh::{explode_parts, ExplodeMode, Polygon};
+
+    let cube = Cube::new();
+    let part = |x: f32| {
+        let vertices: Vec<Vertex> = cube
+            .shared_vertex_iter()
+            .map(|mut v| {
+                v.pos.x += x;
+                v
+            })
+            .collect();
+        let polygons: Vec<Polygon<usize>> = cube.indexed_polygon_iter().collect();
+        (vertices, polygons)
+    };
+    let parts = vec![part(-2.), part(0.), part(2.)];
+
+    let exploded = explode_parts(&parts, ExplodeMode::Radial, 1.);
+    assert_eq!(3, exploded.len());
+    assert_eq!(parts[1], exploded[1]);
+    assert_eq!(exploded[0].0[0].pos.x, parts[0].0[0].pos.x - 2.);
+    assert_eq!(exploded[2].0[0].pos.x, parts[2].0[0].pos.x + 2.);
+
+    let along_y = explode_parts(&parts, ExplodeMode::Axis([0., 1., 0.].into()), 1.);
+    assert_eq!(parts, along_y);
+
+    // an empty part neither moves nor shifts the center of the others
+    let apart = vec![part(4.), part(6.)];
+    let mut with_empty = apart.clone();
+    with_empty.push((Vec::new(), Vec::new()));
+    let exploded = explode_parts(&apart, ExplodeMode::Radial, 1.);
+    let exploded_with_empty = explode_parts(&with_empty, ExplodeMode::Radial, 1.);
+    assert_eq!(&exploded[..], &exploded_with_empty[..2]);
+    assert_eq!(exploded[0].0[0].pos.x, apart[0].0[0].pos.x - 1.);
+    assert!(exploded_with_empty[2].0.is_empty());
+}
+
+#[test]
+fn progressive_ico_sphere() {
+    use genmesh::generators::{IcoSphere, IndexedPolygon, ProgressiveIcoSphere, SharedVertex};
+
+    let lod = ProgressiveIcoSphere::new(3);
+    assert_eq!(4, lod.level_count());
+    let all: Vec<Vertex> = lod.shared_vertex_iter().collect();
+
+    for level in 0..lod.level_count() {
+        let sphere = IcoSphere::subdivide(level);
+        let view = lod.level(level);
+        assert_eq!(sphere.shared_vertex_count(), view.shared_vertex_count());
+        assert_eq!(sphere.indexed_polygon_count(), view.indexed_polygon_count());
+
+        // the vertices of each level are a prefix of the shared buffer
+        let prefix: Vec<Vertex> = view.shared_vertex_iter().collect();
+        assert_eq!(&all[..prefix.len()], &prefix[..]);
+
+        let expected: Vec<Polygon<usize>> = sphere.indexed_polygon_iter().collect();
+        let indices: Vec<Polygon<usize>> = view.indexed_polygon_iter().collect();
+        assert_eq!(expected, indices);
+    }
+}
+
+#[test]
+fn unique_lines() {
+    use genmesh::generators::{Cube, IndexedPolygon, SharedVertex};
+    use genmesh::{Line, UniqueLines};
+
+    let quads = [Quad::new(0u8, 1, 2, 3), Quad::new(1u8, 0, 4, 5)];
+    let lines: Vec<Line<u8>> = quads.iter().cloned().unique_lines().collect();
+    assert_eq!(7, lines.len());
+    assert_eq!(Line::new(0, 1), lines[0]);
+    assert_eq!(Line::new(0, 4), lines[4]);
+
+    // a plane with shared corners has 12 edges in a 2x2 grid
+    let plane = Plane::subdivide(2, 2);
+    assert_eq!(12, plane.indexed_polygon_iter().unique_lines().count());
+
+    // the cube duplicates its corners per face, so nothing is shared between
+    // faces; welding the indices by position leaves the 12 edges of a cube.
+    let cube = Cube::new();
+    let positions: Vec<_> = cube.shared_vertex_iter().map(|v| v.pos).collect();
+    let weld = |i: usize| positions.iter().position(|p| *p == positions[i]).unwrap();
+    assert_eq!(24, cube.indexed_polygon_iter().unique_lines().count());
+    assert_eq!(
+        12,
+        cube.indexed_polygon_iter()
+            .vertex(weld)
+            .unique_lines()
+            .count()
+    );
+}
+
+#[test]
+fn scene_builder() {
+    use genmesh::generators::{Cube, IndexedPolygon, SharedVertex};
+    use genmesh::SceneBuilder;
+
+    let identity = [
+        [1., 0., 0., 0.],
+        [0., 1., 0., 0.],
+        [0., 0., 1., 0.],
+        [0., 0., 0., 1.],
+    ];
+    let moved = [
+        [2., 0., 0., 0.],
+        [0., 1., 0., 0.],
+        [0., 0., 1., 0.],
+        [5., 0., 0., 1.],
+    ];
+    let mirrored = [
+        [-1., 0., 0., 0.],
+        [0., 1., 0., 0.],
+        [0., 0., 1., 0.],
+        [0., 0., 0., 1.],
+    ];
+    let cube = Cube::new();
+    let scene = SceneBuilder::new()
+        .add(&cube, identity, 3)
+        .add(&cube, moved, 1)
+        .add(&cube, mirrored, 3)
+        .build();
+
+    assert_eq!(3 * 24, scene.vertices.len());
+    assert_eq!(3 * 12, scene.triangles.len());
+    assert_eq!(scene.triangles.len(), scene.materials.len());
+    assert_eq!(&[3; 12][..], &scene.materials[..12]);
+
+    // the second copy indexes into its own vertices, which are moved
+    let t = &scene.triangles[12];
+    assert!(t.x >= 24 && t.y >= 24 && t.z >= 24);
+    for (src, dst) in cube.shared_vertex_iter().zip(&scene.vertices[24..48]) {
+        assert_eq!(src.pos.x * 2. + 5., dst.pos.x);
+        assert_eq!(src.normal, dst.normal);
+    }
+
+    // the mirrored copy keeps facing outward
+    let first: Vec<_> = cube.indexed_polygon_iter().triangulate().collect();
+    let last = &scene.triangles[24];
+    assert_eq!(
+        (first[0].x + 48, first[0].z + 48, first[0].y + 48),
+        (last.x, last.y, last.z)
+    );
+    let flipped = scene.vertices[48];
+    assert_eq!(-cube.shared_vertex(0).normal.x, flipped.normal.x);
+
+    let parts = scene.split_by_material();
+    assert_eq!(2, parts.len());
+    assert_eq!(1, parts[0].material);
+    assert_eq!(24, parts[0].vertices.len());
+    assert_eq!(12, parts[0].triangles.len());
+    assert_eq!(3, parts[1].material);
+    assert_eq!(48, parts[1].vertices.len());
+    assert_eq!(24, parts[1].triangles.len());
+}
+
+#[test]
+fn mixed_polygon_streams() {
+    use genmesh::{Line, Lines};
+
+    let ngon = |start: u8, n: u8| Polygon::PolyNGon((start..start + n).collect());
+    // large polygons overflow the buffers of the iterators, which must
+    // still keep everything in order
+    let polygons = vec![
+        ngon(0, 7),
+        Polygon::PolyQuad(Quad::new(7, 8, 9, 10)),
+        ngon(11, 6),
+        Polygon::PolyTri(Triangle::new(17, 18, 19)),
+    ];
+
+    let vertices: Vec<u8> = polygons.clone().into_iter().vertices().collect();
+    assert_eq!(vertices, (0..20).collect::<Vec<u8>>());
+
+    let lines: Vec<_> = polygons.clone().into_iter().lines().collect();
+    let at = lines.iter().position(|l| *l == Line::new(7, 8)).unwrap();
+    assert!(lines[..at].iter().all(|l| l.x < 7 && l.y < 7));
+    assert_eq!(
+        lines[at..at + 4],
+        [
+            Line::new(7, 8),
+            Line::new(8, 9),
+            Line::new(9, 10),
+            Line::new(10, 7)
+        ]
+    );
+    assert_eq!(lines[lines.len() - 1], Line::new(19, 17));
+
+    let triangles: Vec<_> = polygons.into_iter().triangulate().collect();
+    assert_eq!(triangles.len(), 5 + 2 + 4 + 1);
+    assert_eq!(triangles[4], Triangle::new(0, 5, 6));
+    assert_eq!(triangles[5], Triangle::new(7, 8, 9));
+    assert_eq!(triangles[7], Triangle::new(11, 12, 13));
+    assert_eq!(triangles[11], Triangle::new(17, 18, 19));
+}
+
+#[test]
+fn ngon_constructors() {
+    use genmesh::NGon;
+    use std::iter::FromIterator;
+
+    let mut pushed = NGon::with_capacity(12);
+    for i in 0..12u32 {
+        pushed.add_vertex(i);
+    }
+    let collected = NGon::from_iter(0..12u32);
+    assert_eq!(pushed, collected);
+    assert_eq!(collected.verts.len(), 12);
+
+    let mut triangles = Vec::new();
+    collected.emit_triangles(|t| triangles.push(t));
+    assert_eq!(triangles.len(), 10);
+    assert_eq!(triangles[9], Triangle::new(0, 10, 11));
+}
+
+#[test]
+fn const_ngon() {
+    use genmesh::{ConstNGon, EmitLines, FlipWinding, Line, MapVertex, NGon};
+
+    let pentagon = ConstNGon::new([0u8, 1, 2, 3, 4]);
+
+    let mut triangles = Vec::new();
+    pentagon.emit_triangles(|t| triangles.push(t));
+    assert_eq!(
+        triangles,
+        vec![
+            Triangle::new(0, 1, 2),
+            Triangle::new(0, 2, 3),
+            Triangle::new(0, 3, 4)
+        ]
+    );
+
+    let mut lines = Vec::new();
+    pentagon.emit_lines(|l| lines.push(l));
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[4], Line::new(4, 0));
+
+    assert_eq!(pentagon.map_vertex(|v| v * 2).verts, [0, 2, 4, 6, 8]);
+    assert_eq!(pentagon.flip_winding().verts, [0, 4, 3, 2, 1]);
+    assert_eq!(NGon::from(pentagon), (0..5).collect::<NGon<u8>>());
+
+    let vertices: Vec<u8> = vec![pentagon, pentagon].into_iter().vertices().collect();
+    assert_eq!(vertices.len(), 10);
+    assert_eq!(vertices[5..], [0, 1, 2, 3, 4]);
+}
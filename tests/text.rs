@@ -0,0 +1,93 @@
+#![cfg(feature = "text")]
+
+extern crate genmesh;
+
+use cgmath::{InnerSpace, Vector3};
+use genmesh::generators::{IndexedPolygon, SharedVertex, TextMesh};
+use genmesh::text::Face;
+use genmesh::{indexed_volume, EmitTriangles, Position, Triangle, Triangulate};
+
+fn volume(text: &TextMesh) -> f32 {
+    let positions: Vec<Position> = text.shared_vertex_iter().map(|v| v.pos).collect();
+    let triangles: Vec<Triangle<usize>> = text.indexed_polygon_iter().triangulate().collect();
+    indexed_volume(&positions, &triangles)
+}
+
+// every vertex normal leans the same way as the triangles around it
+fn assert_normals_agree(text: &TextMesh) {
+    for poly in text.indexed_polygon_iter() {
+        poly.emit_triangles(|t| {
+            let p = |i: usize| Vector3::from(text.shared_vertex(i).pos);
+            let face = (p(t.y) - p(t.x)).cross(p(t.z) - p(t.x));
+            for i in [t.x, t.y, t.z] {
+                let n = Vector3::from(text.shared_vertex(i).normal);
+                assert!((n.magnitude() - 1.).abs() < 1e-4);
+                assert!(n.dot(face) > 0., "{:?} against {:?}", n, face);
+            }
+        });
+    }
+}
+
+#[test]
+fn extruded_contours() {
+    // a square frame, with the hole wound the same way as the outline
+    let outline = [[0., 0.], [4., 0.], [4., 4.], [0., 4.]];
+    let hole = [[1., 1.], [3., 1.], [3., 3.], [1., 3.]];
+    let frame = TextMesh::from_contours(&[outline, hole], 0.5, 0.);
+    // two triangulated caps, and a side quad for each edge
+    assert_eq!(frame.len(), 2 * 8 + 8);
+    assert!((volume(&frame) - 12. * 0.5).abs() < 1e-4);
+    assert_normals_agree(&frame);
+
+    // the winding of the contours doesn't matter
+    let mut reversed = outline;
+    reversed.reverse();
+    let flipped = TextMesh::from_contours(&[hole, reversed], 0.5, 0.);
+    assert!((volume(&flipped) - 12. * 0.5).abs() < 1e-4);
+
+    // a bevel adds a band of quads at the front and the back; the frame
+    // inset by d has an area of 12 - 24d, so each bevel cuts away 12b²
+    let bevelled = TextMesh::from_contours(&[outline, hole], 0.5, 0.1);
+    assert_eq!(bevelled.len(), 2 * 8 + 3 * 8);
+    assert!((volume(&bevelled) - (12. * 0.5 - 2. * 12. * 0.1 * 0.1)).abs() < 1e-4);
+    assert_normals_agree(&bevelled);
+    for v in bevelled.shared_vertex_iter() {
+        assert!(v.pos.x >= 0. && v.pos.x <= 4. && v.pos.z >= 0. && v.pos.z <= 0.5);
+    }
+
+    // flat text only has the front
+    let flat = TextMesh::from_contours(&[outline, hole], 0., 0.1);
+    assert_eq!(flat.len(), 8);
+    assert!(flat
+        .shared_vertex_iter()
+        .all(|v| v.pos.z == 0. && v.normal.z == 1.));
+}
+
+#[test]
+fn font_glyphs() {
+    // only run where a system font is around
+    let data = match std::fs::read("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf") {
+        Ok(data) => data,
+        Err(_) => return,
+    };
+    let face = Face::parse(&data, 0).unwrap();
+    let text = TextMesh::new(&face, "Ob", 0.2, 0.02);
+    assert!(text.len() > 0);
+    assert!(volume(&text) > 0.);
+    assert_normals_agree(&text);
+
+    // the letters are about an em high, and side by side
+    let max_x = text
+        .shared_vertex_iter()
+        .map(|v| v.pos.x)
+        .fold(0f32, f32::max);
+    assert!(max_x > 1. && max_x < 1.5);
+
+    // a second line goes below the first
+    let lines = TextMesh::new(&face, "O\nO", 0.2, 0.);
+    let min_y = lines
+        .shared_vertex_iter()
+        .map(|v| v.pos.y)
+        .fold(f32::MAX, f32::min);
+    assert!(min_y < -1.);
+}
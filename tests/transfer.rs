@@ -0,0 +1,61 @@
+extern crate genmesh;
+
+use cgmath::{InnerSpace, Vector3};
+use genmesh::generators::{IcoSphere, IndexedPolygon, Plane, SharedVertex};
+use genmesh::{transfer_attributes, Position, TexturedVertex, Triangle, Triangulate, Vertex};
+
+#[test]
+fn normals_from_a_finer_sphere() {
+    let fine = IcoSphere::subdivide(4);
+    let source: Vec<Vertex> = fine.shared_vertex_iter().collect();
+    let triangles: Vec<Triangle<usize>> = fine.indexed_polygon_iter().triangulate().collect();
+    let target: Vec<Vertex> = IcoSphere::subdivide(1).shared_vertex_iter().collect();
+
+    let transferred = transfer_attributes(&source, &triangles, &target);
+    assert_eq!(transferred.len(), target.len());
+    for (t, v) in transferred.iter().zip(&target) {
+        let t = t.as_ref().unwrap();
+        assert!(t.distance < 1e-3);
+        let n = Vector3::from(t.normal.unwrap());
+        assert!(n.dot(v.normal.into()) > 0.999);
+        assert!((t.barycentric.iter().sum::<f32>() - 1.).abs() < 1e-5);
+        assert!(t.uv.is_none() && t.color.is_none() && t.custom.is_empty());
+    }
+}
+
+#[test]
+fn uvs_across_a_gap() {
+    let plane = Plane::subdivide(4, 4);
+    let source: Vec<TexturedVertex> = plane
+        .shared_vertex_iter()
+        .map(|v| TexturedVertex {
+            pos: v.pos,
+            normal: v.normal,
+            uv: [v.pos.x * 0.5 + 0.5, v.pos.y * 0.5 + 0.5],
+        })
+        .collect();
+    let triangles: Vec<Triangle<usize>> = plane.indexed_polygon_iter().triangulate().collect();
+    let target: Vec<Position> = vec![
+        [0.3, -0.7, 0.25].into(),
+        [-0.45, 0.1, -0.5].into(),
+        // off the side of the plane, at its edge
+        [2., 0.5, 0.].into(),
+    ];
+
+    let transferred = transfer_attributes(&source, &triangles, &target);
+    let uv = |i: usize| transferred[i].as_ref().unwrap().uv.unwrap();
+    let close = |a: [f32; 2], b: [f32; 2]| (a[0] - b[0]).abs() < 1e-5 && (a[1] - b[1]).abs() < 1e-5;
+    assert!(close(uv(0), [0.65, 0.15]));
+    assert!(close(uv(1), [0.275, 0.55]));
+    assert!(close(uv(2), [1., 0.75]));
+    assert!((transferred[0].as_ref().unwrap().distance - 0.25).abs() < 1e-6);
+    assert!((transferred[2].as_ref().unwrap().distance - 1.).abs() < 1e-6);
+    assert_eq!(
+        transferred[1].as_ref().unwrap().normal,
+        Some([0., 0., 1.].into())
+    );
+
+    // nothing to transfer from
+    let none = transfer_attributes(&source, &[], &target);
+    assert!(none.iter().all(|t| t.is_none()));
+}
@@ -0,0 +1,110 @@
+extern crate genmesh;
+
+use std::f32::consts::PI;
+
+use cgmath::{InnerSpace, Vector3};
+use genmesh::validate::validate;
+use genmesh::{
+    expand_lsystem, indexed_volume, weld, Neighbors, Polygon, Position, Triangle, Triangulate,
+    Turtle, Vertex, Vertices,
+};
+
+// weld the tubes into an indexed mesh
+fn indexed(polygons: Vec<Polygon<Vertex>>) -> (Vec<Vertex>, Vec<Triangle<usize>>) {
+    let mut vertices: Vec<Vertex> = polygons.into_iter().triangulate().vertices().collect();
+    let mut triangles: Vec<Triangle<usize>> = (0..vertices.len() / 3)
+        .map(|i| Triangle::new(3 * i, 3 * i + 1, 3 * i + 2))
+        .collect();
+    weld(&mut vertices, &mut triangles, 1e-5);
+    (vertices, triangles)
+}
+
+fn assert_closed(vertices: &[Vertex], triangles: &[Triangle<usize>]) {
+    let positions: Vec<Position> = vertices.iter().map(|v| v.pos).collect();
+    assert!(validate(&positions, triangles).is_valid());
+    let neighbors = Neighbors::new(Vec::<()>::new(), triangles.to_vec());
+    assert!(neighbors.boundary_loops().is_empty());
+    assert!(indexed_volume(&positions, triangles) > 0.);
+}
+
+#[test]
+fn lsystem() {
+    assert_eq!("F+F+F+F", expand_lsystem("F", &[('F', "F+F")], 2));
+    assert_eq!(
+        "F[+X]F[-X]+X",
+        expand_lsystem("X", &[('X', "F[+X]F[-X]+X")], 1)
+    );
+    assert_eq!("ab", expand_lsystem("ab", &[], 3));
+}
+
+#[test]
+fn straight_tube() {
+    let turtle = Turtle::new(0.5).forward(1.).forward(2.);
+    let skeleton = turtle.skeleton();
+    assert_eq!(2, skeleton.len());
+    assert_eq!(Position::from([0., 0., 3.]), skeleton[1].y);
+
+    let polygons: Vec<Polygon<Vertex>> = turtle.tubes(6).collect();
+    // two lengths of six quads, and two caps
+    assert_eq!(14, polygons.len());
+    for v in polygons.iter().cloned().vertices() {
+        let r = Vector3::new(v.pos.x, v.pos.y, 0.).magnitude();
+        assert!((r - 0.5).abs() < 1e-5);
+    }
+
+    let (vertices, triangles) = indexed(polygons);
+    assert_closed(&vertices, &triangles);
+    // the rings are shared, and the caps have their own vertices
+    let positions: Vec<Position> = vertices.iter().map(|v| v.pos).collect();
+    let volume = indexed_volume(&positions, &triangles);
+    let hexagon = 3. * 3f32.sqrt() / 2. * 0.25;
+    assert!((volume - hexagon * 3.).abs() < 1e-4);
+}
+
+#[test]
+fn bends_without_cracks() {
+    let turtle = Turtle::new(0.2)
+        .forward(1.)
+        .yaw(0.6)
+        .forward(1.)
+        .pitch(-0.4)
+        .forward(1.)
+        .yaw(-PI / 2.)
+        .forward(1.);
+    let (vertices, triangles) = indexed(turtle.tubes(12).collect());
+    assert_closed(&vertices, &triangles);
+}
+
+#[test]
+fn branches() {
+    let commands = expand_lsystem("X", &[('X', "F[+X][-X]!FX"), ('F', "FF")], 3);
+    let turtle = Turtle::new(0.1).interpret(&commands, 0.2, 0.4, 0.8);
+    let count = commands.chars().filter(|&c| c == 'F').count();
+    assert_eq!(count, turtle.skeleton().len());
+
+    let polygons: Vec<Polygon<Vertex>> = turtle.tubes(5).collect();
+    assert!(polygons.len() >= count * 5);
+    for v in polygons.into_iter().vertices() {
+        assert!((Vector3::from(v.normal).magnitude() - 1.).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn jump_and_pop() {
+    let turtle = Turtle::new(0.1)
+        .push()
+        .jump(1.)
+        .forward(1.)
+        .pop()
+        .pitch(PI / 2.)
+        .forward(1.);
+    let skeleton = turtle.skeleton();
+    assert_eq!(2, skeleton.len());
+    assert_eq!(Position::from([0., 0., 1.]), skeleton[0].x);
+    assert_eq!(Position::from([0., 0., 0.]), skeleton[1].x);
+    // pitching up turns the heading towards y
+    assert!((skeleton[1].y.y - 1.).abs() < 1e-5);
+    // both segments are tubes closed at both ends
+    let (vertices, triangles) = indexed(turtle.tubes(4).collect());
+    assert_closed(&vertices, &triangles);
+}
@@ -0,0 +1,205 @@
+extern crate genmesh;
+
+use genmesh::generators::{Cube, Cylinder, IcoSphere, IndexedPolygon, Plane, SharedVertex};
+use genmesh::uv::{lightmap_atlas, Atlas, ProjectUv, Projection};
+use genmesh::{
+    weld, Axis, Polygon, Position, TexturedVertex, Triangle, Triangulate, Vertex, Vertices,
+};
+
+fn close(a: [f32; 2], b: [f32; 2]) -> bool {
+    (a[0] - b[0]).abs() < 1e-5 && (a[1] - b[1]).abs() < 1e-5
+}
+
+#[test]
+fn planar() {
+    let z = Projection::Planar { axis: Axis::Z };
+    assert_eq!(z.project([1., 2., 3.], [0., 0., 1.]), [1., 2.]);
+    let x = Projection::Planar { axis: Axis::X };
+    assert_eq!(x.project([1., 2., 3.], [0., 0., 1.]), [2., 3.]);
+    let y = Projection::Planar { axis: Axis::Y };
+    assert_eq!(y.project([1., 2., 3.], [0., 0., 1.]), [3., 1.]);
+
+    for v in Plane::new().project_uv(z).vertices() {
+        assert_eq!(v.uv, [v.pos.x, v.pos.y]);
+    }
+}
+
+#[test]
+fn box_faces() {
+    let cube: Vec<Polygon<TexturedVertex>> = Cube::new().project_uv(Projection::Box).collect();
+    assert_eq!(cube.len(), 6);
+    for face in cube {
+        let vertices: Vec<TexturedVertex> = face.as_vertices().collect();
+        // every face is projected without distortion, so its corners are
+        // the corners of the face, counter clockwise
+        let half = 1. / 3f32.sqrt();
+        for v in &vertices {
+            assert!(v.uv.iter().all(|c| (c.abs() - half).abs() < 1e-6));
+        }
+        let area: f32 = (0..4)
+            .map(|k| {
+                let (a, b) = (vertices[k].uv, vertices[(k + 1) % 4].uv);
+                a[0] * b[1] - a[1] * b[0]
+            })
+            .sum();
+        assert!((area - 8. / 3.).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn box_uses_face_normal() {
+    // a triangle leaning towards z, with vertex normals that disagree
+    let v = |x: f32, y: f32, z: f32, n: [f32; 3]| Vertex {
+        pos: [x, y, z].into(),
+        normal: n.into(),
+    };
+    let t = Polygon::PolyTri(Triangle::new(
+        v(0., 0., 0., [1., 0., 0.]),
+        v(1., 0., 0.2, [0., 1., 0.]),
+        v(0., 1., 0.2, [0., 0., 1.]),
+    ));
+    let boxed: Vec<TexturedVertex> = std::iter::once(t.clone())
+        .project_uv(Projection::Box)
+        .vertices()
+        .collect();
+    let uvs: Vec<[f32; 2]> = boxed.iter().map(|v| v.uv).collect();
+    assert_eq!(uvs, vec![[0., 0.], [1., 0.], [0., 1.]]);
+
+    let triplanar: Vec<[f32; 2]> = std::iter::once(t)
+        .project_uv(Projection::Triplanar)
+        .vertices()
+        .map(|v| v.uv)
+        .collect();
+    assert_eq!(triplanar, vec![[0., 0.], [0.2, 1.], [0., 1.]]);
+}
+
+#[test]
+fn cylindrical_seam() {
+    let projection = Projection::Cylindrical { axis: Axis::Z };
+    assert!(close(
+        projection.project([0., 1., 5.], [0., 0., 0.]),
+        [0.25, 5.]
+    ));
+    assert!(close(
+        projection.project([-1., 0., 0.], [0., 0., 0.]),
+        [0.5, 0.]
+    ));
+
+    let sides: Vec<Polygon<TexturedVertex>> = Cylinder::new(8)
+        .project_uv(projection)
+        .filter(|p| match p {
+            Polygon::PolyQuad(q) => q.x.normal.z == 0.,
+            _ => false,
+        })
+        .collect();
+    assert_eq!(sides.len(), 8);
+    for side in sides {
+        let us: Vec<f32> = side.as_vertices().map(|v| v.uv[0]).collect();
+        let min = us.iter().cloned().fold(f32::INFINITY, f32::min);
+        let max = us.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        // one eighth of the way around, never the whole texture backwards
+        assert!((max - min - 0.125).abs() < 1e-5);
+        assert!(max <= 1. + 1e-6);
+    }
+}
+
+#[test]
+fn spherical() {
+    let projection = Projection::Spherical { axis: Axis::Z };
+    assert!(close(
+        projection.project([0., 0., 2.], [0., 0., 1.]),
+        [0., 1.]
+    ));
+    assert!(close(
+        projection.project([0., -3., 0.], [0., 0., 1.]),
+        [0.75, 0.5]
+    ));
+
+    let sphere: Vec<Polygon<TexturedVertex>> =
+        IcoSphere::subdivide(2).project_uv(projection).collect();
+    for t in sphere {
+        let vertices: Vec<TexturedVertex> = t.as_vertices().collect();
+        let us: Vec<f32> = vertices.iter().map(|v| v.uv[0]).collect();
+        let min = us.iter().cloned().fold(f32::INFINITY, f32::min);
+        let max = us.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        assert!(max - min < 0.25);
+        for v in vertices {
+            let p: Position = v.pos;
+            let r = (p.x * p.x + p.y * p.y + p.z * p.z).sqrt();
+            let expected = 0.5 + (p.z / r).asin() / std::f32::consts::PI;
+            assert!((v.uv[1] - expected).abs() < 1e-5);
+        }
+    }
+}
+
+fn uv_area(atlas: &Atlas, t: &Triangle<usize>) -> f32 {
+    let [a, b, c] = [t.x, t.y, t.z].map(|i| atlas.uvs[i]);
+    ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])) / 2.
+}
+
+// checks that the charts are apart and inside the square, and that no
+// triangle is flipped
+fn assert_packed(atlas: &Atlas, padding: f32) {
+    let count = atlas.charts.iter().max().map_or(0, |&c| c + 1);
+    let mut boxes = vec![([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]); count];
+    for (t, &c) in atlas.triangles.iter().zip(&atlas.charts) {
+        assert!(uv_area(atlas, t) > 0.);
+        for i in [t.x, t.y, t.z] {
+            let (lo, hi) = &mut boxes[c];
+            for (k, &x) in atlas.uvs[i].iter().enumerate() {
+                lo[k] = lo[k].min(x);
+                hi[k] = hi[k].max(x);
+            }
+        }
+    }
+    for (i, a) in boxes.iter().enumerate() {
+        for k in 0..2 {
+            assert!(a.0[k] >= padding / 2. - 1e-6 && a.1[k] <= 1. - padding / 2. + 1e-6);
+        }
+        for b in &boxes[i + 1..] {
+            let gap = (0..2)
+                .map(|k| (b.0[k] - a.1[k]).max(a.0[k] - b.1[k]))
+                .fold(f32::NEG_INFINITY, f32::max);
+            assert!(gap >= padding - 1e-6);
+        }
+    }
+}
+
+#[test]
+fn lightmap_cube() {
+    let cube = Cube::new();
+    let mut vertices: Vec<Vertex> = cube.shared_vertex_iter().collect();
+    let mut triangles: Vec<Triangle<usize>> = cube.indexed_polygon_iter().triangulate().collect();
+    weld(&mut vertices, &mut triangles, 1e-5);
+    assert_eq!(vertices.len(), 8);
+    let positions: Vec<Position> = vertices.iter().map(|v| v.pos).collect();
+
+    let atlas = lightmap_atlas(&positions, &triangles, 0.5, 0.02);
+    assert_eq!(atlas.triangles.len(), 12);
+    assert_eq!(atlas.charts.iter().max(), Some(&5));
+    // the corners are split between the three faces around them
+    assert_eq!(atlas.source.len(), 24);
+    assert_eq!(atlas.uvs.len(), 24);
+    for (t, u) in triangles.iter().zip(&atlas.triangles) {
+        assert_eq!([t.x, t.y, t.z], [u.x, u.y, u.z].map(|i| atlas.source[i]));
+    }
+    assert_packed(&atlas, 0.02);
+    // every face gets the same space
+    let first = uv_area(&atlas, &atlas.triangles[0]);
+    for t in &atlas.triangles {
+        assert!((uv_area(&atlas, t) - first).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn lightmap_sphere() {
+    let sphere = IcoSphere::subdivide(2);
+    let positions: Vec<Position> = sphere.shared_vertex_iter().map(|v| v.pos).collect();
+    let triangles: Vec<Triangle<usize>> = sphere.indexed_polygon_iter().triangulate().collect();
+    let atlas = lightmap_atlas(&positions, &triangles, 0.6, 0.01);
+    let charts = atlas.charts.iter().max().unwrap() + 1;
+    assert!(charts > 6 && charts < triangles.len());
+    assert_packed(&atlas, 0.01);
+
+    assert!(lightmap_atlas(&[], &[], 0.6, 0.01).uvs.is_empty());
+}
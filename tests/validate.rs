@@ -0,0 +1,74 @@
+extern crate genmesh;
+
+use genmesh::generators::{Cube, IndexedPolygon, SharedVertex};
+use genmesh::validate::{validate, Report};
+use genmesh::{Line, Position, Triangle, Triangulate};
+
+fn welded_cube() -> (Vec<Position>, Vec<Triangle<usize>>) {
+    let cube = Cube::new();
+    let all: Vec<Position> = cube.shared_vertex_iter().map(|v| v.pos).collect();
+    let mut positions: Vec<Position> = Vec::new();
+    let mut weld = |i: usize| match positions.iter().position(|p| *p == all[i]) {
+        Some(j) => j,
+        None => {
+            positions.push(all[i]);
+            positions.len() - 1
+        }
+    };
+    let triangles = cube
+        .indexed_polygon_iter()
+        .triangulate()
+        .map(|t| Triangle::new(weld(t.x), weld(t.y), weld(t.z)))
+        .collect();
+    (positions, triangles)
+}
+
+#[test]
+fn valid_cube() {
+    let (positions, triangles) = welded_cube();
+    let report = validate(&positions, &triangles);
+    assert_eq!(Report::default(), report);
+    assert!(report.is_valid());
+}
+
+#[test]
+fn broken_cube() {
+    let (mut positions, mut triangles) = welded_cube();
+
+    // turn one face around
+    let t = triangles[0].clone();
+    triangles[0] = Triangle::new(t.x, t.z, t.y);
+    let report = validate(&positions, &triangles);
+    assert!(report.non_manifold_edges.is_empty());
+    assert!(report.duplicate_faces.is_empty());
+    assert_eq!(3, report.inconsistent_winding.len());
+    let mut corners = [t.x, t.y, t.z];
+    corners.sort_unstable();
+    assert!(report
+        .inconsistent_winding
+        .contains(&Line::new(corners[0], corners[1])));
+    triangles[0] = t.clone();
+
+    // a copy of a face, seen from the other side
+    triangles.push(Triangle::new(t.z, t.y, t.x));
+    let report = validate(&positions, &triangles);
+    assert_eq!(vec![(0, 12)], report.duplicate_faces);
+    assert_eq!(3, report.non_manifold_edges.len());
+    triangles.pop();
+
+    // a sliver whose corners are on a line, and a repeated index
+    positions.push([2., 2., 2.].into());
+    positions.push([3., 3., 3.].into());
+    let p = positions.len();
+    let center = positions
+        .iter()
+        .position(|p| p.x == p.y && p.y == p.z && p.x > 0.)
+        .unwrap();
+    triangles.push(Triangle::new(center, p - 2, p - 1));
+    triangles.push(Triangle::new(0, 0, 1));
+    triangles.push(Triangle::new(0, 1, p));
+    let report = validate(&positions, &triangles);
+    assert_eq!(vec![12, 13], report.degenerate);
+    assert_eq!(vec![14], report.out_of_range);
+    assert!(!report.is_valid());
+}
@@ -0,0 +1,103 @@
+extern crate genmesh;
+
+mod common;
+
+use genmesh::generators::{Cube, SphereUv};
+use genmesh::validate::validate;
+use genmesh::{unshare, weld, Position, Triangle, Vertex};
+
+use common::indexed;
+
+#[test]
+fn weld_cube() {
+    let (mut vertices, mut triangles) = indexed(&Cube::new(), None);
+    let remap = weld(&mut vertices, &mut triangles, 0.);
+    assert_eq!(8, vertices.len());
+    assert_eq!(12, triangles.len());
+    assert_eq!(24, remap.len());
+    assert_eq!(Some(0), remap[0]);
+
+    let positions: Vec<Position> = vertices.iter().map(|v| v.pos).collect();
+    assert!(validate(&positions, &triangles).is_valid());
+}
+
+#[test]
+fn weld_nearby() {
+    // two unit squares side by side, with a gap smaller than epsilon
+    let v = |x: f32, y: f32| Vertex {
+        pos: [x, y, 0.].into(),
+        normal: [0., 0., 1.].into(),
+    };
+    let mut vertices = vec![
+        v(0., 0.),
+        v(1., 0.),
+        v(1., 1.),
+        v(0., 1.),
+        v(1.001, 0.),
+        v(2., 0.),
+        v(2., 1.),
+        v(1.001, 1.),
+    ];
+    let mut triangles = vec![
+        Triangle::new(0, 1, 2),
+        Triangle::new(0, 2, 3),
+        Triangle::new(4, 5, 6),
+        Triangle::new(4, 6, 7),
+        // a sliver across the gap, which collapses
+        Triangle::new(1, 4, 2),
+    ];
+
+    let mut copy = (vertices.clone(), triangles.clone());
+    weld(&mut copy.0, &mut copy.1, 1e-4);
+    assert_eq!(8, copy.0.len());
+    assert_eq!(5, copy.1.len());
+
+    let remap = weld(&mut vertices, &mut triangles, 0.01);
+    assert_eq!(6, vertices.len());
+    assert_eq!(4, triangles.len());
+    assert_eq!(remap[1], remap[4]);
+    assert_eq!(remap[2], remap[7]);
+    // the first vertex of the pair is the one kept
+    assert_eq!(1., vertices[remap[4].unwrap()].pos.x);
+}
+
+#[test]
+fn weld_sphere_seam() {
+    // the UV sphere repeats its seam and poles, welding leaves the
+    // vertices of a closed sphere
+    let (mut vertices, mut triangles) = indexed(&SphereUv::new(8, 6), None);
+    let before = triangles.len();
+    let remap = weld(&mut vertices, &mut triangles, 1e-5);
+    assert_eq!(8 * 5 + 2, vertices.len());
+    assert_eq!(before, triangles.len());
+    assert!(remap.iter().all(|r| r.is_some()));
+
+    let positions: Vec<Position> = vertices.iter().map(|v| v.pos).collect();
+    assert!(validate(&positions, &triangles).is_valid());
+}
+
+#[test]
+fn unshare_sphere() {
+    let (vertices, triangles) = indexed(&SphereUv::new(8, 6), None);
+    let (mut flat, mut faces) = unshare(&vertices, &triangles);
+    assert_eq!(flat.len(), 3 * triangles.len());
+    assert_eq!(faces.len(), triangles.len());
+    for (t, f) in triangles.iter().zip(&faces) {
+        assert_eq!(flat[f.x].pos, vertices[t.x].pos);
+        assert_eq!(flat[f.y].pos, vertices[t.y].pos);
+        assert_eq!(flat[f.z].pos, vertices[t.z].pos);
+        // the three corners share the normal of the face, which points out
+        assert_eq!(flat[f.x].normal, flat[f.y].normal);
+        assert_eq!(flat[f.x].normal, flat[f.z].normal);
+        let n = flat[f.x].normal;
+        let p = flat[f.x].pos;
+        assert!(n.x * p.x + n.y * p.y + n.z * p.z > 0.);
+    }
+
+    // welding puts it back together
+    weld(&mut flat, &mut faces, 0.);
+    let (mut welded, mut expected) = (vertices.clone(), triangles.clone());
+    weld(&mut welded, &mut expected, 0.);
+    assert_eq!(flat.len(), welded.len());
+    assert_eq!(faces.len(), expected.len());
+}
@@ -98,7 +98,7 @@ where
     // this means that there is no polygon who's neighbor switches winding
     // direction, but it does not mean that the polygon is correct. They
     // all could be backwards. So this still requires a secondary inspection.
-    assert!(lines.len() == 0);
+    assert!(lines.is_empty());
 }
 
 #[test]
@@ -116,6 +116,20 @@ fn wind_plane() {
     }));
 }
 
+#[test]
+fn wind_annulus() {
+    // like the plane, the annulus is flat and open, so it is lifted off
+    // the origin to check that it faces +Z.
+    test_outward(generators::Annulus::new(0.5, 1., 8, 3).vertex(|mut v| {
+        v.pos.z = 1.;
+        v
+    }));
+    test_outward(generators::Annulus::new(0., 1., 8, 3).vertex(|mut v| {
+        v.pos.z = 1.;
+        v
+    }));
+}
+
 #[test]
 fn gen_cube() {
     test_outward(generators::Cube::new());
@@ -156,3 +170,171 @@ fn gen_torus() {
     // convex and will fail this test.
     test_closed(generators::Torus::new(10.0, 5.0, 8, 8));
 }
+
+#[test]
+fn wind_tubes() {
+    use genmesh::{Tubes, Vertices};
+
+    // a single tube away from the origin, checked against its own axis
+    let line = Line::new(
+        Vertex {
+            pos: [0., 0., -1.].into(),
+            normal: [0., 0., 1.].into(),
+        },
+        Vertex {
+            pos: [0., 0., 1.].into(),
+            normal: [0., 0., 1.].into(),
+        },
+    );
+    test_outward(std::iter::once(line.clone()).tubes(0.5, 8));
+
+    // with joints, the wireframe of a cube turns into a closed, if
+    // self-intersecting, surface made of 12 tubes and 8 spheres
+    let cube_lines: Vec<_> = generators::Cube::new().lines().collect();
+    let polys: Vec<_> = cube_lines
+        .iter()
+        .cloned()
+        .tubes(0.1, 6)
+        .with_joints(6, 4)
+        .collect();
+    assert_eq!(24 * 6 + 8 * 6 * 4, polys.len());
+    for v in polys.into_iter().vertices() {
+        assert!(v.pos.x.abs() <= 1. && v.pos.y.abs() <= 1. && v.pos.z.abs() <= 1.);
+    }
+}
+
+#[test]
+fn reverse_winding() {
+    use genmesh::{FlipWinding, NGon, Polygon, ReverseWinding, Triangle};
+
+    // turning a closed shape inside out keeps it closed, and turning it
+    // back restores it
+    let cube: Vec<_> = generators::Cube::new().collect();
+    let inside_out: Vec<_> = cube.iter().cloned().reverse_winding().collect();
+    test_closed(inside_out.iter().cloned());
+    assert_eq!(
+        cube,
+        inside_out
+            .iter()
+            .cloned()
+            .reverse_winding()
+            .collect::<Vec<_>>()
+    );
+    for (a, b) in cube.iter().zip(&inside_out) {
+        let (a, b) = match (a, b) {
+            (Polygon::PolyQuad(a), Polygon::PolyQuad(b)) => (a, b),
+            _ => unreachable!(),
+        };
+        assert_eq!(a.x.pos, b.x.pos);
+        assert_eq!(a.y.pos, b.w.pos);
+        assert_eq!(-a.x.normal.z, b.x.normal.z);
+    }
+
+    let mut ngon = NGon::new();
+    for i in 0..5 {
+        ngon.add_vertex(i);
+    }
+    let flipped: Vec<_> = ngon.flip_winding().verts.into_iter().collect();
+    assert_eq!(vec![0, 4, 3, 2, 1], flipped);
+    assert_eq!(
+        Triangle::new(0, 2, 1),
+        Triangle::new(0, 1, 2).flip_winding()
+    );
+}
+
+#[test]
+fn consistent_winding() {
+    use genmesh::generators::{IndexedPolygon, SharedVertex};
+    use genmesh::{make_winding_consistent, FlipWinding, Triangle, Triangulate};
+
+    let cube = generators::Cube::new();
+    let positions: Vec<_> = cube.shared_vertex_iter().map(|v| v.pos).collect();
+    let weld = |i: usize| positions.iter().position(|p| *p == positions[i]).unwrap();
+    let good: Vec<Triangle<usize>> = cube
+        .indexed_polygon_iter()
+        .triangulate()
+        .map(|t| Triangle::new(weld(t.x), weld(t.y), weld(t.z)))
+        .collect();
+
+    let mut mesh = good.clone();
+    assert_eq!(0, make_winding_consistent(&mut mesh));
+    assert_eq!(good, mesh);
+
+    // a few flipped faces are turned back, the majority wins
+    for &i in &[0, 5, 7] {
+        mesh[i] = mesh[i].clone().flip_winding();
+    }
+    assert_eq!(3, make_winding_consistent(&mut mesh));
+    assert_eq!(good, mesh);
+
+    // with most of the faces flipped, the whole cube ends up inside out
+    for t in mesh.iter_mut().skip(2) {
+        *t = t.clone().flip_winding();
+    }
+    assert_eq!(2, make_winding_consistent(&mut mesh));
+    for (a, b) in good.iter().zip(&mesh) {
+        assert_eq!(a.clone().flip_winding(), *b);
+    }
+}
+
+#[test]
+fn inside_out() {
+    use genmesh::generators::{Cube, IcoSphere, IndexedPolygon, SharedVertex, SphereUv};
+    use genmesh::{
+        indexed_volume, EmitTriangles, InsideOut, Polygon, Position, Triangulate, VertexAttributes,
+    };
+
+    fn volume<G, V>(generator: &G) -> f32
+    where
+        G: SharedVertex<V> + IndexedPolygon<Polygon<usize>>,
+        V: VertexAttributes,
+    {
+        let positions: Vec<Position> = generator
+            .shared_vertex_iter()
+            .map(|v| v.position())
+            .collect();
+        let triangles: Vec<_> = generator.indexed_polygon_iter().triangulate().collect();
+        indexed_volume(&positions, &triangles)
+    }
+
+    fn check<G, V>(generator: G, inverted: InsideOut<G>)
+    where
+        G: SharedVertex<V> + IndexedPolygon<Polygon<usize>>,
+        InsideOut<G>:
+            SharedVertex<V> + IndexedPolygon<Polygon<usize>> + Iterator<Item = Polygon<V>>,
+        V: VertexAttributes + Clone,
+    {
+        let outside = volume(&generator);
+        assert!(outside > 0.);
+        assert!((volume(&inverted) + outside).abs() < 1e-5);
+
+        for (a, b) in generator
+            .shared_vertex_iter()
+            .zip(inverted.shared_vertex_iter())
+        {
+            let (a, b) = (a.normal().unwrap(), b.normal().unwrap());
+            assert_eq!([-a.x, -a.y, -a.z], [b.x, b.y, b.z]);
+        }
+        // the streamed polygons face inward too, along their normals
+        for poly in inverted {
+            poly.emit_triangles(|t| {
+                let [a, b, c] = [&t.x, &t.y, &t.z].map(|v| cgmath::Vector3::from(v.position()));
+                let n = (b - a).cross(c - a);
+                assert!(n.dot(t.x.normal().unwrap().into()) >= 0.);
+                assert!(n.dot(a) <= 1e-6);
+            });
+        }
+    }
+
+    check(Cube::new(), Cube::new().inside_out());
+    check(Cube::textured(), Cube::textured().inside_out());
+    check(SphereUv::new(8, 6), SphereUv::new(8, 6).inside_out());
+    check(
+        IcoSphere::subdivide(1),
+        IcoSphere::subdivide(1).inside_out(),
+    );
+    check(
+        IcoSphere::subdivide(1).with_uvs(),
+        IcoSphere::subdivide(1).with_uvs().inside_out(),
+    );
+}